@@ -136,6 +136,12 @@ impl From<events::ReactorError> for AppAuthHandlerError {
     }
 }
 
+impl From<events::ParseError> for AppAuthHandlerError {
+    fn from(err: events::ParseError) -> Self {
+        AppAuthHandlerError::InvalidMessage(err.to_string())
+    }
+}
+
 impl From<events::WebSocketError> for AppAuthHandlerError {
     fn from(err: events::WebSocketError) -> Self {
         AppAuthHandlerError::WebSocket(err)
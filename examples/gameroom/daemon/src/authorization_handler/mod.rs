@@ -20,16 +20,15 @@ pub use error::AppAuthHandlerError;
 pub mod sabre;
 mod state_delta;
 
-use std::fmt::Write;
 use std::thread;
 use std::time::{Duration, Instant, SystemTime};
 
-use diesel::connection::Connection;
+use diesel::{connection::Connection, pg::PgConnection, QueryResult};
 use gameroom_database::{
     helpers,
     models::{
-        ActiveGameroom, Gameroom, GameroomProposal, NewGameroomMember, NewGameroomProposal,
-        NewGameroomService, NewProposalVoteRecord,
+        ActiveGameroom, Gameroom, GameroomProposal, NewDeadLetterEvent, NewGameroomMember,
+        NewGameroomProposal, NewGameroomService, NewProposalVoteRecord, ProcessedEvent,
     },
     ConnectionPool,
 };
@@ -39,10 +38,13 @@ use splinter::{
         AdminServiceEvent, CircuitProposal, CreateCircuit, SplinterNode, SplinterService,
     },
     events::{Igniter, ParseBytes, ParseError, WebSocketClient, WebSocketError, WsResponse},
+    hex::to_hex,
 };
-use state_delta::XoStateDeltaProcessor;
+pub use state_delta::StateDeltaBatchConfig;
+use state_delta::{StateDeltaBatcher, XoStateDeltaProcessor};
 
 use crate::application_metadata::ApplicationMetadata;
+use crate::config::GameroomConfig;
 
 use self::sabre::setup_xo;
 
@@ -52,11 +54,14 @@ const RECONNECT: bool = true;
 /// default limit for number of consecutives failed reconnection attempts
 const RECONNECT_LIMIT: u64 = 10;
 
+/// default limit in seconds for the total time spent reconnecting, regardless of attempts
+const RECONNECT_TIMEOUT: u64 = 300;
+
 /// default timeout in seconds if no message is received from server
 const CONNECTION_TIMEOUT: u64 = 60;
 
-// The admin protocol version supported by the gameroom  app auth handler
-const GAMEROOM_ADMIN_PROTOCOL_VERSION: &str = "1";
+/// the admin event registration whose processed events are tracked in the database
+const ADMIN_EVENT_REGISTRATION: &str = "gameroom";
 
 #[derive(Deserialize, Debug, Clone)]
 struct Event {
@@ -64,6 +69,9 @@ struct Event {
 
     #[serde(flatten)]
     admin_event: AdminServiceEvent,
+
+    #[serde(default)]
+    event_id: Option<i64>,
 }
 
 impl ParseBytes<Event> for Event {
@@ -72,14 +80,32 @@ impl ParseBytes<Event> for Event {
     }
 }
 
-pub fn run(
+/// The settings used to subscribe to the scabbard state change events of a gameroom
+#[derive(Clone)]
+struct SubscriptionSettings {
     splinterd_url: String,
     authorization: String,
+    state_delta_batch: StateDeltaBatchConfig,
+}
+
+pub fn run(
+    config: &GameroomConfig,
+    authorization: String,
     node_id: String,
     db_conn: ConnectionPool,
     private_key: String,
     igniter: Igniter,
 ) -> Result<(), AppAuthHandlerError> {
+    let splinterd_url = config.splinterd_url().to_string();
+    let subscription = SubscriptionSettings {
+        splinterd_url: splinterd_url.clone(),
+        authorization: authorization.clone(),
+        state_delta_batch: StateDeltaBatchConfig::new(
+            Duration::from_millis(config.state_delta_batch_window()),
+            config.state_delta_batch_size(),
+        ),
+    };
+
     let pool = db_conn.get()?;
     let registration_route = helpers::get_last_updated_proposal_time(&pool)?
         .map(|time| {
@@ -93,29 +119,29 @@ pub fn run(
         })
         .unwrap_or_else(|| format!("{}/ws/admin/register/gameroom", splinterd_url));
 
-    let ws_url = splinterd_url.clone();
-    let ws_authorization = authorization.clone();
+    let ws_subscription = subscription.clone();
     let ws_node_id = node_id.clone();
     let ws_db_conn = db_conn.clone();
-    let mut ws = WebSocketClient::new(&registration_route, &authorization, move |ctx, event| {
-        if let Err(err) = process_admin_event(
-            event,
-            &ws_db_conn,
-            &ws_node_id,
-            &private_key,
-            &ws_url,
-            &ws_authorization,
-            ctx.igniter(),
-        ) {
-            error!("Failed to process admin event: {}", err);
-        }
-        WsResponse::Empty
-    });
+    let mut ws = WebSocketClient::new(
+        &registration_route,
+        &authorization,
+        move |ctx, payload: Vec<u8>| {
+            if let Err(err) = handle_admin_event(
+                &payload,
+                &ws_db_conn,
+                &ws_node_id,
+                &private_key,
+                &ws_subscription,
+                ctx.igniter(),
+            ) {
+                error!("Failed to dead-letter admin event: {}", err);
+            }
+            WsResponse::Empty
+        },
+    );
 
     let on_open_db_conn = db_conn.clone();
     let on_open_igniter = igniter.clone();
-    let on_open_url = splinterd_url.clone();
-    let on_open_authorization = authorization.clone();
     ws.on_open(move |_| {
         let conn = match on_open_db_conn.get() {
             Ok(conn) => conn,
@@ -134,12 +160,7 @@ pub fn run(
         };
 
         for gameroom in gamerooms.iter() {
-            let ws = resubscribe(
-                &on_open_url,
-                &on_open_authorization,
-                gameroom,
-                &on_open_db_conn,
-            );
+            let ws = resubscribe(&subscription, gameroom, &on_open_db_conn);
             if let Err(err) = on_open_igniter.start_ws(&ws) {
                 error!("Failed to resubscribe to active gameroom: {}", err);
             }
@@ -148,13 +169,11 @@ pub fn run(
         WsResponse::Empty
     });
 
-    ws.header(
-        "SplinterProtocolVersion",
-        GAMEROOM_ADMIN_PROTOCOL_VERSION.to_string(),
-    );
+    set_admin_protocol_version(&mut ws, config.admin_protocol_version().to_string());
 
     ws.set_reconnect(RECONNECT);
     ws.set_reconnect_limit(RECONNECT_LIMIT);
+    ws.set_reconnect_timeout(Duration::from_secs(RECONNECT_TIMEOUT));
     ws.set_timeout(CONNECTION_TIMEOUT);
 
     let on_reconnect_url = splinterd_url.clone();
@@ -233,17 +252,66 @@ pub fn run(
     igniter.start_ws(&ws).map_err(AppAuthHandlerError::from)
 }
 
+/// Parses and processes a raw admin event.
+///
+/// If the event cannot be parsed or processed, the raw payload is written to the dead-letter
+/// table along with the error, so that it may be inspected and replayed later. An error is only
+/// returned if the event could not be dead-lettered.
+fn handle_admin_event(
+    payload: &[u8],
+    pool: &ConnectionPool,
+    node_id: &str,
+    private_key: &str,
+    subscription: &SubscriptionSettings,
+    igniter: Igniter,
+) -> Result<(), AppAuthHandlerError> {
+    let result = Event::from_bytes(payload)
+        .map_err(AppAuthHandlerError::from)
+        .and_then(|event| {
+            process_admin_event(event, pool, node_id, private_key, subscription, igniter)
+        });
+
+    if let Err(err) = result {
+        error!("Failed to process admin event: {}", err);
+        helpers::insert_dead_letter_event(
+            &*pool.get()?,
+            &NewDeadLetterEvent {
+                payload: String::from_utf8_lossy(payload).into_owned(),
+                error: err.to_string(),
+                created_time: SystemTime::now(),
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Processes an admin event, updating the gameroom tables accordingly.
+///
+/// Events that carry an id are only processed once: the id is recorded as the last processed
+/// event within the same transaction as the event's changes, and any event with an id at or
+/// below the recorded one is skipped.
 fn process_admin_event(
     event: Event,
     pool: &ConnectionPool,
     node_id: &str,
     private_key: &str,
-    url: &str,
-    authorization: &str,
+    subscription: &SubscriptionSettings,
     igniter: Igniter,
 ) -> Result<(), AppAuthHandlerError> {
     debug!("Received the event at {}", event.timestamp);
     let time: SystemTime = SystemTime::UNIX_EPOCH + Duration::from_millis(event.timestamp);
+    let event_id = event.event_id;
+
+    if let Some(event_id) = event_id {
+        let last_processed =
+            helpers::get_last_processed_event_id(&*pool.get()?, ADMIN_EVENT_REGISTRATION)?;
+        if last_processed.map(|last| event_id <= last).unwrap_or(false) {
+            debug!("Skipping already processed event {}", event_id);
+            return Ok(());
+        }
+    }
+
     match event.admin_event {
         AdminServiceEvent::ProposalSubmitted(msg_proposal) => {
             // convert requester public key to hex
@@ -280,6 +348,7 @@ fn process_admin_event(
                 helpers::insert_gameroom_proposal(conn, proposal)?;
                 helpers::insert_gameroom_services(conn, &services)?;
                 helpers::insert_gameroom_members(conn, &nodes)?;
+                mark_event_processed(conn, event_id, time)?;
 
                 debug!("Inserted new proposal into database");
                 Ok(())
@@ -314,6 +383,7 @@ fn process_admin_event(
                 helpers::insert_gameroom_notification(conn, &[notification])?;
                 helpers::update_gameroom_proposal_status(conn, proposal.id, &time, "Pending")?;
                 helpers::insert_proposal_vote_record(conn, &[vote])?;
+                mark_event_processed(conn, event_id, time)?;
 
                 debug!("Inserted new vote into database");
                 Ok(())
@@ -365,6 +435,7 @@ fn process_admin_event(
                 )?;
 
                 helpers::insert_proposal_vote_record(conn, &[vote])?;
+                mark_event_processed(conn, event_id, time)?;
 
                 debug!("Updated proposal to status 'Accepted'");
                 Ok(())
@@ -415,6 +486,7 @@ fn process_admin_event(
                     "Rejected",
                 )?;
                 helpers::insert_proposal_vote_record(conn, &[vote])?;
+                mark_event_processed(conn, event_id, time)?;
                 debug!("Updated proposal to status 'Rejected'");
                 Ok(())
             })
@@ -425,6 +497,7 @@ fn process_admin_event(
             // If the gameroom already exists and is in the ready state, skip
             // processing the event.
             if helpers::gameroom_service_is_active(conn, &msg_proposal.circuit_id)? {
+                mark_event_processed(conn, event_id, time)?;
                 return Ok(());
             }
 
@@ -442,6 +515,7 @@ fn process_admin_event(
                         "New gameroom does not have any services for this node: {}",
                         node_id
                     );
+                    mark_event_processed(conn, event_id, time)?;
                     return Ok(());
                 }
             };
@@ -483,6 +557,7 @@ fn process_admin_event(
                     "Accepted",
                     "Ready",
                 )?;
+                mark_event_processed(conn, event_id, time)?;
 
                 debug!("Updated proposal to status 'Ready'");
 
@@ -495,20 +570,23 @@ fn process_admin_event(
                 &proposal.requester,
                 pool,
             )?;
+            let batcher = StateDeltaBatcher::new(subscription.state_delta_batch, move |events| {
+                if let Err(err) = processor.handle_state_change_events(events) {
+                    error!(
+                        "An error occurred while handling state change events: {:?}",
+                        err
+                    );
+                }
+            })?;
 
             let mut xo_ws = WebSocketClient::new(
                 &format!(
                     "{}/scabbard/{}/{}/ws/subscribe",
-                    url, msg_proposal.circuit_id, service_id
+                    subscription.splinterd_url, msg_proposal.circuit_id, service_id
                 ),
-                authorization,
+                &subscription.authorization,
                 move |_, event| {
-                    if let Err(err) = processor.handle_state_change_event(event) {
-                        error!(
-                            "An error occurred while handling a state change event: {:?}",
-                            err
-                        );
-                    }
+                    batcher.add(event);
                     WsResponse::Empty
                 },
             );
@@ -518,8 +596,8 @@ fn process_admin_event(
                 SCABBARD_PROTOCOL_VERSION.to_string(),
             );
 
-            let url_to_string = url.to_string();
-            let authorization_to_string = authorization.to_string();
+            let url_to_string = subscription.splinterd_url.clone();
+            let authorization_to_string = subscription.authorization.clone();
             let private_key_to_string = private_key.to_string();
             xo_ws.on_open(move |ctx| {
                 debug!("Starting XO State Delta Export");
@@ -560,17 +638,26 @@ fn process_admin_event(
 }
 
 fn resubscribe(
-    url: &str,
-    authorization: &str,
+    subscription: &SubscriptionSettings,
     gameroom: &ActiveGameroom,
     db_pool: &ConnectionPool,
 ) -> WebSocketClient<StateChangeEvent> {
-    let processor = XoStateDeltaProcessor::new(
+    let batcher = XoStateDeltaProcessor::new(
         &gameroom.circuit_id,
         &gameroom.requester_node_id,
         &gameroom.requester,
         db_pool,
-    );
+    )
+    .and_then(|processor| {
+        StateDeltaBatcher::new(subscription.state_delta_batch, move |events| {
+            if let Err(err) = processor.handle_state_change_events(events) {
+                error!(
+                    "An error occurred while handling state change events: {:?}",
+                    err
+                );
+            }
+        })
+    });
 
     let query_string = if gameroom.last_event.is_empty() {
         "".into()
@@ -581,19 +668,12 @@ fn resubscribe(
     let mut ws = WebSocketClient::new(
         &format!(
             "{}/scabbard/{}/{}/ws/subscribe{}",
-            url, gameroom.circuit_id, gameroom.service_id, query_string,
+            subscription.splinterd_url, gameroom.circuit_id, gameroom.service_id, query_string,
         ),
-        authorization,
+        &subscription.authorization,
         move |_, event| {
-            match &processor {
-                Ok(processor) => {
-                    if let Err(err) = processor.handle_state_change_event(event) {
-                        error!(
-                            "An error occurred while handling a state change event: {:?}",
-                            err
-                        );
-                    }
-                }
+            match &batcher {
+                Ok(batcher) => batcher.add(event),
                 Err(err) => error!("Failed to initialize state delta processor: {:?}", err),
             }
             WsResponse::Empty
@@ -702,6 +782,31 @@ fn parse_splinter_nodes(
         .collect()
 }
 
+/// Records the given event as the last one processed for the gameroom registration. Events
+/// without an id are not tracked.
+fn mark_event_processed(
+    conn: &PgConnection,
+    event_id: Option<i64>,
+    time: SystemTime,
+) -> QueryResult<()> {
+    match event_id {
+        Some(event_id) => helpers::update_last_processed_event_id(
+            conn,
+            &ProcessedEvent {
+                registration: ADMIN_EVENT_REGISTRATION.to_string(),
+                last_event_id: event_id,
+                updated_time: time,
+            },
+        ),
+        None => Ok(()),
+    }
+}
+
+/// Sets the admin protocol version the websocket client requests from splinterd.
+fn set_admin_protocol_version(ws: &mut WebSocketClient, admin_protocol_version: String) {
+    ws.header("SplinterProtocolVersion", admin_protocol_version);
+}
+
 fn get_pending_proposal_with_circuit_id(
     pool: &ConnectionPool,
     circuit_id: &str,
@@ -716,15 +821,6 @@ fn get_pending_proposal_with_circuit_id(
     )
 }
 
-pub fn to_hex(bytes: &[u8]) -> String {
-    let mut buf = String::new();
-    for b in bytes {
-        write!(&mut buf, "{:02x}", b).expect("Unable to write to string");
-    }
-
-    buf
-}
-
 #[cfg(all(feature = "test-authorization-handler", test))]
 mod test {
     use super::*;
@@ -755,8 +851,15 @@ mod test {
         clear_gameroom_notification_table(&pool);
 
         let message = get_submit_proposal_msg("01234-ABCDE");
-        process_admin_event(message, &pool, "", "", "", "", reactor.igniter())
-            .expect("Error processing message");
+        process_admin_event(
+            message,
+            &pool,
+            "",
+            "",
+            &subscription_settings(),
+            reactor.igniter(),
+        )
+        .expect("Error processing message");
 
         let proposals = query_proposals_table(&pool);
 
@@ -785,8 +888,15 @@ mod test {
         clear_gameroom_notification_table(&pool);
 
         let message = get_submit_proposal_msg("01234-ABCDE");
-        process_admin_event(message, &pool, "", "", "", "", reactor.igniter())
-            .expect("Error processing message");
+        process_admin_event(
+            message,
+            &pool,
+            "",
+            "",
+            &subscription_settings(),
+            reactor.igniter(),
+        )
+        .expect("Error processing message");
 
         let gamerooms = query_gameroom_table(&pool);
 
@@ -823,8 +933,15 @@ mod test {
         clear_gameroom_notification_table(&pool);
 
         let message = get_submit_proposal_msg("01234-ABCDE");
-        process_admin_event(message, &pool, "", "", "", "", reactor.igniter())
-            .expect("Error processing message");
+        process_admin_event(
+            message,
+            &pool,
+            "",
+            "",
+            &subscription_settings(),
+            reactor.igniter(),
+        )
+        .expect("Error processing message");
 
         let members = query_gameroom_members_table(&pool);
 
@@ -849,8 +966,15 @@ mod test {
         clear_gameroom_notification_table(&pool);
 
         let message = get_submit_proposal_msg("01234-ABCDE");
-        process_admin_event(message, &pool, "", "", "", "", reactor.igniter())
-            .expect("Error processing message");
+        process_admin_event(
+            message,
+            &pool,
+            "",
+            "",
+            &subscription_settings(),
+            reactor.igniter(),
+        )
+        .expect("Error processing message");
 
         let services = query_gameroom_service_table(&pool);
 
@@ -876,8 +1000,15 @@ mod test {
         clear_gameroom_notification_table(&pool);
 
         let message = get_submit_proposal_msg("01234-ABCDE");
-        process_admin_event(message, &pool, "", "", "", "", reactor.igniter())
-            .expect("Error processing message");
+        process_admin_event(
+            message,
+            &pool,
+            "",
+            "",
+            &subscription_settings(),
+            reactor.igniter(),
+        )
+        .expect("Error processing message");
 
         let notifications = query_gameroom_notification_table(&pool);
 
@@ -930,8 +1061,15 @@ mod test {
         let accept_message = get_accept_proposal_msg("01234-ABCDE");
 
         // accept proposal
-        process_admin_event(accept_message, &pool, "", "", "", "", reactor.igniter())
-            .expect("Error processing message");
+        process_admin_event(
+            accept_message,
+            &pool,
+            "",
+            "",
+            &subscription_settings(),
+            reactor.igniter(),
+        )
+        .expect("Error processing message");
 
         let proposals = query_proposals_table(&pool);
 
@@ -981,7 +1119,14 @@ mod test {
         let accept_message = get_accept_proposal_msg("01234-ABCDE");
 
         // accept proposal
-        match process_admin_event(accept_message, &pool, "", "", "", "", reactor.igniter()) {
+        match process_admin_event(
+            accept_message,
+            &pool,
+            "",
+            "",
+            &subscription_settings(),
+            reactor.igniter(),
+        ) {
             Ok(()) => panic!("Pending proposal for circuit is missing, error should be returned"),
             Err(AppAuthHandlerError::Database(msg)) => {
                 assert!(msg.contains("Could not find open proposal for circuit: 01234-ABCDE"));
@@ -1024,8 +1169,15 @@ mod test {
         let rejected_message = get_reject_proposal_msg("01234-ABCDE");
 
         // reject proposal
-        process_admin_event(rejected_message, &pool, "", "", "", "", reactor.igniter())
-            .expect("Error processing message");
+        process_admin_event(
+            rejected_message,
+            &pool,
+            "",
+            "",
+            &subscription_settings(),
+            reactor.igniter(),
+        )
+        .expect("Error processing message");
 
         let proposals = query_proposals_table(&pool);
 
@@ -1086,7 +1238,14 @@ mod test {
         let rejected_message = get_reject_proposal_msg("01234-ABCDE");
 
         // reject proposal
-        match process_admin_event(rejected_message, &pool, "", "", "", "", reactor.igniter()) {
+        match process_admin_event(
+            rejected_message,
+            &pool,
+            "",
+            "",
+            &subscription_settings(),
+            reactor.igniter(),
+        ) {
             Ok(()) => panic!("Pending proposal for circuit is missing, error should be returned"),
             Err(AppAuthHandlerError::Database(msg)) => {
                 assert!(msg.contains("Could not find open proposal for circuit: 01234-ABCDE"));
@@ -1120,8 +1279,15 @@ mod test {
         let vote_message = get_vote_proposal_msg("01234-ABCDE");
 
         // vote proposal
-        process_admin_event(vote_message, &pool, "", "", "", "", reactor.igniter())
-            .expect("Error processing message");
+        process_admin_event(
+            vote_message,
+            &pool,
+            "",
+            "",
+            &subscription_settings(),
+            reactor.igniter(),
+        )
+        .expect("Error processing message");
 
         let proposals = query_proposals_table(&pool);
 
@@ -1167,8 +1333,15 @@ mod test {
         let vote_message = get_vote_proposal_msg("01234-ABCDE");
 
         // vote proposal
-        process_admin_event(vote_message, &pool, "", "", "", "", reactor.igniter())
-            .expect("Error processing message");
+        process_admin_event(
+            vote_message,
+            &pool,
+            "",
+            "",
+            &subscription_settings(),
+            reactor.igniter(),
+        )
+        .expect("Error processing message");
 
         let notifications = query_gameroom_notification_table(&pool);
 
@@ -1206,7 +1379,14 @@ mod test {
         let vote_message = get_vote_proposal_msg("01234-ABCDE");
 
         // vote proposal
-        match process_admin_event(vote_message, &pool, "", "", "", "", reactor.igniter()) {
+        match process_admin_event(
+            vote_message,
+            &pool,
+            "",
+            "",
+            &subscription_settings(),
+            reactor.igniter(),
+        ) {
             Ok(()) => panic!("Pending proposal for circuit is missing, error should be returned"),
             Err(AppAuthHandlerError::Database(msg)) => {
                 assert!(msg.contains("Could not find open proposal for circuit: 01234-ABCDE"));
@@ -1215,6 +1395,101 @@ mod test {
         }
     }
 
+    #[test]
+    /// Tests if the SplinterProtocolVersion header of the admin websocket client is set to the
+    /// admin protocol version supplied by the caller
+    fn test_set_admin_protocol_version() {
+        let mut ws: WebSocketClient = WebSocketClient::new(
+            "http://localhost:8080/ws/admin/register/gameroom",
+            "",
+            |_, _| WsResponse::Empty,
+        );
+
+        set_admin_protocol_version(&mut ws, "2".to_string());
+
+        assert_eq!(
+            ws.headers().get("SplinterProtocolVersion"),
+            Some(&"2".to_string())
+        );
+    }
+
+    #[test]
+    /// Tests if an admin event that cannot be parsed is written to the dead-letter table, along
+    /// with its raw payload and the parse error
+    fn test_handle_admin_event_dead_letters_malformed_event() {
+        let reactor = Reactor::new();
+        let pool: ConnectionPool = gameroom_database::create_connection_pool(DATABASE_URL)
+            .expect("Failed to get database connection pool");
+
+        clear_dead_letter_event_table(&pool);
+
+        handle_admin_event(
+            b"{\"not\": \"an admin event\"}",
+            &pool,
+            "",
+            "",
+            &subscription_settings(),
+            reactor.igniter(),
+        )
+        .expect("Error dead-lettering message");
+
+        let dead_letters =
+            helpers::list_dead_letter_events(&*pool.get().expect("Error getting db connection"))
+                .expect("Error fetching dead-lettered events");
+
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].payload, "{\"not\": \"an admin event\"}");
+        assert!(!dead_letters[0].error.is_empty());
+    }
+
+    #[test]
+    /// Tests if an admin event that has already been processed is skipped when it is replayed,
+    /// leaving the gameroom tables unchanged
+    fn test_process_admin_event_skips_processed_event() {
+        let reactor = Reactor::new();
+        let pool: ConnectionPool = gameroom_database::create_connection_pool(DATABASE_URL)
+            .expect("Failed to get database connection pool");
+
+        clear_gameroom_table(&pool);
+        clear_gameroom_notification_table(&pool);
+        clear_processed_event_table(&pool);
+
+        let mut message = get_submit_proposal_msg("01234-ABCDE");
+        message.event_id = Some(1);
+
+        process_admin_event(
+            message.clone(),
+            &pool,
+            "",
+            "",
+            &subscription_settings(),
+            reactor.igniter(),
+        )
+        .expect("Error processing message");
+
+        let last_processed = helpers::get_last_processed_event_id(
+            &*pool.get().expect("Error getting db connection"),
+            ADMIN_EVENT_REGISTRATION,
+        )
+        .expect("Error fetching last processed event id");
+        assert_eq!(last_processed, Some(1));
+
+        // Replaying the event would fail on the duplicate gameroom if it were processed again
+        process_admin_event(
+            message,
+            &pool,
+            "",
+            "",
+            &subscription_settings(),
+            reactor.igniter(),
+        )
+        .expect("Error processing replayed message");
+
+        assert_eq!(query_proposals_table(&pool).len(), 1);
+        assert_eq!(query_gameroom_table(&pool).len(), 1);
+        assert_eq!(query_gameroom_notification_table(&pool).len(), 1);
+    }
+
     #[test]
     /// Tests if the admin message CreateProposal to a database GameroomProposal is successful
     fn test_parse_proposal() {
@@ -1334,6 +1609,7 @@ mod test {
                 get_msg_proposal_with_vote(circuit_id),
                 public_key(),
             )),
+            event_id: None,
         }
     }
 
@@ -1344,6 +1620,7 @@ mod test {
                 get_msg_proposal_with_vote(circuit_id),
                 public_key(),
             )),
+            event_id: None,
         }
     }
 
@@ -1354,6 +1631,7 @@ mod test {
                 get_msg_proposal_with_vote(circuit_id),
                 public_key(),
             )),
+            event_id: None,
         }
     }
 
@@ -1361,6 +1639,7 @@ mod test {
         Event {
             timestamp: current_time_millis(),
             admin_event: AdminServiceEvent::ProposalSubmitted(get_msg_proposal(circuit_id)),
+            event_id: None,
         }
     }
 
@@ -1572,6 +1851,24 @@ mod test {
             .expect("Error cleaning gameroom table");
     }
 
+    fn clear_dead_letter_event_table(pool: &ConnectionPool) {
+        use gameroom_database::schema::dead_letter_event::dsl::*;
+
+        let conn = &*pool.get().expect("Error getting db connection");
+        diesel::delete(dead_letter_event)
+            .execute(conn)
+            .expect("Error cleaning dead_letter_event table");
+    }
+
+    fn clear_processed_event_table(pool: &ConnectionPool) {
+        use gameroom_database::schema::processed_event::dsl::*;
+
+        let conn = &*pool.get().expect("Error getting db connection");
+        diesel::delete(processed_event)
+            .execute(conn)
+            .expect("Error cleaning processed_event table");
+    }
+
     fn clear_gameroom_notification_table(pool: &ConnectionPool) {
         use gameroom_database::schema::gameroom_notification::dsl::*;
 
@@ -1581,6 +1878,14 @@ mod test {
             .expect("Error cleaning gameroom_notification table");
     }
 
+    fn subscription_settings() -> SubscriptionSettings {
+        SubscriptionSettings {
+            splinterd_url: "".into(),
+            authorization: "".into(),
+            state_delta_batch: StateDeltaBatchConfig::new(Duration::from_millis(100), 100),
+        }
+    }
+
     fn public_key() -> Vec<u8> {
         vec![73, 119, 65, 65, 65, 81]
     }
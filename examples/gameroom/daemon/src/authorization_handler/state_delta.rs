@@ -15,9 +15,18 @@
  * -----------------------------------------------------------------------------
  */
 
-use std::{error::Error, fmt, time::SystemTime};
+use std::{
+    error::Error,
+    fmt,
+    sync::{
+        mpsc::{channel, RecvTimeoutError, Sender},
+        Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime},
+};
 
-use diesel::connection::Connection;
+use diesel::{connection::Connection, pg::PgConnection};
 use gameroom_database::{
     error, helpers,
     models::{NewXoGame, XoGame},
@@ -52,121 +61,140 @@ impl XoStateDeltaProcessor {
         })
     }
 
-    pub fn handle_state_change_event(
+    /// Handles a batch of state change events in a single database transaction.
+    ///
+    /// If the batch fails, its events are handled again one at a time, each in its own
+    /// transaction, so that the events before the one that cannot be handled are not held back.
+    /// Handling stops at the first event that fails, and its error is returned.
+    pub fn handle_state_change_events(
         &self,
-        change_event: StateChangeEvent,
+        change_events: Vec<StateChangeEvent>,
     ) -> Result<(), StateDeltaError> {
-        // Update the last seen state change event
-        let time = SystemTime::now();
         let conn = &*self.db_pool.get()?;
-        conn.transaction::<_, error::DatabaseError, _>(|| {
+        commit_in_batch(&change_events, |events| {
+            self.commit_state_change_events(conn, events)
+        })
+    }
+
+    /// Handles the given state change events in a single database transaction.
+    ///
+    /// The gameroom's last seen event is only advanced to the final event once all of the events
+    /// have been committed, so events that fail are replayed on resubscribe.
+    fn commit_state_change_events(
+        &self,
+        conn: &PgConnection,
+        change_events: &[StateChangeEvent],
+    ) -> Result<(), StateDeltaError> {
+        let last_event_id = match change_events.last() {
+            Some(change_event) => &change_event.id,
+            None => return Ok(()),
+        };
+
+        conn.transaction::<_, StateDeltaError, _>(|| {
+            for change_event in change_events {
+                for change in &change_event.state_changes {
+                    self.handle_state_change(conn, change)?;
+                }
+            }
+
+            // Update the last seen state change event
             helpers::update_gameroom_service_last_event(
                 conn,
                 &self.circuit_id,
-                &time,
-                &change_event.id,
+                &SystemTime::now(),
+                last_event_id,
             )?;
-            Ok(())
-        })?;
 
-        for change in change_event.state_changes {
-            self.handle_state_change(&change)?;
-        }
-
-        Ok(())
+            Ok(())
+        })
     }
 
-    fn handle_state_change(&self, change: &StateChange) -> Result<(), StateDeltaError> {
+    fn handle_state_change(
+        &self,
+        conn: &PgConnection,
+        change: &StateChange,
+    ) -> Result<(), StateDeltaError> {
         debug!("Received state change: {}", change);
         match change {
             StateChange::Set { key, .. } if key == &self.contract_address => {
                 debug!("Xo contract created successfully");
                 let time = SystemTime::now();
-                let conn = &*self.db_pool.get()?;
-                conn.transaction::<_, error::DatabaseError, _>(|| {
+                let notification = helpers::create_new_notification(
+                    "circuit_active",
+                    &self.requester,
+                    &self.node_id,
+                    &self.circuit_id,
+                );
+                helpers::insert_gameroom_notification(conn, &[notification])?;
+                helpers::update_gameroom_status(conn, &self.circuit_id, &time, "Active")?;
+                helpers::update_gameroom_member_status(
+                    conn,
+                    &self.circuit_id,
+                    &time,
+                    "Ready",
+                    "Active",
+                )?;
+                helpers::update_gameroom_service_status(
+                    conn,
+                    &self.circuit_id,
+                    &time,
+                    "Ready",
+                    "Active",
+                )?;
+
+                Ok(())
+            }
+            StateChange::Set { key, value } if &key[..6] == XO_PREFIX => {
+                let time = SystemTime::now();
+                let game_state: Vec<String> = String::from_utf8(value.to_vec())
+                    .map_err(|err| StateDeltaError::XoPayloadParseError(format!("{:?}", err)))
+                    .map(|s| s.split(',').map(String::from).collect())?;
+
+                if let Some(game) = helpers::fetch_xo_game(conn, &self.circuit_id, &game_state[0])?
+                {
+                    helpers::update_xo_game(
+                        conn,
+                        XoGame {
+                            game_board: game_state[1].clone(),
+                            game_status: game_state[2].clone(),
+                            player_1: game_state[3].clone(),
+                            player_2: game_state[4].clone(),
+                            updated_time: time,
+                            ..game
+                        },
+                    )?;
+
                     let notification = helpers::create_new_notification(
-                        "circuit_active",
+                        &format!("game_updated:{}", game_state[0]),
                         &self.requester,
                         &self.node_id,
                         &self.circuit_id,
                     );
                     helpers::insert_gameroom_notification(conn, &[notification])?;
-                    helpers::update_gameroom_status(conn, &self.circuit_id, &time, "Active")?;
-                    helpers::update_gameroom_member_status(
+                } else {
+                    helpers::insert_xo_game(
                         conn,
-                        &self.circuit_id,
-                        &time,
-                        "Ready",
-                        "Active",
+                        NewXoGame {
+                            circuit_id: self.circuit_id.clone(),
+                            game_name: game_state[0].clone(),
+                            game_board: game_state[1].clone(),
+                            game_status: game_state[2].clone(),
+                            player_1: game_state[3].clone(),
+                            player_2: game_state[4].clone(),
+                            created_time: time,
+                            updated_time: time,
+                        },
                     )?;
-                    helpers::update_gameroom_service_status(
-                        conn,
+                    let notification = helpers::create_new_notification(
+                        &format!("new_game_created:{}", game_state[0]),
+                        &self.requester,
+                        &self.node_id,
                         &self.circuit_id,
-                        &time,
-                        "Ready",
-                        "Active",
-                    )?;
-
-                    Ok(())
-                })
-                .map_err(StateDeltaError::from)
-            }
-            StateChange::Set { key, value } if &key[..6] == XO_PREFIX => {
-                let time = SystemTime::now();
-                let game_state: Vec<String> = String::from_utf8(value.to_vec())
-                    .map_err(|err| StateDeltaError::XoPayloadParseError(format!("{:?}", err)))
-                    .map(|s| s.split(',').map(String::from).collect())?;
-
-                let conn = &*self.db_pool.get()?;
-                conn.transaction::<_, error::DatabaseError, _>(|| {
-                    if let Some(game) =
-                        helpers::fetch_xo_game(conn, &self.circuit_id, &game_state[0])?
-                    {
-                        helpers::update_xo_game(
-                            conn,
-                            XoGame {
-                                game_board: game_state[1].clone(),
-                                game_status: game_state[2].clone(),
-                                player_1: game_state[3].clone(),
-                                player_2: game_state[4].clone(),
-                                updated_time: time,
-                                ..game
-                            },
-                        )?;
-
-                        let notification = helpers::create_new_notification(
-                            &format!("game_updated:{}", game_state[0]),
-                            &self.requester,
-                            &self.node_id,
-                            &self.circuit_id,
-                        );
-                        helpers::insert_gameroom_notification(conn, &[notification])?;
-                    } else {
-                        helpers::insert_xo_game(
-                            conn,
-                            NewXoGame {
-                                circuit_id: self.circuit_id.clone(),
-                                game_name: game_state[0].clone(),
-                                game_board: game_state[1].clone(),
-                                game_status: game_state[2].clone(),
-                                player_1: game_state[3].clone(),
-                                player_2: game_state[4].clone(),
-                                created_time: time,
-                                updated_time: time,
-                            },
-                        )?;
-                        let notification = helpers::create_new_notification(
-                            &format!("new_game_created:{}", game_state[0]),
-                            &self.requester,
-                            &self.node_id,
-                            &self.circuit_id,
-                        );
-                        helpers::insert_gameroom_notification(conn, &[notification])?;
-                    }
+                    );
+                    helpers::insert_gameroom_notification(conn, &[notification])?;
+                }
 
-                    Ok(())
-                })
-                .map_err(StateDeltaError::from)
+                Ok(())
             }
             StateChange::Delete { .. } => {
                 debug!("Delete state skipping...");
@@ -180,6 +208,144 @@ impl XoStateDeltaProcessor {
     }
 }
 
+/// Commits the state change events as a single batch with `commit`.
+///
+/// If the batch fails, its events are committed again one at a time. Committing stops at the
+/// first event that fails, so the last seen event is never advanced past it, and that event and
+/// the ones after it are replayed on resubscribe.
+fn commit_in_batch<F>(
+    change_events: &[StateChangeEvent],
+    mut commit: F,
+) -> Result<(), StateDeltaError>
+where
+    F: FnMut(&[StateChangeEvent]) -> Result<(), StateDeltaError>,
+{
+    let err = match commit(change_events) {
+        Ok(()) => return Ok(()),
+        Err(err) if change_events.len() <= 1 => return Err(err),
+        Err(err) => err,
+    };
+
+    warn!(
+        "Failed to handle batch of {} state change events, handling them individually: {}",
+        change_events.len(),
+        err
+    );
+    for change_event in change_events.chunks(1) {
+        commit(change_event)?;
+    }
+
+    Ok(())
+}
+
+/// Determines how many state change events are accumulated before they are handled as a batch.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StateDeltaBatchConfig {
+    batch_window: Duration,
+    max_batch_size: usize,
+}
+
+impl StateDeltaBatchConfig {
+    /// Creates a config that handles a batch once `max_batch_size` events have been received,
+    /// or once `batch_window` has elapsed since the first event of the batch.
+    pub fn new(batch_window: Duration, max_batch_size: usize) -> Self {
+        Self {
+            batch_window,
+            max_batch_size,
+        }
+    }
+}
+
+/// Accumulates state change events so that they may be handled in batches.
+///
+/// Events are passed to the batch handler, on a separate thread, once the configured maximum
+/// batch size has been reached or once the batch window has elapsed since the first event of the
+/// batch, whichever comes first. Any pending events are handled when the batcher is dropped.
+pub struct StateDeltaBatcher {
+    sender: Option<Mutex<Sender<StateChangeEvent>>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl StateDeltaBatcher {
+    pub fn new<F>(
+        config: StateDeltaBatchConfig,
+        mut on_batch: F,
+    ) -> Result<Self, AppAuthHandlerError>
+    where
+        F: FnMut(Vec<StateChangeEvent>) + Send + 'static,
+    {
+        let (sender, receiver) = channel();
+        let batch_window = config.batch_window;
+        let max_batch_size = config.max_batch_size.max(1);
+
+        let join_handle = thread::Builder::new()
+            .name("StateDeltaBatcher".into())
+            .spawn(move || {
+                let mut batch = Vec::new();
+                let mut deadline: Option<Instant> = None;
+                loop {
+                    let received = match deadline {
+                        Some(deadline) => receiver
+                            .recv_timeout(deadline.saturating_duration_since(Instant::now())),
+                        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                    };
+
+                    match received {
+                        Ok(event) => {
+                            if batch.is_empty() {
+                                deadline = Some(Instant::now() + batch_window);
+                            }
+                            batch.push(event);
+                            if batch.len() < max_batch_size {
+                                continue;
+                            }
+                        }
+                        Err(RecvTimeoutError::Timeout) => (),
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+
+                    deadline = None;
+                    on_batch(batch.split_off(0));
+                }
+
+                if !batch.is_empty() {
+                    on_batch(batch);
+                }
+            })
+            .map_err(AppAuthHandlerError::Io)?;
+
+        Ok(StateDeltaBatcher {
+            sender: Some(Mutex::new(sender)),
+            join_handle: Some(join_handle),
+        })
+    }
+
+    /// Adds a state change event to the current batch.
+    pub fn add(&self, change_event: StateChangeEvent) {
+        let sent = self.sender.as_ref().map(|sender| match sender.lock() {
+            Ok(sender) => sender.send(change_event).is_ok(),
+            Err(_) => false,
+        });
+
+        if sent != Some(true) {
+            error!("Unable to add state change event to batch; batch handler has stopped");
+        }
+    }
+}
+
+impl Drop for StateDeltaBatcher {
+    fn drop(&mut self) {
+        // Dropping the sender disconnects the channel, which causes the batch thread to handle
+        // any pending events and exit.
+        self.sender.take();
+        if let Some(join_handle) = self.join_handle.take() {
+            if join_handle.join().is_err() {
+                error!("State delta batch thread panicked");
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum StateDeltaError {
     XoPayloadParseError(String),
@@ -211,3 +377,111 @@ impl From<error::DatabaseError> for StateDeltaError {
         StateDeltaError::DatabaseError(err)
     }
 }
+
+impl From<diesel::result::Error> for StateDeltaError {
+    fn from(err: diesel::result::Error) -> Self {
+        StateDeltaError::DatabaseError(error::DatabaseError::from(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::mpsc::Receiver;
+
+    /// Tests that a burst of state change events is handled in batches.
+    ///
+    /// 1. Create a batcher with a maximum batch size of 3 and a 200ms batch window
+    /// 2. Add a burst of 7 events
+    /// 3. Verify that two full batches of 3 events are handled, in order
+    /// 4. Verify that the remaining event is handled on its own, once the batch window elapses
+    /// 5. Add 2 more events and drop the batcher before the batch window elapses
+    /// 6. Verify that the pending events are handled when the batcher is dropped
+    #[test]
+    fn test_state_delta_batcher() {
+        let (sender, receiver) = channel();
+        let config = StateDeltaBatchConfig::new(Duration::from_millis(200), 3);
+        let batcher = StateDeltaBatcher::new(config, move |events| {
+            sender
+                .send(events.into_iter().map(|event| event.id).collect::<Vec<_>>())
+                .expect("Unable to send batch");
+        })
+        .expect("Unable to create batcher");
+
+        for i in 0..7 {
+            batcher.add(state_change_event(i));
+        }
+
+        assert_eq!(next_batch(&receiver), vec!["0", "1", "2"]);
+        assert_eq!(next_batch(&receiver), vec!["3", "4", "5"]);
+        assert_eq!(next_batch(&receiver), vec!["6"]);
+
+        let (sender, receiver) = channel();
+        let config = StateDeltaBatchConfig::new(Duration::from_secs(60), 3);
+        let batcher = StateDeltaBatcher::new(config, move |events| {
+            sender
+                .send(events.into_iter().map(|event| event.id).collect::<Vec<_>>())
+                .expect("Unable to send batch");
+        })
+        .expect("Unable to create batcher");
+
+        batcher.add(state_change_event(7));
+        batcher.add(state_change_event(8));
+        drop(batcher);
+
+        assert_eq!(next_batch(&receiver), vec!["7", "8"]);
+        assert!(receiver.try_recv().is_err());
+    }
+
+    /// Tests that when a batch fails, its events are committed one at a time up to the event
+    /// that fails, and none of the events after it are committed.
+    ///
+    /// 1. Commit a batch of 5 events, where committing any batch containing event 2 fails
+    /// 2. Verify that an error is returned
+    /// 3. Verify that the full batch was attempted, followed by events 0, 1 and 2 on their own
+    /// 4. Verify that events 3 and 4 were not committed
+    #[test]
+    fn test_commit_in_batch_stops_at_failure() {
+        let change_events = (0..5).map(state_change_event).collect::<Vec<_>>();
+
+        let mut attempts = vec![];
+        let result = commit_in_batch(&change_events, |events| {
+            let ids = events
+                .iter()
+                .map(|event| event.id.clone())
+                .collect::<Vec<_>>();
+            let fails = ids.iter().any(|id| id == "2");
+            attempts.push(ids);
+            if fails {
+                Err(StateDeltaError::XoPayloadParseError("bad payload".into()))
+            } else {
+                Ok(())
+            }
+        });
+
+        assert!(result.is_err());
+        assert_eq!(
+            attempts,
+            vec![
+                vec!["0", "1", "2", "3", "4"],
+                vec!["0"],
+                vec!["1"],
+                vec!["2"],
+            ]
+        );
+    }
+
+    fn state_change_event(id: usize) -> StateChangeEvent {
+        StateChangeEvent {
+            id: id.to_string(),
+            state_changes: vec![],
+        }
+    }
+
+    fn next_batch(receiver: &Receiver<Vec<String>>) -> Vec<String> {
+        receiver
+            .recv_timeout(Duration::from_secs(5))
+            .expect("No batch was handled")
+    }
+}
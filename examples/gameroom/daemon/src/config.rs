@@ -28,6 +28,9 @@ pub struct GameroomConfig {
     database_url: String,
     splinterd_url: String,
     key: String,
+    admin_protocol_version: String,
+    state_delta_batch_window: u64,
+    state_delta_batch_size: usize,
 }
 
 impl GameroomConfig {
@@ -45,6 +48,21 @@ impl GameroomConfig {
     pub fn key(&self) -> &str {
         &self.key
     }
+
+    pub fn admin_protocol_version(&self) -> &str {
+        &self.admin_protocol_version
+    }
+
+    /// Returns the time in milliseconds that scabbard state change events are accumulated before
+    /// they are committed together.
+    pub fn state_delta_batch_window(&self) -> u64 {
+        self.state_delta_batch_window
+    }
+
+    /// Returns the maximum number of scabbard state change events committed together.
+    pub fn state_delta_batch_size(&self) -> usize {
+        self.state_delta_batch_size
+    }
 }
 
 pub struct GameroomConfigBuilder {
@@ -52,6 +70,9 @@ pub struct GameroomConfigBuilder {
     database_url: Option<String>,
     splinterd_url: Option<String>,
     key: Option<String>,
+    admin_protocol_version: Option<String>,
+    state_delta_batch_window: Option<u64>,
+    state_delta_batch_size: Option<usize>,
 }
 
 impl Default for GameroomConfigBuilder {
@@ -63,6 +84,9 @@ impl Default for GameroomConfigBuilder {
             ),
             splinterd_url: Some("http://127.0.0.1:8080".to_owned()),
             key: None,
+            admin_protocol_version: Some("1".to_owned()),
+            state_delta_batch_window: Some(100),
+            state_delta_batch_size: Some(100),
         }
     }
 }
@@ -89,6 +113,22 @@ impl GameroomConfigBuilder {
                 .value_of("key")
                 .map(ToOwned::to_owned)
                 .or_else(|| self.key.take()),
+
+            admin_protocol_version: matches
+                .value_of("admin_protocol_version")
+                .map(ToOwned::to_owned)
+                .or_else(|| self.admin_protocol_version.take()),
+
+            // The values are validated as integers when the arguments are parsed
+            state_delta_batch_window: matches
+                .value_of("state_delta_batch_window")
+                .and_then(|value| value.parse().ok())
+                .or_else(|| self.state_delta_batch_window.take()),
+
+            state_delta_batch_size: matches
+                .value_of("state_delta_batch_size")
+                .and_then(|value| value.parse().ok())
+                .or_else(|| self.state_delta_batch_size.take()),
         }
     }
 
@@ -110,6 +150,15 @@ impl GameroomConfigBuilder {
                 .key
                 .take()
                 .ok_or_else(|| ConfigurationError::MissingValue("key".to_owned()))?,
+            admin_protocol_version: self.admin_protocol_version.take().ok_or_else(|| {
+                ConfigurationError::MissingValue("admin_protocol_version".to_owned())
+            })?,
+            state_delta_batch_window: self.state_delta_batch_window.take().ok_or_else(|| {
+                ConfigurationError::MissingValue("state_delta_batch_window".to_owned())
+            })?,
+            state_delta_batch_size: self.state_delta_batch_size.take().ok_or_else(|| {
+                ConfigurationError::MissingValue("state_delta_batch_size".to_owned())
+            })?,
         })
     }
 }
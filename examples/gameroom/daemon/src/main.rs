@@ -51,6 +51,9 @@ fn run() -> Result<(), GameroomDaemonError> {
         (@arg bind: -b --bind +takes_value "connection endpoint for Gameroom rest API")
         (@arg splinterd_url: --("splinterd-url") +takes_value "connection endpoint to SplinterD rest API")
         (@arg key: --("key") +takes_value "path to the GameroomD signing key")
+        (@arg admin_protocol_version: --("admin-protocol-version") +takes_value "admin protocol version to request from SplinterD")
+        (@arg state_delta_batch_window: --("state-delta-batch-window") +takes_value {is_integer} "time in milliseconds that scabbard state changes are accumulated before they are committed together")
+        (@arg state_delta_batch_size: --("state-delta-batch-size") +takes_value {is_integer} "maximum number of scabbard state changes committed together")
     )
     .get_matches();
 
@@ -100,7 +103,7 @@ fn run() -> Result<(), GameroomDaemonError> {
     let reactor = Reactor::new();
 
     authorization_handler::run(
-        config.splinterd_url().into(),
+        &config,
         authorization.clone(),
         node.identity.clone(),
         connection_pool.clone(),
@@ -147,6 +150,13 @@ fn run() -> Result<(), GameroomDaemonError> {
     Ok(())
 }
 
+fn is_integer(value: String) -> Result<(), String> {
+    value
+        .parse::<u64>()
+        .map(|_| ())
+        .map_err(|_| format!("{} is not a non-negative integer", value))
+}
+
 fn main() {
     if let Err(e) = run() {
         error!("{}", e);
@@ -0,0 +1,36 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::models::{DeadLetterEvent, NewDeadLetterEvent};
+use crate::schema::dead_letter_event;
+use diesel::{dsl::insert_into, pg::PgConnection, prelude::*, QueryResult};
+
+pub fn insert_dead_letter_event(
+    conn: &PgConnection,
+    event: &NewDeadLetterEvent,
+) -> QueryResult<()> {
+    insert_into(dead_letter_event::table)
+        .values(event)
+        .execute(conn)
+        .map(|_| ())
+}
+
+/// Lists the dead-lettered events, oldest first, so they may be replayed in the order they were
+/// received.
+pub fn list_dead_letter_events(conn: &PgConnection) -> QueryResult<Vec<DeadLetterEvent>> {
+    dead_letter_event::table
+        .select(dead_letter_event::all_columns)
+        .order(dead_letter_event::id.asc())
+        .load::<DeadLetterEvent>(conn)
+}
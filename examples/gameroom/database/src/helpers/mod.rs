@@ -12,10 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod dead_letter;
 mod gameroom;
 mod notification;
+mod processed_event;
 mod xo_games;
 
+pub use dead_letter::{insert_dead_letter_event, list_dead_letter_events};
 pub use gameroom::{
     fetch_active_gamerooms, fetch_gameroom, fetch_gameroom_by_alias,
     fetch_gameroom_members_by_circuit_id_and_status, fetch_gameroom_proposal_with_status,
@@ -32,6 +35,7 @@ pub use notification::{
     get_unread_notification_count, insert_gameroom_notification,
     list_unread_notifications_with_paging, update_gameroom_notification,
 };
+pub use processed_event::{get_last_processed_event_id, update_last_processed_event_id};
 pub use xo_games::{
     fetch_xo_game, get_xo_game_count, insert_xo_game, list_xo_games, update_xo_game,
 };
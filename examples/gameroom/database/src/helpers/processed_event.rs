@@ -0,0 +1,47 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::models::ProcessedEvent;
+use crate::schema::processed_event;
+use diesel::{dsl::insert_into, pg::upsert::excluded, pg::PgConnection, prelude::*, QueryResult};
+
+/// Returns the id of the last admin event processed for the given registration, if any.
+pub fn get_last_processed_event_id(
+    conn: &PgConnection,
+    registration: &str,
+) -> QueryResult<Option<i64>> {
+    processed_event::table
+        .filter(processed_event::registration.eq(registration))
+        .select(processed_event::last_event_id)
+        .first::<i64>(conn)
+        .optional()
+}
+
+/// Records the id of the last admin event processed for a registration, replacing any previous
+/// value.
+pub fn update_last_processed_event_id(
+    conn: &PgConnection,
+    event: &ProcessedEvent,
+) -> QueryResult<()> {
+    insert_into(processed_event::table)
+        .values(event)
+        .on_conflict(processed_event::registration)
+        .do_update()
+        .set((
+            processed_event::last_event_id.eq(excluded(processed_event::last_event_id)),
+            processed_event::updated_time.eq(excluded(processed_event::updated_time)),
+        ))
+        .execute(conn)
+        .map(|_| ())
+}
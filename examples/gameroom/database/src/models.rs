@@ -197,3 +197,28 @@ pub struct ActiveGameroom {
     pub requester: String,
     pub requester_node_id: String,
 }
+
+#[derive(Queryable, Identifiable, PartialEq, Debug)]
+#[table_name = "dead_letter_event"]
+pub struct DeadLetterEvent {
+    pub id: i64,
+    pub payload: String,
+    pub error: String,
+    pub created_time: SystemTime,
+}
+
+#[derive(Debug, Insertable)]
+#[table_name = "dead_letter_event"]
+pub struct NewDeadLetterEvent {
+    pub payload: String,
+    pub error: String,
+    pub created_time: SystemTime,
+}
+
+#[derive(Queryable, Insertable, PartialEq, Debug)]
+#[table_name = "processed_event"]
+pub struct ProcessedEvent {
+    pub registration: String,
+    pub last_event_id: i64,
+    pub updated_time: SystemTime,
+}
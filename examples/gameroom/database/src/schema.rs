@@ -108,4 +108,21 @@ table! {
     }
 }
 
+table! {
+    dead_letter_event (id) {
+        id -> Int8,
+        payload -> Text,
+        error -> Text,
+        created_time -> Timestamp,
+    }
+}
+
+table! {
+    processed_event (registration) {
+        registration -> Text,
+        last_event_id -> Int8,
+        updated_time -> Timestamp,
+    }
+}
+
 allow_tables_to_appear_in_same_query!(gameroom_service, gameroom_proposal);
@@ -68,6 +68,59 @@ fn main() {
         .customize(Customize::default())
         .run()
         .expect("unable to run protoc");
+
+    // Generate the lists of migration versions that are embedded for each database
+    for database in &["postgres", "sqlite"] {
+        write_migration_versions(database, &out_dir);
+    }
+}
+
+/// Writes the versions of the migrations in the given database's migrations directory, in the
+/// order they are run, as a Rust slice expression to `<database>_migration_versions.rs` in the
+/// output directory. The versions are derived from the directory names the same way diesel
+/// derives them when the migrations are embedded.
+fn write_migration_versions(database: &str, out_dir: &str) {
+    let migrations_dir = format!("./src/migrations/diesel/{}/migrations", database);
+
+    let mut versions = fs::read_dir(&migrations_dir)
+        .expect("Unable to read migrations directory")
+        .map(|entry| {
+            entry
+                .expect("Unable to read migrations directory entry")
+                .path()
+        })
+        .filter(|path| path.join("up.sql").is_file())
+        .filter_map(|path| {
+            let name = path
+                .file_name()
+                .expect("Unable to extract migration name")
+                .to_str()
+                .expect("Unable to extract migration name")
+                .to_owned();
+            if name.starts_with('.') {
+                return None;
+            }
+            name.split('_')
+                .next()
+                .map(|version| version.replace('-', ""))
+        })
+        .collect::<Vec<_>>();
+    versions.sort();
+
+    let content = format!(
+        "&[{}]",
+        versions
+            .iter()
+            .map(|version| format!("{:?}", version))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    let mut file =
+        File::create(Path::new(out_dir).join(format!("{}_migration_versions.rs", database)))
+            .expect("Unable to create migration versions file");
+    file.write_all(content.as_bytes())
+        .expect("Unable to write migration versions file");
 }
 
 fn glob_simple(pattern: &str) -> Vec<String> {
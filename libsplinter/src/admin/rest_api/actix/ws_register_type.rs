@@ -328,8 +328,8 @@ impl AdminServiceEventSubscriber for WsAdminServiceEventSubscriber {
     }
 }
 
-// `timestamp` is set to the current time to allow for backward-compatibility, as the
-// `timestamp` is not used by the `AdminServiceStore`.
+// `timestamp` is the time the event was added to the `AdminServiceStore`, so it is the same
+// whenever the event is replayed to a client.
 #[derive(Debug, Serialize, Clone)]
 struct JsonAdminEvent {
     #[serde(serialize_with = "st_as_millis")]
@@ -355,7 +355,7 @@ impl JsonAdminEvent {
     ) -> Result<Self, InvalidStateError> {
         match protocol_version {
             1 => Ok(Self {
-                timestamp: time::SystemTime::now(),
+                timestamp: *event.created_time(),
                 event: None,
                 event_v1: Some(v1::AdminServiceEvent::try_from(event).map_err(|_| {
                     InvalidStateError::with_message(
@@ -366,7 +366,7 @@ impl JsonAdminEvent {
             }),
             // Handles 2
             2 => Ok(Self {
-                timestamp: time::SystemTime::now(),
+                timestamp: *event.created_time(),
                 event: Some(AdminServiceEvent::from(event)),
                 event_v1: None,
                 event_id: Some(*event.event_id()),
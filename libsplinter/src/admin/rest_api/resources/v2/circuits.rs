@@ -81,6 +81,7 @@ impl From<String> for CircuitStatus {
         match &*str {
             "disbanded" => CircuitStatus::Disbanded,
             "abandoned" => CircuitStatus::Abandoned,
+            "deleted" => CircuitStatus::Deleted,
             _ => CircuitStatus::Active,
         }
     }
@@ -92,6 +92,7 @@ impl CreateCircuit {
             admin::Circuit_CircuitStatus::ACTIVE => CircuitStatus::Active,
             admin::Circuit_CircuitStatus::DISBANDED => CircuitStatus::Disbanded,
             admin::Circuit_CircuitStatus::ABANDONED => CircuitStatus::Abandoned,
+            admin::Circuit_CircuitStatus::DELETED => CircuitStatus::Deleted,
             admin::Circuit_CircuitStatus::UNSET_CIRCUIT_STATUS => {
                 debug!("Defaulting `UNSET_CIRCUIT_STATUS` of proposed circuit to `Active`");
                 CircuitStatus::Active
@@ -211,6 +212,9 @@ impl CreateCircuit {
                 CircuitStatus::Abandoned => {
                     circuit.set_circuit_status(admin::Circuit_CircuitStatus::ABANDONED);
                 }
+                CircuitStatus::Deleted => {
+                    circuit.set_circuit_status(admin::Circuit_CircuitStatus::DELETED);
+                }
             };
         }
 
@@ -293,6 +297,9 @@ impl TryInto<admin::Circuit> for CreateCircuit {
             CircuitStatus::Abandoned => {
                 circuit.set_circuit_status(admin::Circuit_CircuitStatus::ABANDONED);
             }
+            CircuitStatus::Deleted => {
+                circuit.set_circuit_status(admin::Circuit_CircuitStatus::DELETED);
+            }
         };
 
         Ok(circuit)
@@ -383,6 +390,7 @@ pub enum CircuitStatus {
     Active,
     Disbanded,
     Abandoned,
+    Deleted,
 }
 
 impl Default for CircuitStatus {
@@ -397,6 +405,7 @@ impl From<&store::CircuitStatus> for CircuitStatus {
             store::CircuitStatus::Active => CircuitStatus::Active,
             store::CircuitStatus::Disbanded => CircuitStatus::Disbanded,
             store::CircuitStatus::Abandoned => CircuitStatus::Abandoned,
+            store::CircuitStatus::Deleted => CircuitStatus::Deleted,
         }
     }
 }
@@ -407,6 +416,7 @@ impl std::fmt::Display for CircuitStatus {
             CircuitStatus::Active => f.write_str("Active"),
             CircuitStatus::Disbanded => f.write_str("Disbanded"),
             CircuitStatus::Abandoned => f.write_str("Abandoned"),
+            CircuitStatus::Deleted => f.write_str("Deleted"),
         }
     }
 }
@@ -662,6 +672,54 @@ impl From<store::CircuitProposal> for CircuitProposal {
     }
 }
 
+impl From<&store::Service> for SplinterService {
+    fn from(service: &store::Service) -> Self {
+        SplinterService {
+            service_id: service.service_id().into(),
+            service_type: service.service_type().into(),
+            allowed_nodes: vec![service.node_id().to_string()],
+            arguments: service.arguments().to_vec(),
+        }
+    }
+}
+
+impl From<&store::CircuitNode> for SplinterNode {
+    fn from(node: &store::CircuitNode) -> Self {
+        SplinterNode {
+            node_id: node.node_id().to_string(),
+            endpoints: node.endpoints().to_vec(),
+            public_key: node
+                .public_key()
+                .clone()
+                .map(|public_key| public_key.into_bytes()),
+        }
+    }
+}
+
+/// Converts a circuit from the admin store into its definition as a `CreateCircuit` message.
+///
+/// The store does not keep the application metadata or comments of a circuit, so the message has
+/// empty application metadata and no comments.
+impl From<store::Circuit> for CreateCircuit {
+    fn from(circuit: store::Circuit) -> Self {
+        CreateCircuit {
+            circuit_id: circuit.circuit_id().into(),
+            roster: circuit.roster().iter().map(SplinterService::from).collect(),
+            members: circuit.members().iter().map(SplinterNode::from).collect(),
+            authorization_type: AuthorizationType::from(circuit.authorization_type()),
+            persistence: PersistenceType::from(circuit.persistence()),
+            durability: DurabilityType::from(circuit.durability()),
+            routes: RouteType::from(circuit.routes()),
+            circuit_management_type: circuit.circuit_management_type().into(),
+            application_metadata: vec![],
+            comments: None,
+            display_name: circuit.display_name().clone(),
+            circuit_version: circuit.circuit_version(),
+            circuit_status: CircuitStatus::from(circuit.circuit_status()),
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub enum ProposalType {
     Create,
@@ -800,3 +858,100 @@ impl From<&store::AdminServiceEvent> for AdminServiceEvent {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::convert::TryFrom;
+
+    use crate::admin::store::{
+        Circuit, CircuitBuilder, CircuitNodeBuilder, ProposedCircuit, ServiceBuilder,
+    };
+    use crate::public_key::PublicKey;
+
+    /// Verify that a circuit from the admin store is converted to a `CreateCircuit` message with
+    /// the circuit's services and members, and that converting the message back results in the
+    /// original circuit.
+    #[test]
+    fn create_circuit_from_store_circuit() {
+        let circuit = CircuitBuilder::new()
+            .with_circuit_id("01234-ABCDE")
+            .with_roster(&[
+                ServiceBuilder::new()
+                    .with_service_id("a000")
+                    .with_service_type("scabbard")
+                    .with_node_id("node-a")
+                    .with_arguments(&[("peer_services".into(), "[\"a001\"]".into())])
+                    .build()
+                    .expect("Unable to build service"),
+                ServiceBuilder::new()
+                    .with_service_id("a001")
+                    .with_service_type("scabbard")
+                    .with_node_id("node-b")
+                    .with_arguments(&[("peer_services".into(), "[\"a000\"]".into())])
+                    .build()
+                    .expect("Unable to build service"),
+            ])
+            .with_members(&[
+                CircuitNodeBuilder::new()
+                    .with_node_id("node-a")
+                    .with_endpoints(&["tcps://node-a:8044".into()])
+                    .with_public_key(&PublicKey::from_bytes(vec![1, 2, 3]))
+                    .build()
+                    .expect("Unable to build node"),
+                CircuitNodeBuilder::new()
+                    .with_node_id("node-b")
+                    .with_endpoints(&["tcps://node-b:8044".into()])
+                    .build()
+                    .expect("Unable to build node"),
+            ])
+            .with_circuit_management_type("test")
+            .with_display_name("Test Circuit")
+            .with_circuit_version(2)
+            .build()
+            .expect("Unable to build circuit");
+
+        let create_circuit = CreateCircuit::from(circuit.clone());
+
+        assert_eq!(create_circuit.circuit_id, "01234-ABCDE");
+        assert_eq!(
+            create_circuit.roster,
+            vec![
+                SplinterService {
+                    service_id: "a000".into(),
+                    service_type: "scabbard".into(),
+                    allowed_nodes: vec!["node-a".into()],
+                    arguments: vec![("peer_services".into(), "[\"a001\"]".into())],
+                },
+                SplinterService {
+                    service_id: "a001".into(),
+                    service_type: "scabbard".into(),
+                    allowed_nodes: vec!["node-b".into()],
+                    arguments: vec![("peer_services".into(), "[\"a000\"]".into())],
+                },
+            ]
+        );
+        assert_eq!(
+            create_circuit.members,
+            vec![
+                SplinterNode {
+                    node_id: "node-a".into(),
+                    endpoints: vec!["tcps://node-a:8044".into()],
+                    public_key: Some(vec![1, 2, 3]),
+                },
+                SplinterNode {
+                    node_id: "node-b".into(),
+                    endpoints: vec!["tcps://node-b:8044".into()],
+                    public_key: None,
+                },
+            ]
+        );
+        assert_eq!(create_circuit.display_name, Some("Test Circuit".into()));
+        assert_eq!(create_circuit.circuit_version, 2);
+
+        let proposed_circuit =
+            ProposedCircuit::try_from(&create_circuit).expect("Unable to convert message");
+        assert_eq!(Circuit::from(proposed_circuit), circuit);
+    }
+}
@@ -317,6 +317,9 @@ pub enum CircuitStatus {
     Active,
     Disbanded,
     Abandoned,
+    /// A tombstone for a circuit that has been deleted, which is retained in the store to keep
+    /// its history
+    Deleted,
 }
 
 impl Default for CircuitStatus {
@@ -331,6 +334,7 @@ impl From<&messages::CircuitStatus> for CircuitStatus {
             messages::CircuitStatus::Active => CircuitStatus::Active,
             messages::CircuitStatus::Disbanded => CircuitStatus::Disbanded,
             messages::CircuitStatus::Abandoned => CircuitStatus::Abandoned,
+            messages::CircuitStatus::Deleted => CircuitStatus::Deleted,
         }
     }
 }
@@ -343,6 +347,7 @@ impl TryFrom<&admin::Circuit_CircuitStatus> for CircuitStatus {
             admin::Circuit_CircuitStatus::ACTIVE => Ok(CircuitStatus::Active),
             admin::Circuit_CircuitStatus::DISBANDED => Ok(CircuitStatus::Disbanded),
             admin::Circuit_CircuitStatus::ABANDONED => Ok(CircuitStatus::Abandoned),
+            admin::Circuit_CircuitStatus::DELETED => Ok(CircuitStatus::Deleted),
             admin::Circuit_CircuitStatus::UNSET_CIRCUIT_STATUS => {
                 debug!("Defaulting `UNSET_CIRCUIT_STATUS` of proposed circuit to `Active`");
                 Ok(CircuitStatus::Active)
@@ -357,10 +362,113 @@ impl From<&CircuitStatus> for admin::Circuit_CircuitStatus {
             CircuitStatus::Active => admin::Circuit_CircuitStatus::ACTIVE,
             CircuitStatus::Disbanded => admin::Circuit_CircuitStatus::DISBANDED,
             CircuitStatus::Abandoned => admin::Circuit_CircuitStatus::ABANDONED,
+            CircuitStatus::Deleted => admin::Circuit_CircuitStatus::DELETED,
         }
     }
 }
 
+/// The field by which circuits are ordered when listed
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CircuitOrder {
+    /// Order by circuit ID
+    Id,
+    /// Order by circuit management type
+    ManagementType,
+    /// Order by circuit version
+    CircuitVersion,
+}
+
+impl Default for CircuitOrder {
+    fn default() -> Self {
+        CircuitOrder::Id
+    }
+}
+
+/// The direction in which listed circuits are ordered
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CircuitOrderDirection {
+    Asc,
+    Desc,
+}
+
+impl Default for CircuitOrderDirection {
+    fn default() -> Self {
+        CircuitOrderDirection::Asc
+    }
+}
+
+/// Options used to list a page of circuits from the admin store
+///
+/// By default, all circuits are listed in ascending order of circuit ID. Circuits that are equal
+/// in the chosen field are always ordered by ascending circuit ID, so that pages do not overlap.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct CircuitQueryOptions {
+    offset: i64,
+    limit: Option<i64>,
+    order: CircuitOrder,
+    direction: CircuitOrderDirection,
+}
+
+impl CircuitQueryOptions {
+    /// Creates a new `CircuitQueryOptions` that lists all circuits in ascending order of
+    /// circuit ID
+    pub fn new() -> Self {
+        CircuitQueryOptions::default()
+    }
+
+    /// Sets the number of circuits skipped before the first circuit listed
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - the number of circuits to skip
+    pub fn with_offset(mut self, offset: i64) -> CircuitQueryOptions {
+        self.offset = offset;
+        self
+    }
+
+    /// Sets the maximum number of circuits listed
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - the maximum number of circuits
+    pub fn with_limit(mut self, limit: i64) -> CircuitQueryOptions {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sets the order in which circuits are listed
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - the field the circuits are ordered by
+    /// * `direction` - whether the circuits are listed in ascending or descending order
+    pub fn with_order(
+        mut self,
+        order: CircuitOrder,
+        direction: CircuitOrderDirection,
+    ) -> CircuitQueryOptions {
+        self.order = order;
+        self.direction = direction;
+        self
+    }
+
+    pub fn offset(&self) -> i64 {
+        self.offset
+    }
+
+    pub fn limit(&self) -> Option<i64> {
+        self.limit
+    }
+
+    pub fn order(&self) -> CircuitOrder {
+        self.order
+    }
+
+    pub fn direction(&self) -> CircuitOrderDirection {
+        self.direction
+    }
+}
+
 /// Builder to be used to build a `Circuit`
 #[derive(Default, Clone)]
 pub struct CircuitBuilder {
@@ -71,6 +71,21 @@ impl CircuitProposal {
         &self.requester_node_id
     }
 
+    /// Returns `true` if the circuit hash of the proposal matches the hash computed from the
+    /// proposed circuit
+    pub fn verify_hash(&self) -> bool {
+        match self.circuit.compute_hash() {
+            Ok(hash) => hash == self.circuit_hash,
+            Err(err) => {
+                error!(
+                    "Unable to compute hash of proposal {}: {}",
+                    self.circuit_id, err
+                );
+                false
+            }
+        }
+    }
+
     pub fn builder(&self) -> CircuitProposalBuilder {
         CircuitProposalBuilder::new()
             .with_proposal_type(self.proposal_type())
@@ -475,6 +490,47 @@ impl VoteRecordBuilder {
     }
 }
 
+/// The tally of the votes on a circuit proposal
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VoteSummary {
+    accept_count: u64,
+    reject_count: u64,
+    outstanding_member_ids: Vec<String>,
+}
+
+impl VoteSummary {
+    /// Creates a new vote summary
+    ///
+    /// # Arguments
+    ///
+    /// * `accept_count` - The number of accept votes
+    /// * `reject_count` - The number of reject votes
+    /// * `outstanding_member_ids` - The IDs of the members that have not voted
+    pub fn new(accept_count: u64, reject_count: u64, outstanding_member_ids: Vec<String>) -> Self {
+        VoteSummary {
+            accept_count,
+            reject_count,
+            outstanding_member_ids,
+        }
+    }
+
+    /// Returns the number of accept votes
+    pub fn accept_count(&self) -> u64 {
+        self.accept_count
+    }
+
+    /// Returns the number of reject votes
+    pub fn reject_count(&self) -> u64 {
+        self.reject_count
+    }
+
+    /// Returns the IDs of the members that have not voted, in the order they are listed in the
+    /// proposed circuit
+    pub fn outstanding_member_ids(&self) -> &[String] {
+        &self.outstanding_member_ids
+    }
+}
+
 /// Represents a vote, either accept or reject, for a circuit proposal
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Vote {
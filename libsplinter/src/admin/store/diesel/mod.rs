@@ -25,43 +25,75 @@ mod models;
 mod operations;
 mod schema;
 
-use std::sync::{Arc, RwLock};
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
 
 use diesel::r2d2::{ConnectionManager, Pool};
 
 use crate::admin::messages;
 use crate::admin::store::{
     error::AdminServiceStoreError, AdminServiceStore, Circuit, CircuitNode, CircuitPredicate,
-    CircuitProposal, Service, ServiceId,
+    CircuitProposal, CircuitQueryOptions, CircuitStatus, Service, ServiceId, StorageStats,
+    VoteRecord, VoteSummary,
 };
-use crate::admin::store::{AdminServiceEvent, EventIter};
+use crate::admin::store::{AdminServiceEvent, EventIter, EventQueryOptions, EventStream};
+use crate::error::InvalidStateError;
 use crate::store::pool::ConnectionPool;
 
 use operations::add_circuit::AdminServiceStoreAddCircuitOperation as _;
 use operations::add_event::AdminServiceStoreAddEventOperation as _;
 use operations::add_proposal::AdminServiceStoreAddProposalOperation as _;
+use operations::add_proposal_with_event::AdminServiceStoreAddProposalWithEventOperation as _;
+use operations::append_vote::AdminServiceStoreAppendVoteOperation as _;
 use operations::count_circuits::AdminServiceStoreCountCircuitsOperation as _;
 use operations::count_proposals::AdminServiceStoreCountProposalsOperation as _;
+use operations::count_services_by_type::AdminServiceStoreCountServicesByTypeOperation as _;
+use operations::delete_circuit::AdminServiceStoreDeleteCircuitOperation as _;
 use operations::get_circuit::AdminServiceStoreFetchCircuitOperation as _;
+use operations::get_circuits::AdminServiceStoreFetchCircuitsOperation as _;
 use operations::get_node::AdminServiceStoreFetchNodeOperation as _;
 use operations::get_proposal::AdminServiceStoreFetchProposalOperation as _;
+use operations::get_proposal_with_circuit::AdminServiceStoreFetchProposalWithCircuitOperation as _;
 use operations::get_service::AdminServiceStoreFetchServiceOperation as _;
+use operations::list_circuit_management_types::AdminServiceStoreListCircuitManagementTypesOperation as _;
 use operations::list_circuits::AdminServiceStoreListCircuitsOperation as _;
-use operations::list_events_by_management_type_since::AdminServiceStoreListEventsByManagementTypeSinceOperation as _;
-use operations::list_events_since::AdminServiceStoreListEventsSinceOperation as _;
+use operations::list_circuits_updated_since::AdminServiceStoreListCircuitsUpdatedSinceOperation as _;
+use operations::list_events_with_options::AdminServiceStoreListEventsWithOptionsOperation as _;
+use operations::list_for_requester_node::AdminServiceStoreListForRequesterNodeOperation as _;
 use operations::list_nodes::AdminServiceStoreListNodesOperation as _;
+use operations::list_orphaned_nodes::AdminServiceStoreListOrphanedNodesOperation as _;
+use operations::list_proposal_management_types::AdminServiceStoreListProposalManagementTypesOperation as _;
 use operations::list_proposals::AdminServiceStoreListProposalsOperation as _;
+use operations::list_proposals_pending_vote_from::AdminServiceStoreListProposalsPendingVoteFromOperation as _;
+use operations::list_proposals_updated_since::AdminServiceStoreListProposalsUpdatedSinceOperation as _;
 use operations::list_services::AdminServiceStoreListServicesOperation as _;
+use operations::proposal_vote_summary::AdminServiceStoreProposalVoteSummaryOperation as _;
 use operations::remove_circuit::AdminServiceStoreRemoveCircuitOperation as _;
+use operations::remove_orphaned_nodes::AdminServiceStoreRemoveOrphanedNodesOperation as _;
 use operations::remove_proposal::AdminServiceStoreRemoveProposalOperation as _;
+use operations::remove_proposals_by_management_type::AdminServiceStoreRemoveProposalsByManagementTypeOperation as _;
+use operations::service_exists::AdminServiceStoreServiceExistsOperation as _;
+use operations::set_circuit_status::AdminServiceStoreSetCircuitStatusOperation as _;
+use operations::storage_stats::AdminServiceStoreStorageStatsOperation as _;
 use operations::update_circuit::AdminServiceStoreUpdateCircuitOperation as _;
 use operations::update_proposal::AdminServiceStoreUpdateProposalOperation as _;
 use operations::upgrade::AdminServiceStoreUpgradeProposalToCircuitOperation as _;
 use operations::AdminServiceStoreOperations;
 
+/// The number of events loaded at a time by the stream returned from `stream_events_since`.
+const EVENT_STREAM_BATCH_SIZE: i64 = 500;
+
 /// A database-backed AdminServiceStore, powered by [`Diesel`](https://crates.io/crates/diesel).
 pub struct DieselAdminServiceStore<C: diesel::Connection + 'static> {
     connection_pool: ConnectionPool<C>,
+    event_subscribers: Arc<Mutex<Vec<Sender<AdminServiceEvent>>>>,
+    verify_circuit_hashes: bool,
+    max_roster_size: Option<usize>,
+    /// The statuses of the circuits listed or counted when no status predicate is given, or
+    /// `None` for every status
+    default_circuit_statuses: Option<Vec<CircuitStatus>>,
 }
 
 impl<C: diesel::Connection> DieselAdminServiceStore<C> {
@@ -73,6 +105,10 @@ impl<C: diesel::Connection> DieselAdminServiceStore<C> {
     pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
         DieselAdminServiceStore {
             connection_pool: connection_pool.into(),
+            event_subscribers: Arc::new(Mutex::new(Vec::new())),
+            verify_circuit_hashes: false,
+            max_roster_size: None,
+            default_circuit_statuses: Some(vec![CircuitStatus::Active]),
         }
     }
 
@@ -89,6 +125,186 @@ impl<C: diesel::Connection> DieselAdminServiceStore<C> {
     ) -> Self {
         Self {
             connection_pool: connection_pool.into(),
+            event_subscribers: Arc::new(Mutex::new(Vec::new())),
+            verify_circuit_hashes: false,
+            max_roster_size: None,
+            default_circuit_statuses: Some(vec![CircuitStatus::Active]),
+        }
+    }
+
+    /// Enables or disables verification of proposal circuit hashes.
+    ///
+    /// When enabled, `add_proposal` and `update_proposal` reject a proposal whose circuit hash
+    /// does not match the hash computed from its proposed circuit.
+    pub fn with_circuit_hash_verification(mut self, verify_circuit_hashes: bool) -> Self {
+        self.verify_circuit_hashes = verify_circuit_hashes;
+        self
+    }
+
+    /// Sets the maximum number of services in the roster of a circuit or proposed circuit.
+    ///
+    /// When set, `add_circuit` and `add_proposal` reject a circuit whose roster has more services
+    /// than the maximum. By default, the roster size is not limited.
+    pub fn with_max_roster_size(mut self, max_roster_size: usize) -> Self {
+        self.max_roster_size = Some(max_roster_size);
+        self
+    }
+
+    /// Enables retrying writes that fail because an SQLite database is busy or locked by another
+    /// connection.
+    ///
+    /// A failed write is retried up to `max_retries` times, waiting `initial_backoff` before the
+    /// first retry and doubling the wait before each following retry. By default, such writes are
+    /// not retried. Writes to a PostgreSQL database are unaffected.
+    pub fn with_busy_retry(mut self, max_retries: u32, initial_backoff: Duration) -> Self {
+        self.connection_pool = self
+            .connection_pool
+            .with_busy_retry(max_retries, initial_backoff);
+        self
+    }
+
+    /// Sets the statuses of the circuits returned by `list_circuits` and `count_circuits` when no
+    /// `CircuitPredicate::CircuitStatus` predicate is given.
+    ///
+    /// By default, only active circuits are returned.
+    pub fn with_default_circuit_statuses(mut self, statuses: Vec<CircuitStatus>) -> Self {
+        self.default_circuit_statuses = Some(statuses);
+        self
+    }
+
+    /// Includes circuits of every status, including deleted circuits, in `list_circuits` and
+    /// `count_circuits` when no `CircuitPredicate::CircuitStatus` predicate is given.
+    pub fn with_all_circuit_statuses_by_default(mut self) -> Self {
+        self.default_circuit_statuses = None;
+        self
+    }
+
+    /// Returns the given predicates along with the default circuit status predicates, if the
+    /// given predicates do not filter by circuit status.
+    fn with_default_status_predicates(
+        &self,
+        predicates: &[CircuitPredicate],
+    ) -> Vec<CircuitPredicate> {
+        let mut predicates = predicates.to_vec();
+        if !predicates
+            .iter()
+            .any(|predicate| matches!(predicate, CircuitPredicate::CircuitStatus(_)))
+        {
+            let statuses = self.default_circuit_statuses.clone().unwrap_or_else(|| {
+                vec![
+                    CircuitStatus::Active,
+                    CircuitStatus::Disbanded,
+                    CircuitStatus::Abandoned,
+                    CircuitStatus::Deleted,
+                ]
+            });
+            predicates.extend(statuses.into_iter().map(CircuitPredicate::CircuitStatus));
+        }
+        predicates
+    }
+
+    /// Checks the size of a circuit's roster, if a maximum roster size is set.
+    fn check_roster_size(
+        &self,
+        circuit_id: &str,
+        roster_size: usize,
+    ) -> Result<(), AdminServiceStoreError> {
+        match self.max_roster_size {
+            Some(max_roster_size) if roster_size > max_roster_size => {
+                Err(AdminServiceStoreError::InvalidStateError(
+                    InvalidStateError::with_message(format!(
+                        "Roster of circuit {} has {} services, more than the maximum of {}",
+                        circuit_id, roster_size, max_roster_size
+                    )),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the circuit hash of the proposal, if circuit hash verification is enabled.
+    fn check_circuit_hash(&self, proposal: &CircuitProposal) -> Result<(), AdminServiceStoreError> {
+        if self.verify_circuit_hashes && !proposal.verify_hash() {
+            return Err(AdminServiceStoreError::InvalidStateError(
+                InvalidStateError::with_message(format!(
+                    "Circuit hash of proposal {} does not match its proposed circuit",
+                    proposal.circuit_id()
+                )),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Sends the event to each live subscriber, dropping any whose receiver has been dropped.
+    fn notify_event_subscribers(&self, event: &AdminServiceEvent) {
+        self.event_subscribers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+    }
+
+    fn add_event_subscriber(&self) -> Receiver<AdminServiceEvent> {
+        let (sender, receiver) = channel();
+        self.event_subscribers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(sender);
+        receiver
+    }
+}
+
+/// An iterator over events that loads them in batches, ordered by event ID.
+///
+/// Each batch is loaded by `load_batch`, given the ID of the last event returned so far. A batch
+/// smaller than `EVENT_STREAM_BATCH_SIZE` is the last one.
+struct BatchedEventStream<F> {
+    load_batch: F,
+    last_event_id: i64,
+    batch: Option<EventIter>,
+    finished: bool,
+}
+
+impl<F> BatchedEventStream<F>
+where
+    F: FnMut(i64) -> Result<EventIter, AdminServiceStoreError>,
+{
+    fn new(start: i64, load_batch: F) -> Self {
+        Self {
+            load_batch,
+            last_event_id: start,
+            batch: None,
+            finished: false,
+        }
+    }
+}
+
+impl<F> Iterator for BatchedEventStream<F>
+where
+    F: FnMut(i64) -> Result<EventIter, AdminServiceStoreError>,
+{
+    type Item = Result<AdminServiceEvent, AdminServiceStoreError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.batch.as_mut().and_then(|batch| batch.next()) {
+                self.last_event_id = *event.event_id();
+                return Some(Ok(event));
+            }
+
+            if self.finished {
+                return None;
+            }
+
+            match (self.load_batch)(self.last_event_id) {
+                Ok(batch) => {
+                    self.finished = (batch.len() as i64) < EVENT_STREAM_BATCH_SIZE;
+                    self.batch = Some(batch);
+                }
+                Err(err) => {
+                    self.finished = true;
+                    return Some(Err(err));
+                }
+            }
         }
     }
 }
@@ -98,6 +314,10 @@ impl Clone for DieselAdminServiceStore<diesel::sqlite::SqliteConnection> {
     fn clone(&self) -> Self {
         Self {
             connection_pool: self.connection_pool.clone(),
+            event_subscribers: self.event_subscribers.clone(),
+            verify_circuit_hashes: self.verify_circuit_hashes,
+            max_roster_size: self.max_roster_size,
+            default_circuit_statuses: self.default_circuit_statuses.clone(),
         }
     }
 }
@@ -107,6 +327,10 @@ impl Clone for DieselAdminServiceStore<diesel::pg::PgConnection> {
     fn clone(&self) -> Self {
         Self {
             connection_pool: self.connection_pool.clone(),
+            event_subscribers: self.event_subscribers.clone(),
+            verify_circuit_hashes: self.verify_circuit_hashes,
+            max_roster_size: self.max_roster_size,
+            default_circuit_statuses: self.default_circuit_statuses.clone(),
         }
     }
 }
@@ -114,13 +338,62 @@ impl Clone for DieselAdminServiceStore<diesel::pg::PgConnection> {
 #[cfg(feature = "postgres")]
 impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
     fn add_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError> {
+        self.check_roster_size(proposal.circuit_id(), proposal.circuit().roster().len())?;
+        self.check_circuit_hash(&proposal)?;
         self.connection_pool
-            .execute_write(|conn| AdminServiceStoreOperations::new(conn).add_proposal(proposal))
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).add_proposal(&proposal))
+    }
+
+    fn add_proposal_with_event(
+        &self,
+        proposal: CircuitProposal,
+        event: messages::AdminServiceEvent,
+    ) -> Result<AdminServiceEvent, AdminServiceStoreError> {
+        self.check_roster_size(proposal.circuit_id(), proposal.circuit().roster().len())?;
+        self.check_circuit_hash(&proposal)?;
+        let event = self.connection_pool.execute_write(|conn| {
+            AdminServiceStoreOperations::new(conn).add_proposal_with_event(&proposal, &event)
+        })?;
+        self.notify_event_subscribers(&event);
+        Ok(event)
     }
 
     fn update_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError> {
+        self.check_circuit_hash(&proposal)?;
         self.connection_pool
-            .execute_write(|conn| AdminServiceStoreOperations::new(conn).update_proposal(proposal))
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).update_proposal(&proposal))
+    }
+
+    fn update_proposal_returning_previous(
+        &self,
+        proposal: CircuitProposal,
+    ) -> Result<Option<CircuitProposal>, AdminServiceStoreError> {
+        self.check_circuit_hash(&proposal)?;
+        self.connection_pool.execute_write(|conn| {
+            AdminServiceStoreOperations::new(conn).update_proposal_returning_previous(&proposal)
+        })
+    }
+
+    fn update_proposal_if_unchanged(
+        &self,
+        proposal: CircuitProposal,
+        expected_hash: &str,
+    ) -> Result<(), AdminServiceStoreError> {
+        self.check_circuit_hash(&proposal)?;
+        self.connection_pool.execute_write(|conn| {
+            AdminServiceStoreOperations::new(conn)
+                .update_proposal_if_unchanged(&proposal, expected_hash)
+        })
+    }
+
+    fn append_vote(
+        &self,
+        proposal_id: &str,
+        vote: VoteRecord,
+    ) -> Result<CircuitProposal, AdminServiceStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            AdminServiceStoreOperations::new(conn).append_vote(proposal_id, &vote)
+        })
     }
 
     fn remove_proposal(&self, proposal_id: &str) -> Result<(), AdminServiceStoreError> {
@@ -129,6 +402,16 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
         })
     }
 
+    fn remove_proposals_by_management_type(
+        &self,
+        management_type: &str,
+    ) -> Result<u64, AdminServiceStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            AdminServiceStoreOperations::new(conn)
+                .remove_proposals_by_management_type(management_type)
+        })
+    }
+
     fn get_proposal(
         &self,
         proposal_id: &str,
@@ -137,6 +420,15 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
             .execute_read(|conn| AdminServiceStoreOperations::new(conn).get_proposal(proposal_id))
     }
 
+    fn get_proposal_with_circuit(
+        &self,
+        id: &str,
+    ) -> Result<Option<(Option<CircuitProposal>, Option<Circuit>)>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).get_proposal_with_circuit(id)
+        })
+    }
+
     fn list_proposals(
         &self,
         predicates: &[CircuitPredicate],
@@ -153,19 +445,72 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
             .execute_read(|conn| AdminServiceStoreOperations::new(conn).count_proposals(predicates))
     }
 
+    fn list_proposals_pending_vote_from(
+        &self,
+        node_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_proposals_pending_vote_from(node_id)
+        })
+    }
+
+    fn list_for_requester_node(
+        &self,
+        node_id: &str,
+    ) -> Result<(Vec<CircuitProposal>, Vec<Circuit>), AdminServiceStoreError> {
+        let predicates = self.with_default_status_predicates(&[]);
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_for_requester_node(node_id, &predicates)
+        })
+    }
+
+    fn proposal_vote_summary(
+        &self,
+        proposal_id: &str,
+    ) -> Result<Option<VoteSummary>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).proposal_vote_summary(proposal_id)
+        })
+    }
+
+    fn list_proposals_updated_since(
+        &self,
+        since: i64,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_proposals_updated_since(since)
+        })
+    }
+
+    fn list_proposal_management_types(&self) -> Result<Vec<String>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_proposal_management_types()
+        })
+    }
+
     fn add_circuit(
         &self,
         circuit: Circuit,
         nodes: Vec<CircuitNode>,
     ) -> Result<(), AdminServiceStoreError> {
+        self.check_roster_size(circuit.circuit_id(), circuit.roster().len())?;
         self.connection_pool.execute_write(|conn| {
-            AdminServiceStoreOperations::new(conn).add_circuit(circuit, nodes)
+            AdminServiceStoreOperations::new(conn).add_circuit(&circuit, &nodes)
         })
     }
 
     fn update_circuit(&self, circuit: Circuit) -> Result<(), AdminServiceStoreError> {
         self.connection_pool
-            .execute_write(|conn| AdminServiceStoreOperations::new(conn).update_circuit(circuit))
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).update_circuit(&circuit))
+    }
+
+    fn update_circuit_returning_previous(
+        &self,
+        circuit: Circuit,
+    ) -> Result<Option<Circuit>, AdminServiceStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            AdminServiceStoreOperations::new(conn).update_circuit_returning_previous(&circuit)
+        })
     }
 
     fn remove_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
@@ -173,25 +518,76 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
             .execute_write(|conn| AdminServiceStoreOperations::new(conn).remove_circuit(circuit_id))
     }
 
+    fn delete_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).delete_circuit(circuit_id))
+    }
+
+    fn set_circuit_status(
+        &self,
+        circuit_id: &str,
+        status: CircuitStatus,
+    ) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            AdminServiceStoreOperations::new(conn).set_circuit_status(circuit_id, &status)
+        })
+    }
+
     fn get_circuit(&self, circuit_id: &str) -> Result<Option<Circuit>, AdminServiceStoreError> {
         self.connection_pool
             .execute_read(|conn| AdminServiceStoreOperations::new(conn).get_circuit(circuit_id))
     }
 
+    fn get_circuits(
+        &self,
+        circuit_ids: &[&str],
+    ) -> Result<HashMap<String, Circuit>, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).get_circuits(circuit_ids))
+    }
+
     fn list_circuits(
         &self,
         predicates: &[CircuitPredicate],
     ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        let predicates = self.with_default_status_predicates(predicates);
         self.connection_pool
-            .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_circuits(predicates))
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_circuits(&predicates))
+    }
+
+    fn list_circuits_page(
+        &self,
+        predicates: &[CircuitPredicate],
+        options: &CircuitQueryOptions,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        let predicates = self.with_default_status_predicates(predicates);
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_circuits_page(&predicates, options)
+        })
     }
 
     fn count_circuits(
         &self,
         predicates: &[CircuitPredicate],
     ) -> Result<u32, AdminServiceStoreError> {
+        let predicates = self.with_default_status_predicates(predicates);
         self.connection_pool
-            .execute_read(|conn| AdminServiceStoreOperations::new(conn).count_circuits(predicates))
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).count_circuits(&predicates))
+    }
+
+    fn list_circuits_updated_since(
+        &self,
+        since: i64,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_circuits_updated_since(since)
+        })
+    }
+
+    fn list_circuit_management_types(&self) -> Result<Vec<String>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_circuit_management_types()
+        })
     }
 
     fn upgrade_proposal_to_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
@@ -212,6 +608,16 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
             .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_nodes())
     }
 
+    fn list_orphaned_nodes(&self) -> Result<Vec<CircuitNode>, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_orphaned_nodes())
+    }
+
+    fn remove_orphaned_nodes(&self) -> Result<u64, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).remove_orphaned_nodes())
+    }
+
     fn get_service(
         &self,
         service_id: &ServiceId,
@@ -220,6 +626,11 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
             .execute_read(|conn| AdminServiceStoreOperations::new(conn).get_service(service_id))
     }
 
+    fn service_exists(&self, service_id: &ServiceId) -> Result<bool, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).service_exists(service_id))
+    }
+
     fn list_services(
         &self,
         circuit_id: &str,
@@ -228,30 +639,50 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
             .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_services(circuit_id))
     }
 
-    fn add_event(
-        &self,
-        event: messages::AdminServiceEvent,
-    ) -> Result<AdminServiceEvent, AdminServiceStoreError> {
+    fn count_services_by_type(&self) -> Result<HashMap<String, u64>, AdminServiceStoreError> {
         self.connection_pool
-            .execute_write(|conn| AdminServiceStoreOperations::new(conn).add_event(event))
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).count_services_by_type())
     }
 
-    fn list_events_since(&self, start: i64) -> Result<EventIter, AdminServiceStoreError> {
+    fn storage_stats(&self) -> Result<StorageStats, AdminServiceStoreError> {
         self.connection_pool
-            .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_events_since(start))
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).storage_stats())
     }
 
-    fn list_events_by_management_type_since(
+    fn add_event(
         &self,
-        management_type: String,
-        start: i64,
-    ) -> Result<EventIter, AdminServiceStoreError> {
+        event: messages::AdminServiceEvent,
+    ) -> Result<AdminServiceEvent, AdminServiceStoreError> {
+        let event = self
+            .connection_pool
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).add_event(&event))?;
+        self.notify_event_subscribers(&event);
+        Ok(event)
+    }
+
+    fn list_events(&self, options: EventQueryOptions) -> Result<EventIter, AdminServiceStoreError> {
         self.connection_pool.execute_read(|conn| {
-            AdminServiceStoreOperations::new(conn)
-                .list_events_by_management_type_since(management_type, start)
+            AdminServiceStoreOperations::new(conn).list_events_with_options(&options)
         })
     }
 
+    fn stream_events_since(&self, start: i64) -> EventStream {
+        let store = self.clone();
+        Box::new(BatchedEventStream::new(start, move |last_event_id| {
+            store.connection_pool.execute_read(|conn| {
+                AdminServiceStoreOperations::new(conn).list_events_with_options(
+                    &EventQueryOptions::new()
+                        .with_start(last_event_id)
+                        .with_limit(EVENT_STREAM_BATCH_SIZE),
+                )
+            })
+        }))
+    }
+
+    fn subscribe_to_events(&self) -> Receiver<AdminServiceEvent> {
+        self.add_event_subscriber()
+    }
+
     fn clone_boxed(&self) -> Box<dyn AdminServiceStore> {
         Box::new(self.clone())
     }
@@ -260,21 +691,82 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
 #[cfg(feature = "sqlite")]
 impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnection> {
     fn add_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError> {
-        self.connection_pool
-            .execute_write(|conn| AdminServiceStoreOperations::new(conn).add_proposal(proposal))
+        self.check_roster_size(proposal.circuit_id(), proposal.circuit().roster().len())?;
+        self.check_circuit_hash(&proposal)?;
+        self.connection_pool.execute_write_with_retry(|conn| {
+            AdminServiceStoreOperations::new(conn).add_proposal(&proposal)
+        })
+    }
+
+    fn add_proposal_with_event(
+        &self,
+        proposal: CircuitProposal,
+        event: messages::AdminServiceEvent,
+    ) -> Result<AdminServiceEvent, AdminServiceStoreError> {
+        self.check_roster_size(proposal.circuit_id(), proposal.circuit().roster().len())?;
+        self.check_circuit_hash(&proposal)?;
+        let event = self.connection_pool.execute_write_with_retry(|conn| {
+            AdminServiceStoreOperations::new(conn).add_proposal_with_event(&proposal, &event)
+        })?;
+        self.notify_event_subscribers(&event);
+        Ok(event)
     }
 
     fn update_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError> {
-        self.connection_pool
-            .execute_write(|conn| AdminServiceStoreOperations::new(conn).update_proposal(proposal))
+        self.check_circuit_hash(&proposal)?;
+        self.connection_pool.execute_write_with_retry(|conn| {
+            AdminServiceStoreOperations::new(conn).update_proposal(&proposal)
+        })
+    }
+
+    fn update_proposal_returning_previous(
+        &self,
+        proposal: CircuitProposal,
+    ) -> Result<Option<CircuitProposal>, AdminServiceStoreError> {
+        self.check_circuit_hash(&proposal)?;
+        self.connection_pool.execute_write_with_retry(|conn| {
+            AdminServiceStoreOperations::new(conn).update_proposal_returning_previous(&proposal)
+        })
+    }
+
+    fn update_proposal_if_unchanged(
+        &self,
+        proposal: CircuitProposal,
+        expected_hash: &str,
+    ) -> Result<(), AdminServiceStoreError> {
+        self.check_circuit_hash(&proposal)?;
+        self.connection_pool.execute_write_with_retry(|conn| {
+            AdminServiceStoreOperations::new(conn)
+                .update_proposal_if_unchanged(&proposal, expected_hash)
+        })
+    }
+
+    fn append_vote(
+        &self,
+        proposal_id: &str,
+        vote: VoteRecord,
+    ) -> Result<CircuitProposal, AdminServiceStoreError> {
+        self.connection_pool.execute_write_with_retry(|conn| {
+            AdminServiceStoreOperations::new(conn).append_vote(proposal_id, &vote)
+        })
     }
 
     fn remove_proposal(&self, proposal_id: &str) -> Result<(), AdminServiceStoreError> {
-        self.connection_pool.execute_write(|conn| {
+        self.connection_pool.execute_write_with_retry(|conn| {
             AdminServiceStoreOperations::new(conn).remove_proposal(proposal_id)
         })
     }
 
+    fn remove_proposals_by_management_type(
+        &self,
+        management_type: &str,
+    ) -> Result<u64, AdminServiceStoreError> {
+        self.connection_pool.execute_write_with_retry(|conn| {
+            AdminServiceStoreOperations::new(conn)
+                .remove_proposals_by_management_type(management_type)
+        })
+    }
+
     fn get_proposal(
         &self,
         proposal_id: &str,
@@ -283,6 +775,15 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnect
             .execute_read(|conn| AdminServiceStoreOperations::new(conn).get_proposal(proposal_id))
     }
 
+    fn get_proposal_with_circuit(
+        &self,
+        id: &str,
+    ) -> Result<Option<(Option<CircuitProposal>, Option<Circuit>)>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).get_proposal_with_circuit(id)
+        })
+    }
+
     fn list_proposals(
         &self,
         predicates: &[CircuitPredicate],
@@ -299,50 +800,157 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnect
             .execute_read(|conn| AdminServiceStoreOperations::new(conn).count_proposals(predicates))
     }
 
-    fn add_circuit(
+    fn list_proposals_pending_vote_from(
         &self,
-        circuit: Circuit,
-        nodes: Vec<CircuitNode>,
-    ) -> Result<(), AdminServiceStoreError> {
-        self.connection_pool.execute_write(|conn| {
-            AdminServiceStoreOperations::new(conn).add_circuit(circuit, nodes)
+        node_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_proposals_pending_vote_from(node_id)
         })
     }
 
-    fn update_circuit(&self, circuit: Circuit) -> Result<(), AdminServiceStoreError> {
-        self.connection_pool
-            .execute_write(|conn| AdminServiceStoreOperations::new(conn).update_circuit(circuit))
+    fn list_for_requester_node(
+        &self,
+        node_id: &str,
+    ) -> Result<(Vec<CircuitProposal>, Vec<Circuit>), AdminServiceStoreError> {
+        let predicates = self.with_default_status_predicates(&[]);
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_for_requester_node(node_id, &predicates)
+        })
     }
 
-    fn remove_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
-        self.connection_pool
-            .execute_write(|conn| AdminServiceStoreOperations::new(conn).remove_circuit(circuit_id))
+    fn proposal_vote_summary(
+        &self,
+        proposal_id: &str,
+    ) -> Result<Option<VoteSummary>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).proposal_vote_summary(proposal_id)
+        })
     }
 
-    fn get_circuit(&self, circuit_id: &str) -> Result<Option<Circuit>, AdminServiceStoreError> {
-        self.connection_pool
-            .execute_read(|conn| AdminServiceStoreOperations::new(conn).get_circuit(circuit_id))
+    fn list_proposals_updated_since(
+        &self,
+        since: i64,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_proposals_updated_since(since)
+        })
     }
 
-    fn list_circuits(
-        &self,
-        predicates: &[CircuitPredicate],
-    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
-        self.connection_pool
-            .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_circuits(predicates))
+    fn list_proposal_management_types(&self) -> Result<Vec<String>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_proposal_management_types()
+        })
     }
 
-    fn count_circuits(
+    fn add_circuit(
         &self,
-        predicates: &[CircuitPredicate],
-    ) -> Result<u32, AdminServiceStoreError> {
-        self.connection_pool
-            .execute_read(|conn| AdminServiceStoreOperations::new(conn).count_circuits(predicates))
+        circuit: Circuit,
+        nodes: Vec<CircuitNode>,
+    ) -> Result<(), AdminServiceStoreError> {
+        self.check_roster_size(circuit.circuit_id(), circuit.roster().len())?;
+        self.connection_pool.execute_write_with_retry(|conn| {
+            AdminServiceStoreOperations::new(conn).add_circuit(&circuit, &nodes)
+        })
     }
 
-    fn upgrade_proposal_to_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
-        self.connection_pool.execute_write(|conn| {
-            AdminServiceStoreOperations::new(conn).upgrade_proposal_to_circuit(circuit_id)
+    fn update_circuit(&self, circuit: Circuit) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool.execute_write_with_retry(|conn| {
+            AdminServiceStoreOperations::new(conn).update_circuit(&circuit)
+        })
+    }
+
+    fn update_circuit_returning_previous(
+        &self,
+        circuit: Circuit,
+    ) -> Result<Option<Circuit>, AdminServiceStoreError> {
+        self.connection_pool.execute_write_with_retry(|conn| {
+            AdminServiceStoreOperations::new(conn).update_circuit_returning_previous(&circuit)
+        })
+    }
+
+    fn remove_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool.execute_write_with_retry(|conn| {
+            AdminServiceStoreOperations::new(conn).remove_circuit(circuit_id)
+        })
+    }
+
+    fn delete_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool.execute_write_with_retry(|conn| {
+            AdminServiceStoreOperations::new(conn).delete_circuit(circuit_id)
+        })
+    }
+
+    fn set_circuit_status(
+        &self,
+        circuit_id: &str,
+        status: CircuitStatus,
+    ) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool.execute_write_with_retry(|conn| {
+            AdminServiceStoreOperations::new(conn).set_circuit_status(circuit_id, &status)
+        })
+    }
+
+    fn get_circuit(&self, circuit_id: &str) -> Result<Option<Circuit>, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).get_circuit(circuit_id))
+    }
+
+    fn get_circuits(
+        &self,
+        circuit_ids: &[&str],
+    ) -> Result<HashMap<String, Circuit>, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).get_circuits(circuit_ids))
+    }
+
+    fn list_circuits(
+        &self,
+        predicates: &[CircuitPredicate],
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        let predicates = self.with_default_status_predicates(predicates);
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_circuits(&predicates))
+    }
+
+    fn list_circuits_page(
+        &self,
+        predicates: &[CircuitPredicate],
+        options: &CircuitQueryOptions,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        let predicates = self.with_default_status_predicates(predicates);
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_circuits_page(&predicates, options)
+        })
+    }
+
+    fn count_circuits(
+        &self,
+        predicates: &[CircuitPredicate],
+    ) -> Result<u32, AdminServiceStoreError> {
+        let predicates = self.with_default_status_predicates(predicates);
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).count_circuits(&predicates))
+    }
+
+    fn list_circuits_updated_since(
+        &self,
+        since: i64,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_circuits_updated_since(since)
+        })
+    }
+
+    fn list_circuit_management_types(&self) -> Result<Vec<String>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_circuit_management_types()
+        })
+    }
+
+    fn upgrade_proposal_to_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool.execute_write_with_retry(|conn| {
+            AdminServiceStoreOperations::new(conn).upgrade_proposal_to_circuit(circuit_id)
         })
     }
 
@@ -358,6 +966,17 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnect
             .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_nodes())
     }
 
+    fn list_orphaned_nodes(&self) -> Result<Vec<CircuitNode>, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_orphaned_nodes())
+    }
+
+    fn remove_orphaned_nodes(&self) -> Result<u64, AdminServiceStoreError> {
+        self.connection_pool.execute_write_with_retry(|conn| {
+            AdminServiceStoreOperations::new(conn).remove_orphaned_nodes()
+        })
+    }
+
     fn get_service(
         &self,
         service_id: &ServiceId,
@@ -366,6 +985,11 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnect
             .execute_read(|conn| AdminServiceStoreOperations::new(conn).get_service(service_id))
     }
 
+    fn service_exists(&self, service_id: &ServiceId) -> Result<bool, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).service_exists(service_id))
+    }
+
     fn list_services(
         &self,
         circuit_id: &str,
@@ -374,30 +998,50 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnect
             .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_services(circuit_id))
     }
 
-    fn add_event(
-        &self,
-        event: messages::AdminServiceEvent,
-    ) -> Result<AdminServiceEvent, AdminServiceStoreError> {
+    fn count_services_by_type(&self) -> Result<HashMap<String, u64>, AdminServiceStoreError> {
         self.connection_pool
-            .execute_write(|conn| AdminServiceStoreOperations::new(conn).add_event(event))
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).count_services_by_type())
     }
 
-    fn list_events_since(&self, start: i64) -> Result<EventIter, AdminServiceStoreError> {
+    fn storage_stats(&self) -> Result<StorageStats, AdminServiceStoreError> {
         self.connection_pool
-            .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_events_since(start))
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).storage_stats())
     }
 
-    fn list_events_by_management_type_since(
+    fn add_event(
         &self,
-        management_type: String,
-        start: i64,
-    ) -> Result<EventIter, AdminServiceStoreError> {
+        event: messages::AdminServiceEvent,
+    ) -> Result<AdminServiceEvent, AdminServiceStoreError> {
+        let event = self.connection_pool.execute_write_with_retry(|conn| {
+            AdminServiceStoreOperations::new(conn).add_event(&event)
+        })?;
+        self.notify_event_subscribers(&event);
+        Ok(event)
+    }
+
+    fn list_events(&self, options: EventQueryOptions) -> Result<EventIter, AdminServiceStoreError> {
         self.connection_pool.execute_read(|conn| {
-            AdminServiceStoreOperations::new(conn)
-                .list_events_by_management_type_since(management_type, start)
+            AdminServiceStoreOperations::new(conn).list_events_with_options(&options)
         })
     }
 
+    fn stream_events_since(&self, start: i64) -> EventStream {
+        let store = self.clone();
+        Box::new(BatchedEventStream::new(start, move |last_event_id| {
+            store.connection_pool.execute_read(|conn| {
+                AdminServiceStoreOperations::new(conn).list_events_with_options(
+                    &EventQueryOptions::new()
+                        .with_start(last_event_id)
+                        .with_limit(EVENT_STREAM_BATCH_SIZE),
+                )
+            })
+        }))
+    }
+
+    fn subscribe_to_events(&self) -> Receiver<AdminServiceEvent> {
+        self.add_event_subscriber()
+    }
+
     fn clone_boxed(&self) -> Box<dyn AdminServiceStore> {
         Box::new(self.clone())
     }
@@ -407,13 +1051,17 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnect
 pub mod tests {
     use super::*;
 
+    use std::time::{SystemTime, UNIX_EPOCH};
+
     use crate::admin::store::{
-        CircuitBuilder, CircuitNodeBuilder, CircuitProposal, CircuitProposalBuilder, CircuitStatus,
-        ProposalType, ProposedCircuitBuilder, ProposedNodeBuilder, ProposedServiceBuilder,
-        ServiceBuilder, Vote, VoteRecordBuilder,
+        CircuitBuilder, CircuitNodeBuilder, CircuitOrder, CircuitOrderDirection, CircuitProposal,
+        CircuitProposalBuilder, CircuitStatus, ProposalType, ProposedCircuitBuilder,
+        ProposedNodeBuilder, ProposedServiceBuilder, ServiceBuilder, Vote, VoteRecordBuilder,
+        UNSET_CIRCUIT_VERSION,
     };
 
-    use crate::admin::store::{AdminServiceEventBuilder, EventType};
+    use crate::admin::store::{AdminServiceEventBuilder, EventOrder, EventType, EventTypeFilter};
+    use crate::error::ConstraintViolationType;
     use crate::hex::parse_hex;
     use crate::migrations::run_sqlite_migrations;
     use crate::public_key::PublicKey;
@@ -642,6 +1290,59 @@ pub mod tests {
         assert_eq!(None, fetched_proposal);
     }
 
+    /// Verify that only the proposals of the given management type are removed
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a "gameroom" proposal and a "test" proposal
+    /// 4. Remove the "gameroom" proposals and validate one was removed
+    /// 5. Validate the "gameroom" proposal was removed and the "test" proposal remains
+    /// 6. Validate removing the "gameroom" proposals again removes none
+    #[test]
+    fn test_remove_proposals_by_management_type() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        store
+            .add_proposal(create_extra_proposal())
+            .expect("Unable to add circuit proposal");
+        store
+            .add_proposal(create_messages_proposal("test"))
+            .expect("Unable to add circuit proposal");
+
+        assert_eq!(
+            store
+                .remove_proposals_by_management_type("gameroom")
+                .expect("Unable to remove proposals"),
+            1
+        );
+
+        assert_eq!(
+            None,
+            store
+                .get_proposal("WBKLF-AAAAA")
+                .expect("Unable to get proposal")
+        );
+        assert!(store
+            .get_proposal("WBKLF-BBBBB")
+            .expect("Unable to get proposal")
+            .is_some());
+        assert_eq!(
+            store
+                .list_proposal_management_types()
+                .expect("Unable to list management types"),
+            vec!["test"]
+        );
+
+        assert_eq!(
+            store
+                .remove_proposals_by_management_type("gameroom")
+                .expect("Unable to remove proposals"),
+            0
+        );
+    }
+
     /// Verify that a proposal can be added to the store correctly and then updated from the store
     ///
     /// 1. Run sqlite migrations
@@ -698,18 +1399,48 @@ pub mod tests {
         assert_eq!(updated_proposal, fetched_proposal);
     }
 
-    /// Verify that a proposal can be upgraded to a circuit
+    /// Verify that updating a proposal that does not exist returns a `NotFound` constraint
+    /// violation
     ///
     /// 1. Run sqlite migrations
     /// 2. Create DieselAdminServiceStore
-    /// 3. Create a proposal
-    /// 4. Add proposal to store
-    /// 5. Fetch Proposal from store
-    /// 6. Validate fetched proposal is the same as the proposal added
-    /// 7. Call upgrade_proposal_to_circuit for the proposal
-    /// 8. Fetch the new circuit and validate it is as expected
+    /// 3. Attempt to update a proposal that was never added
+    /// 4. Validate a `ConstraintViolationError` with the `NotFound` type is returned
+    /// 5. Validate the proposal was not added to the store
     #[test]
-    fn test_upgrade_proposals() {
+    fn test_update_nonexistent_proposal() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        match store.update_proposal(create_proposal()) {
+            Err(AdminServiceStoreError::ConstraintViolationError(err)) => {
+                assert_eq!(err.violation_type(), &ConstraintViolationType::NotFound)
+            }
+            res => panic!(
+                "Expected Err(ConstraintViolationError), got {:?} instead",
+                res
+            ),
+        }
+
+        assert_eq!(
+            store
+                .get_proposal("WBKLF-BBBBB")
+                .expect("Unable to get proposal"),
+            None
+        );
+    }
+
+    /// Verify that a proposal is updated by `update_proposal_if_unchanged` when the stored
+    /// circuit hash matches the expected hash
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a proposal to the store
+    /// 4. Update the proposal with a new vote, expecting the hash of the added proposal
+    /// 5. Validate the fetched proposal matches the updated proposal
+    #[test]
+    fn test_update_proposal_if_unchanged() {
         let pool = create_connection_pool_and_migrate();
 
         let store = DieselAdminServiceStore::new(pool);
@@ -720,88 +1451,1830 @@ pub mod tests {
             .add_proposal(proposal.clone())
             .expect("Unable to add circuit proposal");
 
+        let updated_proposal = proposal
+            .builder()
+            .with_votes(&vec![VoteRecordBuilder::new()
+                .with_public_key(&PublicKey::from_bytes(
+                    parse_hex("035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550")
+                        .unwrap(),
+                ))
+                .with_vote(&Vote::Accept)
+                .with_voter_node_id("bubba-node-000")
+                .build()
+                .expect("Unable to build vote record")])
+            .build()
+            .expect("Unable to build updated proposal");
+
+        store
+            .update_proposal_if_unchanged(updated_proposal.clone(), proposal.circuit_hash())
+            .expect("Unable to update proposal");
+
         let fetched_proposal = store
             .get_proposal("WBKLF-BBBBB")
             .expect("Unable to get proposal")
             .expect("Got None when expecting proposal");
 
-        assert_eq!(proposal, fetched_proposal);
+        assert_eq!(updated_proposal, fetched_proposal);
+    }
+
+    /// Verify that `update_proposal_if_unchanged` does not overwrite a proposal whose circuit
+    /// hash has changed since it was read
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a proposal to the store
+    /// 4. Update the stored proposal with a new circuit hash, as a concurrent writer would
+    /// 5. Attempt to update the proposal with a new vote, expecting the original hash
+    /// 6. Validate a `ConstraintViolationError` is returned and the concurrent update is kept
+    #[test]
+    fn test_update_proposal_if_unchanged_conflict() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let proposal = create_proposal();
 
         store
-            .upgrade_proposal_to_circuit("WBKLF-BBBBB")
+            .add_proposal(proposal.clone())
             .expect("Unable to add circuit proposal");
 
-        assert!(store
+        let concurrent_proposal = proposal
+            .builder()
+            .with_circuit_hash("updated-circuit-hash")
+            .build()
+            .expect("Unable to build concurrent proposal");
+
+        store
+            .update_proposal(concurrent_proposal.clone())
+            .expect("Unable to update proposal");
+
+        let updated_proposal = proposal
+            .builder()
+            .with_votes(&vec![VoteRecordBuilder::new()
+                .with_public_key(&PublicKey::from_bytes(
+                    parse_hex("035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550")
+                        .unwrap(),
+                ))
+                .with_vote(&Vote::Accept)
+                .with_voter_node_id("bubba-node-000")
+                .build()
+                .expect("Unable to build vote record")])
+            .build()
+            .expect("Unable to build updated proposal");
+
+        match store.update_proposal_if_unchanged(updated_proposal, proposal.circuit_hash()) {
+            Err(AdminServiceStoreError::ConstraintViolationError(_)) => (),
+            res => panic!(
+                "Expected Err(ConstraintViolationError), got {:?} instead",
+                res
+            ),
+        }
+
+        let fetched_proposal = store
             .get_proposal("WBKLF-BBBBB")
             .expect("Unable to get proposal")
-            .is_none());
-
-        let fetched_circuit = store
-            .get_circuit("WBKLF-BBBBB")
-            .expect("Unable to get circuit")
-            .expect("Got None when expecting circuit");
+            .expect("Got None when expecting proposal");
 
-        assert_eq!(
-            create_circuit_from_proposal("WBKLF-BBBBB", CircuitStatus::Active),
-            fetched_circuit
-        );
+        assert_eq!(concurrent_proposal, fetched_proposal);
     }
 
-    /// Verify that a circuit can be added to the store correctly and then fetched from the store
+    /// Verify that `update_proposal_if_unchanged` returns a `NotFound` error for a proposal that
+    /// does not exist
     ///
     /// 1. Run sqlite migrations
     /// 2. Create DieselAdminServiceStore
-    /// 3. Create a circuit and nodes
-    /// 4. Add circuit and nodes to store
-    /// 5. Fetch Circuit from store
-    /// 6. Validate fetched circuit is the same as the circuit added
-    /// 7. Fetch CircuitNode from store
-    /// 8. Validate fetched node is the same as the node added
+    /// 3. Attempt to update a proposal that has not been added
+    /// 4. Validate a `ConstraintViolationError` with a `NotFound` violation type is returned
     #[test]
-    fn test_add_get_circuit_and_nodes() {
+    fn test_update_proposal_if_unchanged_missing() {
         let pool = create_connection_pool_and_migrate();
 
         let store = DieselAdminServiceStore::new(pool);
 
-        let circuit = create_circuit("WBKLF-BBBBB", CircuitStatus::Active);
-
-        let nodes = create_nodes();
-
-        store
-            .add_circuit(circuit.clone(), nodes)
-            .expect("Unable to add circuit");
-
-        let fetched_circuit = store
-            .get_circuit("WBKLF-BBBBB")
-            .expect("Unable to get circuit")
-            .expect("Got None when expecting circuit");
-
-        let fetched_node = store
-            .get_node("bubba-node-000")
-            .expect("Unable to get node")
-            .expect("Got None when expecting node");
+        let proposal = create_proposal();
 
-        assert_eq!(circuit, fetched_circuit);
-        assert_eq!(
-            fetched_node,
-            CircuitNodeBuilder::default()
-                .with_node_id("bubba-node-000".into())
-                .with_endpoints(&vec!["tcps://splinterd-node-bubba:8044".into()])
-                .build()
-                .expect("Unable to build node"),
-        )
+        match store.update_proposal_if_unchanged(proposal.clone(), proposal.circuit_hash()) {
+            Err(AdminServiceStoreError::ConstraintViolationError(err)) => {
+                assert_eq!(err.violation_type(), &ConstraintViolationType::NotFound)
+            }
+            res => panic!(
+                "Expected Err(ConstraintViolationError), got {:?} instead",
+                res
+            ),
+        }
     }
 
-    /// Verify that list_circuits works correctly
+    /// Verify that `update_proposal_returning_previous` returns the proposal that was stored
+    /// before the update, and returns `None` for a proposal that does not exist
     ///
     /// 1. Run sqlite migrations
     /// 2. Create DieselAdminServiceStore
-    /// 3. Create a circuit and nodes
-    /// 4. Add circuit to store
-    /// 5. List circuits from store with no predicates, validate added circuit is returned
-    /// 6. List circuits from store with management type predicate, validate added circuit is
-    ///    returned
-    /// 7. List circuits from store with member predicate, validate added circuit is
+    /// 3. Validate `None` is returned when updating a proposal that was never added, and that
+    ///    the proposal is not added
+    /// 4. Add a proposal to the store
+    /// 5. Update the proposal with a new vote, validating the added proposal is returned
+    /// 6. Validate the fetched proposal now matches the updated proposal
+    #[test]
+    fn test_update_proposal_returning_previous() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let proposal = create_proposal();
+
+        let previous = store
+            .update_proposal_returning_previous(proposal.clone())
+            .expect("Unable to update proposal");
+
+        assert_eq!(previous, None);
+        assert_eq!(
+            store
+                .get_proposal("WBKLF-BBBBB")
+                .expect("Unable to get proposal"),
+            None
+        );
+
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        let updated_proposal = proposal
+            .builder()
+            .with_votes(&vec![VoteRecordBuilder::new()
+                .with_public_key(&PublicKey::from_bytes(
+                    parse_hex("035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550")
+                        .unwrap(),
+                ))
+                .with_vote(&Vote::Accept)
+                .with_voter_node_id("bubba-node-000")
+                .build()
+                .expect("Unable to build vote record")])
+            .build()
+            .expect("Unable to build updated proposal");
+
+        let previous = store
+            .update_proposal_returning_previous(updated_proposal.clone())
+            .expect("Unable to update proposal");
+
+        assert_eq!(previous, Some(proposal));
+
+        let fetched_proposal = store
+            .get_proposal("WBKLF-BBBBB")
+            .expect("Unable to get proposal")
+            .expect("Got None when expecting proposal");
+
+        assert_eq!(updated_proposal, fetched_proposal);
+    }
+
+    /// Verify that `append_vote` adds a vote to the end of a proposal's votes
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a proposal, with votes from bubba-node-000 and bubba-node-002, to the store
+    /// 4. Append a vote from acme-node-000
+    /// 5. Validate the returned and fetched proposals both end with the appended vote
+    #[test]
+    fn test_append_vote() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let proposal = create_proposal();
+
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        let vote = create_vote_record("acme-node-000");
+        let updated_proposal = store
+            .append_vote("WBKLF-BBBBB", vote.clone())
+            .expect("Unable to append vote");
+
+        let mut expected_votes = proposal.votes().to_vec();
+        expected_votes.push(vote);
+        assert_eq!(updated_proposal.votes(), expected_votes.as_slice());
+
+        let fetched_proposal = store
+            .get_proposal("WBKLF-BBBBB")
+            .expect("Unable to get proposal")
+            .expect("Got None when expecting proposal");
+
+        assert_eq!(updated_proposal, fetched_proposal);
+    }
+
+    /// Verify that `append_vote` rejects a second vote from the same node
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a proposal, with a vote from bubba-node-000, to the store
+    /// 4. Attempt to append another vote from bubba-node-000
+    /// 5. Validate a `ConstraintViolationError` is returned and the votes are unchanged
+    #[test]
+    fn test_append_vote_duplicate_voter() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let proposal = create_proposal();
+
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        match store.append_vote("WBKLF-BBBBB", create_vote_record("bubba-node-000")) {
+            Err(AdminServiceStoreError::ConstraintViolationError(_)) => (),
+            res => panic!(
+                "Expected Err(ConstraintViolationError), got {:?} instead",
+                res
+            ),
+        }
+
+        let fetched_proposal = store
+            .get_proposal("WBKLF-BBBBB")
+            .expect("Unable to get proposal")
+            .expect("Got None when expecting proposal");
+
+        assert_eq!(proposal.votes(), fetched_proposal.votes());
+    }
+
+    /// Verify that `append_vote` returns a `NotFound` error for a proposal that does not exist
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Attempt to append a vote to a proposal that has not been added
+    /// 4. Validate a `ConstraintViolationError` with a `NotFound` violation type is returned
+    #[test]
+    fn test_append_vote_missing_proposal() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        match store.append_vote("WBKLF-BBBBB", create_vote_record("acme-node-000")) {
+            Err(AdminServiceStoreError::ConstraintViolationError(err)) => {
+                assert_eq!(err.violation_type(), &ConstraintViolationType::NotFound)
+            }
+            res => panic!(
+                "Expected Err(ConstraintViolationError), got {:?} instead",
+                res
+            ),
+        }
+    }
+
+    /// Verify that votes appended concurrently by different nodes are all kept
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a proposal to the store
+    /// 4. Append votes from acme-node-000 and acme-node-001 on separate threads
+    /// 5. Validate both appends succeed and the fetched proposal contains both votes
+    #[test]
+    fn test_append_vote_concurrent() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let proposal = create_proposal();
+
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        let handles = vec!["acme-node-000", "acme-node-001"]
+            .into_iter()
+            .map(|voter_node_id| {
+                let store = store.clone();
+                std::thread::spawn(move || {
+                    store.append_vote("WBKLF-BBBBB", create_vote_record(voter_node_id))
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            handle
+                .join()
+                .expect("Vote thread panicked")
+                .expect("Unable to append vote");
+        }
+
+        let fetched_proposal = store
+            .get_proposal("WBKLF-BBBBB")
+            .expect("Unable to get proposal")
+            .expect("Got None when expecting proposal");
+
+        let voters = fetched_proposal
+            .votes()
+            .iter()
+            .map(|vote| vote.voter_node_id())
+            .collect::<Vec<_>>();
+        assert_eq!(voters.len(), proposal.votes().len() + 2);
+        assert!(voters.contains(&"acme-node-000"));
+        assert!(voters.contains(&"acme-node-001"));
+    }
+
+    /// Test that list_proposals_pending_vote_from returns only the proposals the node is a member
+    /// of and has not yet voted on
+    ///
+    /// 1. Add a proposal with members acme-node-000 and bubba-node-000, where only
+    ///    bubba-node-000 has voted
+    /// 2. Validate that the proposal is pending a vote from acme-node-000
+    /// 3. Validate that the proposal is not pending a vote from bubba-node-000
+    /// 4. Validate that the proposal is not pending a vote from a node that is not a member
+    #[test]
+    fn test_list_proposals_pending_vote_from() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let proposal = create_proposal();
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add proposal");
+
+        let pending = store
+            .list_proposals_pending_vote_from("acme-node-000")
+            .expect("Unable to list proposals")
+            .collect::<Vec<_>>();
+        assert_eq!(pending, vec![proposal]);
+
+        assert_eq!(
+            store
+                .list_proposals_pending_vote_from("bubba-node-000")
+                .expect("Unable to list proposals")
+                .len(),
+            0
+        );
+
+        assert_eq!(
+            store
+                .list_proposals_pending_vote_from("gumbo-node-000")
+                .expect("Unable to list proposals")
+                .len(),
+            0
+        );
+    }
+
+    /// Test that list_for_requester_node returns the proposals requested by a node and the
+    /// circuits it is a member of
+    ///
+    /// 1. Add a proposal requested by bubba-node-000 and a proposal requested by acme-node-000
+    /// 2. Add two circuits with bubba-node-000 as a member and one circuit without it
+    /// 3. Validate that only the proposal requested by bubba-node-000 and the two circuits it is a
+    ///    member of are returned for bubba-node-000
+    #[test]
+    fn test_list_for_requester_node() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let proposal = create_proposal()
+            .builder()
+            .with_requester_node_id("bubba-node-000")
+            .build()
+            .expect("Unable to build proposal");
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add proposal");
+        store
+            .add_proposal(create_extra_proposal())
+            .expect("Unable to add proposal");
+
+        let first_circuit = create_circuit("WBKLF-CCCCC", CircuitStatus::Active);
+        let second_circuit = create_circuit("WBKLF-DDDDD", CircuitStatus::Active);
+        store
+            .add_circuit(first_circuit.clone(), create_nodes())
+            .expect("Unable to add circuit");
+        store
+            .add_circuit(second_circuit.clone(), create_nodes())
+            .expect("Unable to add circuit");
+        store
+            .add_circuit(create_extra_circuit("WBKLF-EEEEE"), create_extra_nodes())
+            .expect("Unable to add circuit");
+
+        let (proposals, mut circuits) = store
+            .list_for_requester_node("bubba-node-000")
+            .expect("Unable to list proposals and circuits");
+        circuits.sort_by(|a, b| a.circuit_id().cmp(b.circuit_id()));
+
+        assert_eq!(proposals, vec![proposal]);
+        assert_eq!(circuits, vec![first_circuit, second_circuit]);
+    }
+
+    /// Test that proposal_vote_summary tallies the votes on a proposal and lists the members that
+    /// have not voted
+    ///
+    /// 1. Add a proposal requested by acme-node-000 with the additional members bubba-node-000,
+    ///    gumbo-node-000 and dandy-node-000, where bubba-node-000 has accepted and
+    ///    gumbo-node-000 has rejected
+    /// 2. Validate that the summary has one accept and one reject vote, and that only
+    ///    dandy-node-000 has not voted
+    /// 3. Validate that there is no summary for a proposal that does not exist
+    #[test]
+    fn test_proposal_vote_summary() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let proposal = create_proposal();
+        let mut members = proposal.circuit().members().to_vec();
+        for node_id in &["gumbo-node-000", "dandy-node-000"] {
+            members.push(
+                ProposedNodeBuilder::default()
+                    .with_node_id(node_id)
+                    .with_endpoints(&[format!("tcps://splinterd-node-{}:8044", node_id)])
+                    .build()
+                    .expect("Unable to build node"),
+            );
+        }
+        let circuit = ProposedCircuitBuilder::default()
+            .with_circuit_id(proposal.circuit_id())
+            .with_roster(proposal.circuit().roster())
+            .with_members(&members)
+            .with_circuit_management_type(proposal.circuit().circuit_management_type())
+            .build()
+            .expect("Unable to build circuit");
+        let vote = |node_id: &str, vote: Vote| {
+            VoteRecordBuilder::new()
+                .with_public_key(&PublicKey::from_bytes(
+                    parse_hex("035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550")
+                        .expect("Unable to parse key"),
+                ))
+                .with_vote(&vote)
+                .with_voter_node_id(node_id)
+                .build()
+                .expect("Unable to build vote record")
+        };
+        let proposal = proposal
+            .builder()
+            .with_circuit(&circuit)
+            .with_votes(&[
+                vote("bubba-node-000", Vote::Accept),
+                vote("gumbo-node-000", Vote::Reject),
+            ])
+            .build()
+            .expect("Unable to build proposal");
+
+        store
+            .add_proposal(proposal)
+            .expect("Unable to add proposal");
+
+        let summary = store
+            .proposal_vote_summary("WBKLF-BBBBB")
+            .expect("Unable to get vote summary")
+            .expect("Vote summary not found");
+        assert_eq!(summary.accept_count(), 1);
+        assert_eq!(summary.reject_count(), 1);
+        assert_eq!(
+            summary.outstanding_member_ids(),
+            &["dandy-node-000".to_string()]
+        );
+
+        assert_eq!(
+            store
+                .proposal_vote_summary("WBKLF-XXXXX")
+                .expect("Unable to get vote summary"),
+            None
+        );
+    }
+
+    /// Test that a store with circuit hash verification enabled only accepts proposals whose
+    /// circuit hash matches their proposed circuit
+    ///
+    /// 1. Create a store with circuit hash verification enabled
+    /// 2. Validate that adding a proposal with a mismatched circuit hash fails
+    /// 3. Validate that adding the proposal with the computed circuit hash succeeds
+    /// 4. Validate that updating the proposal with a mismatched circuit hash fails
+    #[test]
+    fn test_circuit_hash_verification() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool).with_circuit_hash_verification(true);
+
+        let invalid_proposal = create_proposal();
+        assert!(!invalid_proposal.verify_hash());
+        match store.add_proposal(invalid_proposal.clone()) {
+            Err(AdminServiceStoreError::InvalidStateError(_)) => (),
+            res => panic!("Expected Err(InvalidStateError), got {:?} instead", res),
+        }
+
+        let circuit_hash = invalid_proposal
+            .circuit()
+            .compute_hash()
+            .expect("Unable to compute circuit hash");
+        let proposal = invalid_proposal
+            .builder()
+            .with_circuit_hash(&circuit_hash)
+            .build()
+            .expect("Unable to build proposal");
+        assert!(proposal.verify_hash());
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add proposal");
+
+        match store.update_proposal(invalid_proposal) {
+            Err(AdminServiceStoreError::InvalidStateError(_)) => (),
+            res => panic!("Expected Err(InvalidStateError), got {:?} instead", res),
+        }
+
+        assert_eq!(
+            store
+                .get_proposal("WBKLF-BBBBB")
+                .expect("Unable to get proposal"),
+            Some(proposal)
+        );
+    }
+
+    /// Verify that circuits and proposals with rosters larger than the configured maximum are
+    /// rejected, and that rosters within the maximum are accepted
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore with a maximum roster size of 1
+    /// 3. Validate that adding a circuit and a proposal, each with two services, fails with an
+    ///    InvalidStateError and that nothing was stored
+    /// 4. Create DieselAdminServiceStore with a maximum roster size of 2
+    /// 5. Validate that the same circuit and proposal can be added
+    #[test]
+    fn test_max_roster_size() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool.clone()).with_max_roster_size(1);
+
+        let circuit = create_circuit("WBKLF-AAAAA", CircuitStatus::Active);
+        let proposal = create_proposal();
+        assert_eq!(circuit.roster().len(), 2);
+        assert_eq!(proposal.circuit().roster().len(), 2);
+
+        match store.add_circuit(circuit.clone(), create_nodes()) {
+            Err(AdminServiceStoreError::InvalidStateError(_)) => (),
+            res => panic!("Expected Err(InvalidStateError), got {:?} instead", res),
+        }
+        match store.add_proposal(proposal.clone()) {
+            Err(AdminServiceStoreError::InvalidStateError(_)) => (),
+            res => panic!("Expected Err(InvalidStateError), got {:?} instead", res),
+        }
+        assert_eq!(
+            store
+                .get_circuit("WBKLF-AAAAA")
+                .expect("Unable to get circuit"),
+            None
+        );
+        assert_eq!(
+            store
+                .get_proposal("WBKLF-BBBBB")
+                .expect("Unable to get proposal"),
+            None
+        );
+
+        let store = DieselAdminServiceStore::new(pool).with_max_roster_size(2);
+
+        store
+            .add_circuit(circuit.clone(), create_nodes())
+            .expect("Unable to add circuit");
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add proposal");
+        assert_eq!(
+            store
+                .get_circuit("WBKLF-AAAAA")
+                .expect("Unable to get circuit"),
+            Some(circuit)
+        );
+        assert_eq!(
+            store
+                .get_proposal("WBKLF-BBBBB")
+                .expect("Unable to get proposal"),
+            Some(proposal)
+        );
+    }
+
+    /// Verify that nodes that are no longer members of any circuit are listed and removed as
+    /// orphans, unless they are part of a circuit proposal
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a circuit with three member nodes and validate that no nodes are orphaned
+    /// 4. Update the circuit to only have one of the nodes as a member
+    /// 5. Add a proposal whose proposed circuit includes one of the dropped nodes
+    /// 6. Validate that only the dropped node that is not part of the proposal is listed as
+    ///    orphaned
+    /// 7. Remove the orphaned nodes and validate that one node was removed
+    /// 8. Validate that no nodes are orphaned and that removing orphaned nodes again removes
+    ///    nothing
+    #[test]
+    fn test_orphaned_nodes() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let charlie_node = CircuitNodeBuilder::default()
+            .with_node_id("charlie-node-000".into())
+            .with_endpoints(&vec!["tcps://splinterd-node-charlie:8044".into()])
+            .build()
+            .expect("Unable to build node");
+        let mut nodes = create_nodes();
+        nodes.push(charlie_node.clone());
+
+        let circuit = create_circuit_builder("WBKLF-AAAAA", CircuitStatus::Active, &nodes)
+            .build()
+            .expect("Unable to build circuit");
+        store
+            .add_circuit(circuit, nodes.clone())
+            .expect("Unable to add circuit");
+
+        assert!(store
+            .list_orphaned_nodes()
+            .expect("Unable to list orphaned nodes")
+            .is_empty());
+
+        // Keep only the bubba node as a member, leaving the acme and charlie nodes unreferenced
+        // by any circuit
+        let circuit = create_circuit_builder("WBKLF-AAAAA", CircuitStatus::Active, &nodes[..1])
+            .build()
+            .expect("Unable to build circuit");
+        store
+            .update_circuit(circuit)
+            .expect("Unable to update circuit");
+
+        // The proposal's proposed circuit includes the acme node
+        store
+            .add_proposal(create_proposal())
+            .expect("Unable to add proposal");
+
+        assert_eq!(
+            store
+                .list_orphaned_nodes()
+                .expect("Unable to list orphaned nodes"),
+            vec![charlie_node]
+        );
+
+        assert_eq!(
+            store
+                .remove_orphaned_nodes()
+                .expect("Unable to remove orphaned nodes"),
+            1
+        );
+
+        assert!(store
+            .list_orphaned_nodes()
+            .expect("Unable to list orphaned nodes")
+            .is_empty());
+        assert_eq!(
+            store
+                .remove_orphaned_nodes()
+                .expect("Unable to remove orphaned nodes"),
+            0
+        );
+    }
+
+    /// Verify that only proposals updated at or after a given time are listed
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add two proposals to the store
+    /// 4. Wait until the clock has moved past the time the proposals were added
+    /// 5. Update one of the proposals with a new vote
+    /// 6. Validate that only the updated proposal is listed as updated since the wait
+    /// 7. Validate that both proposals are listed as updated since the Unix epoch
+    #[test]
+    fn test_list_proposals_updated_since() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let proposal = create_proposal();
+        let extra_proposal = create_extra_proposal();
+
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+        store
+            .add_proposal(extra_proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        let since = wait_for_next_second();
+
+        let updated_proposal = proposal
+            .builder()
+            .with_votes(&vec![VoteRecordBuilder::new()
+                .with_public_key(&PublicKey::from_bytes(
+                    parse_hex("035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550")
+                        .unwrap(),
+                ))
+                .with_vote(&Vote::Accept)
+                .with_voter_node_id("bubba-node-000")
+                .build()
+                .expect("Unable to build vote record")])
+            .build()
+            .expect("Unable to build updated proposal");
+
+        store
+            .update_proposal(updated_proposal.clone())
+            .expect("Unable to update proposal");
+
+        let mut proposals = store
+            .list_proposals_updated_since(since)
+            .expect("Unable to list updated proposals");
+
+        assert_eq!(proposals.next(), Some(updated_proposal));
+        assert_eq!(proposals.next(), None);
+
+        let proposals = store
+            .list_proposals_updated_since(0)
+            .expect("Unable to list updated proposals");
+
+        assert_eq!(proposals.len(), 2);
+    }
+
+    /// Verify that a proposal can be upgraded to a circuit
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Create a proposal
+    /// 4. Add proposal to store
+    /// 5. Fetch Proposal from store
+    /// 6. Validate fetched proposal is the same as the proposal added
+    /// 7. Call upgrade_proposal_to_circuit for the proposal
+    /// 8. Fetch the new circuit and validate it is as expected
+    #[test]
+    fn test_upgrade_proposals() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let proposal = create_proposal();
+
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        let fetched_proposal = store
+            .get_proposal("WBKLF-BBBBB")
+            .expect("Unable to get proposal")
+            .expect("Got None when expecting proposal");
+
+        assert_eq!(proposal, fetched_proposal);
+
+        store
+            .upgrade_proposal_to_circuit("WBKLF-BBBBB")
+            .expect("Unable to add circuit proposal");
+
+        assert!(store
+            .get_proposal("WBKLF-BBBBB")
+            .expect("Unable to get proposal")
+            .is_none());
+
+        let fetched_circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to get circuit")
+            .expect("Got None when expecting circuit");
+
+        assert_eq!(
+            create_circuit_from_proposal("WBKLF-BBBBB", CircuitStatus::Active),
+            fetched_circuit
+        );
+    }
+
+    /// Verify that a proposal and its circuit can be fetched together
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Validate that None is returned when neither the proposal nor the circuit exist
+    /// 4. Add a proposal and validate that only the pending proposal is returned
+    /// 5. Upgrade the proposal and validate that only the resulting circuit is returned
+    #[test]
+    fn test_get_proposal_with_circuit() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        assert!(store
+            .get_proposal_with_circuit("WBKLF-BBBBB")
+            .expect("Unable to get proposal with circuit")
+            .is_none());
+
+        let proposal = create_proposal();
+
+        store
+            .add_proposal(proposal.clone())
+            .expect("Unable to add circuit proposal");
+
+        let (fetched_proposal, fetched_circuit) = store
+            .get_proposal_with_circuit("WBKLF-BBBBB")
+            .expect("Unable to get proposal with circuit")
+            .expect("Got None when expecting proposal");
+
+        assert_eq!(Some(proposal), fetched_proposal);
+        assert!(fetched_circuit.is_none());
+
+        store
+            .upgrade_proposal_to_circuit("WBKLF-BBBBB")
+            .expect("Unable to upgrade proposal");
+
+        let (fetched_proposal, fetched_circuit) = store
+            .get_proposal_with_circuit("WBKLF-BBBBB")
+            .expect("Unable to get proposal with circuit")
+            .expect("Got None when expecting circuit");
+
+        assert!(fetched_proposal.is_none());
+        assert_eq!(
+            Some(create_circuit_from_proposal(
+                "WBKLF-BBBBB",
+                CircuitStatus::Active
+            )),
+            fetched_circuit
+        );
+    }
+
+    /// Verify that upgrading a proposal that does not exist returns a `NotFound` constraint
+    /// violation without changing the store
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Attempt to upgrade a proposal that was never added
+    /// 4. Validate a `ConstraintViolationError` with the `NotFound` type is returned
+    /// 5. Validate no circuit or nodes were added to the store
+    #[test]
+    fn test_upgrade_nonexistent_proposal() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        match store.upgrade_proposal_to_circuit("WBKLF-BBBBB") {
+            Err(AdminServiceStoreError::ConstraintViolationError(err)) => {
+                assert_eq!(err.violation_type(), &ConstraintViolationType::NotFound)
+            }
+            res => panic!(
+                "Expected Err(ConstraintViolationError), got {:?} instead",
+                res
+            ),
+        }
+
+        assert_eq!(
+            store
+                .get_circuit("WBKLF-BBBBB")
+                .expect("Unable to get circuit"),
+            None
+        );
+        assert_eq!(store.list_nodes().expect("Unable to list nodes").count(), 0);
+    }
+
+    /// Verify that several circuits can be fetched by ID at once
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add two circuits to the store, one of them disbanded
+    /// 4. Fetch the two circuits along with an ID that does not exist
+    /// 5. Validate that both circuits are returned, keyed by ID, and the missing ID is absent
+    #[test]
+    fn test_get_circuits() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let active_circuit = create_circuit("WBKLF-AAAAA", CircuitStatus::Active);
+        let disbanded_circuit = create_circuit("WBKLF-BBBBB", CircuitStatus::Disbanded);
+        store
+            .add_circuit(active_circuit.clone(), create_nodes())
+            .expect("Unable to add circuit");
+        store
+            .add_circuit(disbanded_circuit.clone(), create_nodes())
+            .expect("Unable to add circuit");
+
+        let circuits = store
+            .get_circuits(&["WBKLF-AAAAA", "WBKLF-BBBBB", "WBKLF-CCCCC"])
+            .expect("Unable to get circuits");
+
+        assert_eq!(circuits.len(), 2);
+        assert_eq!(circuits.get("WBKLF-AAAAA"), Some(&active_circuit));
+        assert_eq!(circuits.get("WBKLF-BBBBB"), Some(&disbanded_circuit));
+        assert!(!circuits.contains_key("WBKLF-CCCCC"));
+    }
+
+    /// Verify that a circuit whose roster references a node that is not a member cannot be added
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a circuit with only one of the two nodes its services run on
+    /// 4. Validate that an InvalidStateError naming the missing node is returned
+    /// 5. Validate that the circuit was not added to the store
+    #[test]
+    fn test_add_circuit_missing_roster_node() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let nodes = create_nodes()
+            .into_iter()
+            .filter(|node| node.node_id() == "acme-node-000")
+            .collect::<Vec<_>>();
+        let circuit = create_circuit_builder("WBKLF-BBBBB", CircuitStatus::Active, &nodes)
+            .build()
+            .expect("Unable to build circuit");
+
+        match store.add_circuit(circuit, nodes) {
+            Err(AdminServiceStoreError::InvalidStateError(err)) => {
+                assert!(err.to_string().contains("bubba-node-000"));
+                assert!(!err.to_string().contains("acme-node-000"));
+            }
+            res => panic!("Expected InvalidStateError, got {:?}", res),
+        }
+
+        assert_eq!(
+            store
+                .get_circuit("WBKLF-BBBBB")
+                .expect("Unable to fetch circuit"),
+            None
+        );
+    }
+
+    /// Verify that a circuit or proposal whose roster has a duplicated service ID cannot be added
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a circuit with two services that have the same ID
+    /// 4. Validate that an InvalidStateError naming the duplicated ID is returned, and that the
+    ///    circuit was not added to the store
+    /// 5. Add a proposal with two services that have the same ID
+    /// 6. Validate that an InvalidStateError naming the duplicated ID is returned, and that the
+    ///    proposal was not added to the store
+    #[test]
+    fn test_add_duplicate_service_id() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let circuit = create_circuit_builder("WBKLF-BBBBB", CircuitStatus::Active, &create_nodes())
+            .with_roster(&[
+                ServiceBuilder::default()
+                    .with_service_id("a000")
+                    .with_service_type("scabbard")
+                    .with_node_id("acme-node-000")
+                    .build()
+                    .expect("Unable to build service"),
+                ServiceBuilder::default()
+                    .with_service_id("a000")
+                    .with_service_type("scabbard")
+                    .with_node_id("bubba-node-000")
+                    .build()
+                    .expect("Unable to build service"),
+            ])
+            .build()
+            .expect("Unable to build circuit");
+
+        match store.add_circuit(circuit, create_nodes()) {
+            Err(AdminServiceStoreError::InvalidStateError(err)) => {
+                assert!(err.to_string().contains("duplicate service IDs: a000"));
+            }
+            res => panic!("Expected InvalidStateError, got {:?}", res),
+        }
+        assert_eq!(
+            store
+                .get_circuit("WBKLF-BBBBB")
+                .expect("Unable to fetch circuit"),
+            None
+        );
+
+        let proposal = create_proposal();
+        let proposed_circuit = ProposedCircuitBuilder::default()
+            .with_circuit_id(proposal.circuit_id())
+            .with_roster(&[
+                ProposedServiceBuilder::default()
+                    .with_service_id("a000")
+                    .with_service_type("scabbard")
+                    .with_node_id("acme-node-000")
+                    .build()
+                    .expect("Unable to build service"),
+                ProposedServiceBuilder::default()
+                    .with_service_id("a000")
+                    .with_service_type("scabbard")
+                    .with_node_id("bubba-node-000")
+                    .build()
+                    .expect("Unable to build service"),
+            ])
+            .with_members(proposal.circuit().members())
+            .with_circuit_management_type("gameroom")
+            .build()
+            .expect("Unable to build circuit");
+        let proposal = proposal
+            .builder()
+            .with_circuit(&proposed_circuit)
+            .build()
+            .expect("Unable to build proposal");
+
+        match store.add_proposal(proposal) {
+            Err(AdminServiceStoreError::InvalidStateError(err)) => {
+                assert!(err.to_string().contains("duplicate service IDs: a000"));
+            }
+            res => panic!("Expected InvalidStateError, got {:?}", res),
+        }
+        assert_eq!(
+            store
+                .get_proposal("WBKLF-BBBBB")
+                .expect("Unable to fetch proposal"),
+            None
+        );
+    }
+
+    /// Verify that updating a circuit that does not exist returns a `NotFound` constraint
+    /// violation
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Attempt to update a circuit that was never added
+    /// 4. Validate a `ConstraintViolationError` with the `NotFound` type is returned
+    /// 5. Validate the circuit was not added to the store
+    #[test]
+    fn test_update_nonexistent_circuit() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let circuit = create_circuit("WBKLF-BBBBB", CircuitStatus::Active);
+
+        match store.update_circuit(circuit) {
+            Err(AdminServiceStoreError::ConstraintViolationError(err)) => {
+                assert_eq!(err.violation_type(), &ConstraintViolationType::NotFound)
+            }
+            res => panic!(
+                "Expected Err(ConstraintViolationError), got {:?} instead",
+                res
+            ),
+        }
+
+        assert_eq!(
+            store
+                .get_circuit("WBKLF-BBBBB")
+                .expect("Unable to fetch circuit"),
+            None
+        );
+    }
+
+    /// Verify that `update_circuit_returning_previous` returns the circuit that was stored before
+    /// the update, and returns `None` for a circuit that does not exist
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add an active circuit to the store and fetch it
+    /// 4. Update the circuit to be disbanded, validating the fetched circuit is returned
+    /// 5. Validate the fetched circuit now matches the updated circuit
+    /// 6. Validate `None` is returned for a circuit that was never added, and it is not added
+    #[test]
+    fn test_update_circuit_returning_previous() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        store
+            .add_circuit(
+                create_circuit("WBKLF-BBBBB", CircuitStatus::Active),
+                create_nodes(),
+            )
+            .expect("Unable to add circuit");
+
+        let stored_circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to fetch circuit")
+            .expect("Got None when expecting circuit");
+
+        let updated_circuit = create_circuit("WBKLF-BBBBB", CircuitStatus::Disbanded);
+        let previous = store
+            .update_circuit_returning_previous(updated_circuit.clone())
+            .expect("Unable to update circuit");
+
+        assert_eq!(previous, Some(stored_circuit));
+
+        let fetched_circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to fetch circuit")
+            .expect("Got None when expecting circuit");
+
+        assert_eq!(updated_circuit, fetched_circuit);
+
+        let previous = store
+            .update_circuit_returning_previous(create_extra_circuit("WBKLF-CCCCC"))
+            .expect("Unable to update circuit");
+
+        assert_eq!(previous, None);
+        assert_eq!(
+            store
+                .get_circuit("WBKLF-CCCCC")
+                .expect("Unable to fetch circuit"),
+            None
+        );
+    }
+
+    /// Verify that circuits can be listed and counted by circuit version
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add two circuits at version 1 and one circuit at version 3 to the store
+    /// 4. Validate that the version equality predicate returns only circuits at that version
+    /// 5. Validate that the version less-than predicate returns only circuits below that version
+    /// 6. Validate that both predicates together return only circuits matching both
+    #[test]
+    fn test_list_circuits_by_version() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        for (circuit_id, version) in &[("WBKLF-AAAAA", 1), ("WBKLF-BBBBB", 3), ("WBKLF-CCCCC", 1)] {
+            let circuit =
+                create_circuit_builder(circuit_id, CircuitStatus::Active, &create_nodes())
+                    .with_circuit_version(*version)
+                    .build()
+                    .expect("Unable to build circuit");
+            store
+                .add_circuit(circuit, create_nodes())
+                .expect("Unable to add circuit");
+        }
+
+        let list_ids = |predicates: &[CircuitPredicate]| -> Vec<String> {
+            store
+                .list_circuits(predicates)
+                .expect("Unable to list circuits")
+                .map(|circuit| circuit.circuit_id().to_string())
+                .collect()
+        };
+        let count = |predicates: &[CircuitPredicate]| -> u32 {
+            store
+                .count_circuits(predicates)
+                .expect("Unable to count circuits")
+        };
+
+        assert_eq!(
+            list_ids(&[CircuitPredicate::CircuitVersionEq(1)]),
+            vec!["WBKLF-CCCCC", "WBKLF-AAAAA"]
+        );
+        assert_eq!(count(&[CircuitPredicate::CircuitVersionEq(1)]), 2);
+        assert_eq!(
+            list_ids(&[CircuitPredicate::CircuitVersionEq(3)]),
+            vec!["WBKLF-BBBBB"]
+        );
+        assert_eq!(count(&[CircuitPredicate::CircuitVersionEq(3)]), 1);
+        assert!(list_ids(&[CircuitPredicate::CircuitVersionEq(2)]).is_empty());
+        assert_eq!(count(&[CircuitPredicate::CircuitVersionEq(2)]), 0);
+
+        assert_eq!(
+            list_ids(&[CircuitPredicate::CircuitVersionLt(3)]),
+            vec!["WBKLF-CCCCC", "WBKLF-AAAAA"]
+        );
+        assert_eq!(count(&[CircuitPredicate::CircuitVersionLt(3)]), 2);
+        assert!(list_ids(&[CircuitPredicate::CircuitVersionLt(1)]).is_empty());
+        assert_eq!(count(&[CircuitPredicate::CircuitVersionLt(1)]), 0);
+
+        let predicates = [
+            CircuitPredicate::CircuitVersionEq(3),
+            CircuitPredicate::CircuitVersionLt(3),
+        ];
+        assert!(list_ids(&predicates).is_empty());
+        assert_eq!(count(&predicates), 0);
+    }
+
+    /// Verify that a page of circuits can be listed in order of circuit ID
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add circuits with differing management types and versions to the store
+    /// 4. Validate the circuits are listed in ascending and descending order of circuit ID
+    /// 5. Validate that the offset and limit select the expected page
+    #[test]
+    fn test_list_circuits_page_by_id() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+        add_circuits_for_ordering(&store);
+
+        let options = CircuitQueryOptions::new();
+        assert_eq!(
+            list_page_ids(&store, &options),
+            vec!["WBKLF-AAAAA", "WBKLF-BBBBB", "WBKLF-CCCCC", "WBKLF-DDDDD"]
+        );
+
+        let options = options.with_order(CircuitOrder::Id, CircuitOrderDirection::Desc);
+        assert_eq!(
+            list_page_ids(&store, &options),
+            vec!["WBKLF-DDDDD", "WBKLF-CCCCC", "WBKLF-BBBBB", "WBKLF-AAAAA"]
+        );
+
+        let options = options.with_offset(1).with_limit(2);
+        assert_eq!(
+            list_page_ids(&store, &options),
+            vec!["WBKLF-CCCCC", "WBKLF-BBBBB"]
+        );
+    }
+
+    /// Verify that a page of circuits can be listed in order of management type, with ties
+    /// ordered by circuit ID
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add circuits with differing management types and versions to the store
+    /// 4. Validate the circuits are listed in ascending and descending order of management type
+    /// 5. Validate that the offset and limit select the expected page
+    #[test]
+    fn test_list_circuits_page_by_management_type() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+        add_circuits_for_ordering(&store);
+
+        let options = CircuitQueryOptions::new()
+            .with_order(CircuitOrder::ManagementType, CircuitOrderDirection::Asc);
+        assert_eq!(
+            list_page_ids(&store, &options),
+            vec!["WBKLF-BBBBB", "WBKLF-DDDDD", "WBKLF-AAAAA", "WBKLF-CCCCC"]
+        );
+
+        let options = options.with_order(CircuitOrder::ManagementType, CircuitOrderDirection::Desc);
+        assert_eq!(
+            list_page_ids(&store, &options),
+            vec!["WBKLF-AAAAA", "WBKLF-CCCCC", "WBKLF-BBBBB", "WBKLF-DDDDD"]
+        );
+
+        let options = options.with_offset(1).with_limit(2);
+        assert_eq!(
+            list_page_ids(&store, &options),
+            vec!["WBKLF-CCCCC", "WBKLF-BBBBB"]
+        );
+    }
+
+    /// Verify that a page of circuits can be listed in order of circuit version, with ties
+    /// ordered by circuit ID
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add circuits with differing management types and versions to the store
+    /// 4. Validate the circuits are listed in ascending and descending order of circuit version
+    /// 5. Validate that the offset and limit select the expected page
+    #[test]
+    fn test_list_circuits_page_by_version() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+        add_circuits_for_ordering(&store);
+
+        let options = CircuitQueryOptions::new()
+            .with_order(CircuitOrder::CircuitVersion, CircuitOrderDirection::Asc);
+        assert_eq!(
+            list_page_ids(&store, &options),
+            vec!["WBKLF-CCCCC", "WBKLF-AAAAA", "WBKLF-DDDDD", "WBKLF-BBBBB"]
+        );
+
+        let options = options.with_order(CircuitOrder::CircuitVersion, CircuitOrderDirection::Desc);
+        assert_eq!(
+            list_page_ids(&store, &options),
+            vec!["WBKLF-BBBBB", "WBKLF-AAAAA", "WBKLF-DDDDD", "WBKLF-CCCCC"]
+        );
+
+        let options = options.with_offset(1).with_limit(2);
+        assert_eq!(
+            list_page_ids(&store, &options),
+            vec!["WBKLF-AAAAA", "WBKLF-DDDDD"]
+        );
+    }
+
+    /// Verify that proposals can be listed and counted by circuit version
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a proposal at version 3 and a proposal without a set version to the store
+    /// 4. Validate that the version equality predicate returns only proposals at that version
+    /// 5. Validate that the version less-than predicate returns only proposals below that version
+    #[test]
+    fn test_list_proposals_by_version() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        store
+            .add_proposal(create_proposal())
+            .expect("Unable to add circuit proposal");
+        store
+            .add_proposal(create_extra_proposal())
+            .expect("Unable to add circuit proposal");
+
+        let list_ids = |predicate: CircuitPredicate| -> Vec<String> {
+            store
+                .list_proposals(&[predicate])
+                .expect("Unable to list proposals")
+                .map(|proposal| proposal.circuit_id().to_string())
+                .collect()
+        };
+        let count = |predicate: CircuitPredicate| -> u32 {
+            store
+                .count_proposals(&[predicate])
+                .expect("Unable to count proposals")
+        };
+
+        assert_eq!(
+            list_ids(CircuitPredicate::CircuitVersionEq(3)),
+            vec!["WBKLF-BBBBB"]
+        );
+        assert_eq!(count(CircuitPredicate::CircuitVersionEq(3)), 1);
+        assert_eq!(
+            list_ids(CircuitPredicate::CircuitVersionEq(UNSET_CIRCUIT_VERSION)),
+            vec!["WBKLF-AAAAA"]
+        );
+        assert_eq!(
+            count(CircuitPredicate::CircuitVersionEq(UNSET_CIRCUIT_VERSION)),
+            1
+        );
+
+        assert_eq!(
+            list_ids(CircuitPredicate::CircuitVersionLt(3)),
+            vec!["WBKLF-AAAAA"]
+        );
+        assert_eq!(count(CircuitPredicate::CircuitVersionLt(3)), 1);
+        assert!(list_ids(CircuitPredicate::CircuitVersionLt(UNSET_CIRCUIT_VERSION)).is_empty());
+        assert_eq!(
+            count(CircuitPredicate::CircuitVersionLt(UNSET_CIRCUIT_VERSION)),
+            0
+        );
+    }
+
+    /// Verify that proposals can be listed and counted by proposal type
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a Create proposal and a Disband proposal to the store
+    /// 4. Validate that the proposal type predicate returns only proposals of that type
+    /// 5. Validate that a proposal type with no proposals returns no proposals
+    #[test]
+    fn test_list_proposals_by_proposal_type() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        store
+            .add_proposal(create_proposal())
+            .expect("Unable to add circuit proposal");
+        store
+            .add_proposal(
+                create_extra_proposal()
+                    .builder()
+                    .with_proposal_type(&ProposalType::Disband)
+                    .build()
+                    .expect("Unable to build disband proposal"),
+            )
+            .expect("Unable to add circuit proposal");
+
+        let list_ids = |proposal_type: ProposalType| -> Vec<String> {
+            store
+                .list_proposals(&[CircuitPredicate::ProposalTypeEq(proposal_type)])
+                .expect("Unable to list proposals")
+                .map(|proposal| proposal.circuit_id().to_string())
+                .collect()
+        };
+        let count = |proposal_type: ProposalType| -> u32 {
+            store
+                .count_proposals(&[CircuitPredicate::ProposalTypeEq(proposal_type)])
+                .expect("Unable to count proposals")
+        };
+
+        assert_eq!(list_ids(ProposalType::Create), vec!["WBKLF-BBBBB"]);
+        assert_eq!(count(ProposalType::Create), 1);
+        assert_eq!(list_ids(ProposalType::Disband), vec!["WBKLF-AAAAA"]);
+        assert_eq!(count(ProposalType::Disband), 1);
+        assert!(list_ids(ProposalType::AddNode).is_empty());
+        assert_eq!(count(ProposalType::AddNode), 0);
+    }
+
+    /// Verify that circuits can be listed and counted by a display name substring
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add circuits with several display names to the store
+    /// 4. Validate that a substring matches display names regardless of case
+    /// 5. Validate that `_` and `%` in the substring only match themselves
+    /// 6. Validate that a substring matching no display names returns no circuits
+    #[test]
+    fn test_list_circuits_display_name_contains() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        for (circuit_id, display_name) in &[
+            ("WBKLF-AAAAA", "Acme Supply Chain"),
+            ("WBKLF-BBBBB", "acme_widgets"),
+            ("WBKLF-CCCCC", "100% Bubba"),
+            ("WBKLF-DDDDD", "Gumbo"),
+        ] {
+            let circuit =
+                create_circuit_builder(circuit_id, CircuitStatus::Active, &create_nodes())
+                    .with_display_name(display_name)
+                    .build()
+                    .expect("Unable to build circuit");
+            store
+                .add_circuit(circuit, create_nodes())
+                .expect("Unable to add circuit");
+        }
+
+        let list_ids = |value: &str| -> Vec<String> {
+            store
+                .list_circuits(&[CircuitPredicate::DisplayNameContains(value.to_string())])
+                .expect("Unable to list circuits")
+                .map(|circuit| circuit.circuit_id().to_string())
+                .collect()
+        };
+        let count = |value: &str| -> u32 {
+            store
+                .count_circuits(&[CircuitPredicate::DisplayNameContains(value.to_string())])
+                .expect("Unable to count circuits")
+        };
+
+        assert_eq!(list_ids("ACME"), vec!["WBKLF-BBBBB", "WBKLF-AAAAA"]);
+        assert_eq!(count("ACME"), 2);
+        assert_eq!(list_ids("supply chain"), vec!["WBKLF-AAAAA"]);
+        assert_eq!(count("supply chain"), 1);
+
+        assert_eq!(list_ids("_"), vec!["WBKLF-BBBBB"]);
+        assert_eq!(count("_"), 1);
+        assert_eq!(list_ids("%"), vec!["WBKLF-CCCCC"]);
+        assert_eq!(count("%"), 1);
+        assert_eq!(list_ids("0% b"), vec!["WBKLF-CCCCC"]);
+
+        assert!(list_ids("arcade").is_empty());
+        assert_eq!(count("arcade"), 0);
+    }
+
+    /// Verify that circuits can be listed and counted by a service type that is absent from their
+    /// rosters
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add circuits with only scabbard services, only an echo service, both, and no services
+    /// 4. Validate that circuits without a scabbard service, including the circuit with no
+    ///    services, are listed and counted
+    /// 5. Validate that circuits without an echo service are listed and counted
+    /// 6. Validate that both predicates together only match the circuit with no services
+    #[test]
+    fn test_list_circuits_service_type_absent() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let echo_service = ServiceBuilder::default()
+            .with_service_id("e000")
+            .with_service_type("echo")
+            .with_node_id("acme-node-000")
+            .build()
+            .expect("Unable to build service");
+        let scabbard_roster = create_circuit("WBKLF-AAAAA", CircuitStatus::Active)
+            .roster()
+            .to_vec();
+
+        for (circuit_id, roster) in vec![
+            ("WBKLF-AAAAA", scabbard_roster.clone()),
+            ("WBKLF-BBBBB", vec![echo_service.clone()]),
+            ("WBKLF-CCCCC", vec![]),
+            (
+                "WBKLF-DDDDD",
+                vec![scabbard_roster[0].clone(), echo_service],
+            ),
+        ] {
+            let circuit =
+                create_circuit_builder(circuit_id, CircuitStatus::Active, &create_nodes())
+                    .with_roster(&roster)
+                    .build()
+                    .expect("Unable to build circuit");
+            store
+                .add_circuit(circuit, create_nodes())
+                .expect("Unable to add circuit");
+        }
+
+        let list_ids = |predicates: &[CircuitPredicate]| -> Vec<String> {
+            store
+                .list_circuits(predicates)
+                .expect("Unable to list circuits")
+                .map(|circuit| circuit.circuit_id().to_string())
+                .collect()
+        };
+        let count = |predicates: &[CircuitPredicate]| -> u32 {
+            store
+                .count_circuits(predicates)
+                .expect("Unable to count circuits")
+        };
+
+        let absent = |service_type: &str| CircuitPredicate::ServiceTypeAbsent(service_type.into());
+
+        assert_eq!(
+            list_ids(&[absent("scabbard")]),
+            vec!["WBKLF-CCCCC", "WBKLF-BBBBB"]
+        );
+        assert_eq!(count(&[absent("scabbard")]), 2);
+
+        assert_eq!(
+            list_ids(&[absent("echo")]),
+            vec!["WBKLF-CCCCC", "WBKLF-AAAAA"]
+        );
+        assert_eq!(count(&[absent("echo")]), 2);
+
+        assert_eq!(
+            list_ids(&[absent("scabbard"), absent("echo")]),
+            vec!["WBKLF-CCCCC"]
+        );
+        assert_eq!(count(&[absent("scabbard"), absent("echo")]), 1);
+    }
+
+    /// Verify that a deleted circuit is kept in the store, but is only listed when its status is
+    /// requested
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add two active circuits to the store
+    /// 4. Delete one of the circuits
+    /// 5. Validate the deleted circuit can still be fetched, with a `Deleted` status
+    /// 6. Validate only the remaining circuit is listed and counted by default
+    /// 7. Validate the deleted circuit is listed and counted when the `Deleted` status is
+    ///    requested
+    /// 8. Validate deleting a circuit that does not exist returns a `NotFound` error
+    #[test]
+    fn test_delete_circuit() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        store
+            .add_circuit(
+                create_circuit("WBKLF-AAAAA", CircuitStatus::Active),
+                create_nodes(),
+            )
+            .expect("Unable to add circuit");
+        store
+            .add_circuit(
+                create_circuit("WBKLF-BBBBB", CircuitStatus::Active),
+                create_nodes(),
+            )
+            .expect("Unable to add circuit");
+
+        store
+            .delete_circuit("WBKLF-AAAAA")
+            .expect("Unable to delete circuit");
+
+        let deleted_circuit = store
+            .get_circuit("WBKLF-AAAAA")
+            .expect("Unable to get circuit")
+            .expect("Deleted circuit was removed from the store");
+        assert_eq!(deleted_circuit.circuit_status(), &CircuitStatus::Deleted);
+
+        let listed_ids: Vec<String> = store
+            .list_circuits(&[])
+            .expect("Unable to list circuits")
+            .map(|circuit| circuit.circuit_id().to_string())
+            .collect();
+        assert_eq!(listed_ids, vec!["WBKLF-BBBBB"]);
+        assert_eq!(
+            store.count_circuits(&[]).expect("Unable to count circuits"),
+            1
+        );
+
+        let deleted_predicate = [CircuitPredicate::CircuitStatus(CircuitStatus::Deleted)];
+        let listed_ids: Vec<String> = store
+            .list_circuits(&deleted_predicate)
+            .expect("Unable to list circuits")
+            .map(|circuit| circuit.circuit_id().to_string())
+            .collect();
+        assert_eq!(listed_ids, vec!["WBKLF-AAAAA"]);
+        assert_eq!(
+            store
+                .count_circuits(&deleted_predicate)
+                .expect("Unable to count circuits"),
+            1
+        );
+
+        match store.delete_circuit("WBKLF-CCCCC") {
+            Err(AdminServiceStoreError::ConstraintViolationError(err)) => {
+                assert_eq!(err.violation_type(), &ConstraintViolationType::NotFound)
+            }
+            res => panic!(
+                "Expected Err(ConstraintViolationError), got {:?} instead",
+                res
+            ),
+        }
+    }
+
+    /// Verify that the distinct circuit management types are listed, sorted, across circuits of
+    /// every status
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add four circuits with three management types, one of which is repeated, and with
+    ///    different statuses
+    /// 4. Validate the deduplicated, sorted list of management types is returned
+    #[test]
+    fn test_list_circuit_management_types() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        assert!(store
+            .list_circuit_management_types()
+            .expect("Unable to list management types")
+            .is_empty());
+
+        for (circuit_id, management_type, status) in &[
+            ("WBKLF-AAAAA", "gameroom", CircuitStatus::Active),
+            ("WBKLF-BBBBB", "scabbard", CircuitStatus::Disbanded),
+            ("WBKLF-CCCCC", "arcade", CircuitStatus::Abandoned),
+            ("WBKLF-DDDDD", "gameroom", CircuitStatus::Active),
+        ] {
+            let circuit = create_circuit_builder(circuit_id, status.clone(), &create_nodes())
+                .with_circuit_management_type(management_type)
+                .build()
+                .expect("Unable to build circuit");
+            store
+                .add_circuit(circuit, create_nodes())
+                .expect("Unable to add circuit");
+        }
+
+        assert_eq!(
+            store
+                .list_circuit_management_types()
+                .expect("Unable to list management types"),
+            vec!["arcade", "gameroom", "scabbard"]
+        );
+    }
+
+    /// Verify that the distinct circuit management types of proposals are listed
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add two proposals with the same management type
+    /// 4. Validate the management type is listed once
+    #[test]
+    fn test_list_proposal_management_types() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        store
+            .add_proposal(create_proposal())
+            .expect("Unable to add circuit proposal");
+        store
+            .add_proposal(create_extra_proposal())
+            .expect("Unable to add circuit proposal");
+
+        assert_eq!(
+            store
+                .list_proposal_management_types()
+                .expect("Unable to list management types"),
+            vec!["gameroom"]
+        );
+    }
+
+    /// Verify that a circuit can be added to the store correctly and then fetched from the store
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Create a circuit and nodes
+    /// 4. Add circuit and nodes to store
+    /// 5. Fetch Circuit from store
+    /// 6. Validate fetched circuit is the same as the circuit added
+    /// 7. Fetch CircuitNode from store
+    /// 8. Validate fetched node is the same as the node added
+    #[test]
+    fn test_add_get_circuit_and_nodes() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let circuit = create_circuit("WBKLF-BBBBB", CircuitStatus::Active);
+
+        let nodes = create_nodes();
+
+        store
+            .add_circuit(circuit.clone(), nodes)
+            .expect("Unable to add circuit");
+
+        let fetched_circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to get circuit")
+            .expect("Got None when expecting circuit");
+
+        let fetched_node = store
+            .get_node("bubba-node-000")
+            .expect("Unable to get node")
+            .expect("Got None when expecting node");
+
+        assert_eq!(circuit, fetched_circuit);
+        assert_eq!(
+            fetched_node,
+            CircuitNodeBuilder::default()
+                .with_node_id("bubba-node-000".into())
+                .with_endpoints(&vec!["tcps://splinterd-node-bubba:8044".into()])
+                .build()
+                .expect("Unable to build node"),
+        )
+    }
+
+    /// Verify that a fetched circuit carries all of the endpoints of its members
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Create a circuit with a member that has multiple endpoints
+    /// 4. Add circuit and nodes to store
+    /// 5. Fetch Circuit from store
+    /// 6. Validate each fetched member has all of its endpoints
+    #[test]
+    fn test_get_circuit_member_endpoints() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let nodes = vec![
+            CircuitNodeBuilder::default()
+                .with_node_id("bubba-node-000".into())
+                .with_endpoints(&vec![
+                    "tcps://splinterd-node-bubba:8044".into(),
+                    "tcps://splinterd-node-bubba-alt:8044".into(),
+                ])
+                .build()
+                .expect("Unable to build node"),
+            CircuitNodeBuilder::default()
+                .with_node_id("acme-node-000".into())
+                .with_endpoints(&vec!["tcps://splinterd-node-acme:8044".into()])
+                .build()
+                .expect("Unable to build node"),
+        ];
+
+        let circuit = create_circuit_builder("WBKLF-BBBBB", CircuitStatus::Active, &nodes)
+            .build()
+            .expect("Unable to build circuit");
+
+        store
+            .add_circuit(circuit.clone(), nodes.clone())
+            .expect("Unable to add circuit");
+
+        let fetched_circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to get circuit")
+            .expect("Got None when expecting circuit");
+
+        assert_eq!(fetched_circuit.members(), nodes.as_slice());
+        assert_eq!(circuit, fetched_circuit);
+    }
+
+    /// Verify that get_circuits builds the same circuit as get_circuit, for a circuit with a
+    /// member that has multiple endpoints and a member that has none
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Create a circuit with a member that has two endpoints and a member with no endpoints
+    /// 4. Add circuit and nodes to store
+    /// 5. Validate that the circuit returned by get_circuits is the one returned by get_circuit,
+    ///    with each member listed once
+    #[test]
+    fn test_get_circuits_matches_get_circuit() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let nodes = vec![
+            CircuitNodeBuilder::default()
+                .with_node_id("bubba-node-000".into())
+                .with_endpoints(&vec![
+                    "tcps://splinterd-node-bubba:8044".into(),
+                    "tcps://splinterd-node-bubba-alt:8044".into(),
+                ])
+                .build()
+                .expect("Unable to build node"),
+            CircuitNodeBuilder::default()
+                .with_node_id("acme-node-000".into())
+                .with_endpoints(&vec![])
+                .build()
+                .expect("Unable to build node"),
+        ];
+
+        let circuit = create_circuit_builder("WBKLF-BBBBB", CircuitStatus::Active, &nodes)
+            .build()
+            .expect("Unable to build circuit");
+
+        store
+            .add_circuit(circuit.clone(), nodes.clone())
+            .expect("Unable to add circuit");
+
+        let fetched_circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to get circuit")
+            .expect("Got None when expecting circuit");
+        let circuits = store
+            .get_circuits(&["WBKLF-BBBBB"])
+            .expect("Unable to get circuits");
+
+        assert_eq!(circuits.get("WBKLF-BBBBB"), Some(&fetched_circuit));
+        assert_eq!(fetched_circuit.members(), nodes.as_slice());
+        assert_eq!(circuit, fetched_circuit);
+    }
+
+    /// Verify that list_circuits works correctly
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Create a circuit and nodes
+    /// 4. Add circuit to store
+    /// 5. List circuits from store with no predicates, validate added circuit is returned
+    /// 6. List circuits from store with management type predicate, validate added circuit is
+    ///    returned
+    /// 7. List circuits from store with member predicate, validate added circuit is
     ///    returned
     /// 8. List circuits from store with mismatching management type predicate, validate no
     ///    circuits are returned
@@ -907,12 +3380,274 @@ pub mod tests {
 
         assert_eq!(circuits.next(), None);
 
-        // show all `Active` circuits are returned
-        let circuits = store
-            .list_circuits(&vec![])
-            .expect("Unable to list circuits");
+        // show all `Active` circuits are returned
+        let circuits = store
+            .list_circuits(&vec![])
+            .expect("Unable to list circuits");
+
+        assert_eq!(circuits.len(), 2);
+    }
+
+    /// Verify that the default circuit status filter is applied when listing and counting circuits
+    /// without a circuit status predicate
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create a DieselAdminServiceStore that includes circuits of every status by default
+    /// 3. Add an `Active` and a `Disbanded` circuit to the store
+    /// 4. List and count circuits with no predicates, validate both circuits are returned
+    /// 5. List circuits with the `CircuitStatus::Active` predicate, validate only the `Active`
+    ///    circuit is returned
+    /// 6. Create a store on the same database that only includes `Disbanded` circuits by default
+    /// 7. List and count circuits with no predicates, validate only the `Disbanded` circuit is
+    ///    returned
+    #[test]
+    fn test_list_circuits_default_status_filter() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store =
+            DieselAdminServiceStore::new(pool.clone()).with_all_circuit_statuses_by_default();
+
+        let active_circuit = create_circuit("WBKLF-BBBBB", CircuitStatus::Active);
+        let disbanded_circuit = create_circuit("WBKLF-DDDDD", CircuitStatus::Disbanded);
+
+        store
+            .add_circuit(active_circuit.clone(), create_nodes())
+            .expect("Unable to add circuit");
+        store
+            .add_circuit(disbanded_circuit.clone(), create_nodes())
+            .expect("Unable to add disbanded circuit");
+
+        let circuits = store
+            .list_circuits(&[])
+            .expect("Unable to list circuits")
+            .collect::<Vec<_>>();
+        assert_eq!(
+            circuits,
+            vec![disbanded_circuit.clone(), active_circuit.clone()]
+        );
+        assert_eq!(
+            store.count_circuits(&[]).expect("Unable to count circuits"),
+            2
+        );
+
+        let circuits = store
+            .list_circuits(&[CircuitPredicate::CircuitStatus(CircuitStatus::Active)])
+            .expect("Unable to list circuits with `CircuitStatus` predicate")
+            .collect::<Vec<_>>();
+        assert_eq!(circuits, vec![active_circuit]);
+
+        let store = DieselAdminServiceStore::new(pool)
+            .with_default_circuit_statuses(vec![CircuitStatus::Disbanded]);
+
+        let circuits = store
+            .list_circuits(&[])
+            .expect("Unable to list circuits")
+            .collect::<Vec<_>>();
+        assert_eq!(circuits, vec![disbanded_circuit]);
+        assert_eq!(
+            store.count_circuits(&[]).expect("Unable to count circuits"),
+            1
+        );
+    }
+
+    /// Verify that a clone of a store keeps the store's default circuit status filter
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create a DieselAdminServiceStore that only includes `Disbanded` circuits by default
+    /// 3. Add an `Active` and a `Disbanded` circuit to the store
+    /// 4. Clone the store
+    /// 5. List and count circuits with no predicates on the clone, validate only the `Disbanded`
+    ///    circuit is returned
+    #[test]
+    fn test_clone_keeps_default_status_filter() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool)
+            .with_default_circuit_statuses(vec![CircuitStatus::Disbanded]);
+
+        let disbanded_circuit = create_circuit("WBKLF-DDDDD", CircuitStatus::Disbanded);
+
+        store
+            .add_circuit(
+                create_circuit("WBKLF-BBBBB", CircuitStatus::Active),
+                create_nodes(),
+            )
+            .expect("Unable to add circuit");
+        store
+            .add_circuit(disbanded_circuit.clone(), create_nodes())
+            .expect("Unable to add disbanded circuit");
+
+        let cloned_store = store.clone();
+
+        let circuits = cloned_store
+            .list_circuits(&[])
+            .expect("Unable to list circuits")
+            .collect::<Vec<_>>();
+        assert_eq!(circuits, vec![disbanded_circuit]);
+        assert_eq!(
+            cloned_store
+                .count_circuits(&[])
+                .expect("Unable to count circuits"),
+            1
+        );
+    }
+
+    /// Verify that setting a circuit's status updates the circuit and its services
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add an active circuit with two scabbard services
+    /// 4. Validate the circuit's services are counted as the services of an active circuit
+    /// 5. Set the circuit's status to `Disbanded`
+    /// 6. Validate the fetched circuit has a `Disbanded` status
+    /// 7. Validate the circuit's services are no longer counted as the services of an active
+    ///    circuit
+    /// 8. Validate setting the status of a circuit that does not exist returns a `NotFound`
+    ///    constraint violation
+    #[test]
+    fn test_set_circuit_status() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        store
+            .add_circuit(
+                create_circuit("WBKLF-AAAAA", CircuitStatus::Active),
+                create_nodes(),
+            )
+            .expect("Unable to add circuit");
+
+        let counts = store
+            .count_services_by_type()
+            .expect("Unable to count services");
+        assert_eq!(counts.get("scabbard"), Some(&2));
+
+        store
+            .set_circuit_status("WBKLF-AAAAA", CircuitStatus::Disbanded)
+            .expect("Unable to set circuit status");
+
+        let circuit = store
+            .get_circuit("WBKLF-AAAAA")
+            .expect("Unable to get circuit")
+            .expect("Got None when expecting circuit");
+        assert_eq!(circuit.circuit_status(), &CircuitStatus::Disbanded);
+
+        let counts = store
+            .count_services_by_type()
+            .expect("Unable to count services");
+        assert_eq!(counts.get("scabbard"), None);
+
+        match store.set_circuit_status("WBKLF-BBBBB", CircuitStatus::Disbanded) {
+            Err(AdminServiceStoreError::ConstraintViolationError(err)) => {
+                assert_eq!(err.violation_type(), &ConstraintViolationType::NotFound)
+            }
+            res => panic!(
+                "Expected Err(ConstraintViolationError), got {:?} instead",
+                res
+            ),
+        }
+    }
+
+    /// Verify that count_services_by_type counts the services of active circuits by type
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add an active circuit with two scabbard services
+    /// 4. Add an active circuit with a scabbard service and an echo service
+    /// 5. Add a disbanded circuit with two scabbard services
+    /// 6. Validate that the services of only the active circuits are counted, by type
+    #[test]
+    fn test_count_services_by_type() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        store
+            .add_circuit(
+                create_circuit("WBKLF-AAAAA", CircuitStatus::Active),
+                create_nodes(),
+            )
+            .expect("Unable to add circuit");
+
+        let mixed_circuit =
+            create_circuit_builder("WBKLF-BBBBB", CircuitStatus::Active, &create_nodes())
+                .with_roster(&[
+                    ServiceBuilder::default()
+                        .with_service_id("b000")
+                        .with_service_type("scabbard")
+                        .with_node_id("acme-node-000")
+                        .build()
+                        .expect("Unable to build service"),
+                    ServiceBuilder::default()
+                        .with_service_id("b001")
+                        .with_service_type("echo")
+                        .with_node_id("bubba-node-000")
+                        .build()
+                        .expect("Unable to build service"),
+                ])
+                .build()
+                .expect("Unable to build circuit");
+        store
+            .add_circuit(mixed_circuit, create_nodes())
+            .expect("Unable to add circuit");
+
+        store
+            .add_circuit(
+                create_circuit("WBKLF-CCCCC", CircuitStatus::Disbanded),
+                create_nodes(),
+            )
+            .expect("Unable to add circuit");
+
+        let counts = store
+            .count_services_by_type()
+            .expect("Unable to count services");
+
+        let mut expected = HashMap::new();
+        expected.insert("scabbard".to_string(), 3);
+        expected.insert("echo".to_string(), 1);
+        assert_eq!(counts, expected);
+    }
+
+    /// Verify that storage_stats counts the rows of each table
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Validate that every count is zero in an empty store
+    /// 4. Add two circuits with two services each, whose members are three distinct nodes
+    /// 5. Add a proposal and two events
+    /// 6. Validate that the stats count two circuits, one proposal, three nodes, four services and
+    ///    two events
+    #[test]
+    fn test_storage_stats() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        assert_eq!(
+            store.storage_stats().expect("Unable to get storage stats"),
+            StorageStats::default()
+        );
+
+        store
+            .add_circuit(
+                create_circuit("WBKLF-AAAAA", CircuitStatus::Active),
+                create_nodes(),
+            )
+            .expect("Unable to add circuit");
+        store
+            .add_circuit(create_extra_circuit("WBKLF-CCCCC"), create_extra_nodes())
+            .expect("Unable to add circuit");
+        store
+            .add_proposal(create_proposal())
+            .expect("Unable to add proposal");
+        for _ in 0..2 {
+            store
+                .add_event(create_proposal_submitted_messages_event("test"))
+                .expect("Unable to add event");
+        }
 
-        assert_eq!(circuits.len(), 2);
+        let stats = store.storage_stats().expect("Unable to get storage stats");
+        assert_eq!(stats, StorageStats::new(2, 1, 3, 4, 2));
     }
 
     /// Verify that count_circuits works correctly
@@ -1108,6 +3843,42 @@ pub mod tests {
         assert_eq!(fetched_circuit.roster()[0], fetched_service);
     }
 
+    /// Verify that the existence of a service can be checked
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Create a circuit
+    /// 4. Add circuit to store
+    /// 5. Validate that a service on the circuit exists
+    /// 6. Validate that a service not on the circuit does not exist
+    #[test]
+    fn test_service_exists() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let circuit = create_circuit("WBKLF-BBBBB", CircuitStatus::Active);
+        let nodes = create_nodes();
+
+        store
+            .add_circuit(circuit, nodes)
+            .expect("Unable to add circuit");
+
+        assert!(store
+            .service_exists(&ServiceId::new(
+                "WBKLF-BBBBB".to_string(),
+                "a000".to_string()
+            ))
+            .expect("Unable to check service"));
+
+        assert!(!store
+            .service_exists(&ServiceId::new(
+                "WBKLF-BBBBB".to_string(),
+                "z999".to_string()
+            ))
+            .expect("Unable to check service"));
+    }
+
     /// Verify that all service from a circuit can be listed from the store
     ///
     /// 1. Run sqlite migrations
@@ -1216,7 +3987,114 @@ pub mod tests {
         // Assert only the event added is returned
         assert_eq!(events.len(), 1);
         // Assert the event returned matches the expected values
-        assert_eq!(events, vec![create_proposal_submitted_event(1, "test")],);
+        assert_eq!(
+            without_created_time(events),
+            vec![create_proposal_submitted_event(1, "test")],
+        );
+    }
+
+    #[test]
+    /// Verify that a proposal and its event are added together, and that neither is added if the
+    /// transaction is rolled back
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a proposal with a `ProposalSubmitted` event
+    /// 4. Validate the proposal and the event are both in the store
+    /// 5. Add the same proposal with another event, which fails because the proposal already
+    ///    exists and rolls back the transaction
+    /// 6. Validate the second event was not added and the original proposal is unchanged
+    fn test_add_proposal_with_event() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+        let proposal = create_proposal();
+
+        let event = store
+            .add_proposal_with_event(
+                proposal.clone(),
+                create_proposal_submitted_messages_event("test"),
+            )
+            .expect("Unable to add proposal with event");
+        assert_eq!(*event.event_id(), 1);
+
+        assert_eq!(
+            store
+                .get_proposal(proposal.circuit_id())
+                .expect("Unable to get proposal"),
+            Some(proposal.clone())
+        );
+        let events: Vec<AdminServiceEvent> = store
+            .list_events_since(0)
+            .expect("Unable to get events from store")
+            .collect();
+        assert_eq!(
+            without_created_time(events),
+            vec![create_proposal_submitted_event(1, "test")],
+        );
+
+        match store.add_proposal_with_event(
+            proposal.clone(),
+            create_circuit_ready_messages_event("test"),
+        ) {
+            Err(AdminServiceStoreError::ConstraintViolationError(_)) => (),
+            res => panic!(
+                "Expected Err(ConstraintViolationError), got {:?} instead",
+                res
+            ),
+        }
+
+        let events: Vec<AdminServiceEvent> = store
+            .list_events_since(0)
+            .expect("Unable to get events from store")
+            .collect();
+        assert_eq!(
+            without_created_time(events),
+            vec![create_proposal_submitted_event(1, "test")],
+        );
+        assert_eq!(
+            store
+                .get_proposal(proposal.circuit_id())
+                .expect("Unable to get proposal"),
+            Some(proposal)
+        );
+    }
+
+    #[test]
+    /// Verify that events streamed from the store are all returned, in order, across batches
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceEventStore
+    /// 3. Add enough events to fill several batches, with a partial last batch
+    /// 4. Stream the events since 0 and validate every event is returned, in ID order
+    /// 5. Stream the events since a later ID and validate only the following events are returned
+    fn test_stream_events_since() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+        let event_count = EVENT_STREAM_BATCH_SIZE * 4 + 100;
+        for _ in 0..event_count {
+            store
+                .add_event(create_proposal_submitted_messages_event("test"))
+                .expect("Unable to add event");
+        }
+
+        let event_ids = store
+            .stream_events_since(0)
+            .map(|event| event.map(|event| *event.event_id()))
+            .collect::<Result<Vec<i64>, _>>()
+            .expect("Unable to stream events from store");
+        assert_eq!(event_ids, (1..=event_count).collect::<Vec<i64>>());
+
+        let event_ids = store
+            .stream_events_since(EVENT_STREAM_BATCH_SIZE + 1)
+            .map(|event| event.map(|event| *event.event_id()))
+            .collect::<Result<Vec<i64>, _>>()
+            .expect("Unable to stream events from store");
+        assert_eq!(
+            event_ids,
+            (EVENT_STREAM_BATCH_SIZE + 2..=event_count).collect::<Vec<i64>>()
+        );
     }
 
     #[test]
@@ -1247,7 +4125,36 @@ pub mod tests {
         assert_eq!(events.len(), 2);
         // Assert the event returned matches the expected values
         assert_eq!(
-            events,
+            without_created_time(events),
+            vec![
+                create_proposal_submitted_event(1, "test"),
+                create_circuit_ready_event(2, "test")
+            ],
+        );
+    }
+
+    #[test]
+    /// Verify that events added to the store are sent to subscribers
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceEventStore
+    /// 3. Subscribe to events from the store
+    /// 4. Add two `messages::AdminServiceEvent`s to the store
+    /// 5. Validate that both events arrive on the subscription, with their assigned IDs
+    fn test_subscribe_to_events() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+        let events = store.subscribe_to_events();
+
+        let event_1 = create_proposal_submitted_messages_event("test");
+        store.add_event(event_1).expect("Unable to add event");
+
+        let event_2 = create_circuit_ready_messages_event("test");
+        store.add_event(event_2).expect("Unable to add event");
+
+        assert_eq!(
+            without_created_time(events.try_iter().collect()),
             vec![
                 create_proposal_submitted_event(1, "test"),
                 create_circuit_ready_event(2, "test")
@@ -1255,6 +4162,57 @@ pub mod tests {
         );
     }
 
+    #[test]
+    /// Verify that the creation time of an event is recorded when it is added to the store
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceEventStore
+    /// 3. Add two `messages::AdminServiceEvent`s to the store
+    /// 4. Validate that the events returned by `add_event` match the listed events, including
+    ///    their creation time
+    /// 5. Validate that the creation times fall within the test's run and do not go backwards
+    ///    as the event IDs increase
+    fn test_event_created_time() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+        let before = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("System time is before the Unix epoch")
+            .as_secs();
+
+        let event_1 = store
+            .add_event(create_proposal_submitted_messages_event("test"))
+            .expect("Unable to add event");
+        let event_2 = store
+            .add_event(create_circuit_ready_messages_event("test"))
+            .expect("Unable to add event");
+
+        let after = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("System time is before the Unix epoch")
+            .as_secs();
+
+        let events: Vec<AdminServiceEvent> = store
+            .list_events_since(0)
+            .expect("Unable to get events from store")
+            .collect();
+        assert_eq!(events, vec![event_1.clone(), event_2.clone()]);
+
+        let created_1 = event_1
+            .created_time()
+            .duration_since(UNIX_EPOCH)
+            .expect("Event created before the Unix epoch")
+            .as_secs();
+        let created_2 = event_2
+            .created_time()
+            .duration_since(UNIX_EPOCH)
+            .expect("Event created before the Unix epoch")
+            .as_secs();
+        assert!(before <= created_1 && created_1 <= after);
+        assert!(created_1 <= created_2 && created_2 <= after);
+    }
+
     #[test]
     /// Verify that events can be added to the store correctly and then returned by the store
     ///
@@ -1284,7 +4242,7 @@ pub mod tests {
         assert_eq!(events.len(), 2);
         // Assert the event returned matches the expected values
         assert_eq!(
-            events,
+            without_created_time(events),
             vec![
                 create_circuit_ready_event(2, "test"),
                 create_proposal_vote_event(3, "test")
@@ -1323,7 +4281,10 @@ pub mod tests {
         // Assert one event is returned
         assert_eq!(events.len(), 1);
         // Assert the event returned matches the expected values, with the "not-test" management type
-        assert_eq!(events, vec![create_circuit_ready_event(2, "not-test")],);
+        assert_eq!(
+            without_created_time(events),
+            vec![create_circuit_ready_event(2, "not-test")],
+        );
     }
 
     #[test]
@@ -1357,7 +4318,10 @@ pub mod tests {
         // Assert one event is returned
         assert_eq!(events.len(), 1);
         // Assert the event returned matches the expected values, with the "not-test" management type
-        assert_eq!(events, vec![create_circuit_ready_event(2, "not-test")],);
+        assert_eq!(
+            without_created_time(events),
+            vec![create_circuit_ready_event(2, "not-test")],
+        );
     }
 
     #[test]
@@ -1391,7 +4355,7 @@ pub mod tests {
         assert_eq!(events.len(), 2);
         // Assert the event returned matches the expected values, with the "test" management type
         assert_eq!(
-            events,
+            without_created_time(events),
             vec![
                 create_proposal_submitted_event(1, "test"),
                 create_proposal_vote_event(3, "test")
@@ -1399,6 +4363,214 @@ pub mod tests {
         );
     }
 
+    #[test]
+    /// Verify that events can be listed newest first.
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceEventStore
+    /// 3. Add three events to the store
+    /// 4. List the events in descending order
+    /// 5. Validate that all of the events are returned, newest first
+    fn test_list_events_descending() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+        let event = create_proposal_submitted_messages_event("test");
+        store.add_event(event).expect("Unable to add event");
+        let event_2 = create_circuit_ready_messages_event("not-test");
+        store.add_event(event_2).expect("Unable to add event");
+        let event_3 = create_proposal_vote_messages_event("test");
+        store.add_event(event_3).expect("Unable to add event");
+
+        let events: Vec<AdminServiceEvent> = store
+            .list_events(EventQueryOptions::new().with_order(EventOrder::Desc))
+            .expect("Unable to get events from store")
+            .collect();
+        assert_eq!(
+            without_created_time(events),
+            vec![
+                create_proposal_vote_event(3, "test"),
+                create_circuit_ready_event(2, "not-test"),
+                create_proposal_submitted_event(1, "test"),
+            ],
+        );
+    }
+
+    #[test]
+    /// Verify that events can be listed by event type.
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceEventStore
+    /// 3. Add proposal submitted, circuit ready and proposal vote events to the store
+    /// 4. Validate that listing each type returns only the events of that type
+    /// 5. Validate that the start index is applied along with the event type
+    fn test_list_events_by_type_since() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+        store
+            .add_event(create_proposal_vote_messages_event("test"))
+            .expect("Unable to add event");
+        store
+            .add_event(create_proposal_submitted_messages_event("test"))
+            .expect("Unable to add event");
+        store
+            .add_event(create_circuit_ready_messages_event("test"))
+            .expect("Unable to add event");
+        store
+            .add_event(create_proposal_vote_messages_event("test"))
+            .expect("Unable to add event");
+
+        let list_by_type = |event_type: EventTypeFilter, start: i64| -> Vec<AdminServiceEvent> {
+            without_created_time(
+                store
+                    .list_events_by_type_since(event_type, start)
+                    .expect("Unable to get events from store")
+                    .collect(),
+            )
+        };
+
+        assert_eq!(
+            list_by_type(EventTypeFilter::ProposalVote, 0),
+            vec![
+                create_proposal_vote_event(1, "test"),
+                create_proposal_vote_event(4, "test"),
+            ],
+        );
+        assert_eq!(
+            list_by_type(EventTypeFilter::ProposalSubmitted, 0),
+            vec![create_proposal_submitted_event(2, "test")],
+        );
+        assert_eq!(
+            list_by_type(EventTypeFilter::CircuitReady, 0),
+            vec![create_circuit_ready_event(3, "test")],
+        );
+        assert!(list_by_type(EventTypeFilter::CircuitDisbanded, 0).is_empty());
+
+        assert_eq!(
+            list_by_type(EventTypeFilter::ProposalVote, 1),
+            vec![create_proposal_vote_event(4, "test")],
+        );
+    }
+
+    #[test]
+    /// Verify that the number of events listed by management type can be limited, in either
+    /// order.
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceEventStore
+    /// 3. Add four events to the store, three of which have the "test" management type
+    /// 4. List one "test" event in ascending order and validate that it is the oldest one
+    /// 5. List two "test" events in descending order and validate that they are the newest ones
+    fn test_list_events_limit_by_management_type() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+        let event = create_proposal_submitted_messages_event("test");
+        store.add_event(event).expect("Unable to add event");
+        let event_2 = create_circuit_ready_messages_event("not-test");
+        store.add_event(event_2).expect("Unable to add event");
+        let event_3 = create_proposal_vote_messages_event("test");
+        store.add_event(event_3).expect("Unable to add event");
+        let event_4 = create_circuit_ready_messages_event("test");
+        store.add_event(event_4).expect("Unable to add event");
+
+        let events: Vec<AdminServiceEvent> = store
+            .list_events(
+                EventQueryOptions::new()
+                    .with_management_type("test")
+                    .with_limit(1),
+            )
+            .expect("Unable to get events from store")
+            .collect();
+        assert_eq!(
+            without_created_time(events),
+            vec![create_proposal_submitted_event(1, "test")],
+        );
+
+        let events: Vec<AdminServiceEvent> = store
+            .list_events(
+                EventQueryOptions::new()
+                    .with_management_type("test")
+                    .with_limit(2)
+                    .with_order(EventOrder::Desc),
+            )
+            .expect("Unable to get events from store")
+            .collect();
+        assert_eq!(
+            without_created_time(events),
+            vec![
+                create_circuit_ready_event(4, "test"),
+                create_proposal_vote_event(3, "test"),
+            ],
+        );
+    }
+
+    /// Verify that only circuits updated at or after a given time are listed, whatever their
+    /// status
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add two circuits to the store
+    /// 4. Wait until the clock has moved past the time the circuits were added
+    /// 5. Update one of the circuits to be disbanded
+    /// 6. Validate that only the updated circuit is listed as updated since the wait
+    /// 7. Validate that both circuits are listed as updated since the Unix epoch
+    #[test]
+    fn test_list_circuits_updated_since() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        store
+            .add_circuit(
+                create_circuit("WBKLF-BBBBB", CircuitStatus::Active),
+                create_nodes(),
+            )
+            .expect("Unable to add circuit");
+        store
+            .add_circuit(create_extra_circuit("WBKLF-CCCCC"), create_extra_nodes())
+            .expect("Unable to add circuit");
+
+        let since = wait_for_next_second();
+
+        let updated_circuit = create_circuit("WBKLF-BBBBB", CircuitStatus::Disbanded);
+        store
+            .update_circuit(updated_circuit.clone())
+            .expect("Unable to update circuit");
+
+        let mut circuits = store
+            .list_circuits_updated_since(since)
+            .expect("Unable to list updated circuits");
+
+        assert_eq!(circuits.next(), Some(updated_circuit));
+        assert_eq!(circuits.next(), None);
+
+        let circuits = store
+            .list_circuits_updated_since(0)
+            .expect("Unable to list updated circuits");
+
+        assert_eq!(circuits.len(), 2);
+    }
+
+    /// Sleeps until the current time, in seconds since the Unix epoch, has advanced at least one
+    /// second and returns it. Updated times are recorded in seconds, so anything written before
+    /// this is called is guaranteed to have an earlier updated time.
+    fn wait_for_next_second() -> i64 {
+        let now = || {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("System time is before the Unix epoch")
+                .as_secs() as i64
+        };
+
+        let start = now();
+        while now() <= start {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        now()
+    }
+
     /// Creates a connection pool for an in-memory SQLite database with only a single connection
     /// available. Each connection is backed by a different in-memory SQLite database, so limiting
     /// the pool to a single connection ensures that the same DB is used for all operations.
@@ -1415,6 +4587,18 @@ pub mod tests {
         pool
     }
 
+    fn create_vote_record(voter_node_id: &str) -> VoteRecord {
+        VoteRecordBuilder::new()
+            .with_public_key(&PublicKey::from_bytes(
+                parse_hex("035724d11cae47c8907f8bfdf510488f49df8494ff81b63825bad923733c4ac550")
+                    .unwrap(),
+            ))
+            .with_vote(&Vote::Accept)
+            .with_voter_node_id(voter_node_id)
+            .build()
+            .expect("Unable to build vote record")
+    }
+
     fn create_proposal() -> CircuitProposal {
         CircuitProposalBuilder::default()
             .with_proposal_type(&ProposalType::Create)
@@ -1555,8 +4739,16 @@ pub mod tests {
     }
 
     fn create_circuit(circuit_id: &str, status: CircuitStatus) -> Circuit {
-        let nodes = create_nodes();
+        create_circuit_builder(circuit_id, status, &create_nodes())
+            .build()
+            .expect("Unable to build circuit")
+    }
 
+    fn create_circuit_builder(
+        circuit_id: &str,
+        status: CircuitStatus,
+        nodes: &[CircuitNode],
+    ) -> CircuitBuilder {
         CircuitBuilder::default()
             .with_circuit_id(circuit_id)
             .with_roster(&vec![
@@ -1583,13 +4775,41 @@ pub mod tests {
                     .build()
                     .expect("Unable to build service"),
             ])
-            .with_members(&nodes)
+            .with_members(nodes)
             .with_circuit_management_type("gameroom")
             .with_display_name("test_display")
             .with_circuit_version(3)
             .with_circuit_status(&status)
-            .build()
-            .expect("Unable to build circuit")
+    }
+
+    fn add_circuits_for_ordering(store: &DieselAdminServiceStore<SqliteConnection>) {
+        for (circuit_id, management_type, version) in &[
+            ("WBKLF-AAAAA", "gameroom", 2),
+            ("WBKLF-BBBBB", "arcade", 3),
+            ("WBKLF-CCCCC", "gameroom", 1),
+            ("WBKLF-DDDDD", "arcade", 2),
+        ] {
+            let circuit =
+                create_circuit_builder(circuit_id, CircuitStatus::Active, &create_nodes())
+                    .with_circuit_management_type(management_type)
+                    .with_circuit_version(*version)
+                    .build()
+                    .expect("Unable to build circuit");
+            store
+                .add_circuit(circuit, create_nodes())
+                .expect("Unable to add circuit");
+        }
+    }
+
+    fn list_page_ids(
+        store: &DieselAdminServiceStore<SqliteConnection>,
+        options: &CircuitQueryOptions,
+    ) -> Vec<String> {
+        store
+            .list_circuits_page(&[], options)
+            .expect("Unable to list circuits")
+            .map(|circuit| circuit.circuit_id().to_string())
+            .collect()
     }
 
     fn create_circuit_from_proposal(circuit_id: &str, status: CircuitStatus) -> Circuit {
@@ -1795,11 +5015,29 @@ pub mod tests {
         ]
     }
 
+    // Replaces the creation time of the given events with the Unix epoch, so they can be compared
+    // against the expected events built by this module.
+    fn without_created_time(events: Vec<AdminServiceEvent>) -> Vec<AdminServiceEvent> {
+        events
+            .into_iter()
+            .map(|event| {
+                AdminServiceEventBuilder::new()
+                    .with_event_id(*event.event_id())
+                    .with_event_type(event.event_type())
+                    .with_proposal(event.proposal())
+                    .with_created_time(UNIX_EPOCH)
+                    .build()
+                    .expect("Unable to build AdminServiceEvent")
+            })
+            .collect()
+    }
+
     fn create_proposal_submitted_event(event_id: i64, management_type: &str) -> AdminServiceEvent {
         AdminServiceEventBuilder::new()
             .with_event_id(event_id)
             .with_event_type(&EventType::ProposalSubmitted)
             .with_proposal(&create_messages_proposal(management_type))
+            .with_created_time(UNIX_EPOCH)
             .build()
             .expect("Unable to build AdminServiceEvent")
     }
@@ -1817,6 +5055,7 @@ pub mod tests {
             .with_event_id(event_id)
             .with_event_type(&EventType::CircuitReady)
             .with_proposal(&create_messages_proposal(management_type))
+            .with_created_time(UNIX_EPOCH)
             .build()
             .expect("Unable to build AdminServiceEvent")
     }
@@ -1837,6 +5076,7 @@ pub mod tests {
                 requester: requester.to_vec(),
             })
             .with_proposal(&create_messages_proposal(management_type))
+            .with_created_time(UNIX_EPOCH)
             .build()
             .expect("Unable to build AdminServiceEvent")
     }
@@ -18,6 +18,7 @@
 
 use std::convert::TryFrom;
 use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use diesel::{
     backend::Backend,
@@ -58,6 +59,7 @@ pub struct CircuitProposalModel {
     pub circuit_hash: String,
     pub requester: Vec<u8>,
     pub requester_node_id: String,
+    pub updated_time: i64,
 }
 
 impl From<&CircuitProposal> for CircuitProposalModel {
@@ -68,6 +70,7 @@ impl From<&CircuitProposal> for CircuitProposalModel {
             circuit_hash: proposal.circuit_hash().into(),
             requester: proposal.requester().as_slice().to_vec(),
             requester_node_id: proposal.requester_node_id().into(),
+            updated_time: current_time(),
         }
     }
 }
@@ -418,6 +421,7 @@ pub struct CircuitModel {
     pub display_name: Option<String>,
     pub circuit_version: i32,
     pub circuit_status: CircuitStatusModel,
+    pub updated_time: i64,
 }
 
 impl From<&Circuit> for CircuitModel {
@@ -432,6 +436,7 @@ impl From<&Circuit> for CircuitModel {
             display_name: circuit.display_name().clone(),
             circuit_version: circuit.circuit_version(),
             circuit_status: CircuitStatusModel::from(circuit.circuit_status()),
+            updated_time: current_time(),
         }
     }
 }
@@ -492,6 +497,7 @@ pub struct AdminServiceEventModel {
     pub id: i64,
     pub event_type: String,
     pub data: Option<Vec<u8>>,
+    pub created_time: i64,
 }
 
 #[derive(AsChangeset, Insertable, PartialEq, Debug)]
@@ -499,6 +505,7 @@ pub struct AdminServiceEventModel {
 pub struct NewAdminServiceEventModel<'a> {
     pub event_type: &'a str,
     pub data: Option<&'a [u8]>,
+    pub created_time: i64,
 }
 
 /// Database model representation of a `CircuitProposal` from an `AdminServiceEvent`
@@ -827,26 +834,32 @@ impl<'a> From<&'a messages::AdminServiceEvent> for NewAdminServiceEventModel<'a>
             messages::AdminServiceEvent::ProposalSubmitted(_) => NewAdminServiceEventModel {
                 event_type: "ProposalSubmitted",
                 data: None,
+                created_time: current_time(),
             },
             messages::AdminServiceEvent::ProposalVote((_, data)) => NewAdminServiceEventModel {
                 event_type: "ProposalVote",
                 data: Some(data),
+                created_time: current_time(),
             },
             messages::AdminServiceEvent::ProposalAccepted((_, data)) => NewAdminServiceEventModel {
                 event_type: "ProposalAccepted",
                 data: Some(data),
+                created_time: current_time(),
             },
             messages::AdminServiceEvent::ProposalRejected((_, data)) => NewAdminServiceEventModel {
                 event_type: "ProposalRejected",
                 data: Some(data),
+                created_time: current_time(),
             },
             messages::AdminServiceEvent::CircuitReady(_) => NewAdminServiceEventModel {
                 event_type: "CircuitReady",
                 data: None,
+                created_time: current_time(),
             },
             messages::AdminServiceEvent::CircuitDisbanded(_) => NewAdminServiceEventModel {
                 event_type: "CircuitDisbanded",
                 data: None,
+                created_time: current_time(),
             },
         }
     }
@@ -858,41 +871,48 @@ impl TryFrom<(AdminServiceEventModel, CircuitProposal)> for AdminServiceEvent {
     fn try_from(
         (event_model, proposal): (AdminServiceEventModel, CircuitProposal),
     ) -> Result<Self, Self::Error> {
+        let created_time = system_time_from_secs(event_model.created_time)?;
         match (event_model.event_type.as_ref(), event_model.data) {
             ("ProposalSubmitted", None) => AdminServiceEventBuilder::new()
                 .with_event_id(event_model.id)
                 .with_event_type(&EventType::ProposalSubmitted)
                 .with_proposal(&proposal)
+                .with_created_time(created_time)
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError),
             ("ProposalVote", Some(requester)) => AdminServiceEventBuilder::new()
                 .with_event_id(event_model.id)
                 .with_event_type(&EventType::ProposalVote { requester })
                 .with_proposal(&proposal)
+                .with_created_time(created_time)
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError),
             ("ProposalAccepted", Some(requester)) => AdminServiceEventBuilder::new()
                 .with_event_id(event_model.id)
                 .with_event_type(&EventType::ProposalAccepted { requester })
                 .with_proposal(&proposal)
+                .with_created_time(created_time)
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError),
             ("ProposalRejected", Some(requester)) => AdminServiceEventBuilder::new()
                 .with_event_id(event_model.id)
                 .with_event_type(&EventType::ProposalRejected { requester })
                 .with_proposal(&proposal)
+                .with_created_time(created_time)
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError),
             ("CircuitReady", None) => AdminServiceEventBuilder::new()
                 .with_event_id(event_model.id)
                 .with_event_type(&EventType::CircuitReady)
                 .with_proposal(&proposal)
+                .with_created_time(created_time)
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError),
             ("CircuitDisbanded", None) => AdminServiceEventBuilder::new()
                 .with_event_id(event_model.id)
                 .with_event_type(&EventType::CircuitDisbanded)
                 .with_proposal(&proposal)
+                .with_created_time(created_time)
                 .build()
                 .map_err(AdminServiceStoreError::InvalidStateError),
             _ => Err(AdminServiceStoreError::InvalidStateError(
@@ -1105,6 +1125,7 @@ pub enum CircuitStatusModel {
     Active = 1,
     Disbanded = 2,
     Abandoned = 3,
+    Deleted = 4,
 }
 
 impl From<&CircuitStatus> for CircuitStatusModel {
@@ -1113,6 +1134,7 @@ impl From<&CircuitStatus> for CircuitStatusModel {
             CircuitStatus::Active => CircuitStatusModel::Active,
             CircuitStatus::Disbanded => CircuitStatusModel::Disbanded,
             CircuitStatus::Abandoned => CircuitStatusModel::Abandoned,
+            CircuitStatus::Deleted => CircuitStatusModel::Deleted,
         }
     }
 }
@@ -1123,6 +1145,7 @@ impl From<&messages::CircuitStatus> for CircuitStatusModel {
             messages::CircuitStatus::Active => CircuitStatusModel::Active,
             messages::CircuitStatus::Disbanded => CircuitStatusModel::Disbanded,
             messages::CircuitStatus::Abandoned => CircuitStatusModel::Abandoned,
+            messages::CircuitStatus::Deleted => CircuitStatusModel::Deleted,
         }
     }
 }
@@ -1133,6 +1156,7 @@ impl From<&CircuitStatusModel> for CircuitStatus {
             CircuitStatusModel::Active => CircuitStatus::Active,
             CircuitStatusModel::Disbanded => CircuitStatus::Disbanded,
             CircuitStatusModel::Abandoned => CircuitStatus::Abandoned,
+            CircuitStatusModel::Deleted => CircuitStatus::Deleted,
         }
     }
 }
@@ -1143,6 +1167,7 @@ impl From<&CircuitStatusModel> for messages::CircuitStatus {
             CircuitStatusModel::Active => messages::CircuitStatus::Active,
             CircuitStatusModel::Disbanded => messages::CircuitStatus::Disbanded,
             CircuitStatusModel::Abandoned => messages::CircuitStatus::Abandoned,
+            CircuitStatusModel::Deleted => messages::CircuitStatus::Deleted,
         }
     }
 }
@@ -1183,7 +1208,30 @@ where
             1 => Ok(CircuitStatusModel::Active),
             2 => Ok(CircuitStatusModel::Disbanded),
             3 => Ok(CircuitStatusModel::Abandoned),
+            4 => Ok(CircuitStatusModel::Deleted),
             int => Err(format!("Invalid circuit status {}", int).into()),
         }
     }
 }
+
+/// Converts a time, in seconds since the Unix epoch, into a `SystemTime`.
+pub fn system_time_from_secs(secs: i64) -> Result<SystemTime, AdminServiceStoreError> {
+    u64::try_from(secs)
+        .ok()
+        .and_then(|secs| UNIX_EPOCH.checked_add(Duration::from_secs(secs)))
+        .ok_or_else(|| {
+            AdminServiceStoreError::InvalidStateError(InvalidStateError::with_message(format!(
+                "Timestamp {} could not be represented as a `SystemTime`",
+                secs
+            )))
+        })
+}
+
+/// Returns the current time, in seconds since the Unix epoch, for the `updated_time` and
+/// `created_time` columns.
+pub fn current_time() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
@@ -14,7 +14,7 @@
 
 //! Provides the "add circuit" operation for the `DieselAdminServiceStore`.
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::convert::TryFrom;
 
 use diesel::{dsl::insert_into, prelude::*};
@@ -33,13 +33,13 @@ use crate::admin::store::{
     error::AdminServiceStoreError,
     Circuit, CircuitNode,
 };
-use crate::error::{ConstraintViolationError, ConstraintViolationType};
+use crate::error::{ConstraintViolationError, ConstraintViolationType, InvalidStateError};
 
 pub(in crate::admin::store::diesel) trait AdminServiceStoreAddCircuitOperation {
     fn add_circuit(
         &self,
-        circuit: Circuit,
-        nodes: Vec<CircuitNode>,
+        circuit: &Circuit,
+        nodes: &[CircuitNode],
     ) -> Result<(), AdminServiceStoreError>;
 }
 
@@ -49,9 +49,15 @@ impl<'a> AdminServiceStoreAddCircuitOperation
 {
     fn add_circuit(
         &self,
-        circuit: Circuit,
-        nodes: Vec<CircuitNode>,
+        circuit: &Circuit,
+        nodes: &[CircuitNode],
     ) -> Result<(), AdminServiceStoreError> {
+        check_roster_nodes(circuit, nodes)?;
+        check_unique_service_ids(
+            circuit.circuit_id(),
+            circuit.roster().iter().map(|service| service.service_id()),
+        )?;
+
         self.conn.transaction::<(), _, _>(|| {
             // Check if the circuit already exists in the `AdminServiceStore`, in which case
             // an error is returned.
@@ -67,7 +73,7 @@ impl<'a> AdminServiceStoreAddCircuitOperation
             }
 
             // Create a `CircuitModel` from the `Circuit` to add to database
-            let circuit_model: CircuitModel = CircuitModel::from(&circuit);
+            let circuit_model: CircuitModel = CircuitModel::from(circuit);
             insert_into(circuit::table)
                 .values(circuit_model)
                 .execute(self.conn)?;
@@ -128,11 +134,11 @@ impl<'a> AdminServiceStoreAddCircuitOperation
             }
 
             // Build `Services` and all associated data from `circuit`
-            let circuit_services: Vec<ServiceModel> = Vec::try_from(&circuit)?;
+            let circuit_services: Vec<ServiceModel> = Vec::try_from(circuit)?;
             insert_into(service::table)
                 .values(&circuit_services)
                 .execute(self.conn)?;
-            let service_argument: Vec<ServiceArgumentModel> = Vec::try_from(&circuit)?;
+            let service_argument: Vec<ServiceArgumentModel> = Vec::try_from(circuit)?;
             insert_into(service_argument::table)
                 .values(&service_argument)
                 .execute(self.conn)?;
@@ -148,9 +154,15 @@ impl<'a> AdminServiceStoreAddCircuitOperation
 {
     fn add_circuit(
         &self,
-        circuit: Circuit,
-        nodes: Vec<CircuitNode>,
+        circuit: &Circuit,
+        nodes: &[CircuitNode],
     ) -> Result<(), AdminServiceStoreError> {
+        check_roster_nodes(circuit, nodes)?;
+        check_unique_service_ids(
+            circuit.circuit_id(),
+            circuit.roster().iter().map(|service| service.service_id()),
+        )?;
+
         self.conn.transaction::<(), _, _>(|| {
             // Check if the circuit already exists in the `AdminServiceStore`, in which case
             // an error is returned.
@@ -166,7 +178,7 @@ impl<'a> AdminServiceStoreAddCircuitOperation
             }
 
             // Create a `CircuitModel` from the `Circuit` to add to database
-            let circuit_model: CircuitModel = CircuitModel::from(&circuit);
+            let circuit_model: CircuitModel = CircuitModel::from(circuit);
             insert_into(circuit::table)
                 .values(circuit_model)
                 .execute(self.conn)?;
@@ -227,11 +239,11 @@ impl<'a> AdminServiceStoreAddCircuitOperation
             }
 
             // Build `Services` and all associated data from `circuit`
-            let circuit_services: Vec<ServiceModel> = Vec::try_from(&circuit)?;
+            let circuit_services: Vec<ServiceModel> = Vec::try_from(circuit)?;
             insert_into(service::table)
                 .values(&circuit_services)
                 .execute(self.conn)?;
-            let service_argument: Vec<ServiceArgumentModel> = Vec::try_from(&circuit)?;
+            let service_argument: Vec<ServiceArgumentModel> = Vec::try_from(circuit)?;
             insert_into(service_argument::table)
                 .values(&service_argument)
                 .execute(self.conn)?;
@@ -240,3 +252,60 @@ impl<'a> AdminServiceStoreAddCircuitOperation
         })
     }
 }
+
+/// Checks that every node referenced by a service in the circuit's roster is one of the given
+/// nodes, returning an `InvalidStateError` listing any node IDs that are missing.
+fn check_roster_nodes(
+    circuit: &Circuit,
+    nodes: &[CircuitNode],
+) -> Result<(), AdminServiceStoreError> {
+    let node_ids = nodes
+        .iter()
+        .map(|node| node.node_id())
+        .collect::<HashSet<&str>>();
+    let missing_node_ids = circuit
+        .roster()
+        .iter()
+        .map(|service| service.node_id())
+        .filter(|node_id| !node_ids.contains(node_id))
+        .collect::<BTreeSet<&str>>();
+
+    if missing_node_ids.is_empty() {
+        Ok(())
+    } else {
+        Err(AdminServiceStoreError::InvalidStateError(
+            InvalidStateError::with_message(format!(
+                "Circuit {} has services on nodes that are not members: {}",
+                circuit.circuit_id(),
+                missing_node_ids.into_iter().collect::<Vec<_>>().join(", ")
+            )),
+        ))
+    }
+}
+
+/// Checks that no service ID appears more than once in a circuit's roster, returning an
+/// `InvalidStateError` naming the duplicated IDs if one does.
+pub(super) fn check_unique_service_ids<'a>(
+    circuit_id: &str,
+    service_ids: impl Iterator<Item = &'a str>,
+) -> Result<(), AdminServiceStoreError> {
+    let mut seen = HashSet::new();
+    let duplicate_service_ids = service_ids
+        .filter(|service_id| !seen.insert(*service_id))
+        .collect::<BTreeSet<&str>>();
+
+    if duplicate_service_ids.is_empty() {
+        Ok(())
+    } else {
+        Err(AdminServiceStoreError::InvalidStateError(
+            InvalidStateError::with_message(format!(
+                "Circuit {} has duplicate service IDs: {}",
+                circuit_id,
+                duplicate_service_ids
+                    .into_iter()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        ))
+    }
+}
@@ -26,7 +26,7 @@ use crate::admin::store::diesel::models::AdminServiceEventModel;
 use crate::admin::store::{
     diesel::{
         models::{
-            AdminEventCircuitProposalModel, AdminEventProposedCircuitModel,
+            system_time_from_secs, AdminEventCircuitProposalModel, AdminEventProposedCircuitModel,
             AdminEventProposedNodeEndpointModel, AdminEventProposedNodeModel,
             AdminEventProposedServiceArgumentModel, AdminEventProposedServiceModel,
             AdminEventVoteRecordModel, NewAdminServiceEventModel,
@@ -45,7 +45,7 @@ use crate::error::{ConstraintViolationError, ConstraintViolationType};
 pub(in crate::admin::store::diesel) trait AdminServiceStoreAddEventOperation {
     fn add_event(
         &self,
-        event: messages::AdminServiceEvent,
+        event: &messages::AdminServiceEvent,
     ) -> Result<AdminServiceEvent, AdminServiceStoreError>;
 }
 
@@ -55,11 +55,12 @@ impl<'a> AdminServiceStoreAddEventOperation
 {
     fn add_event(
         &self,
-        event: messages::AdminServiceEvent,
+        event: &messages::AdminServiceEvent,
     ) -> Result<AdminServiceEvent, AdminServiceStoreError> {
         self.conn.transaction::<AdminServiceEvent, _, _>(|| {
             // Create a `NewAdminServiceEventModel` from the event
-            let new_event: NewAdminServiceEventModel = NewAdminServiceEventModel::from(&event);
+            let new_event: NewAdminServiceEventModel = NewAdminServiceEventModel::from(event);
+            let created_time = system_time_from_secs(new_event.created_time)?;
             // This creates the initial event entry, returning the ID from the inserted row
             // to be used to correlate the other `admin_event_*` entries to this event.
             let event_id: i64 = insert_into(admin_service_event::table)
@@ -130,7 +131,7 @@ impl<'a> AdminServiceStoreAddEventOperation
                 .values(vote_records)
                 .execute(self.conn)?;
 
-            AdminServiceEvent::try_from((event_id, &event))
+            AdminServiceEvent::try_from((event_id, created_time, event))
                 .map_err(AdminServiceStoreError::InvalidStateError)
         })
     }
@@ -142,11 +143,12 @@ impl<'a> AdminServiceStoreAddEventOperation
 {
     fn add_event(
         &self,
-        event: messages::AdminServiceEvent,
+        event: &messages::AdminServiceEvent,
     ) -> Result<AdminServiceEvent, AdminServiceStoreError> {
         self.conn.transaction::<AdminServiceEvent, _, _>(|| {
             // Create a `NewAdminServiceEventModel` from the event
-            let new_event: NewAdminServiceEventModel = NewAdminServiceEventModel::from(&event);
+            let new_event: NewAdminServiceEventModel = NewAdminServiceEventModel::from(event);
+            let created_time = system_time_from_secs(new_event.created_time)?;
             // This creates the initial event entry, returning the ID from the inserted row
             // to be used to correlate the other `admin_event_*` entries to this event.
             insert_into(admin_service_event::table)
@@ -223,7 +225,7 @@ impl<'a> AdminServiceStoreAddEventOperation
                 .values(vote_records)
                 .execute(self.conn)?;
 
-            AdminServiceEvent::try_from((event_id, &event))
+            AdminServiceEvent::try_from((event_id, created_time, event))
                 .map_err(AdminServiceStoreError::InvalidStateError)
         })
     }
@@ -17,7 +17,7 @@ use std::convert::TryFrom;
 
 use diesel::{dsl::insert_into, prelude::*};
 
-use super::AdminServiceStoreOperations;
+use super::{add_circuit::check_unique_service_ids, AdminServiceStoreOperations};
 
 use crate::admin::store::{
     diesel::{
@@ -36,14 +36,23 @@ use crate::admin::store::{
 use crate::error::{ConstraintViolationError, ConstraintViolationType};
 
 pub(in crate::admin::store::diesel) trait AdminServiceStoreAddProposalOperation {
-    fn add_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError>;
+    fn add_proposal(&self, proposal: &CircuitProposal) -> Result<(), AdminServiceStoreError>;
 }
 
 #[cfg(feature = "postgres")]
 impl<'a> AdminServiceStoreAddProposalOperation
     for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
 {
-    fn add_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError> {
+    fn add_proposal(&self, proposal: &CircuitProposal) -> Result<(), AdminServiceStoreError> {
+        check_unique_service_ids(
+            proposal.circuit_id(),
+            proposal
+                .circuit()
+                .roster()
+                .iter()
+                .map(|service| service.service_id()),
+        )?;
+
         // Insert `CircuitProposal` and all associated types into database after verifying that
         // the proposal exists
         self.conn.transaction::<(), _, _>(|| {
@@ -60,7 +69,7 @@ impl<'a> AdminServiceStoreAddProposalOperation
             }
 
             // Insert the database model of the `CircuitProposal`
-            let circuit_proposal_model = CircuitProposalModel::from(&proposal);
+            let circuit_proposal_model = CircuitProposalModel::from(proposal);
             insert_into(circuit_proposal::table)
                 .values(circuit_proposal_model)
                 .execute(self.conn)?;
@@ -92,7 +101,7 @@ impl<'a> AdminServiceStoreAddProposalOperation
                 .values(proposed_service_argument)
                 .execute(self.conn)?;
             // Insert `votes` from the `CircuitProposal`
-            let vote_records: Vec<VoteRecordModel> = Vec::try_from(&proposal)?;
+            let vote_records: Vec<VoteRecordModel> = Vec::try_from(proposal)?;
             insert_into(vote_record::table)
                 .values(vote_records)
                 .execute(self.conn)?;
@@ -106,7 +115,16 @@ impl<'a> AdminServiceStoreAddProposalOperation
 impl<'a> AdminServiceStoreAddProposalOperation
     for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
 {
-    fn add_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError> {
+    fn add_proposal(&self, proposal: &CircuitProposal) -> Result<(), AdminServiceStoreError> {
+        check_unique_service_ids(
+            proposal.circuit_id(),
+            proposal
+                .circuit()
+                .roster()
+                .iter()
+                .map(|service| service.service_id()),
+        )?;
+
         // Insert `CircuitProposal` and all associated types into database after verifying that
         // the proposal exists
         self.conn.transaction::<(), _, _>(|| {
@@ -123,7 +141,7 @@ impl<'a> AdminServiceStoreAddProposalOperation
             }
 
             // Insert the database model of the `CircuitProposal`
-            let circuit_proposal_model = CircuitProposalModel::from(&proposal);
+            let circuit_proposal_model = CircuitProposalModel::from(proposal);
             insert_into(circuit_proposal::table)
                 .values(circuit_proposal_model)
                 .execute(self.conn)?;
@@ -155,7 +173,7 @@ impl<'a> AdminServiceStoreAddProposalOperation
                 .values(proposed_service_argument)
                 .execute(self.conn)?;
             // Insert `votes` from the `CircuitProposal`
-            let vote_records: Vec<VoteRecordModel> = Vec::try_from(&proposal)?;
+            let vote_records: Vec<VoteRecordModel> = Vec::try_from(proposal)?;
             insert_into(vote_record::table)
                 .values(vote_records)
                 .execute(self.conn)?;
@@ -0,0 +1,70 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "add proposal with event" operation for the `DieselAdminServiceStore`.
+
+use diesel::prelude::*;
+
+use super::add_event::AdminServiceStoreAddEventOperation as _;
+use super::add_proposal::AdminServiceStoreAddProposalOperation as _;
+use super::AdminServiceStoreOperations;
+
+use crate::admin::service::messages;
+use crate::admin::store::{AdminServiceEvent, AdminServiceStoreError, CircuitProposal};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreAddProposalWithEventOperation {
+    fn add_proposal_with_event(
+        &self,
+        proposal: &CircuitProposal,
+        event: &messages::AdminServiceEvent,
+    ) -> Result<AdminServiceEvent, AdminServiceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AdminServiceStoreAddProposalWithEventOperation
+    for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_proposal_with_event(
+        &self,
+        proposal: &CircuitProposal,
+        event: &messages::AdminServiceEvent,
+    ) -> Result<AdminServiceEvent, AdminServiceStoreError> {
+        // The event is added first so that a failure to add the proposal, such as a proposal
+        // with the same circuit ID already existing, also rolls back the event
+        self.conn.transaction::<AdminServiceEvent, _, _>(|| {
+            let event = self.add_event(event)?;
+            self.add_proposal(proposal)?;
+            Ok(event)
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AdminServiceStoreAddProposalWithEventOperation
+    for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_proposal_with_event(
+        &self,
+        proposal: &CircuitProposal,
+        event: &messages::AdminServiceEvent,
+    ) -> Result<AdminServiceEvent, AdminServiceStoreError> {
+        // The event is added first so that a failure to add the proposal, such as a proposal
+        // with the same circuit ID already existing, also rolls back the event
+        self.conn.transaction::<AdminServiceEvent, _, _>(|| {
+            let event = self.add_event(event)?;
+            self.add_proposal(proposal)?;
+            Ok(event)
+        })
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "append vote" operation for the `DieselAdminServiceStore`.
+
+use std::convert::TryFrom;
+
+use diesel::{
+    dsl::{insert_into, update},
+    prelude::*,
+};
+
+use super::{get_proposal::AdminServiceStoreFetchProposalOperation, AdminServiceStoreOperations};
+use crate::admin::store::{
+    diesel::{
+        models::{current_time, VoteRecordModel},
+        schema::{circuit_proposal, vote_record},
+    },
+    error::AdminServiceStoreError,
+    CircuitProposal, VoteRecord,
+};
+use crate::error::{ConstraintViolationError, ConstraintViolationType, InternalError};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreAppendVoteOperation {
+    fn append_vote(
+        &self,
+        proposal_id: &str,
+        vote: &VoteRecord,
+    ) -> Result<CircuitProposal, AdminServiceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AdminServiceStoreAppendVoteOperation
+    for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn append_vote(
+        &self,
+        proposal_id: &str,
+        vote: &VoteRecord,
+    ) -> Result<CircuitProposal, AdminServiceStoreError> {
+        self.conn.transaction::<CircuitProposal, _, _>(|| {
+            // Lock the `circuit_proposal` entry so concurrent votes are appended one at a time
+            circuit_proposal::table
+                .filter(circuit_proposal::circuit_id.eq(proposal_id))
+                .select(circuit_proposal::circuit_id)
+                .for_update()
+                .first::<String>(self.conn)
+                .optional()?
+                .ok_or_else(proposal_not_found)?;
+
+            // Reject a second vote from the same node
+            let already_voted = vote_record::table
+                .filter(vote_record::circuit_id.eq(proposal_id))
+                .filter(vote_record::voter_node_id.eq(vote.voter_node_id()))
+                .count()
+                .get_result::<i64>(self.conn)?
+                > 0;
+            if already_voted {
+                return Err(duplicate_vote(proposal_id, vote.voter_node_id()));
+            }
+
+            let existing_votes = vote_record::table
+                .filter(vote_record::circuit_id.eq(proposal_id))
+                .count()
+                .get_result::<i64>(self.conn)?;
+            insert_into(vote_record::table)
+                .values(vote_record_model(proposal_id, vote, existing_votes)?)
+                .execute(self.conn)?;
+
+            update(circuit_proposal::table.find(proposal_id))
+                .set(circuit_proposal::updated_time.eq(current_time()))
+                .execute(self.conn)?;
+
+            self.get_proposal(proposal_id)?
+                .ok_or_else(proposal_not_found)
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AdminServiceStoreAppendVoteOperation
+    for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn append_vote(
+        &self,
+        proposal_id: &str,
+        vote: &VoteRecord,
+    ) -> Result<CircuitProposal, AdminServiceStoreError> {
+        self.conn.transaction::<CircuitProposal, _, _>(|| {
+            // Verify the `circuit_proposal` entry to be voted on exists
+            circuit_proposal::table
+                .filter(circuit_proposal::circuit_id.eq(proposal_id))
+                .select(circuit_proposal::circuit_id)
+                .first::<String>(self.conn)
+                .optional()?
+                .ok_or_else(proposal_not_found)?;
+
+            // Reject a second vote from the same node
+            let already_voted = vote_record::table
+                .filter(vote_record::circuit_id.eq(proposal_id))
+                .filter(vote_record::voter_node_id.eq(vote.voter_node_id()))
+                .count()
+                .get_result::<i64>(self.conn)?
+                > 0;
+            if already_voted {
+                return Err(duplicate_vote(proposal_id, vote.voter_node_id()));
+            }
+
+            let existing_votes = vote_record::table
+                .filter(vote_record::circuit_id.eq(proposal_id))
+                .count()
+                .get_result::<i64>(self.conn)?;
+            insert_into(vote_record::table)
+                .values(vote_record_model(proposal_id, vote, existing_votes)?)
+                .execute(self.conn)?;
+
+            update(circuit_proposal::table.find(proposal_id))
+                .set(circuit_proposal::updated_time.eq(current_time()))
+                .execute(self.conn)?;
+
+            self.get_proposal(proposal_id)?
+                .ok_or_else(proposal_not_found)
+        })
+    }
+}
+
+/// Builds the model for a vote appended after the proposal's existing votes.
+fn vote_record_model(
+    proposal_id: &str,
+    vote: &VoteRecord,
+    existing_votes: i64,
+) -> Result<VoteRecordModel, AdminServiceStoreError> {
+    Ok(VoteRecordModel {
+        circuit_id: proposal_id.into(),
+        public_key: vote.public_key().as_slice().to_vec(),
+        vote: String::from(vote.vote()),
+        voter_node_id: vote.voter_node_id().into(),
+        position: i32::try_from(existing_votes).map_err(|_| {
+            AdminServiceStoreError::InternalError(InternalError::with_message(
+                "Unable to convert vote count into i32".to_string(),
+            ))
+        })?,
+    })
+}
+
+fn duplicate_vote(proposal_id: &str, voter_node_id: &str) -> AdminServiceStoreError {
+    AdminServiceStoreError::ConstraintViolationError(ConstraintViolationError::with_violation_type(
+        ConstraintViolationType::Other(format!(
+            "Node {} has already voted on CircuitProposal {}",
+            voter_node_id, proposal_id
+        )),
+    ))
+}
+
+fn proposal_not_found() -> AdminServiceStoreError {
+    AdminServiceStoreError::ConstraintViolationError(ConstraintViolationError::with_violation_type(
+        ConstraintViolationType::NotFound,
+    ))
+}
@@ -17,21 +17,21 @@
 use std::convert::TryFrom;
 
 use diesel::{
-    dsl::{count_star, exists},
+    dsl::{count_star, exists, not},
     prelude::*,
 };
 
 use crate::admin::store::{
     diesel::{
         models::CircuitStatusModel,
-        schema::{circuit, circuit_member},
+        schema::{circuit, circuit_member, service},
     },
     error::AdminServiceStoreError,
     CircuitPredicate,
 };
 use crate::error::InternalError;
 
-use super::AdminServiceStoreOperations;
+use super::{contains_pattern, lower, AdminServiceStoreOperations, LIKE_ESCAPE};
 
 pub(in crate::admin::store::diesel) trait AdminServiceStoreCountCircuitsOperation {
     fn count_circuits(
@@ -73,6 +73,39 @@ where
                 _ => None,
             })
             .collect();
+        // Collects the circuit versions included in the list of `CircuitPredicates`
+        let versions: Vec<i32> = predicates
+            .iter()
+            .filter_map(|pred| match pred {
+                CircuitPredicate::CircuitVersionEq(version) => Some(*version),
+                _ => None,
+            })
+            .collect();
+        // Collects the upper bounds on the circuit version included in the list of
+        // `CircuitPredicates`
+        let versions_below: Vec<i32> = predicates
+            .iter()
+            .filter_map(|pred| match pred {
+                CircuitPredicate::CircuitVersionLt(version) => Some(*version),
+                _ => None,
+            })
+            .collect();
+        // Collects the display name patterns included in the list of `CircuitPredicates`
+        let display_name_patterns: Vec<String> = predicates
+            .iter()
+            .filter_map(|pred| match pred {
+                CircuitPredicate::DisplayNameContains(value) => Some(contains_pattern(value)),
+                _ => None,
+            })
+            .collect();
+        // Collects the service types that must be absent from the rosters of the circuits
+        let absent_service_types: Vec<String> = predicates
+            .iter()
+            .filter_map(|pred| match pred {
+                CircuitPredicate::ServiceTypeAbsent(service_type) => Some(service_type.to_string()),
+                _ => None,
+            })
+            .collect();
         self.conn.transaction::<u32, _, _>(|| {
             // Collects circuits which match the circuit predicates
             let mut query = circuit::table.into_boxed().select(circuit::all_columns);
@@ -93,6 +126,34 @@ where
                 ));
             }
 
+            if !versions.is_empty() {
+                query = query.filter(circuit::circuit_version.eq_any(versions));
+            }
+
+            for version in versions_below {
+                query = query.filter(circuit::circuit_version.lt(version));
+            }
+
+            for pattern in display_name_patterns {
+                // Lower case both sides, as `LIKE` is case-sensitive in PostgreSQL
+                query = query.filter(
+                    lower(circuit::display_name)
+                        .like(pattern.to_lowercase())
+                        .escape(LIKE_ESCAPE),
+                );
+            }
+
+            for service_type in absent_service_types {
+                query = query.filter(not(exists(
+                    // Selects the services of the circuit with the absent service type
+                    service::table.filter(
+                        service::circuit_id
+                            .eq(circuit::circuit_id)
+                            .and(service::service_type.eq(service_type)),
+                    ),
+                )));
+            }
+
             if statuses.is_empty() {
                 // By default, only display active circuits
                 query = query.filter(circuit::circuit_status.eq(CircuitStatusModel::Active));
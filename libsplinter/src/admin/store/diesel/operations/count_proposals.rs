@@ -22,7 +22,7 @@ use diesel::{
 };
 
 use crate::admin::store::{
-    diesel::schema::{proposed_circuit, proposed_node},
+    diesel::schema::{circuit_proposal, proposed_circuit, proposed_node},
     error::AdminServiceStoreError,
     CircuitPredicate,
 };
@@ -63,6 +63,33 @@ where
             })
             .flatten()
             .collect();
+        // Collects the circuit versions included in the list of `CircuitPredicates`
+        let versions: Vec<i32> = predicates
+            .iter()
+            .filter_map(|pred| match pred {
+                CircuitPredicate::CircuitVersionEq(version) => Some(*version),
+                _ => None,
+            })
+            .collect();
+        // Collects the upper bounds on the circuit version included in the list of
+        // `CircuitPredicates`
+        let versions_below: Vec<i32> = predicates
+            .iter()
+            .filter_map(|pred| match pred {
+                CircuitPredicate::CircuitVersionLt(version) => Some(*version),
+                _ => None,
+            })
+            .collect();
+        // Collects the proposal types included in the list of `CircuitPredicates`
+        let proposal_types: Vec<String> = predicates
+            .iter()
+            .filter_map(|pred| match pred {
+                CircuitPredicate::ProposalTypeEq(proposal_type) => {
+                    Some(String::from(proposal_type))
+                }
+                _ => None,
+            })
+            .collect();
 
         self.conn.transaction::<u32, _, _>(|| {
             let mut query = proposed_circuit::table
@@ -81,6 +108,25 @@ where
                 ))
             }
 
+            if !versions.is_empty() {
+                query = query.filter(proposed_circuit::circuit_version.eq_any(versions));
+            }
+
+            for version in versions_below {
+                query = query.filter(proposed_circuit::circuit_version.lt(version));
+            }
+
+            // Selects proposed circuits whose proposal matches the proposal types
+            if !proposal_types.is_empty() {
+                query = query.filter(exists(
+                    circuit_proposal::table.filter(
+                        circuit_proposal::circuit_id
+                            .eq(proposed_circuit::circuit_id)
+                            .and(circuit_proposal::proposal_type.eq_any(proposal_types)),
+                    ),
+                ))
+            }
+
             // Selects proposed circuits that match the management types
             if !management_types.is_empty() {
                 query = query
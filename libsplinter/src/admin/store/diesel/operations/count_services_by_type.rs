@@ -0,0 +1,63 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "count services by type" operation for the `DieselAdminServiceStore`.
+
+use std::collections::HashMap;
+
+use diesel::{
+    dsl::{count_star, exists},
+    prelude::*,
+};
+
+use crate::admin::store::{
+    diesel::{
+        models::CircuitStatusModel,
+        schema::{circuit, service},
+    },
+    error::AdminServiceStoreError,
+};
+
+use super::AdminServiceStoreOperations;
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreCountServicesByTypeOperation {
+    fn count_services_by_type(&self) -> Result<HashMap<String, u64>, AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreCountServicesByTypeOperation for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn count_services_by_type(&self) -> Result<HashMap<String, u64>, AdminServiceStoreError> {
+        let counts = service::table
+            // Only count the services of active circuits
+            .filter(exists(
+                circuit::table.filter(
+                    circuit::circuit_id
+                        .eq(service::circuit_id)
+                        .and(circuit::circuit_status.eq(CircuitStatusModel::Active)),
+                ),
+            ))
+            .group_by(service::service_type)
+            .select((service::service_type, count_star()))
+            .load::<(String, i64)>(self.conn)?;
+
+        Ok(counts
+            .into_iter()
+            .map(|(service_type, count)| (service_type, count as u64))
+            .collect())
+    }
+}
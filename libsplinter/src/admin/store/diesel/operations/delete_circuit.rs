@@ -0,0 +1,79 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "delete circuit" operation for the `DieselAdminServiceStore`.
+
+use diesel::{dsl::update, prelude::*};
+
+use super::AdminServiceStoreOperations;
+use crate::admin::store::{
+    diesel::{
+        models::{current_time, CircuitStatusModel},
+        schema::circuit,
+    },
+    error::AdminServiceStoreError,
+};
+use crate::error::{ConstraintViolationError, ConstraintViolationType};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreDeleteCircuitOperation {
+    fn delete_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AdminServiceStoreDeleteCircuitOperation
+    for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn delete_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
+        // Mark the `circuit` entry as deleted, leaving the circuit and its associated data in
+        // place
+        let updated = update(circuit::table.find(circuit_id))
+            .set((
+                circuit::circuit_status.eq(CircuitStatusModel::Deleted),
+                circuit::updated_time.eq(current_time()),
+            ))
+            .execute(self.conn)?;
+
+        if updated == 0 {
+            return Err(AdminServiceStoreError::ConstraintViolationError(
+                ConstraintViolationError::with_violation_type(ConstraintViolationType::NotFound),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AdminServiceStoreDeleteCircuitOperation
+    for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn delete_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
+        // Mark the `circuit` entry as deleted, leaving the circuit and its associated data in
+        // place
+        let updated = update(circuit::table.find(circuit_id))
+            .set((
+                circuit::circuit_status.eq(CircuitStatusModel::Deleted),
+                circuit::updated_time.eq(current_time()),
+            ))
+            .execute(self.conn)?;
+
+        if updated == 0 {
+            return Err(AdminServiceStoreError::ConstraintViolationError(
+                ConstraintViolationError::with_violation_type(ConstraintViolationType::NotFound),
+            ));
+        }
+
+        Ok(())
+    }
+}
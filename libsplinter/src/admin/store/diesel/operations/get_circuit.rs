@@ -59,29 +59,31 @@ where
                 None => return Ok(None),
             };
 
-            // Collecting the members of the `Circuit`
-            let nodes_info: Vec<(CircuitMemberModel, NodeEndpointModel)> = circuit_member::table
-                .filter(circuit_member::circuit_id.eq(circuit_id.to_string()))
-                // As `circuit_member` and `node_endpoint` have a one-to-many relationship, this join
-                // will return all matching entries as there are `node_endpoint` entries.
-                .order(circuit_member::position)
-                .inner_join(
-                    node_endpoint::table.on(circuit_member::node_id.eq(node_endpoint::node_id)),
-                )
-                .load(self.conn)?;
+            // Collecting the members of the `Circuit`, along with all of their endpoints
+            let nodes_info: Vec<(CircuitMemberModel, Option<NodeEndpointModel>)> =
+                circuit_member::table
+                    .filter(circuit_member::circuit_id.eq(circuit_id.to_string()))
+                    // As `circuit_member` and `node_endpoint` have a one-to-many relationship,
+                    // this join will return as many entries for a member as there are
+                    // `node_endpoint` entries for it. A member without any endpoints is still
+                    // returned once.
+                    .order(circuit_member::position)
+                    .left_join(
+                        node_endpoint::table.on(circuit_member::node_id.eq(node_endpoint::node_id)),
+                    )
+                    .load(self.conn)?;
 
             let mut node_map: HashMap<String, Vec<String>> = HashMap::new();
             let mut nodes: HashMap<String, CircuitMemberModel> = HashMap::new();
             // Iterate over the list of node data retrieved from the database, in order to collect all
             // endpoints associated with the `node_ids` in a HashMap.
             nodes_info.into_iter().for_each(|(node, node_endpoint)| {
-                if let Some(endpoint_list) = node_map.get_mut(&node.node_id) {
+                let endpoint_list = node_map.entry(node.node_id.to_string()).or_default();
+                if let Some(node_endpoint) = node_endpoint {
                     endpoint_list.push(node_endpoint.endpoint);
                     // Ensure only unique endpoints are added to the node's endpoint list
                     endpoint_list.sort();
                     endpoint_list.dedup();
-                } else {
-                    node_map.insert(node.node_id.to_string(), vec![node_endpoint.endpoint]);
                 }
 
                 if !nodes.contains_key(&node.node_id) {
@@ -0,0 +1,65 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "get circuits" operation for the `DieselAdminServiceStore`.
+
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use diesel::sql_types::{Binary, Integer, Nullable, Text};
+
+use super::AdminServiceStoreOperations;
+
+use crate::admin::store::{
+    diesel::{
+        models::{CircuitMemberModel, CircuitModel},
+        schema::circuit,
+    },
+    error::AdminServiceStoreError,
+    Circuit,
+};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreFetchCircuitsOperation {
+    fn get_circuits(
+        &self,
+        circuit_ids: &[&str],
+    ) -> Result<HashMap<String, Circuit>, AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreFetchCircuitsOperation for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    i32: diesel::deserialize::FromSql<Integer, C::Backend>,
+    i16: diesel::deserialize::FromSql<diesel::sql_types::SmallInt, C::Backend>,
+    CircuitMemberModel: diesel::Queryable<(Text, Text, Integer, Nullable<Binary>), C::Backend>,
+{
+    fn get_circuits(
+        &self,
+        circuit_ids: &[&str],
+    ) -> Result<HashMap<String, Circuit>, AdminServiceStoreError> {
+        self.conn.transaction::<HashMap<String, Circuit>, _, _>(|| {
+            let circuits: Vec<CircuitModel> = circuit::table
+                .filter(circuit::circuit_id.eq_any(circuit_ids))
+                .load::<CircuitModel>(self.conn)?;
+
+            Ok(self
+                .build_circuits(circuits)?
+                .into_iter()
+                .map(|circuit| (circuit.circuit_id().to_string(), circuit))
+                .collect())
+        })
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "fetch proposal with circuit" operation for the `DieselAdminServiceStore`.
+
+use diesel::prelude::*;
+
+use super::{
+    get_circuit::AdminServiceStoreFetchCircuitOperation,
+    get_proposal::AdminServiceStoreFetchProposalOperation, AdminServiceStoreOperations,
+};
+use crate::admin::store::{error::AdminServiceStoreError, Circuit, CircuitProposal};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreFetchProposalWithCircuitOperation {
+    fn get_proposal_with_circuit(
+        &self,
+        id: &str,
+    ) -> Result<Option<(Option<CircuitProposal>, Option<Circuit>)>, AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreFetchProposalWithCircuitOperation
+    for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    AdminServiceStoreOperations<'a, C>:
+        AdminServiceStoreFetchProposalOperation + AdminServiceStoreFetchCircuitOperation,
+{
+    fn get_proposal_with_circuit(
+        &self,
+        id: &str,
+    ) -> Result<Option<(Option<CircuitProposal>, Option<Circuit>)>, AdminServiceStoreError> {
+        self.conn
+            .transaction::<Option<(Option<CircuitProposal>, Option<Circuit>)>, _, _>(|| {
+                match (self.get_proposal(id)?, self.get_circuit(id)?) {
+                    (None, None) => Ok(None),
+                    (proposal, circuit) => Ok(Some((proposal, circuit))),
+                }
+            })
+    }
+}
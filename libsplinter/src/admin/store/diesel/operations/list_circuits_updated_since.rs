@@ -0,0 +1,65 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "list circuits updated since" operation for the `DieselAdminServiceStore`.
+
+use diesel::{
+    prelude::*,
+    sql_types::{Binary, Integer, Nullable, Text},
+};
+
+use super::AdminServiceStoreOperations;
+
+use crate::admin::store::{
+    diesel::{
+        models::{CircuitMemberModel, CircuitModel},
+        schema::circuit,
+    },
+    error::AdminServiceStoreError,
+    Circuit,
+};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreListCircuitsUpdatedSinceOperation {
+    fn list_circuits_updated_since(
+        &self,
+        since: i64,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreListCircuitsUpdatedSinceOperation
+    for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    i32: diesel::deserialize::FromSql<Integer, C::Backend>,
+    i16: diesel::deserialize::FromSql<diesel::sql_types::SmallInt, C::Backend>,
+    CircuitMemberModel: diesel::Queryable<(Text, Text, Integer, Nullable<Binary>), C::Backend>,
+{
+    fn list_circuits_updated_since(
+        &self,
+        since: i64,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        self.conn
+            .transaction::<Box<dyn ExactSizeIterator<Item = Circuit>>, _, _>(|| {
+                // Circuits of any status may have been updated, so the status is not filtered
+                let circuits: Vec<CircuitModel> = circuit::table
+                    .filter(circuit::updated_time.ge(since))
+                    .order(circuit::circuit_id.desc())
+                    .load::<CircuitModel>(self.conn)?;
+
+                Ok(Box::new(self.build_circuits(circuits)?.into_iter()))
+            })
+    }
+}
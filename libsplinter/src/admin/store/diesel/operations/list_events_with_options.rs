@@ -0,0 +1,108 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "list events with options" operation for the `DieselAdminServiceStore`.
+
+use diesel::{dsl::exists, prelude::*, types::HasSqlType};
+
+use super::{list_events::AdminServiceStoreListEventsOperation, AdminServiceStoreOperations};
+
+use crate::admin::store::{
+    diesel::schema::{admin_event_proposed_circuit, admin_service_event},
+    AdminServiceEvent, AdminServiceStoreError, EventIter, EventOrder, EventQueryOptions,
+    EventTypeFilter,
+};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreListEventsWithOptionsOperation {
+    fn list_events_with_options(
+        &self,
+        options: &EventQueryOptions,
+    ) -> Result<EventIter, AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreListEventsWithOptionsOperation for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    C::Backend: HasSqlType<diesel::sql_types::BigInt>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, C::Backend>,
+    Vec<u8>: diesel::deserialize::FromSql<diesel::sql_types::Binary, C::Backend>,
+    i16: diesel::deserialize::FromSql<diesel::sql_types::SmallInt, C::Backend>,
+{
+    fn list_events_with_options(
+        &self,
+        options: &EventQueryOptions,
+    ) -> Result<EventIter, AdminServiceStoreError> {
+        self.conn.transaction::<EventIter, _, _>(|| {
+            // Only the IDs of the matching events are selected here; the events and their
+            // proposals are loaded by `list_events`
+            let mut query = admin_service_event::table
+                .into_boxed()
+                .select(admin_service_event::id)
+                .filter(admin_service_event::id.gt(options.start()));
+
+            if let Some(management_type) = options.management_type() {
+                query = query.filter(exists(
+                    admin_event_proposed_circuit::table.filter(
+                        admin_event_proposed_circuit::event_id
+                            .eq(admin_service_event::id)
+                            .and(
+                                admin_event_proposed_circuit::circuit_management_type
+                                    .eq(management_type.to_string()),
+                            ),
+                    ),
+                ));
+            }
+
+            if let Some(event_type) = options.event_type() {
+                query = query.filter(
+                    admin_service_event::event_type.eq(event_type_discriminator(event_type)),
+                );
+            }
+
+            query = match options.order() {
+                EventOrder::Asc => query.order(admin_service_event::id.asc()),
+                EventOrder::Desc => query.order(admin_service_event::id.desc()),
+            };
+
+            if let Some(limit) = options.limit() {
+                query = query.limit(limit);
+            }
+
+            let event_ids: Vec<i64> = query.load(self.conn)?;
+            let events = AdminServiceStoreOperations::new(self.conn).list_events(event_ids)?;
+
+            // `list_events` always returns the events in ascending order
+            match options.order() {
+                EventOrder::Asc => Ok(events),
+                EventOrder::Desc => Ok(Box::new(
+                    events.collect::<Vec<AdminServiceEvent>>().into_iter().rev(),
+                )),
+            }
+        })
+    }
+}
+
+/// Returns the `event_type` stored for the events of the given kind
+fn event_type_discriminator(event_type: EventTypeFilter) -> &'static str {
+    match event_type {
+        EventTypeFilter::ProposalSubmitted => "ProposalSubmitted",
+        EventTypeFilter::ProposalVote => "ProposalVote",
+        EventTypeFilter::ProposalAccepted => "ProposalAccepted",
+        EventTypeFilter::ProposalRejected => "ProposalRejected",
+        EventTypeFilter::CircuitReady => "CircuitReady",
+        EventTypeFilter::CircuitDisbanded => "CircuitDisbanded",
+    }
+}
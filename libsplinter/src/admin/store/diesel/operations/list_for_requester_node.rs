@@ -0,0 +1,73 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "list for requester node" operation for the `DieselAdminServiceStore`.
+
+use std::collections::HashSet;
+
+use diesel::prelude::*;
+
+use super::{
+    list_circuits::AdminServiceStoreListCircuitsOperation,
+    list_proposals::AdminServiceStoreListProposalsOperation, AdminServiceStoreOperations,
+};
+
+use crate::admin::store::{
+    diesel::schema::circuit_proposal, error::AdminServiceStoreError, Circuit, CircuitPredicate,
+    CircuitProposal,
+};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreListForRequesterNodeOperation {
+    fn list_for_requester_node(
+        &self,
+        node_id: &str,
+        circuit_predicates: &[CircuitPredicate],
+    ) -> Result<(Vec<CircuitProposal>, Vec<Circuit>), AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreListForRequesterNodeOperation for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    AdminServiceStoreOperations<'a, C>:
+        AdminServiceStoreListProposalsOperation + AdminServiceStoreListCircuitsOperation,
+{
+    fn list_for_requester_node(
+        &self,
+        node_id: &str,
+        circuit_predicates: &[CircuitPredicate],
+    ) -> Result<(Vec<CircuitProposal>, Vec<Circuit>), AdminServiceStoreError> {
+        self.conn
+            .transaction::<(Vec<CircuitProposal>, Vec<Circuit>), _, _>(|| {
+                // Selects the proposals that were requested by the node
+                let circuit_ids: HashSet<String> = circuit_proposal::table
+                    .filter(circuit_proposal::requester_node_id.eq(node_id))
+                    .select(circuit_proposal::circuit_id)
+                    .load::<String>(self.conn)?
+                    .into_iter()
+                    .collect();
+
+                let proposals = self
+                    .list_proposals(&[])?
+                    .filter(|proposal| circuit_ids.contains(proposal.circuit_id()))
+                    .collect();
+
+                let mut predicates = circuit_predicates.to_vec();
+                predicates.push(CircuitPredicate::MembersInclude(vec![node_id.to_string()]));
+                let circuits = self.list_circuits(&predicates)?.collect();
+
+                Ok((proposals, circuits))
+            })
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "list orphaned nodes" operation for the `DieselAdminServiceStore`.
+
+use std::collections::BTreeMap;
+
+use diesel::{
+    dsl::{exists, not},
+    prelude::*,
+    sql_types::Text,
+};
+
+use crate::admin::store::{
+    diesel::{
+        models::NodeEndpointModel,
+        schema::{circuit_member, node_endpoint, proposed_node},
+    },
+    error::AdminServiceStoreError,
+    CircuitNode, CircuitNodeBuilder,
+};
+use crate::error::InvalidStateError;
+
+use super::AdminServiceStoreOperations;
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreListOrphanedNodesOperation {
+    fn list_orphaned_nodes(&self) -> Result<Vec<CircuitNode>, AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreListOrphanedNodesOperation for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    NodeEndpointModel: diesel::Queryable<(Text, Text), C::Backend>,
+{
+    fn list_orphaned_nodes(&self) -> Result<Vec<CircuitNode>, AdminServiceStoreError> {
+        // Nodes that are part of a proposed circuit are still in use, even if they are not a
+        // member of any circuit yet.
+        let proposed_node_ids = proposed_node::table
+            .select(proposed_node::node_id)
+            .load::<String>(self.conn)?;
+
+        // Collect the `node_endpoint` entries of the nodes that are not a `circuit_member` of any
+        // circuit.
+        let orphaned_endpoints: Vec<NodeEndpointModel> = node_endpoint::table
+            .filter(not(exists(
+                circuit_member::table.filter(circuit_member::node_id.eq(node_endpoint::node_id)),
+            )))
+            .filter(not(node_endpoint::node_id.eq_any(proposed_node_ids)))
+            .order((node_endpoint::node_id, node_endpoint::endpoint))
+            .load(self.conn)?;
+
+        let mut node_map: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for node_endpoint in orphaned_endpoints {
+            node_map
+                .entry(node_endpoint.node_id)
+                .or_default()
+                .push(node_endpoint.endpoint);
+        }
+
+        node_map
+            .into_iter()
+            .map(|(node_id, endpoints)| {
+                CircuitNodeBuilder::new()
+                    .with_node_id(&node_id)
+                    .with_endpoints(&endpoints)
+                    .build()
+            })
+            .collect::<Result<Vec<CircuitNode>, InvalidStateError>>()
+            .map_err(AdminServiceStoreError::InvalidStateError)
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "list proposal management types" operation for the `DieselAdminServiceStore`.
+
+use diesel::prelude::*;
+
+use crate::admin::store::{diesel::schema::proposed_circuit, error::AdminServiceStoreError};
+
+use super::AdminServiceStoreOperations;
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreListProposalManagementTypesOperation {
+    fn list_proposal_management_types(&self) -> Result<Vec<String>, AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreListProposalManagementTypesOperation
+    for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn list_proposal_management_types(&self) -> Result<Vec<String>, AdminServiceStoreError> {
+        proposed_circuit::table
+            .select(proposed_circuit::circuit_management_type)
+            .distinct()
+            .order(proposed_circuit::circuit_management_type.asc())
+            .load::<String>(self.conn)
+            .map_err(AdminServiceStoreError::from)
+    }
+}
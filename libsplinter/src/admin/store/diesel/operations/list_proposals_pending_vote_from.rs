@@ -0,0 +1,76 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "list proposals pending vote from" operation for the `DieselAdminServiceStore`.
+
+use std::collections::HashSet;
+
+use diesel::{
+    dsl::{exists, not},
+    prelude::*,
+};
+
+use super::{list_proposals::AdminServiceStoreListProposalsOperation, AdminServiceStoreOperations};
+
+use crate::admin::store::{
+    diesel::schema::{proposed_node, vote_record},
+    error::AdminServiceStoreError,
+    CircuitProposal,
+};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreListProposalsPendingVoteFromOperation {
+    fn list_proposals_pending_vote_from(
+        &self,
+        node_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreListProposalsPendingVoteFromOperation
+    for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    AdminServiceStoreOperations<'a, C>: AdminServiceStoreListProposalsOperation,
+{
+    fn list_proposals_pending_vote_from(
+        &self,
+        node_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError> {
+        self.conn
+            .transaction::<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, _, _>(|| {
+                // Selects the proposals the node is a member of that have no vote record from
+                // the node
+                let circuit_ids: HashSet<String> = proposed_node::table
+                    .filter(proposed_node::node_id.eq(node_id))
+                    .filter(not(exists(
+                        vote_record::table.filter(
+                            vote_record::circuit_id
+                                .eq(proposed_node::circuit_id)
+                                .and(vote_record::voter_node_id.eq(node_id)),
+                        ),
+                    )))
+                    .select(proposed_node::circuit_id)
+                    .load::<String>(self.conn)?
+                    .into_iter()
+                    .collect();
+
+                let proposals: Vec<CircuitProposal> = self
+                    .list_proposals(&[])?
+                    .filter(|proposal| circuit_ids.contains(proposal.circuit_id()))
+                    .collect();
+
+                Ok(Box::new(proposals.into_iter()))
+            })
+    }
+}
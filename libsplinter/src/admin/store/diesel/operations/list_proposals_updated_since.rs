@@ -0,0 +1,89 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "list proposals updated since" operation for the `DieselAdminServiceStore`.
+
+use diesel::{
+    prelude::*,
+    sql_types::{BigInt, Binary, Integer, Nullable, SmallInt, Text},
+};
+
+use super::AdminServiceStoreOperations;
+
+use crate::admin::store::{
+    diesel::{
+        models::{CircuitProposalModel, ProposedCircuitModel, ProposedNodeModel, VoteRecordModel},
+        schema::{circuit_proposal, proposed_circuit},
+    },
+    error::AdminServiceStoreError,
+    CircuitProposal,
+};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreListProposalsUpdatedSinceOperation {
+    fn list_proposals_updated_since(
+        &self,
+        since: i64,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreListProposalsUpdatedSinceOperation
+    for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<BigInt, C::Backend>,
+    i32: diesel::deserialize::FromSql<Integer, C::Backend>,
+    CircuitProposalModel: diesel::Queryable<(Text, Text, Text, Binary, Text, BigInt), C::Backend>,
+    ProposedCircuitModel: diesel::Queryable<
+        (
+            Text,
+            Text,
+            Text,
+            Text,
+            Text,
+            Text,
+            Nullable<Binary>,
+            Nullable<Text>,
+            Nullable<Text>,
+            Integer,
+            SmallInt,
+        ),
+        C::Backend,
+    >,
+    VoteRecordModel: diesel::Queryable<(Text, Binary, Text, Text, Integer), C::Backend>,
+    ProposedNodeModel: diesel::Queryable<(Text, Text, Integer, Nullable<Binary>), C::Backend>,
+{
+    fn list_proposals_updated_since(
+        &self,
+        since: i64,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError> {
+        self.conn
+            .transaction::<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, _, _>(|| {
+                let proposed_circuits: Vec<ProposedCircuitModel> = proposed_circuit::table
+                    .filter(
+                        proposed_circuit::circuit_id.eq_any(
+                            circuit_proposal::table
+                                .filter(circuit_proposal::updated_time.ge(since))
+                                .select(circuit_proposal::circuit_id),
+                        ),
+                    )
+                    .order(proposed_circuit::circuit_id.desc())
+                    .load::<ProposedCircuitModel>(self.conn)?;
+
+                Ok(Box::new(
+                    self.build_proposals(proposed_circuits)?.into_iter(),
+                ))
+            })
+    }
+}
@@ -17,25 +17,67 @@
 pub(super) mod add_circuit;
 pub(super) mod add_event;
 pub(super) mod add_proposal;
+pub(super) mod add_proposal_with_event;
+pub(super) mod append_vote;
 pub(super) mod count_circuits;
 pub(super) mod count_proposals;
+pub(super) mod count_services_by_type;
+pub(super) mod delete_circuit;
 pub(super) mod get_circuit;
+pub(super) mod get_circuits;
 pub(super) mod get_node;
 pub(super) mod get_proposal;
+pub(super) mod get_proposal_with_circuit;
 pub(super) mod get_service;
+pub(super) mod list_circuit_management_types;
 pub(super) mod list_circuits;
+pub(super) mod list_circuits_updated_since;
 pub(super) mod list_events;
-pub(super) mod list_events_by_management_type_since;
-pub(super) mod list_events_since;
+pub(super) mod list_events_with_options;
+pub(super) mod list_for_requester_node;
 pub(super) mod list_nodes;
+pub(super) mod list_orphaned_nodes;
+pub(super) mod list_proposal_management_types;
 pub(super) mod list_proposals;
+pub(super) mod list_proposals_pending_vote_from;
+pub(super) mod list_proposals_updated_since;
 pub(super) mod list_services;
+pub(super) mod proposal_vote_summary;
 pub(super) mod remove_circuit;
+pub(super) mod remove_orphaned_nodes;
 pub(super) mod remove_proposal;
+pub(super) mod remove_proposals_by_management_type;
+pub(super) mod service_exists;
+pub(super) mod set_circuit_status;
+pub(super) mod storage_stats;
 pub(super) mod update_circuit;
 pub(super) mod update_proposal;
 pub(super) mod upgrade;
 
+sql_function! {
+    /// Converts a string to lower case.
+    fn lower(x: diesel::sql_types::Nullable<diesel::sql_types::Text>)
+        -> diesel::sql_types::Nullable<diesel::sql_types::Text>;
+}
+
+/// The escape character used in `LIKE` patterns created by `contains_pattern`.
+const LIKE_ESCAPE: char = '\\';
+
+/// Creates a `LIKE` pattern matching any value that contains the given string. The `%` and `_`
+/// wildcards in the string are escaped, using `LIKE_ESCAPE`, so they only match themselves.
+fn contains_pattern(value: &str) -> String {
+    let mut pattern = String::with_capacity(value.len() + 2);
+    pattern.push('%');
+    for c in value.chars() {
+        if c == LIKE_ESCAPE || c == '%' || c == '_' {
+            pattern.push(LIKE_ESCAPE);
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
 pub struct AdminServiceStoreOperations<'a, C> {
     conn: &'a C,
 }
@@ -0,0 +1,96 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "proposal vote summary" operation for the `DieselAdminServiceStore`.
+
+use std::convert::TryFrom;
+
+use diesel::{
+    dsl::{count_star, exists, not},
+    prelude::*,
+};
+
+use crate::admin::store::{
+    diesel::schema::{circuit_proposal, proposed_node, vote_record},
+    error::AdminServiceStoreError,
+    Vote, VoteSummary,
+};
+
+use super::AdminServiceStoreOperations;
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreProposalVoteSummaryOperation {
+    fn proposal_vote_summary(
+        &self,
+        proposal_id: &str,
+    ) -> Result<Option<VoteSummary>, AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreProposalVoteSummaryOperation for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn proposal_vote_summary(
+        &self,
+        proposal_id: &str,
+    ) -> Result<Option<VoteSummary>, AdminServiceStoreError> {
+        self.conn.transaction::<Option<VoteSummary>, _, _>(|| {
+            let requester_node_id = match circuit_proposal::table
+                .filter(circuit_proposal::circuit_id.eq(proposal_id))
+                .select(circuit_proposal::requester_node_id)
+                .first::<String>(self.conn)
+                .optional()?
+            {
+                Some(requester_node_id) => requester_node_id,
+                None => return Ok(None),
+            };
+
+            let mut accept_count = 0;
+            let mut reject_count = 0;
+            for (vote, count) in vote_record::table
+                .filter(vote_record::circuit_id.eq(proposal_id))
+                .group_by(vote_record::vote)
+                .select((vote_record::vote, count_star()))
+                .load::<(String, i64)>(self.conn)?
+            {
+                match Vote::try_from(vote)? {
+                    Vote::Accept => accept_count = count as u64,
+                    Vote::Reject => reject_count = count as u64,
+                }
+            }
+
+            // Selects the members, other than the requester's node, that have no vote record
+            let outstanding_member_ids = proposed_node::table
+                .filter(proposed_node::circuit_id.eq(proposal_id))
+                .filter(proposed_node::node_id.ne(requester_node_id))
+                .filter(not(exists(
+                    vote_record::table.filter(
+                        vote_record::circuit_id
+                            .eq(proposed_node::circuit_id)
+                            .and(vote_record::voter_node_id.eq(proposed_node::node_id)),
+                    ),
+                )))
+                .order(proposed_node::position)
+                .select(proposed_node::node_id)
+                .load::<String>(self.conn)?;
+
+            Ok(Some(VoteSummary::new(
+                accept_count,
+                reject_count,
+                outstanding_member_ids,
+            )))
+        })
+    }
+}
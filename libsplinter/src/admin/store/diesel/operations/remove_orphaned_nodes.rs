@@ -0,0 +1,54 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "remove orphaned nodes" operation for the `DieselAdminServiceStore`.
+
+use diesel::{dsl::delete, prelude::*, sql_types::Text};
+
+use crate::admin::store::{
+    diesel::{models::NodeEndpointModel, schema::node_endpoint},
+    error::AdminServiceStoreError,
+};
+
+use super::{
+    list_orphaned_nodes::AdminServiceStoreListOrphanedNodesOperation, AdminServiceStoreOperations,
+};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreRemoveOrphanedNodesOperation {
+    fn remove_orphaned_nodes(&self) -> Result<u64, AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreRemoveOrphanedNodesOperation for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    NodeEndpointModel: diesel::Queryable<(Text, Text), C::Backend>,
+{
+    fn remove_orphaned_nodes(&self) -> Result<u64, AdminServiceStoreError> {
+        self.conn.transaction::<u64, _, _>(|| {
+            let node_ids = self
+                .list_orphaned_nodes()?
+                .iter()
+                .map(|node| node.node_id().to_string())
+                .collect::<Vec<_>>();
+
+            if !node_ids.is_empty() {
+                delete(node_endpoint::table.filter(node_endpoint::node_id.eq_any(&node_ids)))
+                    .execute(self.conn)?;
+            }
+
+            Ok(node_ids.len() as u64)
+        })
+    }
+}
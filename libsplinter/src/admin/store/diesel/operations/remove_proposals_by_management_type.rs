@@ -0,0 +1,62 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "remove proposals by management type" operation for the
+//! `DieselAdminServiceStore`.
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::admin::store::{
+    diesel::schema::{circuit_proposal, proposed_circuit},
+    error::AdminServiceStoreError,
+};
+
+use super::AdminServiceStoreOperations;
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreRemoveProposalsByManagementTypeOperation
+{
+    fn remove_proposals_by_management_type(
+        &self,
+        management_type: &str,
+    ) -> Result<u64, AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreRemoveProposalsByManagementTypeOperation
+    for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn remove_proposals_by_management_type(
+        &self,
+        management_type: &str,
+    ) -> Result<u64, AdminServiceStoreError> {
+        self.conn.transaction::<u64, _, _>(|| {
+            let circuit_ids = proposed_circuit::table
+                .filter(proposed_circuit::circuit_management_type.eq(management_type))
+                .select(proposed_circuit::circuit_id)
+                .load::<String>(self.conn)?;
+
+            // As with removing a single proposal, the `circuit_id` foreign key has cascade
+            // delete, so the rows of the proposed circuit, its members, services, and votes are
+            // removed along with the `circuit_proposal` entries.
+            let removed = delete(
+                circuit_proposal::table.filter(circuit_proposal::circuit_id.eq_any(circuit_ids)),
+            )
+            .execute(self.conn)?;
+
+            Ok(removed as u64)
+        })
+    }
+}
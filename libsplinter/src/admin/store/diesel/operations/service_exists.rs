@@ -0,0 +1,42 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "service exists" operation for the `DieselAdminServiceStore`.
+
+use diesel::{dsl::sql, prelude::*, sql_types::Integer};
+
+use super::AdminServiceStoreOperations;
+use crate::admin::store::{diesel::schema::service, error::AdminServiceStoreError, ServiceId};
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreServiceExistsOperation {
+    fn service_exists(&self, service_id: &ServiceId) -> Result<bool, AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreServiceExistsOperation for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i32: diesel::deserialize::FromSql<Integer, C::Backend>,
+{
+    fn service_exists(&self, service_id: &ServiceId) -> Result<bool, AdminServiceStoreError> {
+        // Only check for a matching `service` entry, without loading the service or its
+        // arguments
+        Ok(service::table
+            .filter(service::circuit_id.eq(&service_id.circuit_id))
+            .filter(service::service_id.eq(&service_id.service_id))
+            .select(sql::<Integer>("1"))
+            .first::<i32>(self.conn)
+            .optional()?
+            .is_some())
+    }
+}
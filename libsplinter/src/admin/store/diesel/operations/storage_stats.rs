@@ -0,0 +1,67 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "storage stats" operation for the `DieselAdminServiceStore`.
+
+use diesel::{dsl::count_star, prelude::*};
+
+use crate::admin::store::{
+    diesel::schema::{admin_service_event, circuit, circuit_proposal, node_endpoint, service},
+    error::AdminServiceStoreError,
+    StorageStats,
+};
+
+use super::AdminServiceStoreOperations;
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreStorageStatsOperation {
+    fn storage_stats(&self) -> Result<StorageStats, AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreStorageStatsOperation for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn storage_stats(&self) -> Result<StorageStats, AdminServiceStoreError> {
+        self.conn.transaction::<StorageStats, _, _>(|| {
+            let circuits = circuit::table
+                .select(count_star())
+                .first::<i64>(self.conn)?;
+            let proposals = circuit_proposal::table
+                .select(count_star())
+                .first::<i64>(self.conn)?;
+            // A node has a row for each of its endpoints, so the distinct node IDs are counted
+            let nodes = node_endpoint::table
+                .select(node_endpoint::node_id)
+                .distinct()
+                .load::<String>(self.conn)?
+                .len();
+            let services = service::table
+                .select(count_star())
+                .first::<i64>(self.conn)?;
+            let events = admin_service_event::table
+                .select(count_star())
+                .first::<i64>(self.conn)?;
+
+            Ok(StorageStats::new(
+                circuits as u64,
+                proposals as u64,
+                nodes as u64,
+                services as u64,
+                events as u64,
+            ))
+        })
+    }
+}
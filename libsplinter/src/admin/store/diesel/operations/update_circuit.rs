@@ -20,7 +20,7 @@ use diesel::{
     prelude::*,
 };
 
-use super::AdminServiceStoreOperations;
+use super::{get_circuit::AdminServiceStoreFetchCircuitOperation, AdminServiceStoreOperations};
 use crate::admin::store::{
     diesel::{
         models::{CircuitMemberModel, CircuitModel, ServiceArgumentModel, ServiceModel},
@@ -29,32 +29,28 @@ use crate::admin::store::{
     error::AdminServiceStoreError,
     Circuit,
 };
-use crate::error::InvalidStateError;
+use crate::error::{ConstraintViolationError, ConstraintViolationType};
 
 pub(in crate::admin::store::diesel) trait AdminServiceStoreUpdateCircuitOperation {
-    fn update_circuit(&self, circuit: Circuit) -> Result<(), AdminServiceStoreError>;
+    fn update_circuit(&self, circuit: &Circuit) -> Result<(), AdminServiceStoreError>;
+
+    /// Updates the circuit and returns the value that was stored before the update, or `None` if
+    /// the circuit did not exist, in which case nothing is updated.
+    fn update_circuit_returning_previous(
+        &self,
+        circuit: &Circuit,
+    ) -> Result<Option<Circuit>, AdminServiceStoreError>;
 }
 
 #[cfg(feature = "postgres")]
 impl<'a> AdminServiceStoreUpdateCircuitOperation
     for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
 {
-    fn update_circuit(&self, circuit: Circuit) -> Result<(), AdminServiceStoreError> {
+    fn update_circuit(&self, circuit: &Circuit) -> Result<(), AdminServiceStoreError> {
         self.conn.transaction::<(), _, _>(|| {
-            // Verify the `circuit` entry to be updated exists
-            circuit::table
-                .filter(circuit::circuit_id.eq(circuit.circuit_id()))
-                .first::<CircuitModel>(self.conn)
-                .optional()?
-                .ok_or_else(|| {
-                    AdminServiceStoreError::InvalidStateError(InvalidStateError::with_message(
-                        String::from("Circuit does not exist in AdminServiceStore"),
-                    ))
-                })?;
-
             // Update existing `Circuit`
-            let circuit_model = CircuitModel::from(&circuit);
-            update(circuit::table.find(circuit.circuit_id()))
+            let circuit_model = CircuitModel::from(circuit);
+            let updated = update(circuit::table.find(circuit.circuit_id()))
                 .set((
                     circuit::authorization_type.eq(circuit_model.authorization_type),
                     circuit::persistence.eq(circuit_model.persistence),
@@ -62,8 +58,18 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
                     circuit::routes.eq(circuit_model.routes),
                     circuit::circuit_management_type.eq(circuit_model.circuit_management_type),
                     circuit::circuit_status.eq(circuit_model.circuit_status),
+                    circuit::updated_time.eq(circuit_model.updated_time),
                 ))
                 .execute(self.conn)?;
+
+            if updated == 0 {
+                return Err(AdminServiceStoreError::ConstraintViolationError(
+                    ConstraintViolationError::with_violation_type(
+                        ConstraintViolationType::NotFound,
+                    ),
+                ));
+            }
+
             // Delete existing data associated with the `Circuit`
             delete(service::table.filter(service::circuit_id.eq(circuit.circuit_id())))
                 .execute(self.conn)?;
@@ -77,43 +83,49 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
             )
             .execute(self.conn)?;
             // Insert new data associate with the `Circuit`
-            let services: Vec<ServiceModel> = Vec::try_from(&circuit)?;
+            let services: Vec<ServiceModel> = Vec::try_from(circuit)?;
             insert_into(service::table)
                 .values(&services)
                 .execute(self.conn)?;
-            let service_argument: Vec<ServiceArgumentModel> = Vec::try_from(&circuit)?;
+            let service_argument: Vec<ServiceArgumentModel> = Vec::try_from(circuit)?;
             insert_into(service_argument::table)
                 .values(&service_argument)
                 .execute(self.conn)?;
-            let circuit_member: Vec<CircuitMemberModel> = Vec::try_from(&circuit)?;
+            let circuit_member: Vec<CircuitMemberModel> = Vec::try_from(circuit)?;
             insert_into(circuit_member::table)
                 .values(circuit_member)
                 .execute(self.conn)?;
             Ok(())
         })
     }
+
+    fn update_circuit_returning_previous(
+        &self,
+        circuit: &Circuit,
+    ) -> Result<Option<Circuit>, AdminServiceStoreError> {
+        self.conn.transaction::<Option<Circuit>, _, _>(|| {
+            // Read the stored `Circuit` before it is replaced
+            let previous = match self.get_circuit(circuit.circuit_id())? {
+                Some(previous) => previous,
+                None => return Ok(None),
+            };
+
+            self.update_circuit(circuit)?;
+
+            Ok(Some(previous))
+        })
+    }
 }
 
 #[cfg(feature = "sqlite")]
 impl<'a> AdminServiceStoreUpdateCircuitOperation
     for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
 {
-    fn update_circuit(&self, circuit: Circuit) -> Result<(), AdminServiceStoreError> {
+    fn update_circuit(&self, circuit: &Circuit) -> Result<(), AdminServiceStoreError> {
         self.conn.transaction::<(), _, _>(|| {
-            // Verify the `circuit` entry to be updated exists
-            circuit::table
-                .filter(circuit::circuit_id.eq(circuit.circuit_id()))
-                .first::<CircuitModel>(self.conn)
-                .optional()?
-                .ok_or_else(|| {
-                    AdminServiceStoreError::InvalidStateError(InvalidStateError::with_message(
-                        String::from("Circuit does not exist in AdminServiceStore"),
-                    ))
-                })?;
-
             // Update existing `Circuit`
-            let circuit_model = CircuitModel::from(&circuit);
-            update(circuit::table.find(circuit.circuit_id()))
+            let circuit_model = CircuitModel::from(circuit);
+            let updated = update(circuit::table.find(circuit.circuit_id()))
                 .set((
                     circuit::authorization_type.eq(circuit_model.authorization_type),
                     circuit::persistence.eq(circuit_model.persistence),
@@ -121,8 +133,18 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
                     circuit::routes.eq(circuit_model.routes),
                     circuit::circuit_management_type.eq(circuit_model.circuit_management_type),
                     circuit::circuit_status.eq(circuit_model.circuit_status),
+                    circuit::updated_time.eq(circuit_model.updated_time),
                 ))
                 .execute(self.conn)?;
+
+            if updated == 0 {
+                return Err(AdminServiceStoreError::ConstraintViolationError(
+                    ConstraintViolationError::with_violation_type(
+                        ConstraintViolationType::NotFound,
+                    ),
+                ));
+            }
+
             // Delete existing data associated with the `Circuit`
             delete(service::table.filter(service::circuit_id.eq(circuit.circuit_id())))
                 .execute(self.conn)?;
@@ -136,19 +158,36 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
             )
             .execute(self.conn)?;
             // Insert new data associate with the `Circuit`
-            let services: Vec<ServiceModel> = Vec::try_from(&circuit)?;
+            let services: Vec<ServiceModel> = Vec::try_from(circuit)?;
             insert_into(service::table)
                 .values(&services)
                 .execute(self.conn)?;
-            let service_argument: Vec<ServiceArgumentModel> = Vec::try_from(&circuit)?;
+            let service_argument: Vec<ServiceArgumentModel> = Vec::try_from(circuit)?;
             insert_into(service_argument::table)
                 .values(&service_argument)
                 .execute(self.conn)?;
-            let circuit_member: Vec<CircuitMemberModel> = Vec::try_from(&circuit)?;
+            let circuit_member: Vec<CircuitMemberModel> = Vec::try_from(circuit)?;
             insert_into(circuit_member::table)
                 .values(circuit_member)
                 .execute(self.conn)?;
             Ok(())
         })
     }
+
+    fn update_circuit_returning_previous(
+        &self,
+        circuit: &Circuit,
+    ) -> Result<Option<Circuit>, AdminServiceStoreError> {
+        self.conn.transaction::<Option<Circuit>, _, _>(|| {
+            // Read the stored `Circuit` before it is replaced
+            let previous = match self.get_circuit(circuit.circuit_id())? {
+                Some(previous) => previous,
+                None => return Ok(None),
+            };
+
+            self.update_circuit(circuit)?;
+
+            Ok(Some(previous))
+        })
+    }
 }
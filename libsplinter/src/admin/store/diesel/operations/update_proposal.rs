@@ -21,7 +21,7 @@ use diesel::{
     prelude::*,
 };
 
-use super::AdminServiceStoreOperations;
+use super::{get_proposal::AdminServiceStoreFetchProposalOperation, AdminServiceStoreOperations};
 use crate::admin::store::{
     diesel::{
         models::{
@@ -36,39 +36,51 @@ use crate::admin::store::{
     error::AdminServiceStoreError,
     CircuitProposal,
 };
-use crate::error::InvalidStateError;
+use crate::error::{ConstraintViolationError, ConstraintViolationType};
 
 pub(in crate::admin::store::diesel) trait AdminServiceStoreUpdateProposalOperation {
-    fn update_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError>;
+    fn update_proposal(&self, proposal: &CircuitProposal) -> Result<(), AdminServiceStoreError>;
+
+    fn update_proposal_if_unchanged(
+        &self,
+        proposal: &CircuitProposal,
+        expected_hash: &str,
+    ) -> Result<(), AdminServiceStoreError>;
+
+    /// Updates the proposal and returns the value that was stored before the update, or `None`
+    /// if the proposal did not exist, in which case nothing is updated.
+    fn update_proposal_returning_previous(
+        &self,
+        proposal: &CircuitProposal,
+    ) -> Result<Option<CircuitProposal>, AdminServiceStoreError>;
 }
 
 #[cfg(feature = "postgres")]
 impl<'a> AdminServiceStoreUpdateProposalOperation
     for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
 {
-    fn update_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError> {
+    fn update_proposal(&self, proposal: &CircuitProposal) -> Result<(), AdminServiceStoreError> {
         self.conn.transaction::<(), _, _>(|| {
-            // Verify the `circuit_proposal` entry to be updated exists
-            circuit_proposal::table
-                .filter(circuit_proposal::circuit_id.eq(proposal.circuit_id()))
-                .first::<CircuitProposalModel>(self.conn)
-                .optional()?
-                .ok_or_else(|| {
-                    AdminServiceStoreError::InvalidStateError(InvalidStateError::with_message(
-                        String::from("CircuitProposal does not exist in AdminServiceStore"),
-                    ))
-                })?;
-
             // Update existing `CircuitProposal`
-            let proposal_model = CircuitProposalModel::from(&proposal);
-            update(circuit_proposal::table.find(proposal.circuit_id()))
+            let proposal_model = CircuitProposalModel::from(proposal);
+            let updated = update(circuit_proposal::table.find(proposal.circuit_id()))
                 .set((
                     circuit_proposal::proposal_type.eq(proposal_model.proposal_type),
                     circuit_proposal::circuit_hash.eq(proposal_model.circuit_hash),
                     circuit_proposal::requester.eq(proposal_model.requester),
                     circuit_proposal::requester_node_id.eq(proposal_model.requester_node_id),
+                    circuit_proposal::updated_time.eq(proposal_model.updated_time),
                 ))
                 .execute(self.conn)?;
+
+            if updated == 0 {
+                return Err(AdminServiceStoreError::ConstraintViolationError(
+                    ConstraintViolationError::with_violation_type(
+                        ConstraintViolationType::NotFound,
+                    ),
+                ));
+            }
+
             // Update existing `ProposedCircuit`
             let proposed_circuit_model = ProposedCircuitModel::from(proposal.circuit());
             update(proposed_circuit::table.find(proposal.circuit_id()))
@@ -133,7 +145,7 @@ impl<'a> AdminServiceStoreUpdateProposalOperation
                 .values(proposed_service_argument)
                 .execute(self.conn)?;
             // Insert `votes` from the `CircuitProposal`
-            let vote_record: Vec<VoteRecordModel> = Vec::try_from(&proposal)?;
+            let vote_record: Vec<VoteRecordModel> = Vec::try_from(proposal)?;
             insert_into(vote_record::table)
                 .values(vote_record)
                 .execute(self.conn)?;
@@ -141,35 +153,89 @@ impl<'a> AdminServiceStoreUpdateProposalOperation
             Ok(())
         })
     }
-}
 
-#[cfg(feature = "sqlite")]
-impl<'a> AdminServiceStoreUpdateProposalOperation
-    for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
-{
-    fn update_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError> {
+    fn update_proposal_if_unchanged(
+        &self,
+        proposal: &CircuitProposal,
+        expected_hash: &str,
+    ) -> Result<(), AdminServiceStoreError> {
         self.conn.transaction::<(), _, _>(|| {
-            // Verify the `circuit_proposal` entry to be updated exists
-            circuit_proposal::table
+            // Verify the stored `circuit_hash` has not changed since the proposal was read
+            let stored_hash = circuit_proposal::table
                 .filter(circuit_proposal::circuit_id.eq(proposal.circuit_id()))
-                .first::<CircuitProposalModel>(self.conn)
+                .select(circuit_proposal::circuit_hash)
+                .for_update()
+                .first::<String>(self.conn)
                 .optional()?
                 .ok_or_else(|| {
-                    AdminServiceStoreError::InvalidStateError(InvalidStateError::with_message(
-                        String::from("CircuitProposal does not exist in AdminServiceStore"),
-                    ))
+                    AdminServiceStoreError::ConstraintViolationError(
+                        ConstraintViolationError::with_violation_type(
+                            ConstraintViolationType::NotFound,
+                        ),
+                    )
                 })?;
 
+            if stored_hash != expected_hash {
+                return Err(AdminServiceStoreError::ConstraintViolationError(
+                    ConstraintViolationError::with_violation_type(ConstraintViolationType::Other(
+                        format!(
+                            "CircuitProposal {} has circuit hash {}, expected {}",
+                            proposal.circuit_id(),
+                            stored_hash,
+                            expected_hash
+                        ),
+                    )),
+                ));
+            }
+
+            self.update_proposal(proposal)
+        })
+    }
+
+    fn update_proposal_returning_previous(
+        &self,
+        proposal: &CircuitProposal,
+    ) -> Result<Option<CircuitProposal>, AdminServiceStoreError> {
+        self.conn.transaction::<Option<CircuitProposal>, _, _>(|| {
+            // Read the stored `CircuitProposal` before it is replaced
+            let previous = match self.get_proposal(proposal.circuit_id())? {
+                Some(previous) => previous,
+                None => return Ok(None),
+            };
+
+            self.update_proposal(proposal)?;
+
+            Ok(Some(previous))
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AdminServiceStoreUpdateProposalOperation
+    for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn update_proposal(&self, proposal: &CircuitProposal) -> Result<(), AdminServiceStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
             // Update existing `CircuitProposal`
-            let proposal_model = CircuitProposalModel::from(&proposal);
-            update(circuit_proposal::table.find(proposal.circuit_id()))
+            let proposal_model = CircuitProposalModel::from(proposal);
+            let updated = update(circuit_proposal::table.find(proposal.circuit_id()))
                 .set((
                     circuit_proposal::proposal_type.eq(proposal_model.proposal_type),
                     circuit_proposal::circuit_hash.eq(proposal_model.circuit_hash),
                     circuit_proposal::requester.eq(proposal_model.requester),
                     circuit_proposal::requester_node_id.eq(proposal_model.requester_node_id),
+                    circuit_proposal::updated_time.eq(proposal_model.updated_time),
                 ))
                 .execute(self.conn)?;
+
+            if updated == 0 {
+                return Err(AdminServiceStoreError::ConstraintViolationError(
+                    ConstraintViolationError::with_violation_type(
+                        ConstraintViolationType::NotFound,
+                    ),
+                ));
+            }
+
             // Update existing `ProposedCircuit`
             let proposed_circuit_model = ProposedCircuitModel::from(proposal.circuit());
             update(proposed_circuit::table.find(proposal.circuit_id()))
@@ -235,7 +301,7 @@ impl<'a> AdminServiceStoreUpdateProposalOperation
                 .values(proposed_service_argument)
                 .execute(self.conn)?;
             // Insert `votes` from the `CircuitProposal`
-            let vote_record: Vec<VoteRecordModel> = Vec::try_from(&proposal)?;
+            let vote_record: Vec<VoteRecordModel> = Vec::try_from(proposal)?;
             insert_into(vote_record::table)
                 .values(vote_record)
                 .execute(self.conn)?;
@@ -243,4 +309,58 @@ impl<'a> AdminServiceStoreUpdateProposalOperation
             Ok(())
         })
     }
+
+    fn update_proposal_if_unchanged(
+        &self,
+        proposal: &CircuitProposal,
+        expected_hash: &str,
+    ) -> Result<(), AdminServiceStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            // Verify the stored `circuit_hash` has not changed since the proposal was read
+            let stored_hash = circuit_proposal::table
+                .filter(circuit_proposal::circuit_id.eq(proposal.circuit_id()))
+                .select(circuit_proposal::circuit_hash)
+                .first::<String>(self.conn)
+                .optional()?
+                .ok_or_else(|| {
+                    AdminServiceStoreError::ConstraintViolationError(
+                        ConstraintViolationError::with_violation_type(
+                            ConstraintViolationType::NotFound,
+                        ),
+                    )
+                })?;
+
+            if stored_hash != expected_hash {
+                return Err(AdminServiceStoreError::ConstraintViolationError(
+                    ConstraintViolationError::with_violation_type(ConstraintViolationType::Other(
+                        format!(
+                            "CircuitProposal {} has circuit hash {}, expected {}",
+                            proposal.circuit_id(),
+                            stored_hash,
+                            expected_hash
+                        ),
+                    )),
+                ));
+            }
+
+            self.update_proposal(proposal)
+        })
+    }
+
+    fn update_proposal_returning_previous(
+        &self,
+        proposal: &CircuitProposal,
+    ) -> Result<Option<CircuitProposal>, AdminServiceStoreError> {
+        self.conn.transaction::<Option<CircuitProposal>, _, _>(|| {
+            // Read the stored `CircuitProposal` before it is replaced
+            let previous = match self.get_proposal(proposal.circuit_id())? {
+                Some(previous) => previous,
+                None => return Ok(None),
+            };
+
+            self.update_proposal(proposal)?;
+
+            Ok(Some(previous))
+        })
+    }
 }
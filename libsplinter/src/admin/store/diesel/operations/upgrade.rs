@@ -17,7 +17,7 @@
 use diesel::prelude::*;
 
 use crate::admin::store::{error::AdminServiceStoreError, CircuitBuilder, CircuitNode, Service};
-use crate::error::InvalidStateError;
+use crate::error::{ConstraintViolationError, ConstraintViolationType};
 
 use super::{
     add_circuit::AdminServiceStoreAddCircuitOperation,
@@ -35,15 +35,15 @@ impl<'a> AdminServiceStoreUpgradeProposalToCircuitOperation
 {
     fn upgrade_proposal_to_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
         self.conn.transaction::<(), _, _>(|| {
-            // Attempting to fetch the proposal to be upgraded. If not found, an error is returned.
-            let proposal = match self.get_proposal(circuit_id)? {
-                Some(proposal) => Ok(proposal),
-                None => Err(AdminServiceStoreError::InvalidStateError(
-                    InvalidStateError::with_message(String::from(
-                        "CircuitProposal does not exist in AdminServiceStore",
-                    )),
-                )),
-            }?;
+            // Attempting to fetch the proposal to be upgraded. If not found, an error is returned
+            // before anything is written.
+            let proposal = self.get_proposal(circuit_id)?.ok_or_else(|| {
+                AdminServiceStoreError::ConstraintViolationError(
+                    ConstraintViolationError::with_violation_type(
+                        ConstraintViolationType::NotFound,
+                    ),
+                )
+            })?;
             // Need to construct the `Circuit` from the `ProposedCircuit`
             let proposed_circuit = proposal.circuit();
             let mut builder = CircuitBuilder::new()
@@ -85,7 +85,7 @@ impl<'a> AdminServiceStoreUpgradeProposalToCircuitOperation
                 .collect::<Vec<CircuitNode>>();
 
             self.remove_proposal(proposal.circuit_id())
-                .and_then(|_| self.add_circuit(circuit, circuit_nodes))?;
+                .and_then(|_| self.add_circuit(&circuit, &circuit_nodes))?;
             Ok(())
         })
     }
@@ -97,15 +97,15 @@ impl<'a> AdminServiceStoreUpgradeProposalToCircuitOperation
 {
     fn upgrade_proposal_to_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
         self.conn.transaction::<(), _, _>(|| {
-            // Attempting to fetch the proposal to be upgraded. If not found, an error is returned.
-            let proposal = match self.get_proposal(circuit_id)? {
-                Some(proposal) => Ok(proposal),
-                None => Err(AdminServiceStoreError::InvalidStateError(
-                    InvalidStateError::with_message(String::from(
-                        "CircuitProposal does not exist in AdminServiceStore",
-                    )),
-                )),
-            }?;
+            // Attempting to fetch the proposal to be upgraded. If not found, an error is returned
+            // before anything is written.
+            let proposal = self.get_proposal(circuit_id)?.ok_or_else(|| {
+                AdminServiceStoreError::ConstraintViolationError(
+                    ConstraintViolationError::with_violation_type(
+                        ConstraintViolationType::NotFound,
+                    ),
+                )
+            })?;
             // Need to construct the `Circuit` from the `ProposedCircuit`
             let proposed_circuit = proposal.circuit();
             let mut builder = CircuitBuilder::new()
@@ -147,7 +147,7 @@ impl<'a> AdminServiceStoreUpgradeProposalToCircuitOperation
                 .collect::<Vec<CircuitNode>>();
 
             self.remove_proposal(proposal.circuit_id())
-                .and_then(|_| self.add_circuit(circuit, circuit_nodes))?;
+                .and_then(|_| self.add_circuit(&circuit, &circuit_nodes))?;
             Ok(())
         })
     }
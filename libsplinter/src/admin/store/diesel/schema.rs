@@ -19,6 +19,7 @@ table! {
         circuit_hash -> Text,
         requester -> Binary,
         requester_node_id -> Text,
+        updated_time -> BigInt,
     }
 }
 
@@ -117,6 +118,7 @@ table! {
         display_name -> Nullable<Text>,
         circuit_version -> Integer,
         circuit_status -> SmallInt,
+        updated_time -> BigInt,
     }
 }
 
@@ -159,6 +161,7 @@ table! {
         id -> Int8,
         event_type -> Text,
         data -> Nullable<Binary>,
+        created_time -> BigInt,
     }
 }
 
@@ -15,6 +15,7 @@
 //! Structs for events associated with the admin store
 
 use std::convert::TryFrom;
+use std::time::SystemTime;
 
 use super::CircuitProposal;
 use crate::admin::service::messages;
@@ -29,6 +30,7 @@ pub struct AdminServiceEvent {
     event_id: i64,
     event_type: EventType,
     proposal: CircuitProposal,
+    created_time: SystemTime,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -54,6 +56,11 @@ impl AdminServiceEvent {
     pub fn proposal(&self) -> &CircuitProposal {
         &self.proposal
     }
+
+    /// Returns the time the event was added to the store
+    pub fn created_time(&self) -> &SystemTime {
+        &self.created_time
+    }
 }
 
 /// Builder to be used to build an `AdminServiceEvent`
@@ -62,6 +69,7 @@ pub struct AdminServiceEventBuilder {
     event_id: Option<i64>,
     event_type: Option<EventType>,
     proposal: Option<CircuitProposal>,
+    created_time: Option<SystemTime>,
 }
 
 impl AdminServiceEventBuilder {
@@ -100,9 +108,20 @@ impl AdminServiceEventBuilder {
         self
     }
 
+    /// Sets the time the event was added to the store. If it is not set, the time the event is
+    /// built is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `created_time` - The time the event was created
+    pub fn with_created_time(mut self, created_time: SystemTime) -> AdminServiceEventBuilder {
+        self.created_time = Some(created_time);
+        self
+    }
+
     /// Builds an `AdminServiceEvent`
     ///
-    /// Returns an error if any of the fields are not set.
+    /// Returns an error if any of the fields, other than `created_time`, are not set.
     pub fn build(self) -> Result<AdminServiceEvent, InvalidStateError> {
         let event_id = self.event_id.ok_or_else(|| {
             InvalidStateError::with_message(
@@ -122,21 +141,139 @@ impl AdminServiceEventBuilder {
             )
         })?;
 
+        let created_time = self.created_time.unwrap_or_else(SystemTime::now);
+
         let admin_service_event = AdminServiceEvent {
             event_id,
             event_type,
             proposal,
+            created_time,
         };
 
         Ok(admin_service_event)
     }
 }
 
-impl TryFrom<(i64, &messages::AdminServiceEvent)> for AdminServiceEvent {
+/// The order in which `AdminServiceEvent`s are listed, by event ID
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EventOrder {
+    /// Oldest event first
+    Asc,
+    /// Newest event first
+    Desc,
+}
+
+impl Default for EventOrder {
+    fn default() -> Self {
+        EventOrder::Asc
+    }
+}
+
+/// The kinds of `AdminServiceEvent`, used to filter the events listed from the admin store
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EventTypeFilter {
+    ProposalSubmitted,
+    ProposalVote,
+    ProposalAccepted,
+    ProposalRejected,
+    CircuitReady,
+    CircuitDisbanded,
+}
+
+/// Options used to list `AdminServiceEvent`s from the admin store
+///
+/// By default, all events are listed in ascending order.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct EventQueryOptions {
+    start: i64,
+    limit: Option<i64>,
+    order: EventOrder,
+    management_type: Option<String>,
+    event_type: Option<EventTypeFilter>,
+}
+
+impl EventQueryOptions {
+    /// Creates a new `EventQueryOptions` that lists all events in ascending order
+    pub fn new() -> Self {
+        EventQueryOptions::default()
+    }
+
+    /// Only list events with an ID greater than the given index
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - index used to filter events
+    pub fn with_start(mut self, start: i64) -> EventQueryOptions {
+        self.start = start;
+        self
+    }
+
+    /// Sets the maximum number of events listed
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - the maximum number of events
+    pub fn with_limit(mut self, limit: i64) -> EventQueryOptions {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sets the order in which events are listed
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - the order of the events, by event ID
+    pub fn with_order(mut self, order: EventOrder) -> EventQueryOptions {
+        self.order = order;
+        self
+    }
+
+    /// Only list events whose proposal has the given circuit management type
+    ///
+    /// # Arguments
+    ///
+    /// * `management_type` - management type used to filter `CircuitProposal`s
+    pub fn with_management_type(mut self, management_type: &str) -> EventQueryOptions {
+        self.management_type = Some(management_type.to_string());
+        self
+    }
+
+    /// Only list events of the given kind
+    ///
+    /// # Arguments
+    ///
+    /// * `event_type` - the kind of events to list
+    pub fn with_event_type(mut self, event_type: EventTypeFilter) -> EventQueryOptions {
+        self.event_type = Some(event_type);
+        self
+    }
+
+    pub fn start(&self) -> i64 {
+        self.start
+    }
+
+    pub fn limit(&self) -> Option<i64> {
+        self.limit
+    }
+
+    pub fn order(&self) -> EventOrder {
+        self.order
+    }
+
+    pub fn management_type(&self) -> Option<&str> {
+        self.management_type.as_deref()
+    }
+
+    pub fn event_type(&self) -> Option<EventTypeFilter> {
+        self.event_type
+    }
+}
+
+impl TryFrom<(i64, SystemTime, &messages::AdminServiceEvent)> for AdminServiceEvent {
     type Error = InvalidStateError;
 
     fn try_from(
-        (event_id, event): (i64, &messages::AdminServiceEvent),
+        (event_id, created_time, event): (i64, SystemTime, &messages::AdminServiceEvent),
     ) -> Result<Self, Self::Error> {
         let proposal = CircuitProposal::try_from(event.proposal())?;
         match event {
@@ -144,6 +281,7 @@ impl TryFrom<(i64, &messages::AdminServiceEvent)> for AdminServiceEvent {
                 event_id,
                 event_type: EventType::ProposalSubmitted,
                 proposal,
+                created_time,
             }),
             messages::AdminServiceEvent::ProposalVote((_, data)) => Ok(AdminServiceEvent {
                 event_id,
@@ -151,6 +289,7 @@ impl TryFrom<(i64, &messages::AdminServiceEvent)> for AdminServiceEvent {
                     requester: data.to_vec(),
                 },
                 proposal,
+                created_time,
             }),
             messages::AdminServiceEvent::ProposalAccepted((_, data)) => Ok(AdminServiceEvent {
                 event_id,
@@ -158,6 +297,7 @@ impl TryFrom<(i64, &messages::AdminServiceEvent)> for AdminServiceEvent {
                     requester: data.to_vec(),
                 },
                 proposal,
+                created_time,
             }),
             messages::AdminServiceEvent::ProposalRejected((_, data)) => Ok(AdminServiceEvent {
                 event_id,
@@ -165,16 +305,19 @@ impl TryFrom<(i64, &messages::AdminServiceEvent)> for AdminServiceEvent {
                     requester: data.to_vec(),
                 },
                 proposal,
+                created_time,
             }),
             messages::AdminServiceEvent::CircuitReady(_) => Ok(AdminServiceEvent {
                 event_id,
                 event_type: EventType::CircuitReady,
                 proposal,
+                created_time,
             }),
             messages::AdminServiceEvent::CircuitDisbanded(_) => Ok(AdminServiceEvent {
                 event_id,
                 event_type: EventType::CircuitDisbanded,
                 proposal,
+                created_time,
             }),
         }
     }
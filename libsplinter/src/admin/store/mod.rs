@@ -41,20 +41,26 @@ mod service;
 pub mod yaml;
 
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt;
+use std::sync::mpsc::Receiver;
 
 use crate::admin::service::messages;
 
 pub use self::circuit::{
-    AuthorizationType, Circuit, CircuitBuilder, CircuitStatus, DurabilityType, PersistenceType,
-    RouteType,
+    AuthorizationType, Circuit, CircuitBuilder, CircuitOrder, CircuitOrderDirection,
+    CircuitQueryOptions, CircuitStatus, DurabilityType, PersistenceType, RouteType,
 };
 pub use self::circuit_node::{CircuitNode, CircuitNodeBuilder};
 pub use self::circuit_proposal::{
     CircuitProposal, CircuitProposalBuilder, ProposalType, Vote, VoteRecord, VoteRecordBuilder,
+    VoteSummary,
 };
 use self::error::AdminServiceStoreError;
-pub use self::event::{AdminServiceEvent, AdminServiceEventBuilder, EventType};
+pub use self::event::{
+    AdminServiceEvent, AdminServiceEventBuilder, EventOrder, EventQueryOptions, EventType,
+    EventTypeFilter,
+};
 pub use self::proposed_circuit::{ProposedCircuit, ProposedCircuitBuilder};
 pub use self::proposed_node::{ProposedNode, ProposedNodeBuilder};
 pub use self::proposed_service::{ProposedService, ProposedServiceBuilder};
@@ -126,10 +132,25 @@ impl PartialOrd for ServiceId {
 }
 
 /// Predicate for filtering the lists of circuits and circuit proposals
+#[derive(Clone)]
 pub enum CircuitPredicate {
     ManagementTypeEq(String),
     MembersInclude(Vec<String>),
     CircuitStatus(CircuitStatus),
+    /// Matches circuits whose display name contains the given string, ignoring case
+    DisplayNameContains(String),
+    /// Matches circuits with the given circuit version
+    CircuitVersionEq(i32),
+    /// Matches circuits with a circuit version lower than the given version
+    CircuitVersionLt(i32),
+    /// Matches circuits that have no service of the given service type, including circuits with
+    /// an empty roster
+    ServiceTypeAbsent(String),
+    /// Matches proposals of the given proposal type
+    ///
+    /// Circuits have no proposal type, so this predicate is ignored when listing or counting
+    /// circuits.
+    ProposalTypeEq(ProposalType),
 }
 
 impl CircuitPredicate {
@@ -152,6 +173,16 @@ impl CircuitPredicate {
                 true
             }
             CircuitPredicate::CircuitStatus(status) => circuit.circuit_status() == status,
+            CircuitPredicate::DisplayNameContains(value) => {
+                display_name_contains(circuit.display_name(), value)
+            }
+            CircuitPredicate::CircuitVersionEq(version) => circuit.circuit_version() == *version,
+            CircuitPredicate::CircuitVersionLt(version) => circuit.circuit_version() < *version,
+            CircuitPredicate::ServiceTypeAbsent(service_type) => !circuit
+                .roster()
+                .iter()
+                .any(|service| service.service_type() == service_type),
+            CircuitPredicate::ProposalTypeEq(_) => true,
         }
     }
 
@@ -177,13 +208,101 @@ impl CircuitPredicate {
             CircuitPredicate::CircuitStatus(status) => {
                 proposal.circuit().circuit_status() == status
             }
+            CircuitPredicate::DisplayNameContains(value) => {
+                display_name_contains(proposal.circuit().display_name(), value)
+            }
+            CircuitPredicate::CircuitVersionEq(version) => {
+                proposal.circuit().circuit_version() == *version
+            }
+            CircuitPredicate::CircuitVersionLt(version) => {
+                proposal.circuit().circuit_version() < *version
+            }
+            CircuitPredicate::ServiceTypeAbsent(service_type) => !proposal
+                .circuit()
+                .roster()
+                .iter()
+                .any(|service| service.service_type() == service_type),
+            CircuitPredicate::ProposalTypeEq(proposal_type) => {
+                proposal.proposal_type() == proposal_type
+            }
         }
     }
 }
 
+/// Returns true if the display name is set and contains the given value, ignoring case.
+fn display_name_contains(display_name: &Option<String>, value: &str) -> bool {
+    display_name
+        .as_ref()
+        .map(|display_name| display_name.to_lowercase().contains(&value.to_lowercase()))
+        .unwrap_or(false)
+}
+
 /// Return type of the admin store's `list_events_*` methods.
 pub type EventIter = Box<dyn ExactSizeIterator<Item = AdminServiceEvent> + Send>;
 
+/// Return type of the admin store's `stream_events_since` method.
+///
+/// Unlike an `EventIter`, the events are loaded as the stream is consumed, so a failure to load
+/// them is returned by the stream itself.
+pub type EventStream =
+    Box<dyn Iterator<Item = Result<AdminServiceEvent, AdminServiceStoreError>> + Send>;
+
+/// The number of rows in each of the admin store's tables
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StorageStats {
+    circuits: u64,
+    proposals: u64,
+    nodes: u64,
+    services: u64,
+    events: u64,
+}
+
+impl StorageStats {
+    /// Creates new storage statistics
+    ///
+    /// # Arguments
+    ///
+    /// * `circuits` - The number of circuits, of every status
+    /// * `proposals` - The number of circuit proposals
+    /// * `nodes` - The number of nodes
+    /// * `services` - The number of services, across every circuit
+    /// * `events` - The number of admin service events
+    pub fn new(circuits: u64, proposals: u64, nodes: u64, services: u64, events: u64) -> Self {
+        StorageStats {
+            circuits,
+            proposals,
+            nodes,
+            services,
+            events,
+        }
+    }
+
+    /// Returns the number of circuits, of every status
+    pub fn circuits(&self) -> u64 {
+        self.circuits
+    }
+
+    /// Returns the number of circuit proposals
+    pub fn proposals(&self) -> u64 {
+        self.proposals
+    }
+
+    /// Returns the number of nodes
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// Returns the number of services, across every circuit
+    pub fn services(&self) -> u64 {
+        self.services
+    }
+
+    /// Returns the number of admin service events
+    pub fn events(&self) -> u64 {
+        self.events
+    }
+}
+
 /// Interface for performing CRUD operations on circuits, proposals, nodes, and services
 pub trait AdminServiceStore: Send + Sync {
     /// Adds a circuit proposal to the store
@@ -195,15 +314,79 @@ pub trait AdminServiceStore: Send + Sync {
     ///  Returns an error if a `CircuitProposal` with the same ID already exists
     fn add_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError>;
 
+    /// Adds a circuit proposal to the store along with an event, such as a `ProposalSubmitted`
+    /// event, in a single transaction. Either both are added or neither is. Returns the recorded
+    /// event with its index.
+    ///
+    /// # Arguments
+    ///
+    ///  * `proposal` - The proposal to be added
+    ///  * `event` - The `AdminServiceEvent` to be added to the store
+    ///
+    ///  Returns an error if a `CircuitProposal` with the same ID already exists
+    fn add_proposal_with_event(
+        &self,
+        proposal: CircuitProposal,
+        event: messages::AdminServiceEvent,
+    ) -> Result<AdminServiceEvent, AdminServiceStoreError>;
+
     /// Updates a circuit proposal in the store
     ///
     /// # Arguments
     ///
     ///  * `proposal` - The proposal with the updated information
     ///
-    ///  Returns an error if a `CircuitProposal` with the same ID does not exist
+    ///  Returns a `ConstraintViolationError` with a `NotFound` violation type if a `CircuitProposal` with
+    ///  the same ID does not exist
     fn update_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError>;
 
+    /// Updates a circuit proposal in the store, only if the stored proposal's circuit hash still
+    /// matches the expected hash
+    ///
+    /// # Arguments
+    ///
+    ///  * `proposal` - The proposal with the updated information
+    ///  * `expected_hash` - The circuit hash of the proposal the update was based on
+    ///
+    ///  Returns a `ConstraintViolationError` if the stored circuit hash does not match
+    ///  `expected_hash`, or a `ConstraintViolationError` with a `NotFound` violation type if a
+    ///  `CircuitProposal` with the same ID does not exist
+    fn update_proposal_if_unchanged(
+        &self,
+        proposal: CircuitProposal,
+        expected_hash: &str,
+    ) -> Result<(), AdminServiceStoreError>;
+
+    /// Updates a circuit proposal in the store and returns the proposal as it was stored before
+    /// the update. The read and the update are applied atomically.
+    ///
+    /// # Arguments
+    ///
+    ///  * `proposal` - The proposal with the updated information
+    ///
+    ///  Returns `None`, without updating anything, if a `CircuitProposal` with the same ID does not
+    ///  exist
+    fn update_proposal_returning_previous(
+        &self,
+        proposal: CircuitProposal,
+    ) -> Result<Option<CircuitProposal>, AdminServiceStoreError>;
+
+    /// Appends a vote to a circuit proposal in a single transaction
+    ///
+    /// # Arguments
+    ///
+    ///  * `proposal_id` - The ID of the proposal being voted on
+    ///  * `vote` - The vote to add to the end of the proposal's votes
+    ///
+    ///  Returns the updated `CircuitProposal`. Returns a `ConstraintViolationError` if the voter
+    ///  node has already voted on the proposal, or a `ConstraintViolationError` with a `NotFound`
+    ///  violation type if a `CircuitProposal` with the ID does not exist
+    fn append_vote(
+        &self,
+        proposal_id: &str,
+        vote: VoteRecord,
+    ) -> Result<CircuitProposal, AdminServiceStoreError>;
+
     /// Removes a circuit proposal from the store
     ///
     /// # Arguments
@@ -213,6 +396,19 @@ pub trait AdminServiceStore: Send + Sync {
     ///  Returns an error if a `CircuitProposal` with specified ID does not exist
     fn remove_proposal(&self, proposal_id: &str) -> Result<(), AdminServiceStoreError>;
 
+    /// Removes all circuit proposals with the given circuit management type from the store, in a
+    /// single transaction
+    ///
+    /// # Arguments
+    ///
+    ///  * `management_type` - The circuit management type of the proposals to be removed
+    ///
+    ///  Returns the number of proposals removed
+    fn remove_proposals_by_management_type(
+        &self,
+        management_type: &str,
+    ) -> Result<u64, AdminServiceStoreError>;
+
     /// Fetches a circuit proposal from the store
     ///
     /// # Arguments
@@ -223,6 +419,20 @@ pub trait AdminServiceStore: Send + Sync {
         proposal_id: &str,
     ) -> Result<Option<CircuitProposal>, AdminServiceStoreError>;
 
+    /// Fetches a circuit proposal and the circuit with the same ID from the store
+    ///
+    /// Both are read together, so the result reflects a single consistent view of the store. The
+    /// circuit is present once the proposal has been upgraded. Returns `None` if neither the
+    /// proposal nor the circuit exists.
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - The unique ID of the circuit proposal and circuit to be returned
+    fn get_proposal_with_circuit(
+        &self,
+        id: &str,
+    ) -> Result<Option<(Option<CircuitProposal>, Option<Circuit>)>, AdminServiceStoreError>;
+
     /// List circuit proposals from the store
     ///
     /// The proposals returned can be filtered by provided `CircuitPredicate`. This enables
@@ -232,6 +442,47 @@ pub trait AdminServiceStore: Send + Sync {
         predicates: &[CircuitPredicate],
     ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError>;
 
+    /// List the circuit proposals that are still waiting on a vote from the given node
+    ///
+    /// A proposal is returned if the node is a member of the proposed circuit and there is no
+    /// vote record from the node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The ID of the node whose vote is pending
+    fn list_proposals_pending_vote_from(
+        &self,
+        node_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError>;
+
+    /// List the circuit proposals requested by the given node and the circuits it is a member of
+    ///
+    /// Both lists are read together, so the result reflects a single consistent view of the
+    /// store.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The ID of the node that requested the proposals and belongs to the circuits
+    fn list_for_requester_node(
+        &self,
+        node_id: &str,
+    ) -> Result<(Vec<CircuitProposal>, Vec<Circuit>), AdminServiceStoreError>;
+
+    /// Summarize the votes on a circuit proposal
+    ///
+    /// The summary counts the accept and reject votes, and lists the members that have not yet
+    /// voted. The requester's node is never listed, as the proposal stands as its vote.
+    ///
+    /// Returns `None` if the proposal does not exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `proposal_id` - The unique ID of the circuit proposal
+    fn proposal_vote_summary(
+        &self,
+        proposal_id: &str,
+    ) -> Result<Option<VoteSummary>, AdminServiceStoreError>;
+
     /// Returns the count of proposals in the store
     ///
     /// # Arguments
@@ -243,6 +494,19 @@ pub trait AdminServiceStore: Send + Sync {
         predicates: &[CircuitPredicate],
     ) -> Result<u32, AdminServiceStoreError>;
 
+    /// List the circuit proposals that have been added or updated at or after the given time
+    ///
+    /// # Arguments
+    ///
+    /// * `since` - time, in seconds since the Unix epoch, used to filter proposals
+    fn list_proposals_updated_since(
+        &self,
+        since: i64,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError>;
+
+    /// List the distinct circuit management types of the circuit proposals in the store, sorted
+    fn list_proposal_management_types(&self) -> Result<Vec<String>, AdminServiceStoreError>;
+
     /// Adds a circuit to the store along with the associated services and nodes
     ///
     /// # Arguments
@@ -263,9 +527,23 @@ pub trait AdminServiceStore: Send + Sync {
     ///
     ///  * `circuit` - The circuit with the updated information
     ///
-    ///  Returns an error if a `CircuitProposal` with the same ID does not exist
+    ///  Returns a `ConstraintViolationError` with a `NotFound` violation type if a `Circuit` with
+    ///  the same ID does not exist
     fn update_circuit(&self, circuit: Circuit) -> Result<(), AdminServiceStoreError>;
 
+    /// Updates a circuit in the store and returns the circuit as it was stored before the update.
+    /// The read and the update are applied atomically.
+    ///
+    /// # Arguments
+    ///
+    ///  * `circuit` - The circuit with the updated information
+    ///
+    ///  Returns `None`, without updating anything, if a `Circuit` with the same ID does not exist
+    fn update_circuit_returning_previous(
+        &self,
+        circuit: Circuit,
+    ) -> Result<Option<Circuit>, AdminServiceStoreError>;
+
     /// Removes a circuit from the store
     ///
     /// # Arguments
@@ -275,6 +553,39 @@ pub trait AdminServiceStore: Send + Sync {
     ///  Returns an error if a `Circuit` with the specified ID does not exist
     fn remove_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError>;
 
+    /// Marks a circuit as deleted, by setting its status to `CircuitStatus::Deleted`
+    ///
+    /// Unlike `remove_circuit`, the circuit is kept in the store, so its history is retained.
+    /// Deleted circuits are not listed unless a `CircuitPredicate::CircuitStatus` predicate for
+    /// the `Deleted` status is provided.
+    ///
+    /// # Arguments
+    ///
+    ///  * `circuit_id` - The unique ID of the circuit to be deleted
+    ///
+    ///  Returns a `ConstraintViolationError` with a `NotFound` violation type if a `Circuit` with
+    ///  the specified ID does not exist
+    fn delete_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError>;
+
+    /// Sets the status of a circuit
+    ///
+    /// Services do not have a status of their own, so the circuit's services take on the new
+    /// status along with it; for example, the services of a disbanded circuit are no longer
+    /// counted by `count_services_by_type`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `circuit_id` - The unique ID of the circuit to be updated
+    ///  * `status` - The new status of the circuit
+    ///
+    ///  Returns a `ConstraintViolationError` with a `NotFound` violation type if a `Circuit` with
+    ///  the specified ID does not exist
+    fn set_circuit_status(
+        &self,
+        circuit_id: &str,
+        status: CircuitStatus,
+    ) -> Result<(), AdminServiceStoreError>;
+
     /// Fetches a circuit from the store
     ///
     /// # Arguments
@@ -282,6 +593,18 @@ pub trait AdminServiceStore: Send + Sync {
     ///  * `circuit_id` - The unique ID of the circuit to be returned
     fn get_circuit(&self, circuit_id: &str) -> Result<Option<Circuit>, AdminServiceStoreError>;
 
+    /// Fetches several circuits from the store, keyed by circuit ID
+    ///
+    /// # Arguments
+    ///
+    ///  * `circuit_ids` - The unique IDs of the circuits to be returned
+    ///
+    ///  Circuits that do not exist in the store are not included in the returned map
+    fn get_circuits(
+        &self,
+        circuit_ids: &[&str],
+    ) -> Result<HashMap<String, Circuit>, AdminServiceStoreError>;
+
     /// List all circuits from the store
     ///
     /// `CircuitPredicate`s may be provided for filtering which circuits are returned.
@@ -290,6 +613,18 @@ pub trait AdminServiceStore: Send + Sync {
         predicates: &[CircuitPredicate],
     ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError>;
 
+    /// List a page of circuits from the store, in the order given by the options
+    ///
+    /// # Arguments
+    ///
+    /// * `predicates` - A list of predicates used to filter the circuits, as in `list_circuits`
+    /// * `options` - the offset, limit and order of the circuits to list
+    fn list_circuits_page(
+        &self,
+        predicates: &[CircuitPredicate],
+        options: &CircuitQueryOptions,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError>;
+
     /// Returns the count of circuits in the store
     ///
     /// # Arguments
@@ -301,6 +636,21 @@ pub trait AdminServiceStore: Send + Sync {
         predicates: &[CircuitPredicate],
     ) -> Result<u32, AdminServiceStoreError>;
 
+    /// List the circuits, of any status, that have been added or updated at or after the given
+    /// time
+    ///
+    /// # Arguments
+    ///
+    /// * `since` - time, in seconds since the Unix epoch, used to filter circuits
+    fn list_circuits_updated_since(
+        &self,
+        since: i64,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError>;
+
+    /// List the distinct circuit management types of the circuits in the store, whatever their
+    /// status, sorted
+    fn list_circuit_management_types(&self) -> Result<Vec<String>, AdminServiceStoreError>;
+
     /// Adds a circuit, along with the associated services and nodes, to the store based on the
     /// proposal that is already in state. The associated circuit proposal for the circuit ID is
     /// also removed.
@@ -308,6 +658,9 @@ pub trait AdminServiceStore: Send + Sync {
     /// # Arguments
     ///
     ///  * `circuit_id` - The ID of the circuit proposal that should be converted to a circuit
+    ///
+    ///  Returns a `ConstraintViolationError` with the `NotFound` type, without changing the
+    ///  store, if a `CircuitProposal` with the given ID does not exist
     fn upgrade_proposal_to_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError>;
 
     /// Fetches a node from the store
@@ -322,6 +675,15 @@ pub trait AdminServiceStore: Send + Sync {
         &self,
     ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitNode>>, AdminServiceStoreError>;
 
+    /// List the nodes that are no longer referenced by any circuit or circuit proposal
+    fn list_orphaned_nodes(&self) -> Result<Vec<CircuitNode>, AdminServiceStoreError>;
+
+    /// Removes the nodes that are no longer referenced by any circuit or circuit proposal,
+    /// returning the number of nodes removed
+    ///
+    /// Nodes that are part of a pending circuit proposal are not removed.
+    fn remove_orphaned_nodes(&self) -> Result<u64, AdminServiceStoreError>;
+
     /// Fetches a service from the store
     ///
     /// # Arguments
@@ -332,6 +694,13 @@ pub trait AdminServiceStore: Send + Sync {
         service_id: &ServiceId,
     ) -> Result<Option<Service>, AdminServiceStoreError>;
 
+    /// Checks whether a service exists in the store, without fetching the service
+    ///
+    /// # Arguments
+    ///
+    ///  * `service_id` - The `ServiceId` of a service made up of the circuit ID and service ID
+    fn service_exists(&self, service_id: &ServiceId) -> Result<bool, AdminServiceStoreError>;
+
     /// List all services in a specific circuit from the store
     ///
     /// # Arguments
@@ -342,6 +711,14 @@ pub trait AdminServiceStore: Send + Sync {
         circuit_id: &str,
     ) -> Result<Box<dyn ExactSizeIterator<Item = Service>>, AdminServiceStoreError>;
 
+    /// Count the services of the active circuits in the store, by service type
+    fn count_services_by_type(&self) -> Result<HashMap<String, u64>, AdminServiceStoreError>;
+
+    /// Returns the number of circuits, proposals, nodes, services and events in the store
+    ///
+    /// The counts are read together, so they reflect a single consistent view of the store.
+    fn storage_stats(&self) -> Result<StorageStats, AdminServiceStoreError>;
+
     /// Add an event to the `AdminServiceEventStore`.  Returns the recorded event index and
     /// a copy of the event.
     ///
@@ -353,12 +730,21 @@ pub trait AdminServiceStore: Send + Sync {
         event: messages::AdminServiceEvent,
     ) -> Result<AdminServiceEvent, AdminServiceStoreError>;
 
+    /// List `AdminServiceEvent`s that match the given options, in the order they specify.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - the start index, limit, order and management type of the events to list
+    fn list_events(&self, options: EventQueryOptions) -> Result<EventIter, AdminServiceStoreError>;
+
     /// List `AdminServiceEvent`s that have been added to the store since the provided index.
     ///
     /// # Arguments
     ///
     /// * `start` - index used to filter events
-    fn list_events_since(&self, start: i64) -> Result<EventIter, AdminServiceStoreError>;
+    fn list_events_since(&self, start: i64) -> Result<EventIter, AdminServiceStoreError> {
+        self.list_events(EventQueryOptions::new().with_start(start))
+    }
 
     /// List `AdminServiceEvent`s, with a corresponding `CircuitProposal` that has the specified
     /// `circuit_management_type`, that have been added to the store since the provided index.
@@ -371,7 +757,48 @@ pub trait AdminServiceStore: Send + Sync {
         &self,
         management_type: String,
         start: i64,
-    ) -> Result<EventIter, AdminServiceStoreError>;
+    ) -> Result<EventIter, AdminServiceStoreError> {
+        self.list_events(
+            EventQueryOptions::new()
+                .with_start(start)
+                .with_management_type(&management_type),
+        )
+    }
+
+    /// List `AdminServiceEvent`s of the given kind that have been added to the store since the
+    /// provided index.
+    ///
+    /// # Arguments
+    ///
+    /// * `event_type` - the kind of events to list
+    /// * `start` - index used to filter events
+    fn list_events_by_type_since(
+        &self,
+        event_type: EventTypeFilter,
+        start: i64,
+    ) -> Result<EventIter, AdminServiceStoreError> {
+        self.list_events(
+            EventQueryOptions::new()
+                .with_start(start)
+                .with_event_type(event_type),
+        )
+    }
+
+    /// Stream the `AdminServiceEvent`s that have been added to the store since the provided
+    /// index, in order of their index.
+    ///
+    /// The events are loaded in batches as the stream is consumed, rather than all at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - index used to filter events
+    fn stream_events_since(&self, start: i64) -> EventStream;
+
+    /// Subscribe to events as they are added to the store.
+    ///
+    /// Every event successfully added via `add_event` after this call, including its assigned
+    /// id, is sent on the returned channel. The subscription ends when the receiver is dropped.
+    fn subscribe_to_events(&self) -> Receiver<AdminServiceEvent>;
 
     fn clone_boxed(&self) -> Box<dyn AdminServiceStore>;
 }
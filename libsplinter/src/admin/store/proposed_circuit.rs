@@ -16,8 +16,12 @@
 
 use std::convert::TryFrom;
 
+use openssl::hash::{hash, MessageDigest};
+use protobuf::Message;
+
 use crate::admin::messages::{self, is_valid_circuit_id};
-use crate::error::InvalidStateError;
+use crate::error::{InternalError, InvalidStateError};
+use crate::hex::to_hex;
 use crate::protos::admin;
 
 use super::{
@@ -153,6 +157,7 @@ impl ProposedCircuit {
             admin::Circuit_CircuitStatus::ACTIVE => CircuitStatus::Active,
             admin::Circuit_CircuitStatus::DISBANDED => CircuitStatus::Disbanded,
             admin::Circuit_CircuitStatus::ABANDONED => CircuitStatus::Abandoned,
+            admin::Circuit_CircuitStatus::DELETED => CircuitStatus::Deleted,
             admin::Circuit_CircuitStatus::UNSET_CIRCUIT_STATUS => {
                 debug!("Defaulting `UNSET_CIRCUIT_STATUS` of proposed circuit to `Active`");
                 CircuitStatus::default()
@@ -208,6 +213,22 @@ impl ProposedCircuit {
         })
     }
 
+    /// Computes the hash of the proposed circuit
+    ///
+    /// The hash is the hex-encoded SHA-256 digest of the circuit's protobuf encoding, which is how
+    /// the admin service computes the `circuit_hash` of a proposal. Protobuf fields are encoded in
+    /// field number order, so the same circuit produces the same hash on every node.
+    pub fn compute_hash(&self) -> Result<String, InternalError> {
+        let bytes = self
+            .clone()
+            .into_proto()
+            .write_to_bytes()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        hash(MessageDigest::sha256(), &bytes)
+            .map(|digest| to_hex(&*digest))
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+
     pub fn into_proto(self) -> admin::Circuit {
         let mut circuit = admin::Circuit::new();
 
@@ -283,6 +304,9 @@ impl ProposedCircuit {
                 CircuitStatus::Abandoned => {
                     circuit.set_circuit_status(admin::Circuit_CircuitStatus::ABANDONED);
                 }
+                CircuitStatus::Deleted => {
+                    circuit.set_circuit_status(admin::Circuit_CircuitStatus::DELETED);
+                }
             }
         }
 
@@ -632,3 +656,57 @@ impl TryFrom<&messages::CreateCircuit> for ProposedCircuit {
         circuit_builder.build()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::admin::store::{ProposedNodeBuilder, ProposedServiceBuilder};
+
+    const EXPECTED_HASH: &str = "0c0671a6876c84c6fbe0e942c0a2cd6f1e24f9f893c6de25082bf036809fca21";
+
+    fn create_proposed_circuit_builder() -> ProposedCircuitBuilder {
+        ProposedCircuitBuilder::new()
+            .with_circuit_id("01234-ABCDE")
+            .with_roster(&[ProposedServiceBuilder::new()
+                .with_service_id("a000")
+                .with_service_type("scabbard")
+                .with_node_id("node-a")
+                .build()
+                .expect("Unable to build service")])
+            .with_members(&[ProposedNodeBuilder::new()
+                .with_node_id("node-a")
+                .with_endpoints(&["tcp://localhost:8044".to_string()])
+                .build()
+                .expect("Unable to build node")])
+            .with_circuit_management_type("test")
+            .with_circuit_version(2)
+    }
+
+    /// Verify that the hash of a known circuit matches the expected, fixed value.
+    #[test]
+    fn test_compute_hash() {
+        let circuit = create_proposed_circuit_builder()
+            .build()
+            .expect("Unable to build circuit");
+
+        assert_eq!(
+            circuit.compute_hash().expect("Unable to compute hash"),
+            EXPECTED_HASH
+        );
+    }
+
+    /// Verify that changing a field of the circuit changes its hash.
+    #[test]
+    fn test_compute_hash_changes_with_circuit() {
+        let circuit = create_proposed_circuit_builder()
+            .with_comments("a change")
+            .build()
+            .expect("Unable to build circuit");
+
+        assert_ne!(
+            circuit.compute_hash().expect("Unable to compute hash"),
+            EXPECTED_HASH
+        );
+    }
+}
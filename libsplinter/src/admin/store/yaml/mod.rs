@@ -19,21 +19,22 @@
 //!
 //! [`YamlAdminServiceStore`]: struct.YamlAdminServiceStore.html
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::convert::TryFrom;
 use std::fs::{rename, File};
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::mpsc::Receiver;
 use std::sync::{Arc, Mutex};
 
-use super::{AdminServiceEvent, EventIter};
+use super::{AdminServiceEvent, EventIter, EventQueryOptions, EventStream};
 use super::{
     AdminServiceStore, AdminServiceStoreError, AuthorizationType, Circuit, CircuitBuilder,
-    CircuitNode, CircuitNodeBuilder, CircuitPredicate, CircuitProposal, CircuitProposalBuilder,
-    CircuitStatus, DurabilityType, PersistenceType, ProposalType, ProposedCircuit,
-    ProposedCircuitBuilder, ProposedNode, ProposedNodeBuilder, ProposedService,
-    ProposedServiceBuilder, RouteType, Service, ServiceBuilder, ServiceId, Vote, VoteRecord,
-    VoteRecordBuilder,
+    CircuitNode, CircuitNodeBuilder, CircuitOrder, CircuitOrderDirection, CircuitPredicate,
+    CircuitProposal, CircuitProposalBuilder, CircuitQueryOptions, CircuitStatus, DurabilityType,
+    PersistenceType, ProposalType, ProposedCircuit, ProposedCircuitBuilder, ProposedNode,
+    ProposedNodeBuilder, ProposedService, ProposedServiceBuilder, RouteType, Service,
+    ServiceBuilder, ServiceId, StorageStats, Vote, VoteRecord, VoteRecordBuilder, VoteSummary,
 };
 use crate::admin::messages;
 
@@ -522,13 +523,28 @@ impl AdminServiceStore for YamlAdminServiceStore {
         })
     }
 
+    /// Adds a circuit proposal to the store along with an event in a single transaction.
+    ///
+    /// # Arguments
+    ///
+    ///  * `proposal` - The proposal to be added
+    ///  * `event` - The `AdminServiceEvent` to be added to the store
+    fn add_proposal_with_event(
+        &self,
+        _proposal: CircuitProposal,
+        _event: messages::AdminServiceEvent,
+    ) -> Result<AdminServiceEvent, AdminServiceStoreError> {
+        unimplemented!()
+    }
+
     /// Updates a circuit proposal in the underlying storage
     ///
     /// # Arguments
     ///
     ///  * `proposal` - The proposal with the updated information
     ///
-    ///  Returns an error if a `CircuitProposal` with the same ID does not exist
+    ///  Returns a `ConstraintViolationError` with a `NotFound` violation type if a `CircuitProposal` with
+    ///  the same ID does not exist
     fn update_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError> {
         {
             let mut state = self.state.lock().map_err(|_| {
@@ -547,13 +563,75 @@ impl AdminServiceStore for YamlAdminServiceStore {
                     .proposals
                     .insert(proposal.circuit_id().to_string(), proposal);
             } else {
-                return Err(AdminServiceStoreError::InvalidStateError(
-                    InvalidStateError::with_message(format!(
-                        "A proposal with ID {} does not exist",
-                        proposal.circuit_id()
+                return Err(AdminServiceStoreError::ConstraintViolationError(
+                    ConstraintViolationError::with_violation_type(
+                        ConstraintViolationType::NotFound,
+                    ),
+                ));
+            }
+        }
+
+        self.write_proposal_state().map_err(|err| {
+            AdminServiceStoreError::InternalError(InternalError::from_source_with_prefix(
+                Box::new(err),
+                "Unable to write proposal state yaml file".to_string(),
+            ))
+        })
+    }
+
+    /// Updates a circuit proposal in the underlying storage, only if the stored proposal's
+    /// circuit hash still matches the expected hash
+    ///
+    /// # Arguments
+    ///
+    ///  * `proposal` - The proposal with the updated information
+    ///  * `expected_hash` - The circuit hash of the proposal the update was based on
+    ///
+    ///  Returns a `ConstraintViolationError` if the stored circuit hash does not match
+    ///  `expected_hash`, or a `ConstraintViolationError` with a `NotFound` violation type if a
+    ///  `CircuitProposal` with the same ID does not exist
+    fn update_proposal_if_unchanged(
+        &self,
+        proposal: CircuitProposal,
+        expected_hash: &str,
+    ) -> Result<(), AdminServiceStoreError> {
+        {
+            let mut state = self.state.lock().map_err(|_| {
+                AdminServiceStoreError::InternalError(InternalError::with_message(
+                    "YAML admin service store's internal lock was poisoned".to_string(),
+                ))
+            })?;
+
+            let stored_hash = state
+                .proposal_state
+                .proposals
+                .get(proposal.circuit_id())
+                .map(|stored| stored.circuit_hash().to_string())
+                .ok_or_else(|| {
+                    AdminServiceStoreError::ConstraintViolationError(
+                        ConstraintViolationError::with_violation_type(
+                            ConstraintViolationType::NotFound,
+                        ),
+                    )
+                })?;
+
+            if stored_hash != expected_hash {
+                return Err(AdminServiceStoreError::ConstraintViolationError(
+                    ConstraintViolationError::with_violation_type(ConstraintViolationType::Other(
+                        format!(
+                            "Proposal {} has circuit hash {}, expected {}",
+                            proposal.circuit_id(),
+                            stored_hash,
+                            expected_hash
+                        ),
                     )),
                 ));
             }
+
+            state
+                .proposal_state
+                .proposals
+                .insert(proposal.circuit_id().to_string(), proposal);
         }
 
         self.write_proposal_state().map_err(|err| {
@@ -564,6 +642,125 @@ impl AdminServiceStore for YamlAdminServiceStore {
         })
     }
 
+    /// Updates a circuit proposal in the underlying storage and returns the proposal as it was
+    /// stored before the update
+    ///
+    /// # Arguments
+    ///
+    ///  * `proposal` - The proposal with the updated information
+    ///
+    ///  Returns `None`, without updating anything, if a `CircuitProposal` with the same ID does not
+    ///  exist
+    fn update_proposal_returning_previous(
+        &self,
+        proposal: CircuitProposal,
+    ) -> Result<Option<CircuitProposal>, AdminServiceStoreError> {
+        let previous = {
+            let mut state = self.state.lock().map_err(|_| {
+                AdminServiceStoreError::InternalError(InternalError::with_message(
+                    "YAML admin service store's internal lock was poisoned".to_string(),
+                ))
+            })?;
+
+            if !state
+                .proposal_state
+                .proposals
+                .contains_key(proposal.circuit_id())
+            {
+                return Ok(None);
+            }
+
+            state
+                .proposal_state
+                .proposals
+                .insert(proposal.circuit_id().to_string(), proposal)
+        };
+
+        self.write_proposal_state().map_err(|err| {
+            AdminServiceStoreError::InternalError(InternalError::from_source_with_prefix(
+                Box::new(err),
+                "Unable to write proposal state yaml file".to_string(),
+            ))
+        })?;
+
+        Ok(previous)
+    }
+
+    /// Appends a vote to a circuit proposal
+    ///
+    /// # Arguments
+    ///
+    ///  * `proposal_id` - The ID of the proposal being voted on
+    ///  * `vote` - The vote to add to the end of the proposal's votes
+    ///
+    ///  Returns the updated `CircuitProposal`. Returns a `ConstraintViolationError` if the voter
+    ///  node has already voted on the proposal, or a `ConstraintViolationError` with a `NotFound`
+    ///  violation type if a `CircuitProposal` with the ID does not exist
+    fn append_vote(
+        &self,
+        proposal_id: &str,
+        vote: VoteRecord,
+    ) -> Result<CircuitProposal, AdminServiceStoreError> {
+        let proposal = {
+            let mut state = self.state.lock().map_err(|_| {
+                AdminServiceStoreError::InternalError(InternalError::with_message(
+                    "YAML admin service store's internal lock was poisoned".to_string(),
+                ))
+            })?;
+
+            let stored = state
+                .proposal_state
+                .proposals
+                .get(proposal_id)
+                .ok_or_else(|| {
+                    AdminServiceStoreError::ConstraintViolationError(
+                        ConstraintViolationError::with_violation_type(
+                            ConstraintViolationType::NotFound,
+                        ),
+                    )
+                })?;
+
+            if stored
+                .votes()
+                .iter()
+                .any(|existing| existing.voter_node_id() == vote.voter_node_id())
+            {
+                return Err(AdminServiceStoreError::ConstraintViolationError(
+                    ConstraintViolationError::with_violation_type(ConstraintViolationType::Other(
+                        format!(
+                            "Node {} has already voted on proposal {}",
+                            vote.voter_node_id(),
+                            proposal_id
+                        ),
+                    )),
+                ));
+            }
+
+            let mut votes = stored.votes().to_vec();
+            votes.push(vote);
+            let proposal = stored
+                .builder()
+                .with_votes(&votes)
+                .build()
+                .map_err(AdminServiceStoreError::InvalidStateError)?;
+
+            state
+                .proposal_state
+                .proposals
+                .insert(proposal_id.to_string(), proposal.clone());
+            proposal
+        };
+
+        self.write_proposal_state().map_err(|err| {
+            AdminServiceStoreError::InternalError(InternalError::from_source_with_prefix(
+                Box::new(err),
+                "Unable to write proposal state yaml file".to_string(),
+            ))
+        })?;
+
+        Ok(proposal)
+    }
+
     /// Removes a circuit proposal from the underlying storage
     ///
     /// # Arguments
@@ -599,6 +796,45 @@ impl AdminServiceStore for YamlAdminServiceStore {
         })
     }
 
+    /// Removes all circuit proposals with the given circuit management type from the underlying
+    /// storage
+    ///
+    /// # Arguments
+    ///
+    ///  * `management_type` - The circuit management type of the proposals to be removed
+    ///
+    ///  Returns the number of proposals removed
+    fn remove_proposals_by_management_type(
+        &self,
+        management_type: &str,
+    ) -> Result<u64, AdminServiceStoreError> {
+        let removed = {
+            let mut state = self.state.lock().map_err(|_| {
+                AdminServiceStoreError::InternalError(InternalError::with_message(
+                    "YAML admin service store's internal lock was poisoned".to_string(),
+                ))
+            })?;
+
+            let proposals = &mut state.proposal_state.proposals;
+            let count = proposals.len();
+            proposals.retain(|_, proposal| {
+                proposal.circuit().circuit_management_type() != management_type
+            });
+            count - proposals.len()
+        };
+
+        if removed > 0 {
+            self.write_proposal_state().map_err(|err| {
+                AdminServiceStoreError::InternalError(InternalError::from_source_with_prefix(
+                    Box::new(err),
+                    "Unable to write proposal state yaml file".to_string(),
+                ))
+            })?;
+        }
+
+        Ok(removed as u64)
+    }
+
     /// Fetches a circuit proposal from the underlying storage
     ///
     /// # Arguments
@@ -622,6 +858,30 @@ impl AdminServiceStore for YamlAdminServiceStore {
             .cloned())
     }
 
+    /// Fetches a circuit proposal and the circuit with the same ID from the underlying storage
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - The unique ID of the circuit proposal and circuit to be returned
+    fn get_proposal_with_circuit(
+        &self,
+        id: &str,
+    ) -> Result<Option<(Option<CircuitProposal>, Option<Circuit>)>, AdminServiceStoreError> {
+        let state = self.state.lock().map_err(|_| {
+            AdminServiceStoreError::InternalError(InternalError::with_message(
+                "YAML admin service store's internal lock was poisoned".to_string(),
+            ))
+        })?;
+
+        match (
+            state.proposal_state.proposals.get(id).cloned(),
+            state.circuit_state.circuits.get(id).cloned(),
+        ) {
+            (None, None) => Ok(None),
+            (proposal, circuit) => Ok(Some((proposal, circuit))),
+        }
+    }
+
     /// List circuit proposals from the underlying storage
     ///
     /// The proposals returned can be filtered by provided CircuitPredicate. This enables
@@ -653,6 +913,112 @@ impl AdminServiceStore for YamlAdminServiceStore {
         Ok(Box::new(proposals.into_iter()))
     }
 
+    /// List the circuit proposals that are still waiting on a vote from the given node
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The ID of the node whose vote is pending
+    fn list_proposals_pending_vote_from(
+        &self,
+        node_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError> {
+        let proposals: Vec<CircuitProposal> = self
+            .state
+            .lock()
+            .map_err(|_| {
+                AdminServiceStoreError::InternalError(InternalError::with_message(
+                    "YAML admin service store's internal lock was poisoned".to_string(),
+                ))
+            })?
+            .proposal_state
+            .proposals
+            .values()
+            .filter(|proposal| {
+                proposal
+                    .circuit()
+                    .members()
+                    .iter()
+                    .any(|member| member.node_id() == node_id)
+                    && !proposal
+                        .votes()
+                        .iter()
+                        .any(|vote| vote.voter_node_id() == node_id)
+            })
+            .cloned()
+            .collect();
+
+        Ok(Box::new(proposals.into_iter()))
+    }
+
+    /// List the circuit proposals requested by the given node and the circuits it is a member of
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The ID of the node that requested the proposals and belongs to the circuits
+    fn list_for_requester_node(
+        &self,
+        node_id: &str,
+    ) -> Result<(Vec<CircuitProposal>, Vec<Circuit>), AdminServiceStoreError> {
+        let state = self.state.lock().map_err(|_| {
+            AdminServiceStoreError::InternalError(InternalError::with_message(
+                "YAML admin service store's internal lock was poisoned".to_string(),
+            ))
+        })?;
+
+        let proposals = state
+            .proposal_state
+            .proposals
+            .values()
+            .filter(|proposal| proposal.requester_node_id() == node_id)
+            .cloned()
+            .collect();
+
+        let circuits = state
+            .circuit_state
+            .circuits
+            .values()
+            .filter(|circuit| {
+                circuit
+                    .members()
+                    .iter()
+                    .any(|member| member.node_id() == node_id)
+            })
+            .cloned()
+            .collect();
+
+        Ok((proposals, circuits))
+    }
+
+    fn proposal_vote_summary(
+        &self,
+        proposal_id: &str,
+    ) -> Result<Option<VoteSummary>, AdminServiceStoreError> {
+        Ok(self.get_proposal(proposal_id)?.map(|proposal| {
+            let votes = proposal.votes();
+            let accept_count = votes
+                .iter()
+                .filter(|vote| vote.vote() == &Vote::Accept)
+                .count() as u64;
+            let outstanding_member_ids = proposal
+                .circuit()
+                .members()
+                .iter()
+                .map(|member| member.node_id())
+                .filter(|node_id| {
+                    *node_id != proposal.requester_node_id()
+                        && !votes.iter().any(|vote| vote.voter_node_id() == *node_id)
+                })
+                .map(String::from)
+                .collect();
+
+            VoteSummary::new(
+                accept_count,
+                votes.len() as u64 - accept_count,
+                outstanding_member_ids,
+            )
+        }))
+    }
+
     /// Returns the count of proposals in the store
     ///
     /// # Arguments
@@ -688,6 +1054,40 @@ impl AdminServiceStore for YamlAdminServiceStore {
         })
     }
 
+    /// The YAML store does not record when proposals are updated, so this operation is not
+    /// supported and always returns an `InternalError`.
+    fn list_proposals_updated_since(
+        &self,
+        _since: i64,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError> {
+        Err(AdminServiceStoreError::InternalError(
+            InternalError::with_message(
+                "The YAML admin service store does not record when proposals are updated"
+                    .to_string(),
+            ),
+        ))
+    }
+
+    /// List the distinct circuit management types of the proposals in the underlying storage,
+    /// sorted
+    fn list_proposal_management_types(&self) -> Result<Vec<String>, AdminServiceStoreError> {
+        let management_types: BTreeSet<String> = self
+            .state
+            .lock()
+            .map_err(|_| {
+                AdminServiceStoreError::InternalError(InternalError::with_message(
+                    "YAML admin service store's internal lock was poisoned".to_string(),
+                ))
+            })?
+            .proposal_state
+            .proposals
+            .values()
+            .map(|proposal| proposal.circuit().circuit_management_type().to_string())
+            .collect();
+
+        Ok(management_types.into_iter().collect())
+    }
+
     /// Adds a circuit to the underlying storage. Also includes the associated Services and
     /// Nodes
     ///
@@ -757,7 +1157,8 @@ impl AdminServiceStore for YamlAdminServiceStore {
     ///
     ///  * `circuit` - The circuit with the updated information
     ///
-    ///  Returns an error if a `CircuitProposal` with the same ID does not exist
+    ///  Returns a `ConstraintViolationError` with a `NotFound` violation type if a `Circuit` with
+    ///  the same ID does not exist
     fn update_circuit(&self, circuit: Circuit) -> Result<(), AdminServiceStoreError> {
         {
             let mut state = self.state.lock().map_err(|_| {
@@ -776,11 +1177,10 @@ impl AdminServiceStore for YamlAdminServiceStore {
                     .circuits
                     .insert(circuit.circuit_id().to_string(), circuit);
             } else {
-                return Err(AdminServiceStoreError::InvalidStateError(
-                    InvalidStateError::with_message(format!(
-                        "A circuit with ID {} does not exist",
-                        circuit.circuit_id()
-                    )),
+                return Err(AdminServiceStoreError::ConstraintViolationError(
+                    ConstraintViolationError::with_violation_type(
+                        ConstraintViolationType::NotFound,
+                    ),
                 ));
             }
         }
@@ -793,6 +1193,49 @@ impl AdminServiceStore for YamlAdminServiceStore {
         })
     }
 
+    /// Updates a circuit in the underlying storage and returns the circuit as it was stored before
+    /// the update
+    ///
+    /// # Arguments
+    ///
+    ///  * `circuit` - The circuit with the updated information
+    ///
+    ///  Returns `None`, without updating anything, if a `Circuit` with the same ID does not exist
+    fn update_circuit_returning_previous(
+        &self,
+        circuit: Circuit,
+    ) -> Result<Option<Circuit>, AdminServiceStoreError> {
+        let previous = {
+            let mut state = self.state.lock().map_err(|_| {
+                AdminServiceStoreError::InternalError(InternalError::with_message(
+                    "YAML admin service store's internal lock was poisoned".to_string(),
+                ))
+            })?;
+
+            if !state
+                .circuit_state
+                .circuits
+                .contains_key(circuit.circuit_id())
+            {
+                return Ok(None);
+            }
+
+            state
+                .circuit_state
+                .circuits
+                .insert(circuit.circuit_id().to_string(), circuit)
+        };
+
+        self.write_circuit_state().map_err(|err| {
+            AdminServiceStoreError::InternalError(InternalError::from_source_with_prefix(
+                Box::new(err),
+                "Unable to write circuit state yaml file".to_string(),
+            ))
+        })?;
+
+        Ok(previous)
+    }
+
     /// Removes a circuit from the underlying storage
     ///
     /// # Arguments
@@ -836,6 +1279,136 @@ impl AdminServiceStore for YamlAdminServiceStore {
         })
     }
 
+    /// Marks a circuit as deleted in the underlying storage, keeping the circuit with a status
+    /// of `CircuitStatus::Deleted`
+    ///
+    /// # Arguments
+    ///
+    ///  * `circuit_id` - The unique ID of the circuit to be deleted
+    ///
+    ///  Returns a `ConstraintViolationError` with a `NotFound` violation type if a `Circuit` with
+    ///  the specified ID does not exist
+    fn delete_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
+        {
+            let mut state = self.state.lock().map_err(|_| {
+                AdminServiceStoreError::InternalError(InternalError::with_message(
+                    "YAML admin service store's internal lock was poisoned".to_string(),
+                ))
+            })?;
+
+            let circuit = state
+                .circuit_state
+                .circuits
+                .get(circuit_id)
+                .ok_or_else(|| {
+                    AdminServiceStoreError::ConstraintViolationError(
+                        ConstraintViolationError::with_violation_type(
+                            ConstraintViolationType::NotFound,
+                        ),
+                    )
+                })?;
+
+            let mut builder = CircuitBuilder::new()
+                .with_circuit_id(circuit.circuit_id())
+                .with_roster(circuit.roster())
+                .with_members(circuit.members())
+                .with_authorization_type(circuit.authorization_type())
+                .with_persistence(circuit.persistence())
+                .with_durability(circuit.durability())
+                .with_routes(circuit.routes())
+                .with_circuit_management_type(circuit.circuit_management_type())
+                .with_circuit_version(circuit.circuit_version())
+                .with_circuit_status(&CircuitStatus::Deleted);
+
+            if let Some(display_name) = circuit.display_name() {
+                builder = builder.with_display_name(display_name);
+            }
+
+            let deleted_circuit = builder
+                .build()
+                .map_err(AdminServiceStoreError::InvalidStateError)?;
+
+            state
+                .circuit_state
+                .circuits
+                .insert(circuit_id.to_string(), deleted_circuit);
+        }
+
+        self.write_circuit_state().map_err(|err| {
+            AdminServiceStoreError::InternalError(InternalError::from_source_with_prefix(
+                Box::new(err),
+                "Unable to write circuit state yaml file".to_string(),
+            ))
+        })
+    }
+
+    /// Sets the status of a circuit in the underlying storage
+    ///
+    /// # Arguments
+    ///
+    ///  * `circuit_id` - The unique ID of the circuit to be updated
+    ///  * `status` - The new status of the circuit
+    ///
+    ///  Returns a `ConstraintViolationError` with a `NotFound` violation type if a `Circuit` with
+    ///  the specified ID does not exist
+    fn set_circuit_status(
+        &self,
+        circuit_id: &str,
+        status: CircuitStatus,
+    ) -> Result<(), AdminServiceStoreError> {
+        {
+            let mut state = self.state.lock().map_err(|_| {
+                AdminServiceStoreError::InternalError(InternalError::with_message(
+                    "YAML admin service store's internal lock was poisoned".to_string(),
+                ))
+            })?;
+
+            let circuit = state
+                .circuit_state
+                .circuits
+                .get(circuit_id)
+                .ok_or_else(|| {
+                    AdminServiceStoreError::ConstraintViolationError(
+                        ConstraintViolationError::with_violation_type(
+                            ConstraintViolationType::NotFound,
+                        ),
+                    )
+                })?;
+
+            let mut builder = CircuitBuilder::new()
+                .with_circuit_id(circuit.circuit_id())
+                .with_roster(circuit.roster())
+                .with_members(circuit.members())
+                .with_authorization_type(circuit.authorization_type())
+                .with_persistence(circuit.persistence())
+                .with_durability(circuit.durability())
+                .with_routes(circuit.routes())
+                .with_circuit_management_type(circuit.circuit_management_type())
+                .with_circuit_version(circuit.circuit_version())
+                .with_circuit_status(&status);
+
+            if let Some(display_name) = circuit.display_name() {
+                builder = builder.with_display_name(display_name);
+            }
+
+            let updated_circuit = builder
+                .build()
+                .map_err(AdminServiceStoreError::InvalidStateError)?;
+
+            state
+                .circuit_state
+                .circuits
+                .insert(circuit_id.to_string(), updated_circuit);
+        }
+
+        self.write_circuit_state().map_err(|err| {
+            AdminServiceStoreError::InternalError(InternalError::from_source_with_prefix(
+                Box::new(err),
+                "Unable to write circuit state yaml file".to_string(),
+            ))
+        })
+    }
+
     /// Fetches a circuit from the underlying storage
     ///
     /// # Arguments
@@ -856,6 +1429,23 @@ impl AdminServiceStore for YamlAdminServiceStore {
             .cloned())
     }
 
+    fn get_circuits(
+        &self,
+        circuit_ids: &[&str],
+    ) -> Result<HashMap<String, Circuit>, AdminServiceStoreError> {
+        let state = self.state.lock().map_err(|_| {
+            AdminServiceStoreError::InternalError(InternalError::with_message(
+                "YAML admin service store's internal lock was poisoned".to_string(),
+            ))
+        })?;
+
+        Ok(circuit_ids
+            .iter()
+            .filter_map(|circuit_id| state.circuit_state.circuits.get(*circuit_id))
+            .map(|circuit| (circuit.circuit_id().to_string(), circuit.clone()))
+            .collect())
+    }
+
     /// List all circuits from the underlying storage
     ///
     /// The proposals returned can be filtered by provided CircuitPredicate. This enables
@@ -878,13 +1468,51 @@ impl AdminServiceStore for YamlAdminServiceStore {
             .map(|(_, circuit)| circuit.clone())
             .collect();
 
-        circuits.retain(|circuit| {
-            predicates
-                .iter()
-                .all(|predicate| predicate.apply_to_circuit(circuit))
+        circuits.retain(|circuit| circuit_matches_predicates(circuit, predicates));
+
+        Ok(Box::new(circuits.into_iter()))
+    }
+
+    /// List a page of circuits from the underlying storage
+    ///
+    /// The circuits are filtered as in `list_circuits`, then sorted and sliced in memory.
+    fn list_circuits_page(
+        &self,
+        predicates: &[CircuitPredicate],
+        options: &CircuitQueryOptions,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        let mut circuits: Vec<Circuit> = self.list_circuits(predicates)?.collect();
+
+        circuits.sort_by(|a, b| {
+            let ordering = match options.order() {
+                CircuitOrder::Id => a.circuit_id().cmp(b.circuit_id()),
+                CircuitOrder::ManagementType => {
+                    a.circuit_management_type().cmp(b.circuit_management_type())
+                }
+                CircuitOrder::CircuitVersion => a.circuit_version().cmp(&b.circuit_version()),
+            };
+            let ordering = match options.direction() {
+                CircuitOrderDirection::Asc => ordering,
+                CircuitOrderDirection::Desc => ordering.reverse(),
+            };
+            // Circuits that are equal in the chosen field are ordered by ascending circuit ID
+            ordering.then_with(|| a.circuit_id().cmp(b.circuit_id()))
         });
 
-        Ok(Box::new(circuits.into_iter()))
+        let offset = usize::try_from(options.offset()).unwrap_or(0);
+        let limit = options
+            .limit()
+            .map(|limit| usize::try_from(limit).unwrap_or(0))
+            .unwrap_or(usize::MAX);
+
+        Ok(Box::new(
+            circuits
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .collect::<Vec<_>>()
+                .into_iter(),
+        ))
     }
 
     /// Returns the count of circuits in the store
@@ -908,11 +1536,7 @@ impl AdminServiceStore for YamlAdminServiceStore {
             .circuit_state
             .circuits
             .iter()
-            .filter(|(_, circuit)| {
-                predicates
-                    .iter()
-                    .all(|predicate| predicate.apply_to_circuit(circuit))
-            })
+            .filter(|(_, circuit)| circuit_matches_predicates(circuit, predicates))
             .count();
 
         u32::try_from(count).map_err(|_| {
@@ -922,6 +1546,40 @@ impl AdminServiceStore for YamlAdminServiceStore {
         })
     }
 
+    /// The YAML store does not record when circuits are updated, so this operation is not
+    /// supported and always returns an `InternalError`.
+    fn list_circuits_updated_since(
+        &self,
+        _since: i64,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        Err(AdminServiceStoreError::InternalError(
+            InternalError::with_message(
+                "The YAML admin service store does not record when circuits are updated"
+                    .to_string(),
+            ),
+        ))
+    }
+
+    /// List the distinct circuit management types of the circuits in the underlying storage,
+    /// sorted
+    fn list_circuit_management_types(&self) -> Result<Vec<String>, AdminServiceStoreError> {
+        let management_types: BTreeSet<String> = self
+            .state
+            .lock()
+            .map_err(|_| {
+                AdminServiceStoreError::InternalError(InternalError::with_message(
+                    "YAML admin service store's internal lock was poisoned".to_string(),
+                ))
+            })?
+            .circuit_state
+            .circuits
+            .values()
+            .map(|circuit| circuit.circuit_management_type().to_string())
+            .collect();
+
+        Ok(management_types.into_iter().collect())
+    }
+
     /// Adds a circuit to the underlying storage based on the proposal that is already in state..
     /// Also includes the associated Services and Nodes. The associated circuit proposal for
     /// the circuit ID is also removed
@@ -965,11 +1623,10 @@ impl AdminServiceStore for YamlAdminServiceStore {
                     }
                 }
             } else {
-                return Err(AdminServiceStoreError::InvalidStateError(
-                    InvalidStateError::with_message(format!(
-                        "A circuit proposal with ID {} does not exist",
-                        circuit_id
-                    )),
+                return Err(AdminServiceStoreError::ConstraintViolationError(
+                    ConstraintViolationError::with_violation_type(
+                        ConstraintViolationType::NotFound,
+                    ),
                 ));
             }
         }
@@ -1025,6 +1682,62 @@ impl AdminServiceStore for YamlAdminServiceStore {
         Ok(nodes)
     }
 
+    /// List the nodes that are no longer referenced by any circuit or circuit proposal
+    fn list_orphaned_nodes(&self) -> Result<Vec<CircuitNode>, AdminServiceStoreError> {
+        let state = self.state.lock().map_err(|_| {
+            AdminServiceStoreError::InternalError(InternalError::with_message(
+                "YAML admin service store's internal lock was poisoned".to_string(),
+            ))
+        })?;
+
+        let referenced_node_ids = referenced_node_ids(&state);
+
+        Ok(state
+            .circuit_state
+            .nodes
+            .values()
+            .filter(|node| !referenced_node_ids.contains(node.node_id()))
+            .cloned()
+            .collect())
+    }
+
+    /// Removes the nodes that are no longer referenced by any circuit or circuit proposal,
+    /// returning the number of nodes removed
+    fn remove_orphaned_nodes(&self) -> Result<u64, AdminServiceStoreError> {
+        let removed = {
+            let mut state = self.state.lock().map_err(|_| {
+                AdminServiceStoreError::InternalError(InternalError::with_message(
+                    "YAML admin service store's internal lock was poisoned".to_string(),
+                ))
+            })?;
+
+            let referenced_node_ids = referenced_node_ids(&state);
+
+            let orphaned_node_ids = state
+                .circuit_state
+                .nodes
+                .keys()
+                .filter(|node_id| !referenced_node_ids.contains(*node_id))
+                .cloned()
+                .collect::<Vec<_>>();
+            for node_id in &orphaned_node_ids {
+                state.circuit_state.nodes.remove(node_id);
+            }
+            orphaned_node_ids.len()
+        };
+
+        if removed > 0 {
+            self.write_circuit_state().map_err(|err| {
+                AdminServiceStoreError::InternalError(InternalError::from_source_with_prefix(
+                    Box::new(err),
+                    "Unable to write circuit state yaml file".to_string(),
+                ))
+            })?;
+        }
+
+        Ok(removed as u64)
+    }
+
     /// Fetches a service from the underlying storage
     ///
     /// # Arguments
@@ -1047,6 +1760,24 @@ impl AdminServiceStore for YamlAdminServiceStore {
             .cloned())
     }
 
+    /// Checks whether a service exists in the underlying storage
+    ///
+    /// # Arguments
+    ///
+    ///  * `service_id` - The `ServiceId` of a service made up of the circuit ID and service ID
+    fn service_exists(&self, service_id: &ServiceId) -> Result<bool, AdminServiceStoreError> {
+        Ok(self
+            .state
+            .lock()
+            .map_err(|_| {
+                AdminServiceStoreError::InternalError(InternalError::with_message(
+                    "YAML admin service store's internal lock was poisoned".to_string(),
+                ))
+            })?
+            .service_directory
+            .contains_key(service_id))
+    }
+
     /// List all services in a specific circuit from the underlying storage
     ///
     /// # Arguments
@@ -1078,6 +1809,58 @@ impl AdminServiceStore for YamlAdminServiceStore {
         Ok(Box::new(services.into_iter()))
     }
 
+    /// Count the services of the active circuits in the underlying storage, by service type
+    fn count_services_by_type(&self) -> Result<HashMap<String, u64>, AdminServiceStoreError> {
+        let mut counts = HashMap::new();
+        for circuit in self
+            .state
+            .lock()
+            .map_err(|_| {
+                AdminServiceStoreError::InternalError(InternalError::with_message(
+                    "YAML admin service store's internal lock was poisoned".to_string(),
+                ))
+            })?
+            .circuit_state
+            .circuits
+            .values()
+            .filter(|circuit| circuit.circuit_status() == &CircuitStatus::Active)
+        {
+            for service in circuit.roster() {
+                *counts
+                    .entry(service.service_type().to_string())
+                    .or_insert(0) += 1;
+            }
+        }
+
+        Ok(counts)
+    }
+
+    /// Returns the number of circuits, proposals, nodes and services in the underlying storage
+    ///
+    /// The YAML store does not store events, so the number of events is always zero.
+    fn storage_stats(&self) -> Result<StorageStats, AdminServiceStoreError> {
+        let state = self.state.lock().map_err(|_| {
+            AdminServiceStoreError::InternalError(InternalError::with_message(
+                "YAML admin service store's internal lock was poisoned".to_string(),
+            ))
+        })?;
+
+        let services = state
+            .circuit_state
+            .circuits
+            .values()
+            .map(|circuit| circuit.roster().len() as u64)
+            .sum();
+
+        Ok(StorageStats::new(
+            state.circuit_state.circuits.len() as u64,
+            state.proposal_state.proposals.len() as u64,
+            state.circuit_state.nodes.len() as u64,
+            services,
+            0,
+        ))
+    }
+
     /// Add an event to the `AdminServiceEventStore`.  Returns the recorded event index and
     /// a copy of the event.
     ///
@@ -1091,27 +1874,29 @@ impl AdminServiceStore for YamlAdminServiceStore {
         unimplemented!()
     }
 
-    /// List `AdminServiceEvent`s that have been added to the store since the provided index.
+    /// List `AdminServiceEvent`s that match the given options.
     ///
     /// # Arguments
     ///
-    /// * `start` - index used to filter events
-    fn list_events_since(&self, _start: i64) -> Result<EventIter, AdminServiceStoreError> {
+    /// * `options` - the start index, limit, order and management type of the events to list
+    fn list_events(
+        &self,
+        _options: EventQueryOptions,
+    ) -> Result<EventIter, AdminServiceStoreError> {
         unimplemented!()
     }
 
-    /// List `AdminServiceEvent`s, with a corresponding `CircuitProposal` that has the specified
-    /// `circuit_management_type`, that have been added to the store since the provided index.
+    /// Stream `AdminServiceEvent`s that have been added to the store since the provided index.
     ///
     /// # Arguments
     ///
-    /// * `management_type` - management type used to filter `CircuitProposal`s
     /// * `start` - index used to filter events
-    fn list_events_by_management_type_since(
-        &self,
-        _management_type: String,
-        _start: i64,
-    ) -> Result<EventIter, AdminServiceStoreError> {
+    fn stream_events_since(&self, _start: i64) -> EventStream {
+        unimplemented!()
+    }
+
+    /// Subscribe to events as they are added to the store.
+    fn subscribe_to_events(&self) -> Receiver<AdminServiceEvent> {
         unimplemented!()
     }
 
@@ -1813,10 +2598,51 @@ struct YamlState {
     service_directory: BTreeMap<ServiceId, Service>,
 }
 
+/// Returns the IDs of the nodes that are members of a circuit or of a proposed circuit.
+fn referenced_node_ids(state: &YamlState) -> BTreeSet<String> {
+    let circuit_members = state
+        .circuit_state
+        .circuits
+        .values()
+        .flat_map(|circuit| circuit.members().iter().map(|node| node.node_id()));
+    let proposed_members = state
+        .proposal_state
+        .proposals
+        .values()
+        .flat_map(|proposal| {
+            proposal
+                .circuit()
+                .members()
+                .iter()
+                .map(|node| node.node_id())
+        });
+
+    circuit_members
+        .chain(proposed_members)
+        .map(String::from)
+        .collect()
+}
+
 fn default_circuit_value() -> i32 {
     1
 }
 
+/// Returns true if the circuit matches all of the predicates. Deleted circuits only match if a
+/// `CircuitPredicate::CircuitStatus` predicate is given.
+fn circuit_matches_predicates(circuit: &Circuit, predicates: &[CircuitPredicate]) -> bool {
+    if circuit.circuit_status() == &CircuitStatus::Deleted
+        && !predicates
+            .iter()
+            .any(|predicate| matches!(predicate, CircuitPredicate::CircuitStatus(_)))
+    {
+        return false;
+    }
+
+    predicates
+        .iter()
+        .all(|predicate| predicate.apply_to_circuit(circuit))
+}
+
 fn default_circuit_status() -> YamlCircuitStatus {
     YamlCircuitStatus::Active
 }
@@ -1827,6 +2653,7 @@ pub enum YamlCircuitStatus {
     Active,
     Disbanded,
     Abandoned,
+    Deleted,
 }
 
 impl From<CircuitStatus> for YamlCircuitStatus {
@@ -1835,6 +2662,7 @@ impl From<CircuitStatus> for YamlCircuitStatus {
             CircuitStatus::Active => YamlCircuitStatus::Active,
             CircuitStatus::Disbanded => YamlCircuitStatus::Disbanded,
             CircuitStatus::Abandoned => YamlCircuitStatus::Abandoned,
+            CircuitStatus::Deleted => YamlCircuitStatus::Deleted,
         }
     }
 }
@@ -1845,6 +2673,7 @@ impl From<YamlCircuitStatus> for CircuitStatus {
             YamlCircuitStatus::Active => CircuitStatus::Active,
             YamlCircuitStatus::Disbanded => CircuitStatus::Disbanded,
             YamlCircuitStatus::Abandoned => CircuitStatus::Abandoned,
+            YamlCircuitStatus::Deleted => CircuitStatus::Deleted,
         }
     }
 }
@@ -1853,7 +2682,7 @@ impl From<YamlCircuitStatus> for CircuitStatus {
 mod tests {
     use std::io::Read;
 
-    use tempfile::Builder;
+    use tempfile::{Builder, TempDir};
 
     use super::*;
 
@@ -2533,6 +3362,206 @@ proposals:
         assert!(store.get_service(&service_id).unwrap().is_some());
     }
 
+    // Validate that the YAML admin service store lists pages of circuits in the requested order
+    //
+    // 1. Creates a temp directory with an existing circuit yaml file
+    // 2. Create a YAML admin service store and add two more circuits with other versions
+    // 3. Validate circuits are listed by descending version, with the offset and limit applied
+    // 4. Validate circuits are listed by ascending ID by default
+    #[test]
+    fn test_list_circuits_page() {
+        let temp_dir = Builder::new()
+            .prefix("test_list_circuits_page")
+            .tempdir()
+            .expect("Failed to create temp dir");
+        let circuit_path = temp_dir
+            .path()
+            .join("circuits.yaml")
+            .to_str()
+            .expect("Failed to get path")
+            .to_string();
+
+        let proposals_path = temp_dir
+            .path()
+            .join("circuit_proposals.yaml")
+            .to_str()
+            .expect("Failed to get path")
+            .to_string();
+
+        write_file(CIRCUIT_STATE, &circuit_path);
+
+        let store = YamlAdminServiceStore::new(circuit_path, proposals_path)
+            .expect("Unable to create yaml admin store");
+
+        for (circuit_id, version) in &[("WBKLF-CCCCC", 2), ("WBKLF-BBBBB", 3)] {
+            let expected_circuit = create_expected_circuit();
+            let circuit = CircuitBuilder::default()
+                .with_circuit_id(circuit_id)
+                .with_roster(expected_circuit.roster())
+                .with_members(expected_circuit.members())
+                .with_circuit_management_type("gameroom")
+                .with_circuit_version(*version)
+                .build()
+                .expect("Unable to build circuit");
+            store
+                .add_circuit(circuit, vec![])
+                .expect("Unable to add circuit");
+        }
+
+        let ids = |options: CircuitQueryOptions| -> Vec<String> {
+            store
+                .list_circuits_page(&[], &options)
+                .expect("Unable to list circuits")
+                .map(|circuit| circuit.circuit_id().to_string())
+                .collect()
+        };
+
+        assert_eq!(
+            ids(CircuitQueryOptions::new()
+                .with_order(CircuitOrder::CircuitVersion, CircuitOrderDirection::Desc)
+                .with_offset(1)
+                .with_limit(1)),
+            vec!["WBKLF-CCCCC"]
+        );
+        assert_eq!(
+            ids(CircuitQueryOptions::new()),
+            vec!["WBKLF-AAAAA", "WBKLF-BBBBB", "WBKLF-CCCCC"]
+        );
+    }
+
+    // Validate that the YAML admin service store only lists a deleted circuit when its status is
+    // requested
+    //
+    // 1. Create a YAML admin service store with two active circuits, and delete one of them
+    // 2. Validate only the remaining circuit is listed by default
+    // 3. Validate only the deleted circuit is listed when the `Deleted` status is requested
+    #[test]
+    fn test_list_circuits_after_delete() {
+        let temp_dir = Builder::new()
+            .prefix("test_list_circuits_after_delete")
+            .tempdir()
+            .expect("Failed to create temp dir");
+        let store = create_store_with_deleted_circuit(&temp_dir);
+
+        let ids = |predicates: &[CircuitPredicate]| -> Vec<String> {
+            store
+                .list_circuits(predicates)
+                .expect("Unable to list circuits")
+                .map(|circuit| circuit.circuit_id().to_string())
+                .collect()
+        };
+
+        assert_eq!(ids(&[]), vec!["WBKLF-BBBBB"]);
+        assert_eq!(
+            ids(&[CircuitPredicate::CircuitStatus(CircuitStatus::Deleted)]),
+            vec!["WBKLF-AAAAA"]
+        );
+    }
+
+    // Validate that the YAML admin service store only counts a deleted circuit when its status is
+    // requested
+    //
+    // 1. Create a YAML admin service store with two active circuits, and delete one of them
+    // 2. Validate only the remaining circuit is counted by default
+    // 3. Validate only the deleted circuit is counted when the `Deleted` status is requested
+    #[test]
+    fn test_count_circuits_after_delete() {
+        let temp_dir = Builder::new()
+            .prefix("test_count_circuits_after_delete")
+            .tempdir()
+            .expect("Failed to create temp dir");
+        let store = create_store_with_deleted_circuit(&temp_dir);
+
+        assert_eq!(
+            store.count_circuits(&[]).expect("Unable to count circuits"),
+            1
+        );
+        assert_eq!(
+            store
+                .count_circuits(&[CircuitPredicate::CircuitStatus(CircuitStatus::Deleted)])
+                .expect("Unable to count circuits"),
+            1
+        );
+    }
+
+    // Validate that the YAML admin service store counts the entries in its state
+    //
+    // 1. Creates a temp directory with existing circuit and proposals yaml files
+    // 2. Create a YAML admin service store
+    // 3. Validate the storage stats count the circuit, proposal, nodes and services in the files,
+    //    and no events
+    #[test]
+    fn test_storage_stats() {
+        let temp_dir = Builder::new()
+            .prefix("test_storage_stats")
+            .tempdir()
+            .expect("Failed to create temp dir");
+        let circuit_path = temp_dir
+            .path()
+            .join("circuits.yaml")
+            .to_str()
+            .expect("Failed to get path")
+            .to_string();
+
+        let proposals_path = temp_dir
+            .path()
+            .join("circuit_proposals.yaml")
+            .to_str()
+            .expect("Failed to get path")
+            .to_string();
+
+        write_file(CIRCUIT_STATE, &circuit_path);
+        write_file(PROPOSAL_STATE, &proposals_path);
+
+        let store = YamlAdminServiceStore::new(circuit_path, proposals_path)
+            .expect("Unable to create yaml admin store");
+
+        let stats = store.storage_stats().expect("Unable to get storage stats");
+
+        assert_eq!(stats, StorageStats::new(1, 1, 2, 2, 0));
+    }
+
+    // Creates a YAML admin service store, in the given directory, with the active circuit
+    // WBKLF-BBBBB and the deleted circuit WBKLF-AAAAA
+    fn create_store_with_deleted_circuit(temp_dir: &TempDir) -> YamlAdminServiceStore {
+        let circuit_path = temp_dir
+            .path()
+            .join("circuits.yaml")
+            .to_str()
+            .expect("Failed to get path")
+            .to_string();
+
+        let proposals_path = temp_dir
+            .path()
+            .join("circuit_proposals.yaml")
+            .to_str()
+            .expect("Failed to get path")
+            .to_string();
+
+        write_file(CIRCUIT_STATE, &circuit_path);
+
+        let store = YamlAdminServiceStore::new(circuit_path, proposals_path)
+            .expect("Unable to create yaml admin store");
+
+        let expected_circuit = create_expected_circuit();
+        let circuit = CircuitBuilder::default()
+            .with_circuit_id("WBKLF-BBBBB")
+            .with_roster(expected_circuit.roster())
+            .with_members(expected_circuit.members())
+            .with_circuit_management_type("gameroom")
+            .build()
+            .expect("Unable to build circuit");
+        store
+            .add_circuit(circuit, vec![])
+            .expect("Unable to add circuit");
+
+        store
+            .delete_circuit("WBKLF-AAAAA")
+            .expect("Unable to delete circuit");
+
+        store
+    }
+
     fn write_file(data: &[u8], file_path: &str) {
         let mut file = File::create(file_path).expect("Error creating test yaml file.");
         file.write_all(data)
@@ -20,4 +20,4 @@ mod ws;
 
 pub use error::{ParseError, ReactorError, WebSocketError};
 pub use reactor::{Igniter, Reactor, ReactorShutdownSignaler};
-pub use ws::{ParseBytes, WebSocketClient, WsResponse};
+pub use ws::{ParseBytes, WebSocketClient, WsResponse, WsState};
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicUsize, Ordering},
     Arc,
 };
 use std::thread;
@@ -35,6 +35,7 @@ pub struct Reactor {
     sender: Sender<ReactorMessage>,
     thread_handle: thread::JoinHandle<()>,
     running: Arc<AtomicBool>,
+    active_connections: Arc<AtomicUsize>,
 }
 
 impl Reactor {
@@ -42,6 +43,8 @@ impl Reactor {
         let (sender, receiver) = bounded::<ReactorMessage>(10);
         let running = Arc::new(AtomicBool::new(true));
         let reactor_running = running.clone();
+        let active_connections = Arc::new(AtomicUsize::new(0));
+        let reactor_active_connections = active_connections.clone();
 
         let thread_builder = thread::Builder::new().name("EventReactor".into());
         let thread_handle = thread_builder
@@ -61,6 +64,7 @@ impl Reactor {
                             let (future, handle) = listen.into_shutdown_handle();
                             runtime.spawn(futures::lazy(|| future.map_err(|_| ())));
                             connections.push(handle);
+                            reactor_active_connections.store(connections.len(), Ordering::SeqCst);
                         }
                         Ok(ReactorMessage::HttpRequest(req)) => {
                             runtime.spawn(req);
@@ -68,6 +72,7 @@ impl Reactor {
                         Ok(ReactorMessage::Stop) => {
                             debug!("Shutting down event reactor");
                             reactor_running.store(false, Ordering::SeqCst);
+                            reactor_active_connections.store(0, Ordering::SeqCst);
 
                             break connections
                                 .into_iter()
@@ -75,9 +80,9 @@ impl Reactor {
                                 .filter_map(|res| if let Err(err) = res { Some(err) } else { None })
                                 .collect::<Vec<WebSocketError>>();
                         }
-                        Err(RecvTimeoutError::Timeout) => {
-                            continue;
-                        }
+                        // Fall through on a timeout, so that connections which have closed
+                        // are still removed when the reactor is otherwise idle
+                        Err(RecvTimeoutError::Timeout) => (),
                         Err(RecvTimeoutError::Disconnected) => {
                             debug!(
                                 "Event reactor sender disconnected; terminating web socket loop..."
@@ -99,6 +104,7 @@ impl Reactor {
                         }
                     }
                     connections = live_connections;
+                    reactor_active_connections.store(connections.len(), Ordering::SeqCst);
                 };
 
                 if let Err(err) = runtime
@@ -126,6 +132,7 @@ impl Reactor {
             sender,
             thread_handle,
             running,
+            active_connections,
         }
     }
 
@@ -133,9 +140,20 @@ impl Reactor {
         Igniter {
             sender: self.sender.clone(),
             reactor_running: self.running.clone(),
+            active_connections: self.active_connections.clone(),
         }
     }
 
+    /// Returns the number of websocket connections that have been started on the reactor and
+    /// have not yet closed.
+    ///
+    /// Closed connections are removed by the reactor's background thread, which checks them at
+    /// least every 500 milliseconds, so the count may briefly include a connection that has just
+    /// closed.
+    pub fn active_connection_count(&self) -> usize {
+        self.active_connections.load(Ordering::SeqCst)
+    }
+
     /// Return a ReactorShutdownSignaler, used to send a shutdown signal to the reactor's
     /// background thread.
     pub fn shutdown_signaler(&self) -> ReactorShutdownSignaler {
@@ -186,6 +204,7 @@ impl ReactorShutdownSignaler {
 pub struct Igniter {
     sender: Sender<ReactorMessage>,
     reactor_running: Arc<AtomicBool>,
+    active_connections: Arc<AtomicUsize>,
 }
 
 impl Igniter {
@@ -221,6 +240,13 @@ impl Igniter {
     pub fn is_reactor_running(&self) -> bool {
         self.reactor_running.load(Ordering::SeqCst)
     }
+
+    /// Returns the number of websocket connections that are active on the reactor.
+    ///
+    /// See `Reactor::active_connection_count`.
+    pub fn active_connection_count(&self) -> usize {
+        self.active_connections.load(Ordering::SeqCst)
+    }
 }
 
 enum ReactorMessage {
@@ -228,3 +254,92 @@ enum ReactorMessage {
     StartWs(Listen),
     HttpRequest(Box<dyn Future<Item = (), Error = ()> + Send + 'static>),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::time::Instant;
+
+    use crate::events::ws::tests::upgrade;
+    use crate::events::WsResponse;
+
+    /// Test that the reactor tracks the number of websocket connections that are active.
+    ///
+    /// 1. Start a server that accepts two websocket connections and holds them open
+    /// 2. Start two clients and verify that the active connection count reaches 2
+    /// 3. Have the server close the first connection and verify that the count drops to 1
+    #[test]
+    fn test_active_connection_count() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Unable to bind listener");
+        let port = listener
+            .local_addr()
+            .expect("Unable to get local address")
+            .port();
+
+        let (close_tx, close_rx) = mpsc::channel::<()>();
+        let server = thread::spawn(move || {
+            let mut streams = (0..2)
+                .map(|_| {
+                    let (stream, _) = listener.accept().expect("Unable to accept connection");
+                    upgrade(stream)
+                })
+                .collect::<Vec<_>>();
+
+            close_rx.recv().expect("Unable to receive close signal");
+            // An unmasked close frame with no payload
+            streams[0]
+                .write_all(&[0x88, 0x00])
+                .expect("Unable to write close frame");
+
+            // Hold the remaining connection open until the test is done
+            let _ = close_rx.recv();
+        });
+
+        let reactor = Reactor::new();
+        assert_eq!(reactor.active_connection_count(), 0);
+
+        let ws = WebSocketClient::new(
+            &format!("http://127.0.0.1:{}", port),
+            "Bearer token",
+            |_, _: Vec<u8>| WsResponse::Empty,
+        );
+        let igniter = reactor.igniter();
+        igniter.start_ws(&ws).expect("Unable to start websocket");
+        igniter.start_ws(&ws).expect("Unable to start websocket");
+
+        wait_for_count(&reactor, 2);
+        assert_eq!(igniter.active_connection_count(), 2);
+
+        close_tx.send(()).expect("Unable to send close signal");
+        wait_for_count(&reactor, 1);
+        assert_eq!(igniter.active_connection_count(), 1);
+
+        drop(close_tx);
+        server.join().expect("Server thread panicked");
+
+        reactor
+            .shutdown_signaler()
+            .signal_shutdown()
+            .expect("Unable to signal shutdown");
+        reactor
+            .wait_for_shutdown()
+            .expect("Unable to shutdown reactor");
+    }
+
+    /// Waits up to 30 seconds for the reactor to report the expected number of connections.
+    fn wait_for_count(reactor: &Reactor, expected: usize) {
+        let start = Instant::now();
+        while reactor.active_connection_count() != expected {
+            assert!(
+                start.elapsed() < Duration::from_secs(30),
+                "Active connection count did not reach {}",
+                expected
+            );
+            thread::sleep(Duration::from_millis(100));
+        }
+    }
+}
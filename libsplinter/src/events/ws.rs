@@ -67,7 +67,17 @@ use futures::{
     sync::mpsc::{channel, Sender},
     Future,
 };
+#[cfg(feature = "events-tls")]
+use hyper::client::HttpConnector;
 use hyper::{self, header, upgrade::Upgraded, Body, Client, Request, StatusCode};
+#[cfg(feature = "events-tls")]
+use hyper_openssl::HttpsConnector;
+#[cfg(feature = "events-tls")]
+use openssl::{
+    error::ErrorStack,
+    ssl::{SslConnector, SslConnectorBuilder, SslMethod},
+    x509::{store::X509StoreBuilder, X509},
+};
 use tokio::codec::{Decoder, Framed};
 use tokio::prelude::*;
 
@@ -75,11 +85,16 @@ use crate::events::{Igniter, ParseError, WebSocketError};
 
 type OnErrorHandle<T> =
     dyn Fn(WebSocketError, Context<T>) -> Result<(), WebSocketError> + Send + Sync + 'static;
+type OnStateChangeHandle = dyn Fn(WsState) + Send + Sync + 'static;
+#[cfg(feature = "events-tls")]
+type TlsConfigHandle = dyn Fn(&mut SslConnectorBuilder) -> Result<(), ErrorStack> + Send + Sync;
 
 const MAX_FRAME_SIZE: usize = 10_000_000;
 const DEFAULT_RECONNECT: bool = false;
 const DEFAULT_RECONNECT_LIMIT: u64 = 10;
 const DEFAULT_TIMEOUT: u64 = 300; // default timeout if no message is received from server in seconds
+#[cfg(feature = "events-tls")]
+const DNS_WORKER_THREADS: usize = 4;
 
 /// Wrapper around future created by `WebSocketClient`. In order for
 /// the future to run it must be passed to `Igniter::start_ws`
@@ -140,10 +155,16 @@ pub struct WebSocketClient<T: ParseBytes<T> + 'static = Vec<u8>> {
     on_open: Option<Arc<dyn Fn(Context<T>) -> WsResponse + Send + Sync + 'static>>,
     on_error: Option<Arc<OnErrorHandle<T>>>,
     on_reconnect: Option<Arc<dyn Fn(&mut WebSocketClient<T>) + Send + Sync + 'static>>,
+    on_state_change: Option<Arc<OnStateChangeHandle>>,
     reconnect: bool,
     reconnect_limit: u64,
+    reconnect_timeout: Option<Duration>,
     timeout: u64,
     additional_headers: HashMap<String, String>,
+    #[cfg(feature = "events-tls")]
+    tls_roots: Option<Vec<X509>>,
+    #[cfg(feature = "events-tls")]
+    tls_config: Option<Arc<TlsConfigHandle>>,
 }
 
 impl<T: ParseBytes<T> + 'static> Clone for WebSocketClient<T> {
@@ -155,10 +176,16 @@ impl<T: ParseBytes<T> + 'static> Clone for WebSocketClient<T> {
             on_open: self.on_open.clone(),
             on_error: self.on_error.clone(),
             on_reconnect: self.on_reconnect.clone(),
+            on_state_change: self.on_state_change.clone(),
             reconnect: self.reconnect,
             reconnect_limit: self.reconnect_limit,
+            reconnect_timeout: self.reconnect_timeout,
             timeout: self.timeout,
             additional_headers: self.additional_headers.clone(),
+            #[cfg(feature = "events-tls")]
+            tls_roots: self.tls_roots.clone(),
+            #[cfg(feature = "events-tls")]
+            tls_config: self.tls_config.clone(),
         }
     }
 }
@@ -175,10 +202,16 @@ impl<T: ParseBytes<T> + 'static> WebSocketClient<T> {
             on_open: None,
             on_error: None,
             on_reconnect: None,
+            on_state_change: None,
             reconnect: DEFAULT_RECONNECT,
             reconnect_limit: DEFAULT_RECONNECT_LIMIT,
+            reconnect_timeout: None,
             timeout: DEFAULT_TIMEOUT,
             additional_headers: HashMap::new(),
+            #[cfg(feature = "events-tls")]
+            tls_roots: None,
+            #[cfg(feature = "events-tls")]
+            tls_config: None,
         }
     }
 
@@ -198,6 +231,13 @@ impl<T: ParseBytes<T> + 'static> WebSocketClient<T> {
         self.reconnect_limit = reconnect_limit
     }
 
+    /// Sets the maximum amount of time the client will spend reconnecting after the connection is
+    /// lost, regardless of the number of attempts made. Once this time has passed, reconnection
+    /// stops and the `on_error` closure is called with a `ReconnectError`.
+    pub fn set_reconnect_timeout(&mut self, reconnect_timeout: Duration) {
+        self.reconnect_timeout = Some(reconnect_timeout)
+    }
+
     pub fn set_timeout(&mut self, timeout: u64) {
         self.timeout = timeout
     }
@@ -214,6 +254,28 @@ impl<T: ParseBytes<T> + 'static> WebSocketClient<T> {
         self.additional_headers.insert(header.into(), value);
     }
 
+    pub fn headers(&self) -> &HashMap<String, String> {
+        &self.additional_headers
+    }
+
+    /// Sets the root certificates used to verify the server's certificate when connecting over
+    /// TLS. These roots are trusted in place of the system trust store, which is used by default.
+    #[cfg(feature = "events-tls")]
+    pub fn set_tls_roots(&mut self, roots: Vec<X509>) {
+        self.tls_roots = Some(roots);
+    }
+
+    /// Sets the optional closure used to customize the TLS configuration before connecting to the
+    /// server. The closure is called with the connector builder after any roots set with
+    /// `set_tls_roots` have been applied.
+    #[cfg(feature = "events-tls")]
+    pub fn set_tls_config<F>(&mut self, tls_config: F)
+    where
+        F: Fn(&mut SslConnectorBuilder) -> Result<(), ErrorStack> + Send + Sync + 'static,
+    {
+        self.tls_config = Some(Arc::new(tls_config));
+    }
+
     pub fn reconnect(&self) -> bool {
         self.reconnect
     }
@@ -222,6 +284,10 @@ impl<T: ParseBytes<T> + 'static> WebSocketClient<T> {
         self.reconnect_limit
     }
 
+    pub fn reconnect_timeout(&self) -> Option<Duration> {
+        self.reconnect_timeout
+    }
+
     pub fn timeout(&self) -> u64 {
         self.timeout
     }
@@ -257,6 +323,43 @@ impl<T: ParseBytes<T> + 'static> WebSocketClient<T> {
         self.on_reconnect = Some(Arc::new(on_reconnect));
     }
 
+    /// Adds optional `on_state_change` closure. This closure is called each time the websocket
+    /// moves to a new `WsState`, so that the state of the connection can be reported without
+    /// inferring it from the other callbacks.
+    pub fn on_state_change<F>(&mut self, on_state_change: F)
+    where
+        F: Fn(WsState) + Send + Sync + 'static,
+    {
+        self.on_state_change = Some(Arc::new(on_state_change));
+    }
+
+    /// Builds the connector used to connect to the server over either plain HTTP or TLS.
+    #[cfg(feature = "events-tls")]
+    fn https_connector(&self) -> Result<HttpsConnector<HttpConnector>, ErrorStack> {
+        let mut http = HttpConnector::new(DNS_WORKER_THREADS);
+        http.enforce_http(false);
+
+        let mut ssl = SslConnector::builder(SslMethod::tls())?;
+        if let Some(roots) = &self.tls_roots {
+            let mut store = X509StoreBuilder::new()?;
+            for root in roots {
+                store.add_cert(root.clone())?;
+            }
+            ssl.set_cert_store(store.build());
+        }
+        if let Some(tls_config) = &self.tls_config {
+            tls_config(&mut ssl)?;
+        }
+
+        HttpsConnector::with_connector(http, ssl)
+    }
+
+    fn notify_state_change(&self, state: WsState) {
+        if let Some(on_state_change) = &self.on_state_change {
+            on_state_change(state);
+        }
+    }
+
     /// Returns `Listen` for WebSocket.
     pub fn listen(&self, mut context: Context<T>) -> Result<Listen, WebSocketError> {
         let url = self.url.clone();
@@ -287,6 +390,11 @@ impl<T: ParseBytes<T> + 'static> WebSocketClient<T> {
 
         debug!("starting: {}", url);
 
+        // Reconnection attempts have already reported the `Reconnecting` state
+        if context.reconnect_count == 0 {
+            self.notify_state_change(WsState::Connecting);
+        }
+
         let mut builder = Request::builder();
         let mut request_builder = builder
             .uri(url)
@@ -304,8 +412,15 @@ impl<T: ParseBytes<T> + 'static> WebSocketClient<T> {
             .body(Body::empty())
             .map_err(|err| WebSocketError::RequestBuilderError(format!("{:?}", err)))?;
 
+        #[cfg(feature = "events-tls")]
+        let client = Client::builder().build::<_, Body>(self.https_connector().map_err(|err| {
+            WebSocketError::ConnectError(format!("Unable to configure TLS: {}", err))
+        })?);
+        #[cfg(not(feature = "events-tls"))]
+        let client = Client::new();
+
         let future = Box::new(
-            Client::new()
+            client
                 .request(request)
                 .and_then(move |res| {
                     if res.status() != StatusCode::SWITCHING_PROTOCOLS {
@@ -448,6 +563,7 @@ impl<T: ParseBytes<T> + 'static> WebSocketClient<T> {
                                     }
                                     WebSocketClientCmd::Stop => {
                                         closed = true;
+                                        context.ws.notify_state_change(WsState::Closed);
                                         let result = do_shutdown(
                                             &mut blocking_sink,
                                             CloseCode::Normal,
@@ -555,6 +671,7 @@ pub struct Context<T: ParseBytes<T> + 'static> {
     ws: WebSocketClient<T>,
     reconnect_count: u64,
     last_reconnect: SystemTime,
+    disconnected_since: Option<SystemTime>,
     wait: Duration,
 }
 
@@ -565,6 +682,7 @@ impl<T: ParseBytes<T> + 'static> Context<T> {
             ws,
             reconnect_count: 0,
             last_reconnect: SystemTime::now(),
+            disconnected_since: None,
             wait: Duration::from_secs(1),
         }
     }
@@ -585,15 +703,32 @@ impl<T: ParseBytes<T> + 'static> Context<T> {
     pub fn ws_connected(&mut self) {
         self.reset_wait();
         self.reset_reconnect_count();
+        self.disconnected_since = None;
+        self.ws.notify_state_change(WsState::Connected);
     }
 
     /// Checks that ws client can reconnect. If it can it attempts to reconnect if it cannot it
     /// calls the on_error function provided by the user and exits.
     pub fn try_reconnect(&mut self) -> Result<(), WebSocketError> {
-        // Check that the ws is configure for automatic reconnect attempts and that the number
-        // of reconnect attempts hasn't exceeded the maximum configure
+        // Check whether the time spent reconnecting has exceeded the maximum configured
+        let disconnected_since = *self.disconnected_since.get_or_insert_with(SystemTime::now);
+        let reconnect_timed_out = self
+            .ws
+            .reconnect_timeout
+            .map_or(false, |reconnect_timeout| {
+                SystemTime::now()
+                    .duration_since(disconnected_since)
+                    .unwrap_or_else(|_| Duration::from_secs(0))
+                    >= reconnect_timeout
+            });
 
-        if self.ws.reconnect && self.reconnect_count < self.ws.reconnect_limit {
+        // Check that the ws is configure for automatic reconnect attempts and that the number
+        // of reconnect attempts and the time spent reconnecting haven't exceeded the maximums
+        // configured
+        if self.ws.reconnect
+            && self.reconnect_count < self.ws.reconnect_limit
+            && !reconnect_timed_out
+        {
             let on_reconnect = self
                 .ws
                 .on_reconnect
@@ -604,7 +739,12 @@ impl<T: ParseBytes<T> + 'static> Context<T> {
 
             self.reconnect()
         } else {
-            let error_message = if self.ws.reconnect {
+            let error_message = if self.ws.reconnect && reconnect_timed_out {
+                WebSocketError::ReconnectError(
+                    "Cannot connect to ws server. Reached maximum time allowed for reconnection"
+                        .to_string(),
+                )
+            } else if self.ws.reconnect {
                 WebSocketError::ReconnectError(
                     "Cannot connect to ws server. Reached maximum limit of reconnection attempts"
                         .to_string(),
@@ -620,11 +760,15 @@ impl<T: ParseBytes<T> + 'static> Context<T> {
 
             self.reset_wait();
             self.reset_reconnect_count();
+            self.disconnected_since = None;
+            self.ws.notify_state_change(WsState::Closed);
             on_error(error_message, self.clone())
         }
     }
 
     fn reconnect(&mut self) -> Result<(), WebSocketError> {
+        self.ws.notify_state_change(WsState::Reconnecting);
+
         // loop until wait time has passed or reactor received shutdown signal
         debug!("Reconnecting in {:?}", self.wait);
         loop {
@@ -672,6 +816,19 @@ enum ConnectionStatus {
     Close(Result<(), WebSocketError>),
 }
 
+/// The state of a `WebSocketClient`'s connection, as reported to its `on_state_change` closure.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WsState {
+    /// The client is establishing its initial connection to the server
+    Connecting,
+    /// The connection is established
+    Connected,
+    /// The connection was lost and the client is attempting to re-establish it
+    Reconnecting,
+    /// The connection is closed and the client will not reconnect
+    Closed,
+}
+
 /// Response object returned by `WebSocket` client callbacks.
 #[derive(Debug)]
 pub enum WsResponse {
@@ -691,3 +848,235 @@ impl ParseBytes<Vec<u8>> for Vec<u8> {
         Ok(bytes.to_vec())
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::{mpsc, Mutex};
+    use std::thread;
+    use std::time::Instant;
+
+    use crate::events::Reactor;
+
+    /// Test that a client configured with a reconnect timeout stops reconnecting to a server that
+    /// stays down once the timeout has passed, even though attempts remain.
+    ///
+    /// 1. Reserve a local port and release it, so that nothing is listening on it
+    /// 2. Start a client with a large reconnect limit and a two second reconnect timeout
+    /// 3. Verify that `on_error` is called with a `ReconnectError` no sooner than the timeout
+    #[test]
+    fn test_reconnect_timeout() {
+        let port = TcpListener::bind("127.0.0.1:0")
+            .expect("Unable to bind listener")
+            .local_addr()
+            .expect("Unable to get local address")
+            .port();
+
+        let reactor = Reactor::new();
+
+        let mut ws = WebSocketClient::new(
+            &format!("http://127.0.0.1:{}", port),
+            "Bearer token",
+            |_, _: Vec<u8>| WsResponse::Empty,
+        );
+        ws.set_reconnect(true);
+        ws.set_reconnect_limit(100);
+        ws.set_reconnect_timeout(Duration::from_secs(2));
+
+        let (tx, rx) = mpsc::channel();
+        let tx = Mutex::new(tx);
+        ws.on_error(move |err, _| {
+            tx.lock()
+                .expect("Sender lock was poisoned")
+                .send(err)
+                .expect("Unable to send error");
+            Ok(())
+        });
+
+        let start = Instant::now();
+        reactor
+            .igniter()
+            .start_ws(&ws)
+            .expect("Unable to start websocket");
+
+        let err = rx
+            .recv_timeout(Duration::from_secs(30))
+            .expect("Reconnection did not terminate");
+
+        assert!(matches!(err, WebSocketError::ReconnectError(_)));
+        assert!(start.elapsed() >= Duration::from_secs(2));
+
+        reactor.shutdown().expect("Unable to shutdown reactor");
+    }
+
+    /// Test that a client reports each transition of its connection through `on_state_change`.
+    ///
+    /// 1. Start a server that accepts two websocket connections, sending a close frame on each,
+    ///    and then stops listening
+    /// 2. Start a client with a reconnect limit of one
+    /// 3. Verify that the client reports connecting and connected, reconnects and connects again
+    ///    after the first close, and is closed once it fails to reconnect after the second
+    #[test]
+    fn test_on_state_change() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Unable to bind listener");
+        let port = listener
+            .local_addr()
+            .expect("Unable to get local address")
+            .port();
+
+        let server = thread::spawn(move || {
+            for _ in 0..2 {
+                let (stream, _) = listener.accept().expect("Unable to accept connection");
+                let mut stream = upgrade(stream);
+                // An unmasked close frame with no payload
+                stream
+                    .write_all(&[0x88, 0x00])
+                    .expect("Unable to write close frame");
+            }
+        });
+
+        let reactor = Reactor::new();
+
+        let mut ws = WebSocketClient::new(
+            &format!("http://127.0.0.1:{}", port),
+            "Bearer token",
+            |_, _: Vec<u8>| WsResponse::Empty,
+        );
+        ws.set_reconnect(true);
+        ws.set_reconnect_limit(1);
+
+        let (tx, rx) = mpsc::channel();
+        let tx = Mutex::new(tx);
+        ws.on_state_change(move |state| {
+            tx.lock()
+                .expect("Sender lock was poisoned")
+                .send(state)
+                .expect("Unable to send state");
+        });
+
+        reactor
+            .igniter()
+            .start_ws(&ws)
+            .expect("Unable to start websocket");
+
+        let mut states = vec![];
+        while states.last() != Some(&WsState::Closed) {
+            states.push(
+                rx.recv_timeout(Duration::from_secs(30))
+                    .expect("Did not receive the expected states"),
+            );
+        }
+
+        assert_eq!(
+            states,
+            vec![
+                WsState::Connecting,
+                WsState::Connected,
+                WsState::Reconnecting,
+                WsState::Connected,
+                WsState::Reconnecting,
+                WsState::Closed,
+            ]
+        );
+
+        server.join().expect("Server thread panicked");
+        reactor.shutdown().expect("Unable to shutdown reactor");
+    }
+
+    /// Test that a client only completes the TLS handshake with a server whose certificate is
+    /// signed by a custom CA once that CA is configured as a root.
+    ///
+    /// 1. Start a TLS server with a certificate signed by a newly created CA
+    /// 2. Start a client without custom roots and verify that the handshake fails
+    /// 3. Start a client with the CA set as its only root and verify that the handshake succeeds
+    #[cfg(feature = "events-tls")]
+    #[test]
+    fn test_tls_roots() {
+        use openssl::ssl::SslAcceptor;
+
+        use crate::transport::tls::tests::{make_ca_cert, make_ca_signed_cert};
+
+        let (ca_key, ca_cert) = make_ca_cert();
+        let (server_key, server_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+
+        let mut acceptor =
+            SslAcceptor::mozilla_intermediate(SslMethod::tls()).expect("Unable to create acceptor");
+        acceptor
+            .set_private_key(&server_key)
+            .expect("Unable to set private key");
+        acceptor
+            .set_certificate(&server_cert)
+            .expect("Unable to set certificate");
+        let acceptor = acceptor.build();
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Unable to bind listener");
+        let port = listener
+            .local_addr()
+            .expect("Unable to get local address")
+            .port();
+
+        let (tx, rx) = mpsc::channel();
+        let server = thread::spawn(move || {
+            for _ in 0..2 {
+                let (stream, _) = listener.accept().expect("Unable to accept connection");
+                tx.send(acceptor.accept(stream).is_ok())
+                    .expect("Unable to send handshake result");
+            }
+        });
+
+        let reactor = Reactor::new();
+
+        let ws = WebSocketClient::new(
+            &format!("https://localhost:{}", port),
+            "Bearer token",
+            |_, _: Vec<u8>| WsResponse::Empty,
+        );
+        reactor
+            .igniter()
+            .start_ws(&ws)
+            .expect("Unable to start websocket");
+        assert!(!rx
+            .recv_timeout(Duration::from_secs(30))
+            .expect("Server did not receive a connection"));
+
+        let mut ws = WebSocketClient::new(
+            &format!("https://localhost:{}", port),
+            "Bearer token",
+            |_, _: Vec<u8>| WsResponse::Empty,
+        );
+        ws.set_tls_roots(vec![ca_cert]);
+        reactor
+            .igniter()
+            .start_ws(&ws)
+            .expect("Unable to start websocket");
+        assert!(rx
+            .recv_timeout(Duration::from_secs(30))
+            .expect("Server did not receive a connection"));
+
+        server.join().expect("Server thread panicked");
+        reactor.shutdown().expect("Unable to shutdown reactor");
+    }
+
+    /// Reads a websocket upgrade request from the stream and accepts it.
+    pub fn upgrade(mut stream: TcpStream) -> TcpStream {
+        let mut request = Vec::new();
+        let mut buffer = [0; 1024];
+        while !request.ends_with(b"\r\n\r\n") {
+            let read = stream.read(&mut buffer).expect("Unable to read request");
+            assert!(read > 0, "Connection closed before upgrade");
+            request.extend_from_slice(&buffer[..read]);
+        }
+
+        stream
+            .write_all(
+                b"HTTP/1.1 101 Switching Protocols\r\n\
+                  Upgrade: websocket\r\n\
+                  Connection: Upgrade\r\n\r\n",
+            )
+            .expect("Unable to write response");
+        stream
+    }
+}
@@ -20,6 +20,7 @@ use serde::de;
 #[cfg(feature = "admin-service")]
 use serde::{Deserializer, Serializer};
 
+/// Encodes the bytes as a lowercase hex string.
 pub fn to_hex(bytes: &[u8]) -> String {
     let mut buf = String::new();
     for b in bytes {
@@ -29,7 +30,22 @@ pub fn to_hex(bytes: &[u8]) -> String {
     buf
 }
 
+/// Decodes a hex string into bytes.
+///
+/// Both uppercase and lowercase digits are accepted, including a mix of the two.
 pub fn parse_hex(hex: &str) -> Result<Vec<u8>, HexError> {
+    let mut res = Vec::with_capacity(hex.len() / 2);
+    parse_hex_into(hex, &mut res)?;
+    Ok(res)
+}
+
+/// Decodes the hex string into the given buffer, replacing its contents.
+///
+/// This allows callers that decode many hex strings to reuse a single allocation. If the string is
+/// not valid hex, an error is returned and the contents of the buffer are unspecified.
+pub fn parse_hex_into(hex: &str, out: &mut Vec<u8>) -> Result<(), HexError> {
+    out.clear();
+
     if hex.len() % 2 != 0 {
         return Err(HexError {
             context: format!("{} is not valid hex: odd number of digits", hex),
@@ -37,9 +53,9 @@ pub fn parse_hex(hex: &str) -> Result<Vec<u8>, HexError> {
         });
     }
 
-    let mut res = vec![];
+    out.reserve(hex.len() / 2);
     for i in (0..hex.len()).step_by(2) {
-        res.push(
+        out.push(
             u8::from_str_radix(&hex[i..i + 2], 16).map_err(|err| HexError {
                 context: format!("{} contains invalid hex", hex),
                 source: Some(Box::new(err)),
@@ -47,7 +63,7 @@ pub fn parse_hex(hex: &str) -> Result<Vec<u8>, HexError> {
         );
     }
 
-    Ok(res)
+    Ok(())
 }
 
 #[cfg(feature = "admin-service")]
@@ -156,4 +172,53 @@ mod tests {
         let empty: Vec<u8> = Vec::with_capacity(0);
         assert_eq!(empty, parse_hex("").expect("unable to parse empty"));
     }
+
+    /// Test that parse_hex_into produces the same output as parse_hex, reusing the buffer across
+    /// calls, and that it rejects malformed input.
+    #[test]
+    fn test_parse_hex_into() {
+        let mut buf = Vec::new();
+        for hex in &["000a", "0163ff", "abcdef", "", "00"] {
+            parse_hex_into(hex, &mut buf).expect("unable to parse hex into buffer");
+            assert_eq!(parse_hex(hex).expect("unable to parse hex"), buf);
+        }
+
+        // check that odd number of digits fails
+        assert!(parse_hex_into("abc", &mut buf).is_err());
+
+        // check that invalid digits fails
+        assert!(parse_hex_into("0G", &mut buf).is_err());
+
+        // check that a previous failure does not affect the next parse
+        parse_hex_into("ff", &mut buf).expect("unable to parse ff");
+        assert_eq!(vec![255u8], buf);
+    }
+
+    /// Test that parse_hex accepts uppercase and mixed-case digits.
+    #[test]
+    fn test_parse_hex_mixed_case() {
+        assert_eq!(
+            vec![0x0au8, 0xbcu8],
+            parse_hex("0AbC").expect("unable to parse 0AbC")
+        );
+        assert_eq!(
+            parse_hex("abcdef").expect("unable to parse abcdef"),
+            parse_hex("ABCDEF").expect("unable to parse ABCDEF")
+        );
+    }
+
+    /// Test that arbitrary bytes round trip through to_hex and parse_hex, and that to_hex always
+    /// produces lowercase digits.
+    #[test]
+    fn test_to_hex_round_trip() {
+        let bytes: Vec<u8> = (0..=255u8).collect();
+        let hex = to_hex(&bytes);
+
+        assert_eq!(bytes.len() * 2, hex.len());
+        assert_eq!(hex.to_lowercase(), hex);
+        assert_eq!(
+            bytes,
+            parse_hex(&hex).expect("unable to parse hex for round trip")
+        );
+    }
 }
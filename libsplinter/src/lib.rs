@@ -81,7 +81,7 @@ pub mod consensus;
 pub mod error;
 #[cfg(feature = "events")]
 pub mod events;
-mod hex;
+pub mod hex;
 pub mod keys;
 pub mod mesh;
 pub mod migrations;
@@ -14,13 +14,14 @@
 use std::time::Duration;
 
 use crate::transport::matrix::{
-    ConnectionMatrixAddError, ConnectionMatrixEnvelope, ConnectionMatrixLifeCycle,
-    ConnectionMatrixReceiver, ConnectionMatrixRecvError, ConnectionMatrixRecvTimeoutError,
-    ConnectionMatrixRemoveError, ConnectionMatrixSendError, ConnectionMatrixSender,
+    ConnectionMatrixAddError, ConnectionMatrixDeliveryError, ConnectionMatrixEnvelope,
+    ConnectionMatrixLifeCycle, ConnectionMatrixReceiver, ConnectionMatrixRecvError,
+    ConnectionMatrixRecvTimeoutError, ConnectionMatrixRemoveError, ConnectionMatrixSendError,
+    ConnectionMatrixSender,
 };
 use crate::transport::Connection;
 
-use super::{Mesh, RecvError, RecvTimeoutError};
+use super::{Mesh, RecvError, RecvTimeoutError, SendError};
 
 #[derive(Clone)]
 /// Mesh specific implementation of ConnectionMatrixLifeCycle
@@ -56,6 +57,24 @@ impl ConnectionMatrixLifeCycle for MeshLifeCycle {
             )
         })
     }
+
+    fn add_alias(&self, alias: String, id: &str) -> Result<(), ConnectionMatrixAddError> {
+        self.mesh.add_alias(alias, id).map_err(|err| {
+            ConnectionMatrixAddError::new(
+                "Unable to add connection alias to matrix".to_string(),
+                Some(Box::new(err)),
+            )
+        })
+    }
+
+    fn remove_alias(&self, alias: &str) -> Result<(), ConnectionMatrixRemoveError> {
+        self.mesh.remove_alias(alias).map_err(|err| {
+            ConnectionMatrixRemoveError::new(
+                "Unable to remove connection alias from matrix".to_string(),
+                Some(Box::new(err)),
+            )
+        })
+    }
 }
 
 #[derive(Clone)]
@@ -80,6 +99,35 @@ impl ConnectionMatrixSender for MeshMatrixSender {
             )
         })
     }
+
+    fn send_confirmed(
+        &self,
+        id: String,
+        message: Vec<u8>,
+    ) -> Result<(), ConnectionMatrixDeliveryError> {
+        let envelope = ConnectionMatrixEnvelope::new(id, message);
+        self.mesh.send(envelope).map_err(|err| match err {
+            SendError::NotFound => ConnectionMatrixDeliveryError::UnknownConnection,
+            SendError::Full(_) => ConnectionMatrixDeliveryError::QueueFull,
+            SendError::Disconnected(_) | SendError::Draining(_) => {
+                ConnectionMatrixDeliveryError::Disconnected
+            }
+            SendError::IoError(_) | SendError::PoisonedLock => {
+                ConnectionMatrixDeliveryError::new_internal_error(
+                    "Unable to send message to connection".to_string(),
+                    Some(Box::new(err)),
+                )
+            }
+        })
+    }
+
+    fn queue_depth(&self, id: &str) -> Option<usize> {
+        self.mesh.outgoing_queue_depth(id).ok()
+    }
+
+    fn is_backpressured(&self, id: &str) -> bool {
+        self.mesh.is_backpressured(id).unwrap_or(false)
+    }
 }
 
 #[derive(Clone)]
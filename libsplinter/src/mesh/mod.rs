@@ -56,11 +56,12 @@ mod outgoing;
 mod pool;
 mod reactor;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::io;
 use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use crate::collections::BiHashMap;
 use crate::error::InternalError;
@@ -81,9 +82,15 @@ pub(in crate::mesh) enum InternalEnvelope {
     Shutdown,
 }
 
+// How often the outgoing queue is checked while draining a connection
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 struct MeshState {
     pub outgoings: HashMap<usize, Outgoing>,
     pub unique_ids: BiHashMap<String, usize>,
+    pub aliases: HashMap<String, usize>,
+    pub draining: HashSet<usize>,
+    pub shutdown_summary: Option<MeshShutdownSummary>,
 }
 
 impl MeshState {
@@ -91,6 +98,59 @@ impl MeshState {
         MeshState {
             outgoings: HashMap::new(),
             unique_ids: BiHashMap::new(),
+            aliases: HashMap::new(),
+            draining: HashSet::new(),
+            shutdown_summary: None,
+        }
+    }
+
+    /// Returns the mesh id of the connection with the given unique id or alias.
+    fn mesh_id(&self, unique_id: &str) -> Option<usize> {
+        self.unique_ids
+            .get_by_key(unique_id)
+            .or_else(|| self.aliases.get(unique_id))
+            .copied()
+    }
+}
+
+/// The connections closed by the mesh while shutting down.
+#[derive(Debug, Default)]
+pub struct MeshShutdownSummary {
+    connections_closed: usize,
+    errors: Vec<String>,
+}
+
+impl MeshShutdownSummary {
+    /// Returns the number of connections that were closed.
+    pub fn connections_closed(&self) -> usize {
+        self.connections_closed
+    }
+
+    /// Returns the errors encountered while closing the connections.
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+}
+
+/// The result of removing a connection with `Mesh::remove_after_drain`.
+pub enum DrainedConnection {
+    /// All queued outbound messages were written before the connection was removed
+    Complete(Box<dyn Connection>),
+    /// The timeout elapsed before all queued outbound messages could be written
+    TimedOut(Box<dyn Connection>),
+}
+
+impl DrainedConnection {
+    /// Returns `true` if all queued outbound messages were written before removal.
+    pub fn is_complete(&self) -> bool {
+        matches!(self, DrainedConnection::Complete(_))
+    }
+
+    /// Returns the removed connection.
+    pub fn into_connection(self) -> Box<dyn Connection> {
+        match self {
+            DrainedConnection::Complete(connection) => connection,
+            DrainedConnection::TimedOut(connection) => connection,
         }
     }
 }
@@ -101,6 +161,7 @@ pub struct Mesh {
     state: Arc<RwLock<MeshState>>,
     incoming: Incoming,
     ctrl: Control,
+    high_water_mark: Option<usize>,
 }
 
 impl Mesh {
@@ -112,9 +173,19 @@ impl Mesh {
             state: Arc::new(RwLock::new(MeshState::new())),
             incoming,
             ctrl,
+            high_water_mark: None,
         }
     }
 
+    /// Set the outgoing queue depth at which a connection is considered to be backpressured.
+    ///
+    /// Sends that push a connection's queue to this depth will log a warning, and
+    /// `is_backpressured` will report `true` for the connection until its queue drains below it.
+    pub fn with_high_water_mark(mut self, high_water_mark: usize) -> Self {
+        self.high_water_mark = Some(high_water_mark);
+        self
+    }
+
     /// Add a new connection to the mesh, moving it to the background thread, and add the returned
     /// mesh id to the unique_ids map
     pub fn add(
@@ -142,12 +213,82 @@ impl Mesh {
             // automatically, causing the control request to fail with NotFound.
 
             state.outgoings.remove(&mesh_id);
+            state.draining.remove(&mesh_id);
+            state.aliases.retain(|_, aliased_id| *aliased_id != mesh_id);
             Ok(connection)
         } else {
             Err(RemoveError::NotFound)
         }
     }
 
+    /// Add an alias for an existing connection, so that envelopes sent to the alias are sent on
+    /// the connection. The alias is removed when the connection is removed.
+    pub fn add_alias(&self, alias: String, unique_id: &str) -> Result<(), AliasError> {
+        let mut state = self.state.write().map_err(|_| AliasError::PoisonedLock)?;
+        let mesh_id = *state
+            .unique_ids
+            .get_by_key(unique_id)
+            .ok_or(AliasError::NotFound)?;
+        state.aliases.insert(alias, mesh_id);
+
+        Ok(())
+    }
+
+    /// Remove an alias added with `add_alias`, leaving the connection in place.
+    pub fn remove_alias(&self, alias: &str) -> Result<(), AliasError> {
+        let mut state = self.state.write().map_err(|_| AliasError::PoisonedLock)?;
+        state
+            .aliases
+            .remove(alias)
+            .map(|_| ())
+            .ok_or(AliasError::NotFound)
+    }
+
+    /// Remove an existing connection from the mesh after its queued outbound messages have been
+    /// written, and return it.
+    ///
+    /// New sends to the connection are rejected with `SendError::Draining` as soon as this is
+    /// called. If the queue has not been flushed by the time `timeout` elapses, the connection is
+    /// removed anyway and any remaining messages are discarded.
+    pub fn remove_after_drain(
+        &self,
+        unique_id: &str,
+        timeout: Duration,
+    ) -> Result<DrainedConnection, RemoveError> {
+        let outgoing = {
+            let mut state = self.state.write().map_err(|_| RemoveError::PoisonedLock)?;
+            let mesh_id = *state
+                .unique_ids
+                .get_by_key(unique_id)
+                .ok_or(RemoveError::NotFound)?;
+            state.draining.insert(mesh_id);
+            state
+                .outgoings
+                .get(&mesh_id)
+                .cloned()
+                .ok_or(RemoveError::NotFound)?
+        };
+
+        let deadline = Instant::now() + timeout;
+        let mut complete = outgoing.depth() == 0;
+        while !complete && Instant::now() < deadline {
+            thread::sleep(DRAIN_POLL_INTERVAL);
+            complete = outgoing.depth() == 0;
+        }
+
+        let connection = self.remove(unique_id)?;
+        if complete {
+            Ok(DrainedConnection::Complete(connection))
+        } else {
+            debug!(
+                "Removing connection {} with {} undelivered messages",
+                unique_id,
+                outgoing.depth()
+            );
+            Ok(DrainedConnection::TimedOut(connection))
+        }
+    }
+
     /// Send the envelope on the mesh.
     ///
     /// This is a convenience function and is equivalent to
@@ -156,20 +297,58 @@ impl Mesh {
         let id = envelope.id().to_string();
         let outgoing = {
             let state = self.state.read().map_err(|_| SendError::PoisonedLock)?;
-            let mesh_id = state
-                .unique_ids
-                .get_by_key(&id)
-                .ok_or(SendError::NotFound)?;
+            let mesh_id = state.mesh_id(&id).ok_or(SendError::NotFound)?;
+            if state.draining.contains(&mesh_id) {
+                return Err(SendError::Draining(envelope));
+            }
             state
                 .outgoings
-                .get(mesh_id)
+                .get(&mesh_id)
                 .cloned()
                 .ok_or(SendError::NotFound)?
         };
 
-        outgoing
-            .send(Vec::from(envelope))
-            .map_err(|err| SendError::from_outgoing_send_error(err, id))
+        let previous_depth = outgoing
+            .send_returning_previous_depth(Vec::from(envelope))
+            .map_err(|err| SendError::from_outgoing_send_error(err, id.clone()))?;
+
+        if let Some(high_water_mark) = self.high_water_mark {
+            // Only warn when this send crossed the mark, not on every send while above it
+            if previous_depth + 1 == high_water_mark {
+                warn!(
+                    "Outgoing queue for connection {} has reached its high-water mark of {}",
+                    id, high_water_mark
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return the number of messages queued for the given connection that have not yet been
+    /// handed to the underlying connection.
+    pub fn outgoing_queue_depth(&self, unique_id: &str) -> Result<usize, QueueDepthError> {
+        let state = self
+            .state
+            .read()
+            .map_err(|_| QueueDepthError::PoisonedLock)?;
+        let mesh_id = state.mesh_id(unique_id).ok_or(QueueDepthError::NotFound)?;
+        state
+            .outgoings
+            .get(&mesh_id)
+            .map(Outgoing::depth)
+            .ok_or(QueueDepthError::NotFound)
+    }
+
+    /// Return whether the outgoing queue for the given connection is at or above the configured
+    /// high-water mark.
+    ///
+    /// If no high-water mark has been set, this always returns `false`.
+    pub fn is_backpressured(&self, unique_id: &str) -> Result<bool, QueueDepthError> {
+        match self.high_water_mark {
+            Some(high_water_mark) => Ok(self.outgoing_queue_depth(unique_id)? >= high_water_mark),
+            None => Ok(false),
+        }
     }
 
     /// Receive a new envelope from the mesh.
@@ -238,14 +417,10 @@ impl Mesh {
         let mesh = self.clone();
         MeshMatrixReceiver::new(mesh)
     }
-}
-
-impl ShutdownHandle for Mesh {
-    fn signal_shutdown(&mut self) {
-        self.ctrl.shutdown();
-    }
 
-    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+    /// Wait for the mesh to shutdown, returning a summary of the connections closed by
+    /// `close_connections`.
+    pub fn wait_for_shutdown_with_summary(self) -> Result<MeshShutdownSummary, InternalError> {
         debug!("Waiting for Mesh to shutdown");
         loop {
             match self.recv() {
@@ -253,7 +428,7 @@ impl ShutdownHandle for Mesh {
                 Err(err) => match err {
                     RecvError::Shutdown => {
                         debug!("Mesh has shutdown");
-                        return Ok(());
+                        break;
                     }
                     RecvError::PoisonedLock => {
                         return Err(InternalError::with_message(
@@ -262,11 +437,65 @@ impl ShutdownHandle for Mesh {
                     }
                     RecvError::Disconnected => {
                         debug!("Mesh has disconnected");
-                        return Ok(());
+                        break;
                     }
                 },
             }
         }
+
+        let mut state = self.state.write().map_err(|_| {
+            InternalError::with_message(
+                "Mesh lock was poisoned while waiting for shutdown".to_string(),
+            )
+        })?;
+        Ok(state.shutdown_summary.take().unwrap_or_default())
+    }
+
+    /// Remove and disconnect all of the connections in the mesh, recording the result so it can
+    /// be returned by `wait_for_shutdown_with_summary`.
+    ///
+    /// This should be called before `signal_shutdown` if the connections should be closed as part
+    /// of shutting down the mesh.
+    pub fn close_connections(&self) {
+        let unique_ids = match self.state.read() {
+            Ok(state) => state.unique_ids.keys().cloned().collect::<Vec<_>>(),
+            Err(_) => {
+                error!("Mesh lock was poisoned while closing connections");
+                return;
+            }
+        };
+
+        let mut summary = MeshShutdownSummary::default();
+        for unique_id in unique_ids {
+            match self.remove(&unique_id) {
+                Ok(mut connection) => match connection.disconnect() {
+                    Ok(()) => summary.connections_closed += 1,
+                    Err(err) => summary.errors.push(format!(
+                        "Unable to disconnect connection {}: {}",
+                        unique_id, err
+                    )),
+                },
+                Err(err) => summary.errors.push(format!(
+                    "Unable to remove connection {}: {}",
+                    unique_id, err
+                )),
+            }
+        }
+
+        match self.state.write() {
+            Ok(mut state) => state.shutdown_summary = Some(summary),
+            Err(_) => error!("Mesh lock was poisoned while closing connections"),
+        }
+    }
+}
+
+impl ShutdownHandle for Mesh {
+    fn signal_shutdown(&mut self) {
+        self.ctrl.shutdown();
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        self.wait_for_shutdown_with_summary().map(|_| ())
     }
 }
 
@@ -276,6 +505,7 @@ pub enum SendError {
     IoError(io::Error),
     Full(Envelope),
     Disconnected(Envelope),
+    Draining(Envelope),
     PoisonedLock,
 }
 
@@ -286,6 +516,7 @@ impl Error for SendError {
             SendError::IoError(err) => Some(err),
             SendError::Full(_) => None,
             SendError::Disconnected(_) => None,
+            SendError::Draining(_) => None,
             SendError::PoisonedLock => None,
         }
     }
@@ -302,6 +533,9 @@ impl std::fmt::Display for SendError {
             SendError::Disconnected(ref envelope) => {
                 write!(f, "connection disconnected {}", envelope.id())
             }
+            SendError::Draining(ref envelope) => {
+                write!(f, "connection {} is draining for removal", envelope.id())
+            }
             SendError::PoisonedLock => write!(f, "MeshState lock was poisoned"),
         }
     }
@@ -319,6 +553,40 @@ impl SendError {
     }
 }
 
+#[derive(Debug)]
+pub enum QueueDepthError {
+    NotFound,
+    PoisonedLock,
+}
+
+impl Error for QueueDepthError {}
+
+impl std::fmt::Display for QueueDepthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            QueueDepthError::NotFound => write!(f, "requested connection cannot be found"),
+            QueueDepthError::PoisonedLock => write!(f, "MeshState lock was poisoned"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AliasError {
+    NotFound,
+    PoisonedLock,
+}
+
+impl Error for AliasError {}
+
+impl std::fmt::Display for AliasError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            AliasError::NotFound => write!(f, "requested connection or alias cannot be found"),
+            AliasError::PoisonedLock => write!(f, "MeshState lock was poisoned"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum RecvError {
     Disconnected,
@@ -378,8 +646,13 @@ mod tests {
     use std::sync::mpsc::channel;
     use std::thread;
 
+    use mio::Evented;
+
+    use crate::transport::matrix::{ConnectionMatrixDeliveryError, ConnectionMatrixSender};
     use crate::transport::{
-        socket::tests::create_test_tls_transport, socket::TcpTransport, Transport,
+        inproc::InprocTransport, socket::tests::create_test_tls_transport, socket::TcpTransport,
+        DisconnectError, RecvError as TransportRecvError, SendError as TransportSendError,
+        Transport,
     };
 
     fn assert_ok<T, E: Debug>(result: Result<T, E>) -> T {
@@ -521,6 +794,154 @@ mod tests {
         handle.join().unwrap();
     }
 
+    // A connection that never completes a send, forcing the mesh to queue outgoing messages
+    struct SlowConnection {
+        inner: Box<dyn Connection>,
+    }
+
+    impl Connection for SlowConnection {
+        fn send(&mut self, _message: &[u8]) -> Result<(), TransportSendError> {
+            Err(TransportSendError::WouldBlock)
+        }
+
+        fn recv(&mut self) -> Result<Vec<u8>, TransportRecvError> {
+            self.inner.recv()
+        }
+
+        fn remote_endpoint(&self) -> String {
+            self.inner.remote_endpoint()
+        }
+
+        fn local_endpoint(&self) -> String {
+            self.inner.local_endpoint()
+        }
+
+        fn disconnect(&mut self) -> Result<(), DisconnectError> {
+            self.inner.disconnect()
+        }
+
+        fn evented(&self) -> &dyn Evented {
+            self.inner.evented()
+        }
+    }
+
+    #[test]
+    // Test that a connection whose outgoing queue grows past the high-water mark is reported as
+    // backpressured.
+    //
+    // 1. Add a connection that never completes a send to a mesh with a high-water mark of 4
+    // 2. Verify the connection is not backpressured
+    // 3. Send 8 messages to the connection
+    // 4. Verify all 8 messages are still queued, as none can be written to the connection
+    // 5. Verify that both the mesh and its matrix sender report the connection as backpressured
+    fn test_outgoing_queue_backpressure() {
+        let mut transport = InprocTransport::default();
+        let mut listener = assert_ok(transport.listen("inproc://backpressure"));
+        let client = assert_ok(transport.connect(&listener.endpoint()));
+        let _server = assert_ok(listener.accept());
+
+        let mesh = Mesh::new(16, 16).with_high_water_mark(4);
+        assert_ok(mesh.add(
+            Box::new(SlowConnection { inner: client }),
+            "slow".to_string(),
+        ));
+
+        assert!(!assert_ok(mesh.is_backpressured("slow")));
+
+        for _ in 0..8 {
+            assert_ok(mesh.send(Envelope::new("slow".to_string(), b"hello".to_vec())));
+        }
+
+        assert_eq!(8, assert_ok(mesh.outgoing_queue_depth("slow")));
+        assert!(assert_ok(mesh.is_backpressured("slow")));
+        assert!(mesh.get_sender().is_backpressured("slow"));
+
+        assert!(matches!(
+            mesh.outgoing_queue_depth("unknown"),
+            Err(QueueDepthError::NotFound)
+        ));
+    }
+
+    #[test]
+    // Test that a confirmed send reports whether the message was enqueued.
+    //
+    // 1. Add an inproc connection to a mesh
+    // 2. Send a message to the connection with send_confirmed and verify it is enqueued and
+    //    received by the peer
+    // 3. Send a message to an unknown connection id and verify an UnknownConnection error is
+    //    returned
+    fn test_send_confirmed() {
+        let mut transport = InprocTransport::default();
+        let mut listener = assert_ok(transport.listen("inproc://send_confirmed"));
+        let client = assert_ok(transport.connect(&listener.endpoint()));
+        let mut server = assert_ok(listener.accept());
+
+        let mesh = Mesh::new(16, 16);
+        assert_ok(mesh.add(client, "client".to_string()));
+        let sender = mesh.get_sender();
+
+        assert_ok(sender.send_confirmed("client".to_string(), b"hello".to_vec()));
+        assert_eq!(b"hello".to_vec(), assert_ok(server.recv()));
+
+        assert!(matches!(
+            sender.send_confirmed("unknown".to_string(), b"hello".to_vec()),
+            Err(ConnectionMatrixDeliveryError::UnknownConnection)
+        ));
+    }
+
+    #[test]
+    // Test that removing a connection after draining delivers all queued messages.
+    //
+    // 1. Add an inproc connection to a mesh and queue several messages on it
+    // 2. Remove the connection with remove_after_drain
+    // 3. Verify the drain completed and the peer received every message in order
+    // 4. Verify the connection can no longer be sent to
+    fn test_remove_after_drain() {
+        let mut transport = InprocTransport::default();
+        let mut listener = assert_ok(transport.listen("inproc://drain"));
+        let client = assert_ok(transport.connect(&listener.endpoint()));
+        let mut server = assert_ok(listener.accept());
+
+        let mesh = Mesh::new(16, 16);
+        assert_ok(mesh.add(client, "client".to_string()));
+
+        for i in 0..5u8 {
+            assert_ok(mesh.send(Envelope::new("client".to_string(), vec![i])));
+        }
+
+        let drained = assert_ok(mesh.remove_after_drain("client", Duration::from_secs(5)));
+        assert!(drained.is_complete());
+
+        for i in 0..5u8 {
+            assert_eq!(vec![i], assert_ok(server.recv()));
+        }
+
+        assert!(matches!(
+            mesh.send(Envelope::new("client".to_string(), b"late".to_vec())),
+            Err(SendError::NotFound)
+        ));
+    }
+
+    #[test]
+    // Test that removing a connection whose queue cannot be flushed reports a timeout.
+    fn test_remove_after_drain_timeout() {
+        let mut transport = InprocTransport::default();
+        let mut listener = assert_ok(transport.listen("inproc://drain_timeout"));
+        let client = assert_ok(transport.connect(&listener.endpoint()));
+        let _server = assert_ok(listener.accept());
+
+        let mesh = Mesh::new(16, 16);
+        assert_ok(mesh.add(
+            Box::new(SlowConnection { inner: client }),
+            "slow".to_string(),
+        ));
+        assert_ok(mesh.send(Envelope::new("slow".to_string(), b"hello".to_vec())));
+
+        let drained = assert_ok(mesh.remove_after_drain("slow", Duration::from_millis(50)));
+        assert!(!drained.is_complete());
+        assert!(matches!(mesh.remove("slow"), Err(RemoveError::NotFound)));
+    }
+
     #[test]
     fn test_connection_send_receive_raw() {
         let raw = TcpTransport::default();
@@ -629,4 +1050,24 @@ mod tests {
 
         handle.join().unwrap();
     }
+
+    // Test that closing the connections before shutting down the mesh reports them in the summary
+    #[test]
+    fn test_shutdown_summary() {
+        let mut transport = InprocTransport::default();
+        let mut listener = assert_ok(transport.listen("inproc://shutdown_summary"));
+
+        let mut mesh = Mesh::new(1, 1);
+        let client = assert_ok(transport.connect("inproc://shutdown_summary"));
+        let server = assert_ok(listener.accept());
+        assert_ok(mesh.add(client, "client".to_string()));
+        assert_ok(mesh.add(server, "server".to_string()));
+
+        mesh.close_connections();
+        mesh.signal_shutdown();
+        let summary = assert_ok(mesh.wait_for_shutdown_with_summary());
+
+        assert_eq!(2, summary.connections_closed());
+        assert!(summary.errors().is_empty());
+    }
 }
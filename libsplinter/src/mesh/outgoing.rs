@@ -15,6 +15,8 @@
 use mio_extras::channel::{SyncSender, TrySendError};
 
 use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 use super::InternalEnvelope;
 
@@ -23,24 +25,50 @@ use super::InternalEnvelope;
 pub struct Outgoing {
     id: usize,
     tx: SyncSender<InternalEnvelope>,
+    depth: Arc<AtomicUsize>,
 }
 
 impl Outgoing {
-    pub(super) fn new(id: usize, tx: SyncSender<InternalEnvelope>) -> Self {
-        Outgoing { id, tx }
+    pub(super) fn new(
+        id: usize,
+        tx: SyncSender<InternalEnvelope>,
+        depth: Arc<AtomicUsize>,
+    ) -> Self {
+        Outgoing { id, tx, depth }
     }
 
     pub fn send(&self, payload: Vec<u8>) -> Result<(), SendError> {
-        self.tx.try_send(InternalEnvelope::Message {
+        self.send_returning_previous_depth(payload).map(|_| ())
+    }
+
+    /// Sends the payload and returns the number of messages that were queued for this connection
+    /// before it.
+    pub(super) fn send_returning_previous_depth(
+        &self,
+        payload: Vec<u8>,
+    ) -> Result<usize, SendError> {
+        // Increment before sending, so the reactor can never observe the message before it has
+        // been counted.
+        let previous_depth = self.depth.fetch_add(1, Ordering::SeqCst);
+        if let Err(err) = self.tx.try_send(InternalEnvelope::Message {
             id: self.id,
             payload,
-        })?;
-        Ok(())
+        }) {
+            self.depth.fetch_sub(1, Ordering::SeqCst);
+            return Err(SendError::from(err));
+        }
+        Ok(previous_depth)
     }
 
     pub fn id(&self) -> usize {
         self.id
     }
+
+    /// Returns the number of messages queued for this connection that have not yet been written to
+    /// the underlying connection.
+    pub fn depth(&self) -> usize {
+        self.depth.load(Ordering::SeqCst)
+    }
 }
 
 #[derive(Debug)]
@@ -20,7 +20,9 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt;
 use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc::TryRecvError;
+use std::sync::Arc;
 
 use crate::transport::{Connection, RecvError, SendError};
 
@@ -64,6 +66,7 @@ impl Pool {
         &mut self,
         connection: Box<dyn Connection>,
         outgoing: mio_channel::Receiver<InternalEnvelope>,
+        outgoing_depth: Arc<AtomicUsize>,
     ) -> Result<usize, io::Error> {
         let connection_token = self.next_token();
         let outgoing_token = self.next_token();
@@ -87,7 +90,14 @@ impl Pool {
         self.tokens.insert(outgoing_token, id);
         self.entries.insert(
             id,
-            Entry::new(id, connection, connection_token, outgoing, outgoing_token),
+            Entry::new(
+                id,
+                connection,
+                connection_token,
+                outgoing,
+                outgoing_token,
+                outgoing_depth,
+            ),
         );
 
         Ok(id)
@@ -205,6 +215,7 @@ struct Entry {
     connection_token: Token,
     outgoing: mio_channel::Receiver<InternalEnvelope>,
     outgoing_token: Token,
+    outgoing_depth: Arc<AtomicUsize>,
     cached: RefCell<Option<Vec<u8>>>,
     write_evented_guard: RefCell<bool>,
 }
@@ -226,6 +237,7 @@ impl Entry {
         connection_token: Token,
         outgoing: mio_channel::Receiver<InternalEnvelope>,
         outgoing_token: Token,
+        outgoing_depth: Arc<AtomicUsize>,
     ) -> Self {
         Entry {
             id,
@@ -233,6 +245,7 @@ impl Entry {
             connection_token,
             outgoing,
             outgoing_token,
+            outgoing_depth,
             cached: RefCell::new(None),
             write_evented_guard: RefCell::new(false),
         }
@@ -324,11 +337,20 @@ impl Entry {
             Ok(conn) => conn,
             Err(_) => {
                 error!("Attempting to mutably borrow connection {} again", self.id);
+                // The message is dropped, so it is no longer queued
+                self.outgoing_depth.fetch_sub(1, Ordering::SeqCst);
                 return Ok(());
             }
         };
 
-        match connection.send(&payload) {
+        let result = connection.send(&payload);
+        // Unless the message is cached to be sent again, it has either been handed to the
+        // connection or dropped, so it is no longer queued
+        if !matches!(result, Err(SendError::WouldBlock)) {
+            self.outgoing_depth.fetch_sub(1, Ordering::SeqCst);
+        }
+
+        match result {
             Ok(()) => {
                 // Return to readable only.
                 if self.write_evented_guard.replace(false) {
@@ -15,7 +15,7 @@
 use mio::{Event, Events, Token};
 use mio_extras::channel as mio_channel;
 
-use std::sync::{mpsc::TryRecvError, Arc, Barrier};
+use std::sync::{atomic::AtomicUsize, mpsc::TryRecvError, Arc, Barrier};
 use std::thread;
 
 use crate::mesh::{
@@ -164,9 +164,10 @@ impl Reactor {
 
     fn add_connection(&mut self, connection: Box<dyn Connection>) -> AddResponse {
         let (tx, rx) = mio_channel::sync_channel(self.outgoing_capacity);
+        let depth = Arc::new(AtomicUsize::new(0));
 
-        match self.pool.add(connection, rx) {
-            Ok(id) => Ok(Outgoing::new(id, tx)),
+        match self.pool.add(connection, rx, depth.clone()) {
+            Ok(id) => Ok(Outgoing::new(id, tx, depth)),
             Err(err) => Err(AddError::Io(err)),
         }
     }
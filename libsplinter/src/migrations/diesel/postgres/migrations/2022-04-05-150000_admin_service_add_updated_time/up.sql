@@ -0,0 +1,21 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- Existing rows are backfilled to the time of the migration by the column default.
+ALTER TABLE circuit_proposal
+ADD COLUMN updated_time BIGINT NOT NULL DEFAULT extract(epoch from now());
+
+ALTER TABLE circuit
+ADD COLUMN updated_time BIGINT NOT NULL DEFAULT extract(epoch from now());
@@ -16,12 +16,20 @@
 
 embed_migrations!("./src/migrations/diesel/postgres/migrations");
 
+use std::collections::HashSet;
+
+use diesel::dsl::sql;
 use diesel::pg::PgConnection;
-use diesel::Connection;
+use diesel::sql_types::Bool;
+use diesel::{Connection, RunQueryDsl};
 use diesel_migrations::MigrationConnection;
 
 use crate::error::InternalError;
 
+/// The versions of the embedded migrations, in the order they are run
+const MIGRATION_VERSIONS: &[&str] =
+    include!(concat!(env!("OUT_DIR"), "/postgres_migration_versions.rs"));
+
 /// Run all pending database migrations.
 ///
 /// # Arguments
@@ -36,6 +44,51 @@ pub fn run_migrations(conn: &PgConnection) -> Result<(), InternalError> {
     Ok(())
 }
 
+/// Get the version of the most recently applied migration, or `None` if no migrations have been
+/// applied
+///
+/// # Arguments
+///
+/// * `conn` - Connection to PostgreSQL database
+///
+pub fn current_migration_version(conn: &PgConnection) -> Result<Option<String>, InternalError> {
+    conn.latest_run_migration_version()
+        .map_err(|err| InternalError::from_source(Box::new(err)))
+}
+
+/// Get the versions of the migrations that have not yet been applied, in the order they would be
+/// run. No migrations are run, and the database is not modified.
+///
+/// # Arguments
+///
+/// * `conn` - Connection to PostgreSQL database
+///
+pub fn pending_migrations(conn: &PgConnection) -> Result<Vec<String>, InternalError> {
+    let applied_versions = if migrations_table_exists(conn)? {
+        conn.previously_run_migration_versions()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+    } else {
+        HashSet::new()
+    };
+
+    Ok(MIGRATION_VERSIONS
+        .iter()
+        .filter(|version| !applied_versions.contains(**version))
+        .map(|version| version.to_string())
+        .collect())
+}
+
+/// Get whether the table that records the applied migrations exists, which it does not until
+/// migrations have been run for the first time
+fn migrations_table_exists(conn: &PgConnection) -> Result<bool, InternalError> {
+    diesel::select(sql::<Bool>(
+        "EXISTS (SELECT 1 FROM information_schema.tables \
+         WHERE table_schema = current_schema() AND table_name = '__diesel_schema_migrations')",
+    ))
+    .get_result(conn)
+    .map_err(|err| InternalError::from_source(Box::new(err)))
+}
+
 /// Get whether there are any pending migrations
 ///
 /// # Arguments
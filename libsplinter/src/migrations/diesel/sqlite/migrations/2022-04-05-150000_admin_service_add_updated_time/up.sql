@@ -0,0 +1,26 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- SQLite does not allow a non-constant default when adding a column, so existing rows are
+-- backfilled to the time of the migration.
+ALTER TABLE circuit_proposal
+ADD COLUMN updated_time INTEGER NOT NULL DEFAULT 0;
+
+UPDATE circuit_proposal SET updated_time = strftime('%s','now');
+
+ALTER TABLE circuit
+ADD COLUMN updated_time INTEGER NOT NULL DEFAULT 0;
+
+UPDATE circuit SET updated_time = strftime('%s','now');
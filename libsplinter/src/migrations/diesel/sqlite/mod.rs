@@ -16,12 +16,20 @@
 
 embed_migrations!("./src/migrations/diesel/sqlite/migrations");
 
+use std::collections::HashSet;
+
+use diesel::dsl::sql;
+use diesel::sql_types::Bool;
 use diesel::sqlite::SqliteConnection;
-use diesel::Connection;
+use diesel::{Connection, RunQueryDsl};
 use diesel_migrations::MigrationConnection;
 
 use crate::error::InternalError;
 
+/// The versions of the embedded migrations, in the order they are run
+const MIGRATION_VERSIONS: &[&str] =
+    include!(concat!(env!("OUT_DIR"), "/sqlite_migration_versions.rs"));
+
 /// Run all pending database migrations.
 ///
 /// # Arguments
@@ -36,6 +44,51 @@ pub fn run_migrations(conn: &SqliteConnection) -> Result<(), InternalError> {
     Ok(())
 }
 
+/// Get the version of the most recently applied migration, or `None` if no migrations have been
+/// applied
+///
+/// # Arguments
+///
+/// * `conn` - Connection to SQLite database
+///
+pub fn current_migration_version(conn: &SqliteConnection) -> Result<Option<String>, InternalError> {
+    conn.latest_run_migration_version()
+        .map_err(|err| InternalError::from_source(Box::new(err)))
+}
+
+/// Get the versions of the migrations that have not yet been applied, in the order they would be
+/// run. No migrations are run, and the database is not modified.
+///
+/// # Arguments
+///
+/// * `conn` - Connection to SQLite database
+///
+pub fn pending_migrations(conn: &SqliteConnection) -> Result<Vec<String>, InternalError> {
+    let applied_versions = if migrations_table_exists(conn)? {
+        conn.previously_run_migration_versions()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+    } else {
+        HashSet::new()
+    };
+
+    Ok(MIGRATION_VERSIONS
+        .iter()
+        .filter(|version| !applied_versions.contains(**version))
+        .map(|version| version.to_string())
+        .collect())
+}
+
+/// Get whether the table that records the applied migrations exists, which it does not until
+/// migrations have been run for the first time
+fn migrations_table_exists(conn: &SqliteConnection) -> Result<bool, InternalError> {
+    diesel::select(sql::<Bool>(
+        "EXISTS (SELECT 1 FROM sqlite_master \
+         WHERE type = 'table' AND name = '__diesel_schema_migrations')",
+    ))
+    .get_result(conn)
+    .map_err(|err| InternalError::from_source(Box::new(err)))
+}
+
 /// Get whether there are any pending migrations
 ///
 /// # Arguments
@@ -59,3 +112,46 @@ pub fn any_pending_migrations(conn: &SqliteConnection) -> Result<bool, InternalE
 
     Ok(current_version == latest_version)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the current migration version is reported once the migrations have been run
+    /// on an in-memory SQLite database.
+    #[test]
+    fn test_current_migration_version() {
+        let conn = SqliteConnection::establish(":memory:").expect("Failed to establish connection");
+
+        run_migrations(&conn).expect("Failed to run migrations");
+
+        let version = current_migration_version(&conn)
+            .expect("Failed to get current migration version")
+            .expect("No migration version reported");
+        assert!(!version.is_empty());
+    }
+
+    /// Verify that all migrations are pending on a fresh in-memory SQLite database, that listing
+    /// them does not apply them, and that none are pending once the migrations have been run.
+    #[test]
+    fn test_pending_migrations() {
+        let conn = SqliteConnection::establish(":memory:").expect("Failed to establish connection");
+
+        let pending = pending_migrations(&conn).expect("Failed to get pending migrations");
+        assert!(!pending.is_empty());
+        assert_eq!(
+            pending,
+            pending_migrations(&conn).expect("Failed to get pending migrations")
+        );
+
+        run_migrations(&conn).expect("Failed to run migrations");
+
+        assert_eq!(
+            pending.last().cloned(),
+            current_migration_version(&conn).expect("Failed to get current migration version")
+        );
+        assert!(pending_migrations(&conn)
+            .expect("Failed to get pending migrations")
+            .is_empty());
+    }
+}
@@ -32,8 +32,16 @@ mod diesel;
 #[cfg(feature = "postgres")]
 pub use self::diesel::postgres::any_pending_migrations as any_pending_postgres_migrations;
 #[cfg(feature = "postgres")]
+pub use self::diesel::postgres::current_migration_version as current_postgres_migration_version;
+#[cfg(feature = "postgres")]
+pub use self::diesel::postgres::pending_migrations as pending_postgres_migrations;
+#[cfg(feature = "postgres")]
 pub use self::diesel::postgres::run_migrations as run_postgres_migrations;
 #[cfg(feature = "sqlite")]
 pub use self::diesel::sqlite::any_pending_migrations as any_pending_sqlite_migrations;
 #[cfg(feature = "sqlite")]
+pub use self::diesel::sqlite::current_migration_version as current_sqlite_migration_version;
+#[cfg(feature = "sqlite")]
+pub use self::diesel::sqlite::pending_migrations as pending_sqlite_migrations;
+#[cfg(feature = "sqlite")]
 pub use self::diesel::sqlite::run_migrations as run_sqlite_migrations;
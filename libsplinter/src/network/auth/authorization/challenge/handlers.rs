@@ -25,6 +25,7 @@ use crate::network::auth::{
     AuthorizationAcceptingAction, AuthorizationAcceptingState, AuthorizationInitiatingAction,
     AuthorizationInitiatingState, AuthorizationManagerStateMachine, AuthorizationMessage, Identity,
 };
+use crate::network::connection_manager::UnauthorizedReason;
 use crate::network::dispatch::{
     ConnectionId, DispatchError, Handler, MessageContext, MessageSender, RawBytes,
 };
@@ -88,6 +89,7 @@ impl Handler for AuthChallengeNonceRequestHandler {
                     context.source_connection_id(),
                     sender,
                     &err.to_string(),
+                    UnauthorizedReason::ProtocolError(err.to_string()),
                 )?;
                 return Ok(());
             }
@@ -218,6 +220,7 @@ impl Handler for AuthChallengeNonceResponseHandler {
                     context.source_connection_id(),
                     sender,
                     &err.to_string(),
+                    UnauthorizedReason::ProtocolError(err.to_string()),
                 )?;
                 return Ok(());
             }
@@ -332,6 +335,7 @@ impl Handler for AuthChallengeSubmitRequestHandler {
                     context.source_connection_id(),
                     sender,
                     "Challenge signature was not valid",
+                    UnauthorizedReason::InvalidSignature,
                 )?;
 
                 return Ok(());
@@ -349,6 +353,7 @@ impl Handler for AuthChallengeSubmitRequestHandler {
                     context.source_connection_id(),
                     sender,
                     "Required public key not submitted",
+                    UnauthorizedReason::UnknownIdentity,
                 )?;
 
                 return Ok(());
@@ -364,6 +369,7 @@ impl Handler for AuthChallengeSubmitRequestHandler {
                 context.source_connection_id(),
                 sender,
                 "No public keys submitted",
+                UnauthorizedReason::UnknownIdentity,
             )?;
 
             return Ok(());
@@ -386,6 +392,7 @@ impl Handler for AuthChallengeSubmitRequestHandler {
                     context.source_connection_id(),
                     sender,
                     &err.to_string(),
+                    UnauthorizedReason::ProtocolError(err.to_string()),
                 )?;
                 return Ok(());
             }
@@ -483,6 +490,7 @@ impl Handler for AuthChallengeSubmitResponseHandler {
                     context.source_connection_id(),
                     sender,
                     &err.to_string(),
+                    UnauthorizedReason::ProtocolError(err.to_string()),
                 )?;
                 return Ok(());
             }
@@ -508,6 +516,7 @@ impl Handler for AuthChallengeSubmitResponseHandler {
                             context.source_connection_id(),
                             sender,
                             &err.to_string(),
+                            UnauthorizedReason::ProtocolError(err.to_string()),
                         )?;
                         return Ok(());
                     }
@@ -536,6 +545,7 @@ fn send_authorization_error(
     connection_id: &str,
     sender: &dyn MessageSender<ConnectionId>,
     error_string: &str,
+    reason: UnauthorizedReason,
 ) -> Result<(), DispatchError> {
     let response = AuthorizationMessage::AuthorizationError(
         AuthorizationError::AuthorizationRejected(error_string.into()),
@@ -551,7 +561,10 @@ fn send_authorization_error(
         })?;
 
     if auth_manager
-        .next_accepting_state(connection_id, AuthorizationAcceptingAction::Unauthorizing)
+        .next_accepting_state(
+            connection_id,
+            AuthorizationAcceptingAction::Unauthorizing(reason),
+        )
         .is_err()
     {
         warn!(
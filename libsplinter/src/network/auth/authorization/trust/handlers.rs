@@ -23,6 +23,7 @@ use crate::network::auth::{
     AuthorizationAcceptingAction, AuthorizationAcceptingState, AuthorizationInitiatingAction,
     AuthorizationInitiatingState, AuthorizationManagerStateMachine, AuthorizationMessage, Identity,
 };
+use crate::network::connection_manager::UnauthorizedReason;
 use crate::network::dispatch::{
     ConnectionId, DispatchError, Handler, MessageContext, MessageSender, RawBytes,
 };
@@ -79,6 +80,7 @@ impl Handler for AuthTrustRequestHandler {
                     context.source_connection_id(),
                     sender,
                     &err.to_string(),
+                    UnauthorizedReason::ProtocolError(err.to_string()),
                 )?;
                 return Ok(());
             }
@@ -167,6 +169,7 @@ impl Handler for AuthTrustResponseHandler {
                     context.source_connection_id(),
                     sender,
                     &err.to_string(),
+                    UnauthorizedReason::ProtocolError(err.to_string()),
                 )?;
                 return Ok(());
             }
@@ -198,6 +201,7 @@ impl Handler for AuthTrustResponseHandler {
                     context.source_connection_id(),
                     sender,
                     &err.to_string(),
+                    UnauthorizedReason::ProtocolError(err.to_string()),
                 )?;
                 return Ok(());
             }
@@ -220,6 +224,7 @@ fn send_authorization_error(
     connection_id: &str,
     sender: &dyn MessageSender<ConnectionId>,
     error_string: &str,
+    reason: UnauthorizedReason,
 ) -> Result<(), DispatchError> {
     let response = AuthorizationMessage::AuthorizationError(
         AuthorizationError::AuthorizationRejected(error_string.into()),
@@ -235,7 +240,10 @@ fn send_authorization_error(
         })?;
 
     if auth_manager
-        .next_accepting_state(connection_id, AuthorizationAcceptingAction::Unauthorizing)
+        .next_accepting_state(
+            connection_id,
+            AuthorizationAcceptingAction::Unauthorizing(reason),
+        )
         .is_err()
     {
         warn!(
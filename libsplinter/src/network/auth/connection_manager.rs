@@ -55,14 +55,17 @@ impl From<ConnectionAuthorizationState> for AuthorizationResult {
                 identity,
                 expected_authorization,
                 local_authorization,
+                dial_back_endpoint: None,
             },
 
             ConnectionAuthorizationState::Unauthorized {
                 connection_id,
                 connection,
+                reason,
             } => AuthorizationResult::Unauthorized {
                 connection_id,
                 connection,
+                reason,
             },
         }
     }
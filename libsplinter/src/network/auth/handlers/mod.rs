@@ -22,6 +22,7 @@ use crate::network::auth::{
     AuthorizationAcceptingAction, AuthorizationAcceptingState, AuthorizationManagerStateMachine,
     AuthorizationMessageSender,
 };
+use crate::network::connection_manager::UnauthorizedReason;
 use crate::network::dispatch::{
     ConnectionId, DispatchError, Dispatcher, Handler, MessageContext, MessageSender,
 };
@@ -105,7 +106,9 @@ impl Handler for AuthorizationErrorHandler {
             AuthorizationError::AuthorizationRejected(err_msg) => {
                 match self.auth_manager.next_accepting_state(
                     context.source_connection_id(),
-                    AuthorizationAcceptingAction::Unauthorizing,
+                    AuthorizationAcceptingAction::Unauthorizing(
+                        UnauthorizedReason::RejectedByRemote(err_msg.clone()),
+                    ),
                 ) {
                     Ok(AuthorizationAcceptingState::Unauthorized) => {
                         info!(
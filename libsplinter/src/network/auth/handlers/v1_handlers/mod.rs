@@ -28,6 +28,7 @@ use crate::network::auth::{
     AuthorizationInitiatingState, AuthorizationManagerStateMachine, AuthorizationMessage,
     ConnectionAuthorizationType,
 };
+use crate::network::connection_manager::UnauthorizedReason;
 use crate::network::dispatch::{
     ConnectionId, DispatchError, Handler, MessageContext, MessageSender,
 };
@@ -82,6 +83,7 @@ impl Handler for AuthProtocolRequestHandler {
                     context.source_connection_id(),
                     sender,
                     &err.to_string(),
+                    UnauthorizedReason::ProtocolError(err.to_string()),
                 )?;
                 return Ok(());
             }
@@ -100,6 +102,7 @@ impl Handler for AuthProtocolRequestHandler {
                         context.source_connection_id(),
                         sender,
                         "Unable to agree on protocol version",
+                        UnauthorizedReason::ProtocolMismatch,
                     )?;
                     return Ok(());
                 };
@@ -223,6 +226,7 @@ impl Handler for AuthProtocolResponseHandler {
                     context.source_connection_id(),
                     sender,
                     &err.to_string(),
+                    UnauthorizedReason::ProtocolError(err.to_string()),
                 )?;
                 return Ok(());
             }
@@ -263,6 +267,7 @@ impl Handler for AuthProtocolResponseHandler {
                                 context.source_connection_id(),
                                 sender,
                                 "Required authorization type not supported",
+                                UnauthorizedReason::ProtocolMismatch,
                             )?;
 
                             return Ok(());
@@ -310,6 +315,7 @@ impl Handler for AuthProtocolResponseHandler {
                                 context.source_connection_id(),
                                 sender,
                                 "Required authorization type not supported",
+                                UnauthorizedReason::ProtocolMismatch,
                             )?;
 
                             return Ok(());
@@ -392,6 +398,7 @@ impl Handler for AuthProtocolResponseHandler {
                                 context.source_connection_id(),
                                 sender,
                                 "Required authorization type not supported",
+                                UnauthorizedReason::ProtocolMismatch,
                             )?;
 
                             return Ok(());
@@ -456,6 +463,7 @@ impl Handler for AuthCompleteHandler {
                 context.source_connection_id(),
                 sender,
                 &err.to_string(),
+                UnauthorizedReason::ProtocolError(err.to_string()),
             )?;
         }
 
@@ -469,6 +477,7 @@ fn send_authorization_error(
     connection_id: &str,
     sender: &dyn MessageSender<ConnectionId>,
     error_string: &str,
+    reason: UnauthorizedReason,
 ) -> Result<(), DispatchError> {
     let response = AuthorizationMessage::AuthorizationError(
         AuthorizationError::AuthorizationRejected(error_string.into()),
@@ -484,7 +493,10 @@ fn send_authorization_error(
         })?;
 
     if auth_manager
-        .next_accepting_state(connection_id, AuthorizationAcceptingAction::Unauthorizing)
+        .next_accepting_state(
+            connection_id,
+            AuthorizationAcceptingAction::Unauthorizing(reason),
+        )
         .is_err()
     {
         warn!(
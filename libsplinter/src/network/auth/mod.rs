@@ -25,6 +25,7 @@ use std::sync::{mpsc, Arc, Mutex};
 use cylinder::{Signer, VerifierFactory};
 use protobuf::Message;
 
+use crate::network::connection_manager::UnauthorizedReason;
 #[cfg(any(feature = "trust-authorization", feature = "challenge-authorization"))]
 use crate::protocol::authorization::AuthProtocolRequest;
 use crate::protocol::authorization::AuthorizationMessage;
@@ -324,18 +325,23 @@ impl AuthorizationConnector {
                     }
                     Err(RecvError::Disconnected) => {
                         error!("Connection unexpectedly disconnected; aborting authorization");
-                        break 'main None;
+                        break 'main Err(UnauthorizedReason::Aborted(
+                            "connection disconnected".into(),
+                        ));
                     }
                     Err(RecvError::IoError(err)) => {
                         error!("Unable to authorize connection due to I/O error: {}", err);
-                        break 'main None;
+                        break 'main Err(UnauthorizedReason::Aborted(format!(
+                            "I/O error: {}",
+                            err
+                        )));
                     }
                     Err(RecvError::ProtocolError(msg)) => {
                         error!(
                             "Unable to authorize connection due to protocol error: {}",
                             msg
                         );
-                        break 'main None;
+                        break 'main Err(UnauthorizedReason::ProtocolError(msg));
                     }
                     Err(RecvError::WouldBlock) => continue,
                 }
@@ -345,7 +351,10 @@ impl AuthorizationConnector {
                         Ok(()) => (),
                         Err(err) => {
                             error!("Unable to send outgoing message; aborting auth: {}", err);
-                            break 'main None;
+                            break 'main Err(UnauthorizedReason::Aborted(format!(
+                                "unable to send message: {}",
+                                err
+                            )));
                         }
                     }
                 }
@@ -354,7 +363,9 @@ impl AuthorizationConnector {
                     Ok(shared) => shared,
                     Err(_) => {
                         error!("connection authorization lock poisoned; aborting auth");
-                        break 'main None;
+                        break 'main Err(UnauthorizedReason::Aborted(
+                            "authorization lock poisoned".into(),
+                        ));
                     }
                 };
 
@@ -365,8 +376,8 @@ impl AuthorizationConnector {
 
             // allow unused variable if challenge-authorization is not enabled
             #[allow(unused_variables)]
-            let auth_state = if let Some((auth_identity, local_authorization)) = authed_identities {
-                match auth_identity {
+            let auth_state = match authed_identities {
+                Ok((auth_identity, local_authorization)) => match auth_identity {
                     Identity::Trust { identity } => ConnectionAuthorizationState::Authorized {
                         connection_id,
                         connection,
@@ -390,12 +401,12 @@ impl AuthorizationConnector {
                             local_authorization: local_authorization.into()
                         }
                     }
-                }
-            } else {
-                ConnectionAuthorizationState::Unauthorized {
+                },
+                Err(reason) => ConnectionAuthorizationState::Unauthorized {
                     connection_id,
                     connection,
-                }
+                    reason,
+                },
             };
 
             if let Err(err) = on_complete_callback(auth_state) {
@@ -442,28 +453,43 @@ impl AuthorizationMessageSender {
 #[derive(Default)]
 pub struct ManagedAuthorizations {
     states: HashMap<String, ManagedAuthorizationState>,
+    // The reasons given for the connections that have been unauthorized
+    unauthorized_reasons: HashMap<String, UnauthorizedReason>,
 }
 
 impl ManagedAuthorizations {
     fn new() -> Self {
         Self {
             states: HashMap::new(),
+            unauthorized_reasons: HashMap::new(),
         }
     }
 
-    fn take_connection_identity(&mut self, connection_id: &str) -> Option<(Identity, Identity)> {
-        self.states.remove(connection_id).and_then(|managed_state| {
-            if let Some(local_authorization) = managed_state.local_authorization {
-                match managed_state.accepting_state {
-                    AuthorizationAcceptingState::Done(identity) => {
-                        Some((identity, local_authorization))
+    /// Removes the authorization state of the connection, returning the remote and local
+    /// identities if the connection was authorized, or the reason it was not.
+    fn take_connection_identity(
+        &mut self,
+        connection_id: &str,
+    ) -> Result<(Identity, Identity), UnauthorizedReason> {
+        let reason = self
+            .unauthorized_reasons
+            .remove(connection_id)
+            .unwrap_or(UnauthorizedReason::UnknownIdentity);
+        self.states
+            .remove(connection_id)
+            .and_then(|managed_state| {
+                if let Some(local_authorization) = managed_state.local_authorization {
+                    match managed_state.accepting_state {
+                        AuthorizationAcceptingState::Done(identity) => {
+                            Some((identity, local_authorization))
+                        }
+                        _ => None,
                     }
-                    _ => None,
+                } else {
+                    None
                 }
-            } else {
-                None
-            }
-        })
+            })
+            .ok_or(reason)
     }
 
     fn is_complete(&self, connection_id: &str) -> Option<bool> {
@@ -503,6 +529,7 @@ pub enum ConnectionAuthorizationState {
     Unauthorized {
         connection_id: String,
         connection: Box<dyn Connection>,
+        reason: UnauthorizedReason,
     },
 }
 
@@ -518,9 +545,14 @@ impl std::fmt::Debug for ConnectionAuthorizationState {
                 .field("connection_id", connection_id)
                 .field("identity", identity)
                 .finish(),
-            ConnectionAuthorizationState::Unauthorized { connection_id, .. } => f
+            ConnectionAuthorizationState::Unauthorized {
+                connection_id,
+                reason,
+                ..
+            } => f
                 .debug_struct("Unauthorized")
                 .field("connection_id", connection_id)
+                .field("reason", reason)
                 .finish(),
         }
     }
@@ -20,6 +20,7 @@ pub mod trust_v1;
 use std::fmt;
 use std::sync::{Arc, Mutex};
 
+use crate::network::connection_manager::UnauthorizedReason;
 #[cfg(feature = "challenge-authorization")]
 use crate::public_key::PublicKey;
 
@@ -156,7 +157,7 @@ pub(crate) enum AuthorizationAcceptingAction {
     #[cfg(feature = "challenge-authorization")]
     Challenge(ChallengeAuthorizationAcceptingAction),
 
-    Unauthorizing,
+    Unauthorizing(UnauthorizedReason),
 }
 
 impl fmt::Display for AuthorizationAcceptingAction {
@@ -177,7 +178,9 @@ impl fmt::Display for AuthorizationAcceptingAction {
             #[cfg(feature = "challenge-authorization")]
             AuthorizationAcceptingAction::Challenge(action) => write!(f, "Challenge: {}", action),
 
-            AuthorizationAcceptingAction::Unauthorizing => f.write_str("Unauthorizing"),
+            AuthorizationAcceptingAction::Unauthorizing(reason) => {
+                write!(f, "Unauthorizing: {}", reason)
+            }
         }
     }
 }
@@ -394,6 +397,12 @@ impl AuthorizationManagerStateMachine {
             AuthorizationActionError::InternalError("Authorization pool lock was poisoned".into())
         })?;
 
+        if let AuthorizationAcceptingAction::Unauthorizing(reason) = &action {
+            shared
+                .unauthorized_reasons
+                .insert(connection_id.to_string(), reason.clone());
+        }
+
         let mut cur_state =
             shared
                 .states
@@ -409,7 +418,7 @@ impl AuthorizationManagerStateMachine {
                     local_authorization: None,
                 });
 
-        if action == AuthorizationAcceptingAction::Unauthorizing {
+        if matches!(action, AuthorizationAcceptingAction::Unauthorizing(_)) {
             cur_state.initiating_state = AuthorizationInitiatingState::Unauthorized;
             cur_state.accepting_state = AuthorizationAcceptingState::Unauthorized;
             return Ok(AuthorizationAcceptingState::Unauthorized);
@@ -24,7 +24,9 @@ use std::collections::HashMap;
 use crate::network::auth::ConnectionAuthorizationType;
 use crate::transport::Connection;
 
-use super::{AuthorizationResult, Authorizer, AuthorizerCallback, AuthorizerError};
+use super::{
+    AuthorizationResult, Authorizer, AuthorizerCallback, AuthorizerError, UnauthorizedReason,
+};
 
 /// Authorize Inproc Connections with predefined identities.
 ///
@@ -77,18 +79,60 @@ impl Authorizer for InprocAuthorizer {
                 local_authorization: ConnectionAuthorizationType::Trust {
                     identity: self.node_id.clone(),
                 },
+                dial_back_endpoint: None,
             })
             .map_err(|err| AuthorizerError(err.to_string()))
         } else {
             (*on_complete)(AuthorizationResult::Unauthorized {
                 connection_id,
                 connection,
+                reason: UnauthorizedReason::UnknownIdentity,
             })
             .map_err(|err| AuthorizerError(err.to_string()))
         }
     }
 }
 
+/// Authorize connections synchronously with a function.
+///
+/// The SyncAuthorizer passes each connection to the given function and calls the completion
+/// callback with its result immediately, on the calling thread. It is intended for authorization
+/// that does not require exchanging messages with the remote node, such as trusting every
+/// connection with a known identity.
+pub struct SyncAuthorizer<F> {
+    authorize: F,
+}
+
+impl<F> SyncAuthorizer<F>
+where
+    F: Fn(String, Box<dyn Connection>) -> AuthorizationResult,
+{
+    /// Construct a new SyncAuthorizer that authorizes connections with the given function.
+    ///
+    /// The function is given the connection ID and the connection, and must return them in its
+    /// `AuthorizationResult`.
+    pub fn new(authorize: F) -> Self {
+        Self { authorize }
+    }
+}
+
+impl<F> Authorizer for SyncAuthorizer<F>
+where
+    F: Fn(String, Box<dyn Connection>) -> AuthorizationResult,
+{
+    fn authorize_connection(
+        &self,
+        connection_id: String,
+        connection: Box<dyn Connection>,
+        on_complete: AuthorizerCallback,
+        _expected_authorization: Option<ConnectionAuthorizationType>,
+        _local_authorization: Option<ConnectionAuthorizationType>,
+    ) -> Result<(), AuthorizerError> {
+        (*on_complete)((self.authorize)(connection_id, connection))
+            .map_err(|err| AuthorizerError(err.to_string()))
+    }
+}
+
 /// A set of Authorizers.
 ///
 /// Authorizers processes a connection by matching the remote endpoint of a connection against a
@@ -415,6 +459,7 @@ mod tests {
                 local_authorization: ConnectionAuthorizationType::Trust {
                     identity: "node_id".to_string(),
                 },
+                dial_back_endpoint: None,
             })
             .map_err(|err| AuthorizerError(format!("Unable to return result: {}", err)))
         }
@@ -12,9 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::mpsc::{channel, Sender};
 use std::thread;
+use std::time::Duration;
 
+use uuid::Uuid;
+
+use crate::network::auth::ConnectionAuthorizationType;
 use crate::protocol::network::{NetworkHeartbeat, NetworkMessage};
 use crate::protos::network;
 use crate::protos::prelude::*;
@@ -23,9 +28,11 @@ use crate::transport::matrix::{ConnectionMatrixLifeCycle, ConnectionMatrixSender
 use crate::transport::Transport;
 
 use super::error::ConnectionManagerError;
+use super::metrics::{ConnectionManagerMetrics, NoopConnectionManagerMetrics};
 use super::{
-    AuthResult, Authorizer, CmMessage, CmRequest, ConnectionManager, ConnectionManagerNotification,
-    ConnectionManagerState, ConnectionMetadataExt, OutboundConnection, SubscriberMap,
+    AuthResult, Authorizer, CmMessage, CmRequest, ConnectionIdGenerator, ConnectionManager,
+    ConnectionManagerConfig, ConnectionManagerNotification, ConnectionManagerState,
+    ConnectionMetadataExt, EndpointChangePolicy, OutboundConnection, SubscriberMap,
 };
 
 const DEFAULT_HEARTBEAT_INTERVAL: u64 = 10;
@@ -38,6 +45,14 @@ pub struct ConnectionManagerBuilder<T, U> {
     transport: Option<Box<dyn Transport + Send>>,
     heartbeat_interval: u64,
     maximum_retry_frequency: u64,
+    authorization_timeout: Option<Duration>,
+    local_identity: Option<ConnectionAuthorizationType>,
+    metrics: Option<Box<dyn ConnectionManagerMetrics>>,
+    connection_id_generator: Option<ConnectionIdGenerator>,
+    endpoint_deduplication: bool,
+    endpoint_change_policy: EndpointChangePolicy,
+    max_concurrent_authorizations: Option<usize>,
+    inbound_reconnection: bool,
 }
 
 impl<T, U> Default for ConnectionManagerBuilder<T, U> {
@@ -49,6 +64,14 @@ impl<T, U> Default for ConnectionManagerBuilder<T, U> {
             transport: None,
             heartbeat_interval: DEFAULT_HEARTBEAT_INTERVAL,
             maximum_retry_frequency: DEFAULT_MAXIMUM_RETRY_FREQUENCY,
+            authorization_timeout: None,
+            local_identity: None,
+            metrics: None,
+            connection_id_generator: None,
+            endpoint_deduplication: false,
+            endpoint_change_policy: EndpointChangePolicy::default(),
+            max_concurrent_authorizations: None,
+            inbound_reconnection: false,
         }
     }
 }
@@ -119,6 +142,94 @@ where
         self
     }
 
+    /// Set the optional authorization timeout for the resulting connection manager.
+    ///
+    /// If a connection's authorization has not completed within this duration, the connection is
+    /// dropped and a `ConnectionManagerNotification::FatalConnectionError` is sent to
+    /// subscribers. Pending authorizations are checked at each heartbeat interval. By default,
+    /// authorizations may take any amount of time.
+    pub fn with_authorization_timeout(mut self, timeout: Duration) -> Self {
+        self.authorization_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the optional local identity for the resulting connection manager.
+    ///
+    /// Any outbound connection that is authorized with this identity is a connection to the local
+    /// node; it is refused and a `ConnectionManagerNotification::FatalConnectionError` is sent to
+    /// subscribers.
+    pub fn with_local_identity(mut self, identity: ConnectionAuthorizationType) -> Self {
+        self.local_identity = Some(identity);
+        self
+    }
+
+    /// Set the optional metrics recorder for the resulting connection manager.
+    ///
+    /// The recorder is notified as connections are established, fail authorization or are
+    /// reconnected, and as heartbeats are sent. By default, no metrics are recorded.
+    pub fn with_metrics(mut self, metrics: Box<dyn ConnectionManagerMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Set the optional connection ID generator for the resulting connection manager.
+    ///
+    /// The generator produces the connection ID of each inbound connection, which allows the IDs
+    /// to be derived deterministically from the connection. By default, a random UUID is used.
+    pub fn with_connection_id_generator(
+        mut self,
+        connection_id_generator: ConnectionIdGenerator,
+    ) -> Self {
+        self.connection_id_generator = Some(connection_id_generator);
+        self
+    }
+
+    /// Set whether the resulting connection manager deduplicates outbound connections by
+    /// endpoint.
+    ///
+    /// When enabled, an outbound connection requested for an endpoint that already has an
+    /// outbound connection, under a different connection ID, reuses the existing connection; the
+    /// new ID becomes an alias of it. Subscribers are sent a `Connected` notification for the
+    /// alias, but further notifications only use the ID of the existing connection. By default,
+    /// connections are not deduplicated.
+    pub fn with_endpoint_deduplication(mut self, endpoint_deduplication: bool) -> Self {
+        self.endpoint_deduplication = endpoint_deduplication;
+        self
+    }
+
+    /// Set how the resulting connection manager handles a request for an existing outbound
+    /// connection ID with a different endpoint.
+    ///
+    /// With `EndpointChangePolicy::Migrate`, the connection manager connects to the new endpoint
+    /// under the existing ID. With `EndpointChangePolicy::Reject`, the request returns an error
+    /// and the existing connection is left as it is. By default, connections are migrated.
+    pub fn with_endpoint_change_policy(mut self, policy: EndpointChangePolicy) -> Self {
+        self.endpoint_change_policy = policy;
+        self
+    }
+
+    /// Set the optional maximum number of concurrent authorizations for the resulting connection
+    /// manager.
+    ///
+    /// Authorizations requested beyond this limit are queued, and are passed to the authorizer in
+    /// the order they were requested as earlier authorizations complete or time out. The limit
+    /// must be at least one. By default, the number of concurrent authorizations is not limited.
+    pub fn with_max_concurrent_authorizations(mut self, max_concurrent: usize) -> Self {
+        self.max_concurrent_authorizations = Some(max_concurrent);
+        self
+    }
+
+    /// Set whether the resulting connection manager reconnects inbound connections.
+    ///
+    /// When enabled, an inbound connection whose authorizer reported a dial-back endpoint is
+    /// converted to an outbound connection to that endpoint when it is disconnected, and is then
+    /// reconnected like any other outbound connection. Inbound connections without a dial-back
+    /// endpoint are unaffected. By default, inbound connections are not reconnected.
+    pub fn with_inbound_reconnection(mut self, inbound_reconnection: bool) -> Self {
+        self.inbound_reconnection = inbound_reconnection;
+        self
+    }
+
     /// Create a started connection manager instance.
     ///
     /// This function creates and starts a `ConnectionManager` instance, which includes a
@@ -131,7 +242,11 @@ where
     pub fn start(mut self) -> Result<ConnectionManager, ConnectionManagerError> {
         let (sender, recv) = channel();
         let heartbeat = self.heartbeat_interval;
-        let retry_frequency = self.maximum_retry_frequency;
+        if self.max_concurrent_authorizations == Some(0) {
+            return Err(ConnectionManagerError::StartUpError(
+                "Maximum concurrent authorizations must be at least one".into(),
+            ));
+        }
 
         let authorizer = self
             .authorizer
@@ -150,16 +265,30 @@ where
             ConnectionManagerError::StartUpError("No matrix life cycle provided".into())
         })?;
 
+        let config = ConnectionManagerConfig {
+            maximum_retry_frequency: self.maximum_retry_frequency,
+            authorization_timeout: self.authorization_timeout,
+            local_identity: self.local_identity.take(),
+            metrics: self
+                .metrics
+                .take()
+                .unwrap_or_else(|| Box::new(NoopConnectionManagerMetrics)),
+            connection_id_generator: self
+                .connection_id_generator
+                .take()
+                .unwrap_or_else(|| Box::new(|_| Uuid::new_v4().to_string())),
+            endpoint_deduplication: self.endpoint_deduplication,
+            endpoint_change_policy: self.endpoint_change_policy,
+            max_concurrent_authorizations: self.max_concurrent_authorizations,
+            inbound_reconnection: self.inbound_reconnection,
+        };
+
         let resender = sender.clone();
         let join_handle = thread::Builder::new()
             .name("Connection Manager".into())
             .spawn(move || {
-                let mut state = ConnectionManagerState::new(
-                    life_cycle,
-                    matrix_sender,
-                    transport,
-                    retry_frequency,
-                );
+                let mut state =
+                    ConnectionManagerState::new(life_cycle, matrix_sender, transport, config);
                 let mut subscribers = SubscriberMap::new();
                 loop {
                     match recv.recv() {
@@ -174,7 +303,12 @@ where
                             );
                         }
                         Ok(CmMessage::AuthResult(auth_result)) => {
-                            handle_auth_result(auth_result, &mut state, &mut subscribers);
+                            handle_auth_result(
+                                auth_result,
+                                &mut state,
+                                &mut subscribers,
+                                &*authorizer,
+                            );
                         }
                         Ok(CmMessage::SendHeartbeats) => send_heartbeats(
                             &mut state,
@@ -242,7 +376,7 @@ fn handle_request<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
             sender,
         } => {
             let response = state
-                .remove_connection(&endpoint, &connection_id)
+                .remove_connection(&endpoint, &connection_id, subscribers)
                 .map(|meta_opt| meta_opt.map(|meta| meta.endpoint().to_owned()));
 
             if sender.send(response).is_err() {
@@ -261,11 +395,32 @@ fn handle_request<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
                 warn!("connector dropped before receiving result of list connections");
             }
         }
+        CmRequest::ListConnectionDetails { sender } => {
+            if sender
+                .send(Ok(state
+                    .connection_metadata()
+                    .iter()
+                    .map(|(_, metadata)| metadata.details())
+                    .collect()))
+                .is_err()
+            {
+                warn!("connector dropped before receiving result of list connection details");
+            }
+        }
+        CmRequest::DebugDump { sender } => {
+            if sender.send(Ok(state.debug_state())).is_err() {
+                warn!("connector dropped before receiving result of debug dump");
+            }
+        }
         CmRequest::AddInboundConnection { sender, connection } => {
             state.add_inbound_connection(connection, sender, internal_sender, authorizer)
         }
-        CmRequest::Subscribe { sender, callback } => {
-            let subscriber_id = subscribers.add_subscriber(callback);
+        CmRequest::Subscribe {
+            sender,
+            callback,
+            filter,
+        } => {
+            let subscriber_id = subscribers.add_subscriber(callback, filter);
             if sender.send(Ok(subscriber_id)).is_err() {
                 warn!("connector dropped before receiving result of remove connection");
             }
@@ -279,6 +434,17 @@ fn handle_request<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
                 warn!("connector dropped before receiving result of remove connection");
             }
         }
+        CmRequest::ForceReconnect {
+            connection_id,
+            sender,
+        } => {
+            let response =
+                state.force_reconnect(&connection_id, subscribers, authorizer, internal_sender);
+
+            if sender.send(response).is_err() {
+                warn!("connector dropped before receiving result of force reconnect");
+            }
+        }
     };
 }
 
@@ -287,6 +453,7 @@ fn handle_auth_result<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
     auth_result: AuthResult,
     state: &mut ConnectionManagerState<T, U>,
     subscribers: &mut SubscriberMap,
+    authorizer: &dyn Authorizer,
 ) {
     match auth_result {
         AuthResult::Outbound {
@@ -302,6 +469,9 @@ fn handle_auth_result<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
             state.on_inbound_authorization_complete(endpoint, auth_result, subscribers);
         }
     }
+
+    // The completed authorization may have made room for a queued one
+    state.dispatch_queued_authorizations(authorizer);
 }
 
 /// Auxiliary method for handling CmManager::SendHeartBeats messages sent to
@@ -320,8 +490,15 @@ fn send_heartbeats<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
         }
     };
 
+    state.expire_pending_authorizations(subscribers);
+    state.dispatch_queued_authorizations(authorizer);
+
     let matrix_sender = state.matrix_sender();
+    let connection_aliases = state.connection_aliases.clone();
+    let inbound_reconnection = state.inbound_reconnection;
     let mut reconnections = vec![];
+    let mut promotions = vec![];
+    let mut heartbeats_sent = 0;
     for (connection_id, metadata) in state.connection_metadata_mut().iter_mut() {
         match metadata.extended_metadata {
             ConnectionMetadataExt::Outbound {
@@ -336,6 +513,13 @@ fn send_heartbeats<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
                         reconnections.push(metadata.clone());
                     }
                 } else {
+                    notify_if_backpressured(
+                        &matrix_sender,
+                        &metadata.endpoint,
+                        &metadata.connection_id,
+                        &connection_aliases,
+                        subscribers,
+                    );
                     trace!(
                         "Sending heartbeat to {} ({})",
                         metadata.endpoint(),
@@ -352,19 +536,34 @@ fn send_heartbeats<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
                             err
                         );
 
-                        subscribers.broadcast(ConnectionManagerNotification::Disconnected {
-                            endpoint: metadata.endpoint.clone(),
-                            identity: metadata.identity.clone(),
-                            connection_id: metadata.connection_id.clone(),
-                        });
+                        subscribers.broadcast_with_aliases(
+                            ConnectionManagerNotification::Disconnected {
+                                endpoint: metadata.endpoint.clone(),
+                                identity: metadata.identity.clone(),
+                                connection_id: metadata.connection_id.clone(),
+                            },
+                            &connection_aliases,
+                        );
                         reconnections.push(metadata.clone());
+                    } else {
+                        heartbeats_sent += 1;
                     }
                 }
             }
             ConnectionMetadataExt::Inbound {
                 ref mut disconnected,
+                ref dial_back_endpoint,
                 ..
             } => {
+                if !*disconnected {
+                    notify_if_backpressured(
+                        &matrix_sender,
+                        &metadata.endpoint,
+                        &metadata.connection_id,
+                        &connection_aliases,
+                        subscribers,
+                    );
+                }
                 trace!(
                     "Sending heartbeat to {} ({})",
                     metadata.endpoint,
@@ -380,19 +579,37 @@ fn send_heartbeats<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
 
                     if !*disconnected {
                         *disconnected = true;
-                        subscribers.broadcast(ConnectionManagerNotification::Disconnected {
-                            endpoint: metadata.endpoint.clone(),
-                            identity: metadata.identity.clone(),
-                            connection_id: metadata.connection_id.clone(),
-                        });
+                        subscribers.broadcast_with_aliases(
+                            ConnectionManagerNotification::Disconnected {
+                                endpoint: metadata.endpoint.clone(),
+                                identity: metadata.identity.clone(),
+                                connection_id: metadata.connection_id.clone(),
+                            },
+                            &connection_aliases,
+                        );
+                    }
+
+                    if inbound_reconnection && dial_back_endpoint.is_some() {
+                        promotions.push(connection_id.clone());
                     }
                 } else {
                     *disconnected = false;
+                    heartbeats_sent += 1;
                 }
             }
         }
     }
 
+    for _ in 0..heartbeats_sent {
+        state.metrics.increment_heartbeats_sent();
+    }
+
+    for connection_id in promotions {
+        if let Some(metadata) = state.promote_inbound_connection(&connection_id) {
+            reconnections.push(metadata);
+        }
+    }
+
     for metadata in reconnections {
         if let Err(err) = state.reconnect(
             metadata.endpoint(),
@@ -411,6 +628,32 @@ fn send_heartbeats<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
     }
 }
 
+/// Broadcasts a `Backpressure` notification if the matrix sender reports that the connection's
+/// outgoing queue has exceeded its high-water mark.
+fn notify_if_backpressured<U: ConnectionMatrixSender>(
+    matrix_sender: &U,
+    endpoint: &str,
+    connection_id: &str,
+    connection_aliases: &HashMap<String, String>,
+    subscribers: &mut SubscriberMap,
+) {
+    if matrix_sender.is_backpressured(connection_id) {
+        let queue_depth = matrix_sender.queue_depth(connection_id).unwrap_or_default();
+        debug!(
+            "Connection {} ({}) is backpressured with {} queued messages",
+            endpoint, connection_id, queue_depth
+        );
+        subscribers.broadcast_with_aliases(
+            ConnectionManagerNotification::Backpressure {
+                endpoint: endpoint.to_string(),
+                connection_id: connection_id.to_string(),
+                queue_depth,
+            },
+            connection_aliases,
+        );
+    }
+}
+
 /// Creates NetworkHeartbeat message and serializes it into a byte array.
 fn create_heartbeat() -> Result<Vec<u8>, ConnectionManagerError> {
     IntoBytes::<network::NetworkMessage>::into_bytes(NetworkMessage::NetworkHeartbeat(
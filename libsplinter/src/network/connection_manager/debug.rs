@@ -0,0 +1,257 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A serializable snapshot of the connection manager's internal state, for debugging.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::hex::to_hex;
+use crate::network::auth::ConnectionAuthorizationType;
+use crate::transport::matrix::{ConnectionMatrixLifeCycle, ConnectionMatrixSender};
+
+use super::{ConnectionManagerState, ConnectionMetadata, ConnectionMetadataExt};
+
+/// The internal state of a connection manager, as returned by `Connector::debug_dump`.
+///
+/// The connections themselves are not included. Identities are reported as the trust identity or
+/// the hex-encoded public key of the connection, so the dump contains no secret material.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ConnectionManagerDebugState {
+    connections: Vec<ConnectionDebugState>,
+    pending_authorizations: Vec<PendingAuthorizationDebugState>,
+    queued_authorizations: Vec<String>,
+    connection_aliases: BTreeMap<String, String>,
+    maximum_retry_frequency: u64,
+    endpoint_deduplication: bool,
+    inbound_reconnection: bool,
+}
+
+impl ConnectionManagerDebugState {
+    /// Returns the connections managed by the connection manager, ordered by connection ID.
+    pub fn connections(&self) -> &[ConnectionDebugState] {
+        &self.connections
+    }
+
+    /// Returns the authorizations that have been started, but have not yet completed, ordered by
+    /// connection ID.
+    pub fn pending_authorizations(&self) -> &[PendingAuthorizationDebugState] {
+        &self.pending_authorizations
+    }
+
+    /// Returns the connection IDs of the authorizations waiting to be passed to the authorizer,
+    /// in the order they will be passed.
+    pub fn queued_authorizations(&self) -> &[String] {
+        &self.queued_authorizations
+    }
+
+    /// Returns the connection IDs that are aliases of another connection, mapped to the ID of that
+    /// connection.
+    pub fn connection_aliases(&self) -> &BTreeMap<String, String> {
+        &self.connection_aliases
+    }
+
+    /// Returns the maximum number of seconds between reconnection attempts.
+    pub fn maximum_retry_frequency(&self) -> u64 {
+        self.maximum_retry_frequency
+    }
+
+    /// Returns true if connection requests for an existing endpoint reuse its connection.
+    pub fn endpoint_deduplication(&self) -> bool {
+        self.endpoint_deduplication
+    }
+
+    /// Returns true if disconnected inbound connections with a dial-back endpoint are reconnected.
+    pub fn inbound_reconnection(&self) -> bool {
+        self.inbound_reconnection
+    }
+}
+
+/// The state of a single connection managed by the connection manager.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ConnectionDebugState {
+    connection_id: String,
+    endpoint: String,
+    identity: String,
+    connected_duration: Duration,
+    direction: ConnectionDirectionDebugState,
+}
+
+impl ConnectionDebugState {
+    /// Returns the ID of the connection.
+    pub fn connection_id(&self) -> &str {
+        &self.connection_id
+    }
+
+    /// Returns the remote endpoint of the connection.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Returns the authorized identity of the remote side of the connection.
+    pub fn identity(&self) -> &str {
+        &self.identity
+    }
+
+    /// Returns how long the connection has been established.
+    pub fn connected_duration(&self) -> Duration {
+        self.connected_duration
+    }
+
+    /// Returns the state that is specific to the direction of the connection.
+    pub fn direction(&self) -> &ConnectionDirectionDebugState {
+        &self.direction
+    }
+}
+
+/// The state of a connection that is specific to whether it is outbound or inbound.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(tag = "direction", rename_all = "snake_case")]
+pub enum ConnectionDirectionDebugState {
+    Outbound {
+        reconnecting: bool,
+        /// The number of seconds between reconnection attempts
+        retry_frequency: u64,
+        reconnection_attempts: u64,
+        since_last_connection_attempt: Duration,
+        expected_authorization: String,
+        local_authorization: String,
+    },
+    Inbound {
+        disconnected: bool,
+        local_authorization: String,
+        dial_back_endpoint: Option<String>,
+    },
+}
+
+/// An authorization that has been started, but has not yet completed.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PendingAuthorizationDebugState {
+    connection_id: String,
+    endpoint: String,
+    pending_duration: Duration,
+}
+
+impl PendingAuthorizationDebugState {
+    /// Returns the ID of the connection being authorized.
+    pub fn connection_id(&self) -> &str {
+        &self.connection_id
+    }
+
+    /// Returns the remote endpoint of the connection being authorized.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Returns how long the authorization has been pending.
+    pub fn pending_duration(&self) -> Duration {
+        self.pending_duration
+    }
+}
+
+impl<T, U> ConnectionManagerState<T, U>
+where
+    T: ConnectionMatrixLifeCycle,
+    U: ConnectionMatrixSender,
+{
+    /// Captures the state for `Connector::debug_dump`.
+    pub(super) fn debug_state(&self) -> ConnectionManagerDebugState {
+        let mut connections: Vec<ConnectionDebugState> = self
+            .connections
+            .values()
+            .map(ConnectionDebugState::from)
+            .collect();
+        connections.sort_by(|a, b| a.connection_id.cmp(&b.connection_id));
+
+        let mut pending_authorizations: Vec<PendingAuthorizationDebugState> = self
+            .pending_authorizations
+            .iter()
+            .map(|(connection_id, pending)| PendingAuthorizationDebugState {
+                connection_id: connection_id.clone(),
+                endpoint: pending.endpoint.clone(),
+                pending_duration: pending.started.elapsed(),
+            })
+            .collect();
+        pending_authorizations.sort_by(|a, b| a.connection_id.cmp(&b.connection_id));
+
+        ConnectionManagerDebugState {
+            connections,
+            pending_authorizations,
+            queued_authorizations: self
+                .queued_authorizations
+                .iter()
+                .map(|queued| queued.connection_id.clone())
+                .collect(),
+            connection_aliases: self
+                .connection_aliases
+                .iter()
+                .map(|(alias, connection_id)| (alias.clone(), connection_id.clone()))
+                .collect(),
+            maximum_retry_frequency: self.maximum_retry_frequency,
+            endpoint_deduplication: self.endpoint_deduplication,
+            inbound_reconnection: self.inbound_reconnection,
+        }
+    }
+}
+
+impl From<&ConnectionMetadata> for ConnectionDebugState {
+    fn from(metadata: &ConnectionMetadata) -> Self {
+        let direction = match &metadata.extended_metadata {
+            ConnectionMetadataExt::Outbound {
+                reconnecting,
+                retry_frequency,
+                last_connection_attempt,
+                reconnection_attempts,
+                expected_authorization,
+                local_authorization,
+            } => ConnectionDirectionDebugState::Outbound {
+                reconnecting: *reconnecting,
+                retry_frequency: *retry_frequency,
+                reconnection_attempts: *reconnection_attempts,
+                since_last_connection_attempt: last_connection_attempt.elapsed(),
+                expected_authorization: describe_identity(expected_authorization),
+                local_authorization: describe_identity(local_authorization),
+            },
+            ConnectionMetadataExt::Inbound {
+                disconnected,
+                local_authorization,
+                dial_back_endpoint,
+            } => ConnectionDirectionDebugState::Inbound {
+                disconnected: *disconnected,
+                local_authorization: describe_identity(local_authorization),
+                dial_back_endpoint: dial_back_endpoint.clone(),
+            },
+        };
+
+        ConnectionDebugState {
+            connection_id: metadata.connection_id.clone(),
+            endpoint: metadata.endpoint.clone(),
+            identity: describe_identity(&metadata.identity),
+            connected_duration: metadata.established.elapsed(),
+            direction,
+        }
+    }
+}
+
+/// Describes an identity by its trust identity, or by its hex-encoded public key.
+fn describe_identity(identity: &ConnectionAuthorizationType) -> String {
+    match identity {
+        ConnectionAuthorizationType::Trust { identity } => format!("trust:{}", identity),
+        ConnectionAuthorizationType::Challenge { public_key } => {
+            format!("challenge:{}", to_hex(public_key.as_slice()))
+        }
+    }
+}
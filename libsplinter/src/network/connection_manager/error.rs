@@ -14,6 +14,8 @@
 
 use std::{error, fmt, io};
 
+use super::UnauthorizedReason;
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum ConnectionManagerError {
     StartUpError(String),
@@ -26,8 +28,12 @@ pub enum ConnectionManagerError {
     },
     ConnectionRemovalError(String),
     ConnectionReconnectError(String),
-    Unauthorized(String),
+    Unauthorized {
+        connection_id: String,
+        reason: UnauthorizedReason,
+    },
     StatePoisoned,
+    Timeout(String),
 }
 
 impl ConnectionManagerError {
@@ -58,12 +64,18 @@ impl fmt::Display for ConnectionManagerError {
             ConnectionManagerError::ConnectionCreationError { context, .. } => f.write_str(context),
             ConnectionManagerError::ConnectionRemovalError(ref s) => f.write_str(s),
             ConnectionManagerError::ConnectionReconnectError(ref s) => f.write_str(s),
-            ConnectionManagerError::Unauthorized(ref connection_id) => {
-                write!(f, "Connection {} failed authorization", connection_id)
-            }
+            ConnectionManagerError::Unauthorized {
+                ref connection_id,
+                ref reason,
+            } => write!(
+                f,
+                "Connection {} failed authorization: {}",
+                connection_id, reason
+            ),
             ConnectionManagerError::StatePoisoned => {
                 f.write_str("Connection state has been poisoned")
             }
+            ConnectionManagerError::Timeout(ref s) => f.write_str(s),
         }
     }
 }
@@ -0,0 +1,50 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Metrics recorded by the connection manager.
+//!
+//! A `ConnectionManagerMetrics` implementation may be provided to the `ConnectionManagerBuilder`
+//! to export counters, such as to Prometheus. By default, no metrics are recorded.
+
+/// Receives the counter increments recorded by a connection manager.
+///
+/// Each method is called from the connection manager's background thread, so implementations
+/// should not block.
+pub trait ConnectionManagerMetrics: Send {
+    /// Called when a connection has been authorized and added to the connection manager.
+    fn increment_connections_established(&self);
+
+    /// Called when an attempt is made to reconnect an outbound connection.
+    fn increment_reconnection_attempts(&self);
+
+    /// Called when a connection fails authorization.
+    fn increment_authorization_failures(&self);
+
+    /// Called when a heartbeat has been sent to a connection.
+    fn increment_heartbeats_sent(&self);
+}
+
+/// A `ConnectionManagerMetrics` implementation that does not record anything.
+#[derive(Default)]
+pub struct NoopConnectionManagerMetrics;
+
+impl ConnectionManagerMetrics for NoopConnectionManagerMetrics {
+    fn increment_connections_established(&self) {}
+
+    fn increment_reconnection_attempts(&self) {}
+
+    fn increment_authorization_failures(&self) {}
+
+    fn increment_heartbeats_sent(&self) {}
+}
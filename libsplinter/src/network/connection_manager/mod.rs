@@ -14,19 +14,25 @@
 
 pub mod authorizers;
 mod builder;
+mod debug;
 mod error;
+mod metrics;
 mod notification;
 
 use std::cmp::min;
-use std::collections::HashMap;
-use std::sync::mpsc::{channel, Sender};
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
 use std::thread;
-use std::time::Instant;
-
-use uuid::Uuid;
+use std::time::{Duration, Instant};
 
 pub use builder::ConnectionManagerBuilder;
+pub use debug::{
+    ConnectionDebugState, ConnectionDirectionDebugState, ConnectionManagerDebugState,
+    PendingAuthorizationDebugState,
+};
 pub use error::{AuthorizerError, ConnectionManagerError};
+pub use metrics::{ConnectionManagerMetrics, NoopConnectionManagerMetrics};
 pub use notification::ConnectionManagerNotification;
 
 use crate::error::InternalError;
@@ -36,11 +42,46 @@ use crate::threading::pacemaker;
 use crate::transport::matrix::{ConnectionMatrixLifeCycle, ConnectionMatrixSender};
 use crate::transport::{ConnectError, Connection, Transport};
 
+/// Logs a message about a single connection.
+///
+/// Each message is prefixed with the connection ID and endpoint as `key=value` pairs, so the
+/// records for one connection can be followed through its life cycle, including reconnections.
+macro_rules! connection_log {
+    ($level:ident, $connection_id:expr, $endpoint:expr, $($arg:tt)+) => {
+        log!(
+            log::Level::$level,
+            "connection_id={} endpoint={}: {}",
+            $connection_id,
+            $endpoint,
+            format_args!($($arg)+)
+        )
+    };
+}
+
 const INITIAL_RETRY_FREQUENCY: u64 = 10;
 
 pub type AuthorizerCallback =
     Box<dyn Fn(AuthorizationResult) -> Result<(), Box<dyn std::error::Error>> + Send>;
 
+/// Produces the connection ID for an inbound connection.
+pub type ConnectionIdGenerator = Box<dyn Fn(&dyn Connection) -> String + Send>;
+
+/// Determines how a connection manager handles a request for an existing outbound connection ID
+/// with a different endpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EndpointChangePolicy {
+    /// Connect to the new endpoint under the existing connection ID
+    Migrate,
+    /// Refuse the request with a `ConnectionManagerError`
+    Reject,
+}
+
+impl Default for EndpointChangePolicy {
+    fn default() -> Self {
+        EndpointChangePolicy::Migrate
+    }
+}
+
 pub trait Authorizer {
     fn authorize_connection(
         &self,
@@ -59,20 +100,146 @@ pub enum AuthorizationResult {
         connection: Box<dyn Connection>,
         expected_authorization: ConnectionAuthorizationType,
         local_authorization: ConnectionAuthorizationType,
+        /// The endpoint the remote node may be dialed back on, if it was learned during
+        /// authorization. Only used for inbound connections.
+        dial_back_endpoint: Option<String>,
     },
     Unauthorized {
         connection_id: String,
         connection: Box<dyn Connection>,
+        /// Why the connection failed authorization
+        reason: UnauthorizedReason,
     },
 }
 
+impl AuthorizationResult {
+    fn connection_id(&self) -> &str {
+        match self {
+            AuthorizationResult::Authorized { connection_id, .. } => connection_id,
+            AuthorizationResult::Unauthorized { connection_id, .. } => connection_id,
+        }
+    }
+}
+
+/// The reason a connection failed authorization.
+#[derive(Clone, Debug, PartialEq)]
+pub enum UnauthorizedReason {
+    /// The remote node did not provide an identity that is permitted to connect
+    UnknownIdentity,
+    /// The remote node could not prove its identity, as its signature was not valid
+    InvalidSignature,
+    /// The nodes do not support a common authorization protocol or authorization type
+    ProtocolMismatch,
+    /// The remote node sent a message that is not valid at its point in the authorization
+    ProtocolError(String),
+    /// The remote node rejected the local node's authorization, with the given message
+    RejectedByRemote(String),
+    /// The authorization was abandoned before it completed, such as when the connection was lost
+    Aborted(String),
+}
+
+impl std::fmt::Display for UnauthorizedReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            UnauthorizedReason::UnknownIdentity => f.write_str("unknown identity"),
+            UnauthorizedReason::InvalidSignature => f.write_str("invalid signature"),
+            UnauthorizedReason::ProtocolMismatch => f.write_str("protocol mismatch"),
+            UnauthorizedReason::ProtocolError(msg) => write!(f, "protocol error: {}", msg),
+            UnauthorizedReason::RejectedByRemote(msg) => {
+                write!(f, "rejected by remote node: {}", msg)
+            }
+            UnauthorizedReason::Aborted(msg) => write!(f, "aborted: {}", msg),
+        }
+    }
+}
+
+/// Waits for the connection with the given ID to be reported as connected or failed, returning
+/// the remote identity of the connection.
+fn wait_for_connection(
+    notifications: &Receiver<ConnectionManagerNotification>,
+    connection_id: &str,
+    deadline: Instant,
+) -> Result<ConnectionAuthorizationType, ConnectionManagerError> {
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match notifications.recv_timeout(remaining) {
+            Ok(ConnectionManagerNotification::Connected {
+                connection_id: id,
+                identity,
+                ..
+            }) if id == connection_id => return Ok(identity),
+            Ok(ConnectionManagerNotification::FatalConnectionError {
+                connection_id: id,
+                error,
+                ..
+            }) if id == connection_id => return Err(error),
+            Ok(_) => (),
+            Err(RecvTimeoutError::Timeout) => {
+                return Err(ConnectionManagerError::Timeout(format!(
+                    "Connection {} was not established in time",
+                    connection_id
+                )))
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(ConnectionManagerError::SendMessageError(
+                    "The connection manager is no longer running".into(),
+                ))
+            }
+        }
+    }
+}
+
+/// Disconnects a connection whose authorization completed after it had already timed out.
+fn drop_timed_out_connection(endpoint: &str, auth_result: AuthorizationResult) {
+    let (connection_id, mut connection) = match auth_result {
+        AuthorizationResult::Authorized {
+            connection_id,
+            connection,
+            ..
+        } => (connection_id, connection),
+        AuthorizationResult::Unauthorized {
+            connection_id,
+            connection,
+            ..
+        } => (connection_id, connection),
+    };
+
+    connection_log!(
+        Debug,
+        connection_id,
+        endpoint,
+        "Dropping connection: authorization completed after timing out"
+    );
+    if let Err(err) = connection.disconnect() {
+        connection_log!(
+            Debug,
+            connection_id,
+            endpoint,
+            "Unable to disconnect timed out connection: {}",
+            err
+        );
+    }
+}
+
+/// Returns the kind of the I/O error that caused a connection attempt to fail, if any.
+fn connect_error_kind(err: &ConnectError) -> Option<io::ErrorKind> {
+    match err {
+        ConnectError::IoError(io_err) => Some(io_err.kind()),
+        _ => None,
+    }
+}
+
 pub type SubscriberId = usize;
 type Subscriber =
     Box<dyn Fn(ConnectionManagerNotification) -> Result<(), Box<dyn std::error::Error>> + Send>;
+type SubscriberFilter = Box<dyn Fn(&ConnectionManagerNotification) -> bool + Send>;
 
 /// Responsible for broadcasting connection manager notifications.
+///
+/// A subscriber may have a filter, in which case it is only sent the notifications that match
+/// the filter.
 struct SubscriberMap {
-    subscribers: HashMap<SubscriberId, Subscriber>,
+    subscribers: HashMap<SubscriberId, (Subscriber, Option<SubscriberFilter>)>,
     next_id: SubscriberId,
 }
 
@@ -86,7 +253,13 @@ impl SubscriberMap {
 
     fn broadcast(&mut self, notification: ConnectionManagerNotification) {
         let mut failures = vec![];
-        for (id, callback) in self.subscribers.iter() {
+        for (id, (callback, filter)) in self.subscribers.iter() {
+            if let Some(filter) = filter {
+                if !(*filter)(&notification) {
+                    continue;
+                }
+            }
+
             if let Err(err) = (*callback)(notification.clone()) {
                 failures.push(*id);
                 debug!("Dropping subscriber ({}): {}", id, err);
@@ -98,10 +271,33 @@ impl SubscriberMap {
         }
     }
 
-    fn add_subscriber(&mut self, subscriber: Subscriber) -> SubscriberId {
+    /// Broadcasts a copy of the notification for each alias of its connection, followed by the
+    /// notification itself.
+    fn broadcast_with_aliases(
+        &mut self,
+        notification: ConnectionManagerNotification,
+        connection_aliases: &HashMap<String, String>,
+    ) {
+        let aliases = connection_aliases
+            .iter()
+            .filter(|(_, existing_id)| existing_id.as_str() == notification.connection_id())
+            .map(|(alias, _)| alias.clone())
+            .collect::<Vec<_>>();
+
+        for alias in aliases {
+            self.broadcast(notification.clone().with_connection_id(alias));
+        }
+        self.broadcast(notification);
+    }
+
+    fn add_subscriber(
+        &mut self,
+        subscriber: Subscriber,
+        filter: Option<SubscriberFilter>,
+    ) -> SubscriberId {
         let subscriber_id = self.next_id;
         self.next_id += 1;
-        self.subscribers.insert(subscriber_id, subscriber);
+        self.subscribers.insert(subscriber_id, (subscriber, filter));
 
         subscriber_id
     }
@@ -136,6 +332,12 @@ enum CmRequest {
     ListConnections {
         sender: Sender<Result<Vec<String>, ConnectionManagerError>>,
     },
+    ListConnectionDetails {
+        sender: Sender<Result<Vec<ConnectionDetails>, ConnectionManagerError>>,
+    },
+    DebugDump {
+        sender: Sender<Result<ConnectionManagerDebugState, ConnectionManagerError>>,
+    },
     AddInboundConnection {
         connection: Box<dyn Connection>,
         sender: Sender<Result<(), ConnectionManagerError>>,
@@ -143,11 +345,16 @@ enum CmRequest {
     Subscribe {
         sender: Sender<Result<SubscriberId, ConnectionManagerError>>,
         callback: Subscriber,
+        filter: Option<SubscriberFilter>,
     },
     Unsubscribe {
         subscriber_id: SubscriberId,
         sender: Sender<Result<(), ConnectionManagerError>>,
     },
+    ForceReconnect {
+        connection_id: String,
+        sender: Sender<Result<(), ConnectionManagerError>>,
+    },
 }
 
 /// Messages sent to ConnectionState to report on the status of a connection
@@ -215,6 +422,119 @@ impl Connector {
         expected_authorization: Option<ConnectionAuthorizationType>,
         local_authorization: Option<ConnectionAuthorizationType>,
     ) -> Result<(), ConnectionManagerError> {
+        let recv = self.send_connection_request(
+            endpoint,
+            connection_id,
+            expected_authorization,
+            local_authorization,
+        )?;
+
+        recv.recv().map_err(|_| {
+            ConnectionManagerError::SendMessageError(
+                "The connection manager is no longer running: could not receive response".into(),
+            )
+        })?
+    }
+
+    /// Request a connection to the given endpoint with a provided connection ID, waiting at most
+    /// the given timeout for the connection manager to respond.
+    ///
+    /// This behaves like `request_connection`, except that it will not block indefinitely if the
+    /// connection manager is busy. If the timeout elapses, the request is still processed by the
+    /// connection manager, but its result is not reported to the caller.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConnectionManagerError::Timeout` if the connection manager does not respond
+    /// within the timeout, or another error if the connection cannot be created.
+    pub fn request_connection_timeout(
+        &self,
+        endpoint: &str,
+        connection_id: &str,
+        expected_authorization: Option<ConnectionAuthorizationType>,
+        local_authorization: Option<ConnectionAuthorizationType>,
+        timeout: Duration,
+    ) -> Result<(), ConnectionManagerError> {
+        let recv = self.send_connection_request(
+            endpoint,
+            connection_id,
+            expected_authorization,
+            local_authorization,
+        )?;
+
+        recv.recv_timeout(timeout).map_err(|err| match err {
+            RecvTimeoutError::Timeout => ConnectionManagerError::Timeout(format!(
+                "The connection manager did not respond to the request for connection {} \
+                 within {:?}",
+                connection_id, timeout
+            )),
+            RecvTimeoutError::Disconnected => ConnectionManagerError::SendMessageError(
+                "The connection manager is no longer running: could not receive response".into(),
+            ),
+        })?
+    }
+
+    /// Request a connection to the given endpoint with a provided connection ID, and wait until
+    /// the connection has completed authorization.
+    ///
+    /// This subscribes to the connection manager's notifications for the duration of the call,
+    /// and blocks until a `Connected` or `FatalConnectionError` notification is received for the
+    /// given connection ID. If the connection already exists, it is reported as connected.
+    ///
+    /// # Returns
+    ///
+    /// The identity of the remote node, as resolved by authorization.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConnectionManagerError::Timeout` if the connection is not established within the
+    /// timeout, the error reported by the `FatalConnectionError` notification if the connection
+    /// fails, or another error if the connection cannot be created.
+    pub fn request_connection_and_wait(
+        &self,
+        endpoint: &str,
+        connection_id: &str,
+        expected_authorization: Option<ConnectionAuthorizationType>,
+        local_authorization: Option<ConnectionAuthorizationType>,
+        timeout: Duration,
+    ) -> Result<ConnectionAuthorizationType, ConnectionManagerError> {
+        let deadline = Instant::now() + timeout;
+
+        let (sender, notifications) = channel();
+        let id = connection_id.to_string();
+        let subscriber_id = self.subscribe_filtered(sender, move |notification| {
+            notification.connection_id() == id
+        })?;
+
+        let result = self
+            .request_connection_timeout(
+                endpoint,
+                connection_id,
+                expected_authorization,
+                local_authorization,
+                timeout,
+            )
+            .and_then(|_| wait_for_connection(&notifications, connection_id, deadline));
+
+        if let Err(err) = self.unsubscribe(subscriber_id) {
+            debug!(
+                "Unable to unsubscribe after waiting for connection {}: {}",
+                connection_id, err
+            );
+        }
+
+        result
+    }
+
+    /// Sends an outbound connection request to the connection manager, returning the channel on
+    /// which the result will be received.
+    fn send_connection_request(
+        &self,
+        endpoint: &str,
+        connection_id: &str,
+        expected_authorization: Option<ConnectionAuthorizationType>,
+        local_authorization: Option<ConnectionAuthorizationType>,
+    ) -> Result<Receiver<Result<(), ConnectionManagerError>>, ConnectionManagerError> {
         let (sender, recv) = channel();
         self.sender
             .send(CmMessage::Request(CmRequest::RequestOutboundConnection {
@@ -230,11 +550,7 @@ impl Connector {
                 )
             })?;
 
-        recv.recv().map_err(|_| {
-            ConnectionManagerError::SendMessageError(
-                "The connection manager is no longer running: could not receive response".into(),
-            )
-        })?
+        Ok(recv)
     }
 
     /// Removes a connection from a connection manager.
@@ -271,6 +587,52 @@ impl Connector {
         })?
     }
 
+    /// Immediately attempts to reconnect an outbound connection.
+    ///
+    /// The connection's retry backoff is reset, so that the next attempt, should this one fail,
+    /// is made at the initial retry frequency rather than waiting out the current backoff
+    /// interval.
+    ///
+    /// # Errors
+    ///
+    /// Returns a ConnectionManagerError if the connection does not exist, is not an outbound
+    /// connection, or the reconnection cannot be started.
+    pub fn force_reconnect(&self, connection_id: &str) -> Result<(), ConnectionManagerError> {
+        let (sender, recv) = channel();
+        self.sender
+            .send(CmMessage::Request(CmRequest::ForceReconnect {
+                connection_id: connection_id.to_string(),
+                sender,
+            }))
+            .map_err(|_| {
+                ConnectionManagerError::SendMessageError(
+                    "The connection manager is no longer running".into(),
+                )
+            })?;
+
+        recv.recv().map_err(|_| {
+            ConnectionManagerError::SendMessageError(
+                "The connection manager is no longer running".into(),
+            )
+        })?
+    }
+
+    /// Returns the connections that are currently established, along with their identities.
+    ///
+    /// The snapshot is taken by the connection manager between the handling of other requests and
+    /// connection events, so it is consistent with the notifications sent to subscribers. A
+    /// component that subscribes after the connection manager has started should call `subscribe`
+    /// before `snapshot`: every connection event after the snapshot is then delivered to the
+    /// subscriber, while events between the two calls are reflected in both the snapshot and the
+    /// notifications, so they should be reconciled idempotently.
+    ///
+    /// # Errors
+    ///
+    /// Returns a ConnectionManagerError if the connections cannot be queried.
+    pub fn snapshot(&self) -> Result<Vec<ConnectionDetails>, ConnectionManagerError> {
+        self.list_connection_details()
+    }
+
     /// Subscribe to notifications for connection events.
     ///
     /// ConnectionManagerNotification instances will be transformed via type `T`'s implementation
@@ -288,6 +650,42 @@ impl Connector {
         &self,
         subscriber: Sender<T>,
     ) -> Result<SubscriberId, ConnectionManagerError>
+    where
+        T: From<ConnectionManagerNotification> + Send + 'static,
+    {
+        self.add_subscriber(subscriber, None)
+    }
+
+    /// Subscribe to the notifications for connection events that match the given filter.
+    ///
+    /// This behaves like `subscribe`, except that the connection manager only sends the
+    /// notifications for which `filter` returns `true` to the given sender.
+    ///
+    /// # Returns
+    ///
+    /// The subscriber id that can be used for unsubscribing the given sender.
+    ///
+    /// # Errors
+    ///
+    /// Return a ConnectionManagerError if the subscriber cannot be registered via the Connector
+    /// instance.
+    pub fn subscribe_filtered<T, F>(
+        &self,
+        subscriber: Sender<T>,
+        filter: F,
+    ) -> Result<SubscriberId, ConnectionManagerError>
+    where
+        T: From<ConnectionManagerNotification> + Send + 'static,
+        F: Fn(&ConnectionManagerNotification) -> bool + Send + 'static,
+    {
+        self.add_subscriber(subscriber, Some(Box::new(filter)))
+    }
+
+    fn add_subscriber<T>(
+        &self,
+        subscriber: Sender<T>,
+        filter: Option<SubscriberFilter>,
+    ) -> Result<SubscriberId, ConnectionManagerError>
     where
         T: From<ConnectionManagerNotification> + Send + 'static,
     {
@@ -298,6 +696,7 @@ impl Connector {
                 callback: Box::new(move |notification| {
                     subscriber.send(T::from(notification)).map_err(Box::from)
                 }),
+                filter,
             }))
             .map_err(|_| {
                 ConnectionManagerError::SendMessageError(
@@ -364,6 +763,63 @@ impl Connector {
         })?
     }
 
+    /// List the details of the connections available to the connection manager.
+    ///
+    /// # Returns
+    ///
+    /// Returns a vector of connection details, including how long each connection has been
+    /// established.
+    ///
+    /// # Errors
+    ///
+    /// Returns a ConnectionManagerError if the connections cannot be queried.
+    pub fn list_connection_details(
+        &self,
+    ) -> Result<Vec<ConnectionDetails>, ConnectionManagerError> {
+        let (sender, recv) = channel();
+        self.sender
+            .send(CmMessage::Request(CmRequest::ListConnectionDetails {
+                sender,
+            }))
+            .map_err(|_| {
+                ConnectionManagerError::SendMessageError(
+                    "The connection manager is no longer running".into(),
+                )
+            })?;
+
+        recv.recv().map_err(|_| {
+            ConnectionManagerError::SendMessageError(
+                "The connection manager is no longer running".into(),
+            )
+        })?
+    }
+
+    /// Dump the full internal state of the connection manager, for debugging.
+    ///
+    /// The dump includes every connection with its direction-specific metadata, such as the
+    /// retry state of outbound connections, as well as the pending authorizations. It is
+    /// serializable, so it may be attached to a bug report.
+    ///
+    /// # Errors
+    ///
+    /// Returns a ConnectionManagerError if the connection manager is no longer running.
+    pub fn debug_dump(&self) -> Result<ConnectionManagerDebugState, ConnectionManagerError> {
+        let (sender, recv) = channel();
+        self.sender
+            .send(CmMessage::Request(CmRequest::DebugDump { sender }))
+            .map_err(|_| {
+                ConnectionManagerError::SendMessageError(
+                    "The connection manager is no longer running".into(),
+                )
+            })?;
+
+        recv.recv().map_err(|_| {
+            ConnectionManagerError::SendMessageError(
+                "The connection manager is no longer running".into(),
+            )
+        })?
+    }
+
     /// Add a new inbound connection.
     ///
     /// # Error
@@ -417,12 +873,56 @@ impl ShutdownHandle for ConnectionManager {
     }
 }
 
+/// The details of a connection managed by the connection manager, as returned by
+/// `Connector::list_connection_details`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConnectionDetails {
+    connection_id: String,
+    endpoint: String,
+    identity: ConnectionAuthorizationType,
+    outbound: bool,
+    connected_duration: Duration,
+}
+
+impl ConnectionDetails {
+    /// Returns the ID of the connection.
+    pub fn connection_id(&self) -> &str {
+        &self.connection_id
+    }
+
+    /// Returns the remote endpoint of the connection.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Returns the authorized identity of the remote side of the connection.
+    pub fn identity(&self) -> &ConnectionAuthorizationType {
+        &self.identity
+    }
+
+    /// Returns true if the connection was requested by the local node, or false if it was
+    /// accepted from a remote node.
+    pub fn is_outbound(&self) -> bool {
+        self.outbound
+    }
+
+    /// Returns how long the connection has been established.
+    ///
+    /// The duration is measured from when the connection last completed authorization, so it is
+    /// reset when an outbound connection is reconnected.
+    pub fn connected_duration(&self) -> Duration {
+        self.connected_duration
+    }
+}
+
 /// Metadata describing a connection managed by the connection manager.
 #[derive(Clone, Debug)]
 struct ConnectionMetadata {
     connection_id: String,
     endpoint: String,
     identity: ConnectionAuthorizationType,
+    /// The time at which the connection completed authorization, which is reset on reconnection
+    established: Instant,
     extended_metadata: ConnectionMetadataExt,
 }
 
@@ -445,6 +945,16 @@ impl ConnectionMetadata {
     fn identity(&self) -> &ConnectionAuthorizationType {
         &self.identity
     }
+
+    fn details(&self) -> ConnectionDetails {
+        ConnectionDetails {
+            connection_id: self.connection_id.clone(),
+            endpoint: self.endpoint.clone(),
+            identity: self.identity.clone(),
+            outbound: self.is_outbound(),
+            connected_duration: self.established.elapsed(),
+        }
+    }
 }
 
 /// Enum describing metadata that is specific to the two different connection
@@ -460,8 +970,14 @@ enum ConnectionMetadataExt {
         local_authorization: ConnectionAuthorizationType,
     },
     Inbound {
+        /// Set when a heartbeat to the connection fails. Inbound connections are not
+        /// reconnected; the flag is cleared if a later heartbeat succeeds.
         disconnected: bool,
         local_authorization: ConnectionAuthorizationType,
+        /// The endpoint learned during authorization that the remote node may be dialed back
+        /// on. If inbound reconnection is enabled, a disconnected inbound connection with a
+        /// dial-back endpoint is promoted to a reconnecting outbound connection.
+        dial_back_endpoint: Option<String>,
     },
 }
 
@@ -490,6 +1006,23 @@ impl ConnectionMetadataExt {
     }
 }
 
+/// An authorization that has been started, but has not yet completed.
+struct PendingAuthorization {
+    endpoint: String,
+    started: Instant,
+}
+
+/// An authorization that is waiting for the number of in-flight authorizations to drop below the
+/// limit before it is passed to the authorizer.
+struct QueuedAuthorization {
+    connection_id: String,
+    endpoint: String,
+    connection: Box<dyn Connection>,
+    on_complete: AuthorizerCallback,
+    expected_authorization: Option<ConnectionAuthorizationType>,
+    local_authorization: Option<ConnectionAuthorizationType>,
+}
+
 /// Information required to request an outboudn connection
 struct OutboundConnection {
     endpoint: String,
@@ -498,7 +1031,20 @@ struct OutboundConnection {
     local_authorization: Option<ConnectionAuthorizationType>,
 }
 
-/// Struct describing the connection manager's internal state and handling
+/// The options of a connection manager that are set by its builder
+struct ConnectionManagerConfig {
+    maximum_retry_frequency: u64,
+    authorization_timeout: Option<Duration>,
+    local_identity: Option<ConnectionAuthorizationType>,
+    metrics: Box<dyn ConnectionManagerMetrics>,
+    connection_id_generator: ConnectionIdGenerator,
+    endpoint_deduplication: bool,
+    endpoint_change_policy: EndpointChangePolicy,
+    max_concurrent_authorizations: Option<usize>,
+    inbound_reconnection: bool,
+}
+
+/// Struct describing the connection manager's internal state and handling
 /// requests sent to the connection manager by its Connectors. Connection state
 /// is responsible for adding, removing, and authorizing connections.
 struct ConnectionManagerState<T, U>
@@ -511,6 +1057,22 @@ where
     matrix_sender: U,
     transport: Box<dyn Transport>,
     maximum_retry_frequency: u64,
+    pending_authorizations: HashMap<String, PendingAuthorization>,
+    authorization_timeout: Option<Duration>,
+    local_identity: Option<ConnectionAuthorizationType>,
+    metrics: Box<dyn ConnectionManagerMetrics>,
+    connection_id_generator: ConnectionIdGenerator,
+    endpoint_deduplication: bool,
+    endpoint_change_policy: EndpointChangePolicy,
+    max_concurrent_authorizations: Option<usize>,
+    /// Authorizations that have been started, but not yet passed to the authorizer. Each is also
+    /// in `pending_authorizations`.
+    queued_authorizations: VecDeque<QueuedAuthorization>,
+    /// Connection IDs that were requested for an endpoint that already had a connection, mapped
+    /// to the ID of that connection.
+    connection_aliases: HashMap<String, String>,
+    /// Whether disconnected inbound connections with a dial-back endpoint are reconnected.
+    inbound_reconnection: bool,
 }
 
 impl<T, U> ConnectionManagerState<T, U>
@@ -522,14 +1084,175 @@ where
         life_cycle: T,
         matrix_sender: U,
         transport: Box<dyn Transport + Send>,
-        maximum_retry_frequency: u64,
+        config: ConnectionManagerConfig,
     ) -> Self {
         Self {
             life_cycle,
             matrix_sender,
             transport,
             connections: HashMap::new(),
-            maximum_retry_frequency,
+            maximum_retry_frequency: config.maximum_retry_frequency,
+            pending_authorizations: HashMap::new(),
+            authorization_timeout: config.authorization_timeout,
+            local_identity: config.local_identity,
+            metrics: config.metrics,
+            connection_id_generator: config.connection_id_generator,
+            endpoint_deduplication: config.endpoint_deduplication,
+            endpoint_change_policy: config.endpoint_change_policy,
+            max_concurrent_authorizations: config.max_concurrent_authorizations,
+            queued_authorizations: VecDeque::new(),
+            connection_aliases: HashMap::new(),
+            inbound_reconnection: config.inbound_reconnection,
+        }
+    }
+
+    /// Records that the authorization of a connection has started.
+    fn start_authorization(&mut self, connection_id: &str, endpoint: &str) {
+        self.pending_authorizations.insert(
+            connection_id.to_string(),
+            PendingAuthorization {
+                endpoint: endpoint.to_string(),
+                started: Instant::now(),
+            },
+        );
+    }
+
+    /// Records that the authorization of a connection has completed.
+    ///
+    /// # Returns
+    ///
+    /// False if the authorization was not pending, because it had already timed out; true
+    /// otherwise.
+    fn complete_authorization(&mut self, connection_id: &str) -> bool {
+        self.pending_authorizations.remove(connection_id).is_some()
+    }
+
+    /// Starts the authorization of a connection, passing it to the authorizer unless the maximum
+    /// number of concurrent authorizations has been reached, in which case it is queued.
+    ///
+    /// # Errors
+    ///
+    /// Returns the authorizer's error if it is unable to start the authorization.
+    fn authorize(
+        &mut self,
+        queued: QueuedAuthorization,
+        authorizer: &dyn Authorizer,
+    ) -> Result<(), AuthorizerError> {
+        // A new authorization of the same connection replaces one that is still queued
+        self.queued_authorizations
+            .retain(|existing| existing.connection_id != queued.connection_id);
+        let at_limit = self.authorization_limit_reached();
+        self.start_authorization(&queued.connection_id, &queued.endpoint);
+
+        if at_limit {
+            connection_log!(
+                Debug,
+                queued.connection_id,
+                queued.endpoint,
+                "Queueing authorization of connection"
+            );
+            self.queued_authorizations.push_back(queued);
+            return Ok(());
+        }
+
+        let connection_id = queued.connection_id.clone();
+        authorizer
+            .authorize_connection(
+                queued.connection_id,
+                queued.connection,
+                queued.on_complete,
+                queued.expected_authorization,
+                queued.local_authorization,
+            )
+            .map_err(|err| {
+                self.complete_authorization(&connection_id);
+                err
+            })
+    }
+
+    /// Passes queued authorizations to the authorizer, in the order they were queued, until the
+    /// maximum number of concurrent authorizations is reached.
+    fn dispatch_queued_authorizations(&mut self, authorizer: &dyn Authorizer) {
+        while !self.authorization_limit_reached() {
+            let queued = match self.queued_authorizations.pop_front() {
+                Some(queued) => queued,
+                None => break,
+            };
+
+            let endpoint = queued.endpoint.clone();
+            let connection_id = queued.connection_id.clone();
+            if let Err(err) = authorizer.authorize_connection(
+                queued.connection_id,
+                queued.connection,
+                queued.on_complete,
+                queued.expected_authorization,
+                queued.local_authorization,
+            ) {
+                self.complete_authorization(&connection_id);
+                connection_log!(
+                    Error,
+                    connection_id,
+                    endpoint,
+                    "Error authorizing connection: {}",
+                    err
+                );
+            }
+        }
+    }
+
+    /// Returns true if the number of authorizations in flight with the authorizer has reached the
+    /// maximum number of concurrent authorizations.
+    fn authorization_limit_reached(&self) -> bool {
+        match self.max_concurrent_authorizations {
+            Some(max_concurrent) => {
+                self.pending_authorizations
+                    .len()
+                    .saturating_sub(self.queued_authorizations.len())
+                    >= max_concurrent
+            }
+            None => false,
+        }
+    }
+
+    /// Removes any pending authorizations that have exceeded the authorization timeout, notifying
+    /// subscribers that the connections have failed.
+    fn expire_pending_authorizations(&mut self, subscribers: &mut SubscriberMap) {
+        let timeout = match self.authorization_timeout {
+            Some(timeout) => timeout,
+            None => return,
+        };
+
+        let expired: Vec<String> = self
+            .pending_authorizations
+            .iter()
+            .filter(|(_, pending)| pending.started.elapsed() >= timeout)
+            .map(|(connection_id, _)| connection_id.to_string())
+            .collect();
+
+        for connection_id in expired {
+            if let Some(pending) = self.pending_authorizations.remove(&connection_id) {
+                self.queued_authorizations
+                    .retain(|queued| queued.connection_id != connection_id);
+                connection_log!(
+                    Warn,
+                    connection_id,
+                    pending.endpoint,
+                    "Authorization of connection timed out"
+                );
+                self.connections.remove(&connection_id);
+                subscribers.broadcast_with_aliases(
+                    ConnectionManagerNotification::FatalConnectionError {
+                        endpoint: pending.endpoint,
+                        connection_id: connection_id.clone(),
+                        error: ConnectionManagerError::Timeout(format!(
+                            "Authorization of connection {} did not complete within {:?}",
+                            connection_id, timeout
+                        )),
+                    },
+                    &self.connection_aliases,
+                );
+                self.remove_stale_aliases(&connection_id);
+            }
         }
     }
 
@@ -542,14 +1265,15 @@ where
         authorizer: &dyn Authorizer,
     ) {
         let endpoint = connection.remote_endpoint();
-        let id = Uuid::new_v4().to_string();
+        let id = (self.connection_id_generator)(&*connection);
 
         // add the connection to the authorization pool.
-        let auth_endpoint = endpoint;
-        if let Err(err) = authorizer.authorize_connection(
-            id,
+        let auth_endpoint = endpoint.clone();
+        let queued = QueuedAuthorization {
+            connection_id: id,
+            endpoint,
             connection,
-            Box::new(move |auth_result| {
+            on_complete: Box::new(move |auth_result| {
                 internal_sender
                     .send(CmMessage::AuthResult(AuthResult::Inbound {
                         endpoint: auth_endpoint.clone(),
@@ -557,9 +1281,10 @@ where
                     }))
                     .map_err(Box::from)
             }),
-            None,
-            None,
-        ) {
+            expected_authorization: None,
+            local_authorization: None,
+        };
+        if let Err(err) = self.authorize(queued, authorizer) {
             if reply_sender
                 .send(Err(ConnectionManagerError::connection_creation_error(
                     &err.to_string(),
@@ -582,6 +1307,13 @@ where
         authorizer: &dyn Authorizer,
         subscribers: &mut SubscriberMap,
     ) {
+        if self.endpoint_deduplication {
+            if let Some(existing_id) = self.find_duplicate_connection(&outbound) {
+                self.add_connection_alias(outbound, existing_id, reply_sender, subscribers);
+                return;
+            }
+        }
+
         if let Some(connection) = self.connections.get(&outbound.connection_id) {
             let identity = connection.identity().clone();
             // if endpoints haven't changed, the connection is either connected or reconnecting
@@ -606,6 +1338,7 @@ where
                     ConnectionMetadataExt::Inbound {
                         ref disconnected,
                         ref local_authorization,
+                        ..
                     } => {
                         if !disconnected {
                             subscribers.broadcast(ConnectionManagerNotification::Connected {
@@ -623,6 +1356,17 @@ where
                 }
                 return;
             }
+
+            if self.endpoint_change_policy == EndpointChangePolicy::Reject {
+                let err = ConnectionManagerError::connection_creation_error(&format!(
+                    "Unable to connect to {} ({}): the connection already exists with endpoint {}",
+                    outbound.endpoint, outbound.connection_id, connection.endpoint
+                ));
+                if reply_sender.send(Err(err)).is_err() {
+                    warn!("connector dropped before receiving result of add connection");
+                }
+                return;
+            }
         }
 
         // The connection id is either new or the associated endpoint has changed
@@ -630,10 +1374,11 @@ where
             Ok(connection) => {
                 // add the connection to the authorization pool.
                 let auth_endpoint = outbound.endpoint.to_string();
-                if let Err(err) = authorizer.authorize_connection(
-                    outbound.connection_id,
+                let queued = QueuedAuthorization {
+                    connection_id: outbound.connection_id.clone(),
+                    endpoint: outbound.endpoint.clone(),
                     connection,
-                    Box::new(move |auth_result| {
+                    on_complete: Box::new(move |auth_result| {
                         internal_sender
                             .send(CmMessage::AuthResult(AuthResult::Outbound {
                                 endpoint: auth_endpoint.clone(),
@@ -641,9 +1386,10 @@ where
                             }))
                             .map_err(Box::from)
                     }),
-                    outbound.expected_authorization.clone(),
-                    outbound.local_authorization.clone(),
-                ) {
+                    expected_authorization: outbound.expected_authorization.clone(),
+                    local_authorization: outbound.local_authorization.clone(),
+                };
+                if let Err(err) = self.authorize(queued, authorizer) {
                     if reply_sender
                         .send(Err(ConnectionManagerError::connection_creation_error(
                             &err.to_string(),
@@ -657,17 +1403,15 @@ where
                 }
             }
             Err(err) => {
-                let connection_error = match err {
-                    ConnectError::IoError(io_err) => {
-                        ConnectionManagerError::connection_creation_error_with_io(
-                            &format!(
-                                "Unable to connect to {} ({})",
-                                outbound.endpoint, outbound.connection_id
-                            ),
-                            io_err.kind(),
-                        )
-                    }
-                    _ => ConnectionManagerError::connection_creation_error(&err.to_string()),
+                let connection_error = match connect_error_kind(&err) {
+                    Some(error_kind) => ConnectionManagerError::connection_creation_error_with_io(
+                        &format!(
+                            "Unable to connect to {} ({})",
+                            outbound.endpoint, outbound.connection_id
+                        ),
+                        error_kind,
+                    ),
+                    None => ConnectionManagerError::connection_creation_error(&err.to_string()),
                 };
                 if reply_sender.send(Err(connection_error)).is_err() {
                     warn!("connector dropped before receiving result of add connection");
@@ -676,6 +1420,134 @@ where
         }
     }
 
+    /// Finds another outbound connection to the endpoint of the requested connection, either
+    /// established or pending authorization.
+    ///
+    /// # Returns
+    ///
+    /// The ID of the existing connection, if the requested connection ID is not already a
+    /// connection of its own.
+    fn find_duplicate_connection(&self, outbound: &OutboundConnection) -> Option<String> {
+        if self.connections.contains_key(&outbound.connection_id) {
+            return None;
+        }
+
+        self.connections
+            .values()
+            .find(|meta| meta.is_outbound() && meta.endpoint == outbound.endpoint)
+            .map(|meta| meta.connection_id.clone())
+            .or_else(|| {
+                self.pending_authorizations
+                    .iter()
+                    .find(|(_, pending)| pending.endpoint == outbound.endpoint)
+                    .map(|(connection_id, _)| connection_id.clone())
+            })
+            .filter(|connection_id| connection_id != &outbound.connection_id)
+    }
+
+    /// Records the requested connection ID as an alias of an existing connection to the same
+    /// endpoint, instead of creating a new connection.
+    ///
+    /// If the existing connection is connected, the alias is added to the matrix life cycle and
+    /// subscribers are sent a `Connected` notification for it; otherwise, this happens when the
+    /// authorization of the existing connection completes. From then on, subscribers are sent a
+    /// copy of each notification for the existing connection for the alias.
+    fn add_connection_alias(
+        &mut self,
+        outbound: OutboundConnection,
+        existing_id: String,
+        reply_sender: Sender<Result<(), ConnectionManagerError>>,
+        subscribers: &mut SubscriberMap,
+    ) {
+        connection_log!(
+            Debug,
+            outbound.connection_id,
+            outbound.endpoint,
+            "Connection reuses existing connection {}",
+            existing_id
+        );
+
+        if let Some(meta) = self.connections.get(&existing_id) {
+            if let ConnectionMetadataExt::Outbound {
+                reconnecting: false,
+                ref local_authorization,
+                ..
+            } = meta.extended_metadata
+            {
+                if let Err(err) = self
+                    .life_cycle
+                    .add_alias(outbound.connection_id.clone(), &existing_id)
+                {
+                    let err = ConnectionManagerError::connection_creation_error(&err.to_string());
+                    if reply_sender.send(Err(err)).is_err() {
+                        warn!("connector dropped before receiving result of add connection");
+                    }
+                    return;
+                }
+
+                subscribers.broadcast(ConnectionManagerNotification::Connected {
+                    endpoint: outbound.endpoint.clone(),
+                    connection_id: outbound.connection_id.clone(),
+                    identity: meta.identity.clone(),
+                    local_identity: local_authorization.clone(),
+                });
+            }
+        }
+
+        self.connection_aliases
+            .insert(outbound.connection_id, existing_id);
+
+        if reply_sender.send(Ok(())).is_err() {
+            warn!("connector dropped before receiving result of add connection");
+        }
+    }
+
+    /// Returns the aliases of the given connection.
+    fn connection_aliases_of(&self, connection_id: &str) -> Vec<String> {
+        self.connection_aliases
+            .iter()
+            .filter(|(_, existing_id)| existing_id.as_str() == connection_id)
+            .map(|(alias, _)| alias.clone())
+            .collect()
+    }
+
+    /// Adds the aliases of the given connection to the matrix life cycle, after the connection
+    /// has been added to it. An alias that cannot be added is dropped, and subscribers are sent a
+    /// `FatalConnectionError` notification for it.
+    fn add_aliases_to_life_cycle(
+        &mut self,
+        endpoint: &str,
+        connection_id: &str,
+        subscribers: &mut SubscriberMap,
+    ) {
+        for alias in self.connection_aliases_of(connection_id) {
+            if let Err(err) = self.life_cycle.add_alias(alias.clone(), connection_id) {
+                connection_log!(
+                    Warn,
+                    alias,
+                    endpoint,
+                    "Unable to add alias of connection {}: {}",
+                    connection_id,
+                    err
+                );
+                self.connection_aliases.remove(&alias);
+                subscribers.broadcast(ConnectionManagerNotification::FatalConnectionError {
+                    endpoint: endpoint.to_string(),
+                    connection_id: alias,
+                    error: ConnectionManagerError::connection_creation_error(&err.to_string()),
+                });
+            }
+        }
+    }
+
+    /// Drops the aliases of the given connection, if it no longer exists.
+    fn remove_stale_aliases(&mut self, connection_id: &str) {
+        if !self.connections.contains_key(connection_id) {
+            self.connection_aliases
+                .retain(|_, existing_id| existing_id != connection_id);
+        }
+    }
+
     /// Adds outbound connection to matrix life cycle after the connection has
     /// been authorized. These connections cannot be reconnected when dropped
     /// or lost.
@@ -694,6 +1566,11 @@ where
         auth_result: AuthorizationResult,
         subscribers: &mut SubscriberMap,
     ) {
+        if !self.complete_authorization(auth_result.connection_id()) {
+            drop_timed_out_connection(&endpoint, auth_result);
+            return;
+        }
+
         match auth_result {
             AuthorizationResult::Authorized {
                 connection_id,
@@ -701,7 +1578,42 @@ where
                 identity,
                 expected_authorization,
                 local_authorization,
+                ..
             } => {
+                if self.local_identity.as_ref() == Some(&identity) {
+                    connection_log!(
+                        Warn,
+                        connection_id,
+                        endpoint,
+                        "Refusing connection: it is a connection to the local node"
+                    );
+                    let mut connection = connection;
+                    if let Err(err) = connection.disconnect() {
+                        connection_log!(
+                            Debug,
+                            connection_id,
+                            endpoint,
+                            "Unable to disconnect connection: {}",
+                            err
+                        );
+                    }
+                    self.connections.remove(&connection_id);
+                    subscribers.broadcast_with_aliases(
+                        ConnectionManagerNotification::FatalConnectionError {
+                            error: ConnectionManagerError::connection_creation_error(&format!(
+                                "Connection {} ({}) is a connection to the local node",
+                                endpoint, connection_id
+                            )),
+                            endpoint,
+                            connection_id: connection_id.clone(),
+                        },
+                        &self.connection_aliases,
+                    );
+                    self.remove_stale_aliases(&connection_id);
+
+                    return;
+                }
+
                 if let Err(err) = self
                     .life_cycle
                     .add(connection, connection_id.clone())
@@ -709,11 +1621,15 @@ where
                         ConnectionManagerError::connection_creation_error(&err.to_string())
                     })
                 {
-                    subscribers.broadcast(ConnectionManagerNotification::FatalConnectionError {
-                        endpoint,
-                        connection_id,
-                        error: err,
-                    });
+                    subscribers.broadcast_with_aliases(
+                        ConnectionManagerNotification::FatalConnectionError {
+                            endpoint,
+                            connection_id: connection_id.clone(),
+                            error: err,
+                        },
+                        &self.connection_aliases,
+                    );
+                    self.remove_stale_aliases(&connection_id);
 
                     return;
                 }
@@ -724,6 +1640,7 @@ where
                         connection_id: connection_id.to_string(),
                         identity: identity.clone(),
                         endpoint: endpoint.clone(),
+                        established: Instant::now(),
                         extended_metadata: ConnectionMetadataExt::Outbound {
                             reconnecting: false,
                             retry_frequency: INITIAL_RETRY_FREQUENCY,
@@ -734,28 +1651,49 @@ where
                         },
                     },
                 );
+                connection_log!(Debug, connection_id, endpoint, "Connection authorized");
 
-                subscribers.broadcast(ConnectionManagerNotification::Connected {
-                    endpoint,
-                    connection_id,
-                    identity,
-                    local_identity: local_authorization,
-                });
+                self.metrics.increment_connections_established();
+                self.add_aliases_to_life_cycle(&endpoint, &connection_id, subscribers);
+                subscribers.broadcast_with_aliases(
+                    ConnectionManagerNotification::Connected {
+                        endpoint,
+                        connection_id,
+                        identity,
+                        local_identity: local_authorization,
+                    },
+                    &self.connection_aliases,
+                );
             }
-            AuthorizationResult::Unauthorized { connection_id, .. } => {
+            AuthorizationResult::Unauthorized {
+                connection_id,
+                reason,
+                ..
+            } => {
+                self.metrics.increment_authorization_failures();
                 if self.connections.remove(&connection_id).is_some() {
-                    warn!(
-                        "Reconnecting connection {} ({}) failed authorization",
-                        endpoint, connection_id
+                    connection_log!(
+                        Warn,
+                        connection_id,
+                        endpoint,
+                        "Reconnecting connection failed authorization: {}",
+                        reason
                     );
                 }
                 // If the connection is unauthorized, notify subscriber this is a bad connection
                 // and will not be added.
-                subscribers.broadcast(ConnectionManagerNotification::FatalConnectionError {
-                    endpoint,
-                    connection_id: connection_id.clone(),
-                    error: ConnectionManagerError::Unauthorized(connection_id),
-                });
+                subscribers.broadcast_with_aliases(
+                    ConnectionManagerNotification::FatalConnectionError {
+                        endpoint,
+                        connection_id: connection_id.clone(),
+                        error: ConnectionManagerError::Unauthorized {
+                            connection_id: connection_id.clone(),
+                            reason,
+                        },
+                    },
+                    &self.connection_aliases,
+                );
+                self.remove_stale_aliases(&connection_id);
             }
         }
     }
@@ -772,12 +1710,18 @@ where
         auth_result: AuthorizationResult,
         subscribers: &mut SubscriberMap,
     ) {
+        if !self.complete_authorization(auth_result.connection_id()) {
+            drop_timed_out_connection(&endpoint, auth_result);
+            return;
+        }
+
         match auth_result {
             AuthorizationResult::Authorized {
                 connection_id,
                 connection,
                 identity,
                 local_authorization,
+                dial_back_endpoint,
                 ..
             } => {
                 if let Err(err) = self
@@ -801,13 +1745,17 @@ where
                         connection_id: connection_id.clone(),
                         endpoint: endpoint.clone(),
                         identity: identity.clone(),
+                        established: Instant::now(),
                         extended_metadata: ConnectionMetadataExt::Inbound {
                             disconnected: false,
                             local_authorization: local_authorization.clone(),
+                            dial_back_endpoint,
                         },
                     },
                 );
+                connection_log!(Debug, connection_id, endpoint, "Connection authorized");
 
+                self.metrics.increment_connections_established();
                 subscribers.broadcast(ConnectionManagerNotification::InboundConnection {
                     endpoint,
                     connection_id,
@@ -815,13 +1763,21 @@ where
                     local_identity: local_authorization,
                 });
             }
-            AuthorizationResult::Unauthorized { connection_id, .. } => {
+            AuthorizationResult::Unauthorized {
+                connection_id,
+                reason,
+                ..
+            } => {
+                self.metrics.increment_authorization_failures();
                 // If the connection is unauthorized, notify subscriber this is a bad connection
                 // and will not be added.
                 subscribers.broadcast(ConnectionManagerNotification::FatalConnectionError {
                     endpoint,
                     connection_id: connection_id.clone(),
-                    error: ConnectionManagerError::Unauthorized(connection_id),
+                    error: ConnectionManagerError::Unauthorized {
+                        connection_id,
+                        reason,
+                    },
                 });
             }
         }
@@ -829,6 +1785,10 @@ where
 
     /// Removes connection from state.
     ///
+    /// Removing an alias leaves the connection it shares in place. Removing a connection with
+    /// aliases removes the aliases as well, and subscribers are sent a `FatalConnectionError`
+    /// notification for each of them.
+    ///
     /// # Returns
     ///
     /// Returns metadata for the connection if available.
@@ -841,8 +1801,23 @@ where
         &mut self,
         endpoint: &str,
         connection_id: &str,
+        subscribers: &mut SubscriberMap,
     ) -> Result<Option<ConnectionMetadata>, ConnectionManagerError> {
-        debug!("Remove connection {} ({})", endpoint, connection_id);
+        connection_log!(Debug, connection_id, endpoint, "Remove connection");
+        if let Some(existing_id) = self.connection_aliases.remove(connection_id) {
+            // The alias is only in the matrix life cycle while the connection is connected
+            if let Err(err) = self.life_cycle.remove_alias(connection_id) {
+                connection_log!(
+                    Debug,
+                    connection_id,
+                    endpoint,
+                    "Unable to remove alias from life cycle: {}",
+                    err
+                );
+            }
+            return Ok(self.connections.get(&existing_id).cloned());
+        }
+
         let meta = if let Some(meta) = self.connections.get_mut(connection_id) {
             meta.clone()
         } else {
@@ -850,6 +1825,17 @@ where
         };
 
         self.connections.remove(connection_id);
+        for alias in self.connection_aliases_of(connection_id) {
+            self.connection_aliases.remove(&alias);
+            subscribers.broadcast(ConnectionManagerNotification::FatalConnectionError {
+                endpoint: meta.endpoint.clone(),
+                connection_id: alias.clone(),
+                error: ConnectionManagerError::ConnectionRemovalError(format!(
+                    "Connection {} was removed with the connection {} it is an alias of",
+                    alias, connection_id
+                )),
+            });
+        }
         // remove mesh id, this may happen before reconnection is attempted
         self.life_cycle
             .remove(meta.connection_id())
@@ -892,80 +1878,187 @@ where
             return Ok(());
         }
 
-        if let Ok(connection) = self.transport.connect(endpoint) {
-            // remove old mesh id, this may happen before reconnection is attempted
-            self.life_cycle
-                .remove(meta.connection_id())
-                .map_err(|err| {
-                    ConnectionManagerError::ConnectionRemovalError(format!(
-                        "Cannot remove connection {} ({}) from life cycle: {}",
-                        endpoint,
+        self.metrics.increment_reconnection_attempts();
+        connection_log!(Debug, connection_id, endpoint, "Attempting to reconnect");
+
+        match self.transport.connect(endpoint) {
+            Ok(connection) => {
+                // remove old mesh id, this may happen before reconnection is attempted
+                self.life_cycle
+                    .remove(meta.connection_id())
+                    .map_err(|err| {
+                        ConnectionManagerError::ConnectionRemovalError(format!(
+                            "Cannot remove connection {} ({}) from life cycle: {}",
+                            endpoint,
+                            meta.connection_id(),
+                            err
+                        ))
+                    })?;
+
+                let auth_endpoint = endpoint.to_string();
+                let queued = QueuedAuthorization {
+                    connection_id: meta.connection_id().into(),
+                    endpoint: endpoint.to_string(),
+                    connection,
+                    on_complete: Box::new(move |auth_result| {
+                        internal_sender
+                            .send(CmMessage::AuthResult(AuthResult::Outbound {
+                                endpoint: auth_endpoint.clone(),
+                                auth_result,
+                            }))
+                            .map_err(Box::from)
+                    }),
+                    expected_authorization: meta.extended_metadata.expected_authorization(),
+                    local_authorization: Some(meta.extended_metadata.local_authorization()),
+                };
+                if let Err(err) = self.authorize(queued, authorizer) {
+                    connection_log!(
+                        Error,
                         meta.connection_id(),
+                        endpoint,
+                        "Error authorizing connection: {}",
                         err
-                    ))
-                })?;
+                    );
+                }
+            }
+            Err(err) => {
+                let reconnection_attempts = match meta.extended_metadata {
+                    ConnectionMetadataExt::Outbound {
+                        ref mut reconnecting,
+                        ref mut retry_frequency,
+                        ref mut last_connection_attempt,
+                        ref mut reconnection_attempts,
+                        ..
+                    } => {
+                        *reconnecting = true;
+                        *retry_frequency = min(*retry_frequency * 2, self.maximum_retry_frequency);
+                        *last_connection_attempt = Instant::now();
+                        *reconnection_attempts += 1;
 
-            let auth_endpoint = endpoint.to_string();
-            if let Err(err) = authorizer.authorize_connection(
-                meta.connection_id().into(),
-                connection,
-                Box::new(move |auth_result| {
-                    internal_sender
-                        .send(CmMessage::AuthResult(AuthResult::Outbound {
-                            endpoint: auth_endpoint.clone(),
-                            auth_result,
-                        }))
-                        .map_err(Box::from)
-                }),
-                meta.extended_metadata.expected_authorization(),
-                Some(meta.extended_metadata.local_authorization()),
-            ) {
-                error!(
-                    "Error authorizing {} ({}): {}",
+                        *reconnection_attempts
+                    }
+                    // We checked earlier that this was an outbound connection
+                    _ => unreachable!(),
+                };
+                connection_log!(
+                    Debug,
+                    connection_id,
                     endpoint,
-                    meta.connection_id(),
+                    "Reconnection attempt {} failed: {}",
+                    reconnection_attempts,
                     err
                 );
+                let identity = meta.identity.clone();
+                self.connections.insert(connection_id.to_string(), meta);
+
+                // Notify subscribers of reconnection failure
+                subscribers.broadcast_with_aliases(
+                    ConnectionManagerNotification::NonFatalConnectionError {
+                        endpoint: endpoint.to_string(),
+                        attempts: reconnection_attempts,
+                        identity,
+                        connection_id: connection_id.to_string(),
+                        error_kind: connect_error_kind(&err),
+                    },
+                    &self.connection_aliases,
+                );
             }
-        } else {
-            let reconnection_attempts = match meta.extended_metadata {
-                ConnectionMetadataExt::Outbound {
-                    ref mut reconnecting,
-                    ref mut retry_frequency,
-                    ref mut last_connection_attempt,
-                    ref mut reconnection_attempts,
-                    ..
-                } => {
-                    *reconnecting = true;
-                    *retry_frequency = min(*retry_frequency * 2, self.maximum_retry_frequency);
-                    *last_connection_attempt = Instant::now();
-                    *reconnection_attempts += 1;
-
-                    *reconnection_attempts
-                }
-                // We checked earlier that this was an outbound connection
-                _ => unreachable!(),
-            };
-            let identity = meta.identity.clone();
-            self.connections.insert(connection_id.to_string(), meta);
-
-            // Notify subscribers of reconnection failure
-            subscribers.broadcast(ConnectionManagerNotification::NonFatalConnectionError {
-                endpoint: endpoint.to_string(),
-                attempts: reconnection_attempts,
-                identity,
-                connection_id: connection_id.to_string(),
-            });
         }
         Ok(())
     }
 
-    fn connection_metadata(&self) -> &HashMap<String, ConnectionMetadata> {
-        &self.connections
-    }
+    /// Resets the retry backoff of an outbound connection and attempts to reconnect it
+    /// immediately, rather than waiting for the next heartbeat.
+    ///
+    /// # Errors
+    ///
+    /// Returns ConnectionManagerError if the connection does not exist, is an inbound
+    /// connection, or if the reconnection operation fails.
+    fn force_reconnect(
+        &mut self,
+        connection_id: &str,
+        subscribers: &mut SubscriberMap,
+        authorizer: &dyn Authorizer,
+        internal_sender: Sender<CmMessage>,
+    ) -> Result<(), ConnectionManagerError> {
+        let meta = self.connections.get_mut(connection_id).ok_or_else(|| {
+            ConnectionManagerError::ConnectionReconnectError(format!(
+                "Cannot reconnect unknown connection {}",
+                connection_id
+            ))
+        })?;
 
-    fn connection_metadata_mut(&mut self) -> &mut HashMap<String, ConnectionMetadata> {
-        &mut self.connections
+        match meta.extended_metadata {
+            ConnectionMetadataExt::Outbound {
+                ref mut retry_frequency,
+                ref mut reconnection_attempts,
+                ..
+            } => {
+                *retry_frequency = INITIAL_RETRY_FREQUENCY;
+                *reconnection_attempts = 0;
+            }
+            ConnectionMetadataExt::Inbound { .. } => {
+                return Err(ConnectionManagerError::ConnectionReconnectError(format!(
+                    "Cannot reconnect inbound connection {}",
+                    connection_id
+                )));
+            }
+        }
+
+        let endpoint = meta.endpoint().to_string();
+        self.reconnect(
+            &endpoint,
+            connection_id,
+            subscribers,
+            authorizer,
+            internal_sender,
+        )
+    }
+
+    /// Converts an inbound connection with a dial-back endpoint into a reconnecting outbound
+    /// connection to that endpoint.
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated metadata for the connection, or `None` if the connection is not an
+    /// inbound connection with a dial-back endpoint.
+    fn promote_inbound_connection(&mut self, connection_id: &str) -> Option<ConnectionMetadata> {
+        let meta = self.connections.get_mut(connection_id)?;
+        let (dial_back_endpoint, local_authorization) = match &meta.extended_metadata {
+            ConnectionMetadataExt::Inbound {
+                dial_back_endpoint: Some(dial_back_endpoint),
+                local_authorization,
+                ..
+            } => (dial_back_endpoint.clone(), local_authorization.clone()),
+            _ => return None,
+        };
+
+        connection_log!(
+            Info,
+            connection_id,
+            dial_back_endpoint,
+            "Promoting disconnected inbound connection from {} to outbound",
+            meta.endpoint
+        );
+        meta.endpoint = dial_back_endpoint;
+        meta.extended_metadata = ConnectionMetadataExt::Outbound {
+            reconnecting: true,
+            retry_frequency: INITIAL_RETRY_FREQUENCY,
+            last_connection_attempt: Instant::now(),
+            reconnection_attempts: 0,
+            expected_authorization: meta.identity.clone(),
+            local_authorization,
+        };
+
+        Some(meta.clone())
+    }
+
+    fn connection_metadata(&self) -> &HashMap<String, ConnectionMetadata> {
+        &self.connections
+    }
+
+    fn connection_metadata_mut(&mut self) -> &mut HashMap<String, ConnectionMetadata> {
+        &mut self.connections
     }
 
     fn matrix_sender(&self) -> U {
@@ -977,8 +2070,8 @@ where
 mod tests {
     use super::*;
 
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::mpsc;
-    #[cfg(feature = "challenge-authorization")]
     use std::sync::{Arc, Mutex};
 
     #[cfg(feature = "challenge-authorization")]
@@ -993,6 +2086,8 @@ mod tests {
     use crate::threading::lifecycle::ShutdownHandle;
     use crate::transport::inproc::InprocTransport;
     use crate::transport::socket::TcpTransport;
+    #[cfg(all(feature = "unix-transport", unix))]
+    use crate::transport::unix::UnixTransport;
 
     #[test]
     fn test_connection_manager_startup_and_shutdown() {
@@ -1042,11 +2137,19 @@ mod tests {
             .expect("Unable to shutdown connection manager");
     }
 
-    /// Test that adding the same connection twice is an idempotent operation
+    /// Test that a connection can be requested over a Unix domain socket, using a socket file in
+    /// a temporary directory.
+    #[cfg(all(feature = "unix-transport", unix))]
     #[test]
-    fn test_mutiple_add_connection_requests() {
-        let mut transport = Box::new(InprocTransport::default());
-        let mut listener = transport.listen("inproc://test").unwrap();
+    fn test_add_connection_request_unix() {
+        let temp_dir = tempfile::Builder::new()
+            .prefix("test_add_connection_request_unix")
+            .tempdir()
+            .expect("Failed to create temp dir");
+        let endpoint = format!("unix://{}", temp_dir.path().join("splinter.sock").display());
+
+        let mut transport = Box::new(UnixTransport::default());
+        let mut listener = transport.listen(&endpoint).unwrap();
 
         thread::spawn(move || {
             listener.accept().unwrap();
@@ -1064,31 +2167,26 @@ mod tests {
         let connector = cm.connector();
 
         connector
-            .request_connection("inproc://test", "test_id", None, None)
+            .request_connection(&endpoint, "test_id", None, None)
             .expect("A connection could not be created");
 
-        connector
-            .request_connection("inproc://test", "test_id", None, None)
-            .expect("A connection could not be re-requested");
-
         cm.signal_shutdown();
         cm.wait_for_shutdown()
             .expect("Unable to shutdown connection manager");
     }
 
-    /// Test that heartbeats are correctly sent to inproc connections
+    /// Test that `request_connection_and_wait` returns the identity of the remote node once the
+    /// connection has been authorized.
     #[test]
-    fn test_heartbeat_inproc() {
+    fn test_request_connection_and_wait() {
         let mut transport = Box::new(InprocTransport::default());
         let mut listener = transport.listen("inproc://test").unwrap();
-        let mesh = Mesh::new(512, 128);
-        let mesh_clone = mesh.clone();
 
         thread::spawn(move || {
-            let conn = listener.accept().unwrap();
-            mesh_clone.add(conn, "test_id".to_string()).unwrap();
+            listener.accept().unwrap();
         });
 
+        let mesh = Mesh::new(512, 128);
         let mut cm = ConnectionManager::builder()
             .with_authorizer(Box::new(NoopAuthorizer::new("test_identity")))
             .with_matrix_life_cycle(mesh.get_life_cycle())
@@ -1099,17 +2197,21 @@ mod tests {
 
         let connector = cm.connector();
 
-        connector
-            .request_connection("inproc://test", "test_id", None, None)
+        let identity = connector
+            .request_connection_and_wait(
+                "inproc://test",
+                "test_id",
+                None,
+                None,
+                Duration::from_secs(10),
+            )
             .expect("A connection could not be created");
 
-        // Verify mesh received heartbeat
-
-        let envelope = mesh.recv().unwrap();
-        let heartbeat: NetworkMessage = Message::parse_from_bytes(&envelope.payload()).unwrap();
         assert_eq!(
-            heartbeat.get_message_type(),
-            NetworkMessageType::NETWORK_HEARTBEAT
+            identity,
+            ConnectionAuthorizationType::Trust {
+                identity: "test_identity".into(),
+            }
         );
 
         cm.signal_shutdown();
@@ -1117,240 +2219,1639 @@ mod tests {
             .expect("Unable to shutdown connection manager");
     }
 
-    /// Test that heartbeats are correctly sent to tcp connections
+    /// Test that a connection can be authorized end-to-end with a `SyncAuthorizer`.
+    ///
+    /// 1. Start a connection manager with a `SyncAuthorizer` that trusts every connection with a
+    ///    fixed identity
+    /// 2. Request an inproc connection and wait for it to be authorized
+    /// 3. Verify the identity returned is the one given by the authorizer
     #[test]
-    fn test_heartbeat_raw_tcp() {
-        let mut transport = Box::new(TcpTransport::default());
-        let mut listener = transport.listen("tcp://localhost:0").unwrap();
-        let endpoint = listener.endpoint();
-
-        let mesh = Mesh::new(512, 128);
+    fn test_sync_authorizer() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
 
-        let (tx, rx) = mpsc::channel();
         thread::spawn(move || {
-            let mut mesh = Mesh::new(512, 128);
-            let conn = listener.accept().unwrap();
-            mesh.add(conn, "test_id".to_string()).unwrap();
-
-            negotiation_connection_auth(&mesh, "test_id", "some-peer");
-
-            // Verify mesh received heartbeat
-
-            let envelope = mesh.recv().unwrap();
-            let heartbeat: NetworkMessage = Message::parse_from_bytes(&envelope.payload()).unwrap();
-            assert_eq!(
-                heartbeat.get_message_type(),
-                NetworkMessageType::NETWORK_HEARTBEAT
-            );
-
-            tx.send(()).expect("Could not send completion signal");
+            listener.accept().unwrap();
+        });
 
-            mesh.signal_shutdown();
-            mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
+        let authorizer = authorizers::SyncAuthorizer::new(|connection_id, connection| {
+            AuthorizationResult::Authorized {
+                connection_id,
+                connection,
+                identity: ConnectionAuthorizationType::Trust {
+                    identity: "sync_identity".into(),
+                },
+                expected_authorization: ConnectionAuthorizationType::Trust {
+                    identity: "sync_identity".into(),
+                },
+                local_authorization: ConnectionAuthorizationType::Trust {
+                    identity: "test_identity".into(),
+                },
+                dial_back_endpoint: None,
+            }
         });
 
-        let auth_mgr = AuthorizationManager::new(
-            "test_identity".into(),
-            #[cfg(feature = "challenge-authorization")]
-            vec![new_signer()],
-            #[cfg(feature = "challenge-authorization")]
-            Arc::new(Mutex::new(Box::new(NoopFactory))),
-        )
-        .expect("Unable to create authorization pool");
+        let mesh = Mesh::new(512, 128);
         let mut cm = ConnectionManager::builder()
-            .with_authorizer(Box::new(auth_mgr.authorization_connector()))
+            .with_authorizer(Box::new(authorizer))
             .with_matrix_life_cycle(mesh.get_life_cycle())
             .with_matrix_sender(mesh.get_sender())
             .with_transport(transport)
             .start()
             .expect("Unable to start Connection Manager");
+
         let connector = cm.connector();
 
-        connector
-            .request_connection(&endpoint, "test_id", None, None)
+        let identity = connector
+            .request_connection_and_wait(
+                "inproc://test",
+                "test_id",
+                None,
+                None,
+                Duration::from_secs(10),
+            )
             .expect("A connection could not be created");
 
-        let (sub_tx, sub_rx): (
-            Sender<ConnectionManagerNotification>,
-            mpsc::Receiver<ConnectionManagerNotification>,
-        ) = channel();
-        connector.subscribe(sub_tx).expect("Unable to respond.");
-
-        // Validate that the connection completed authorization
-        let notification = sub_rx.recv().expect("Cannot receive notification");
-        assert!(
-            notification
-                == ConnectionManagerNotification::Connected {
-                    endpoint: endpoint.clone(),
-                    connection_id: "test_id".to_string(),
-                    identity: ConnectionAuthorizationType::Trust {
-                        identity: "some-peer".into()
-                    },
-                    local_identity: ConnectionAuthorizationType::Trust {
-                        identity: "test_identity".into()
-                    }
-                }
+        assert_eq!(
+            identity,
+            ConnectionAuthorizationType::Trust {
+                identity: "sync_identity".into(),
+            }
         );
 
-        // wait for completion
-        rx.recv().expect("Did not receive completion signal");
-
         cm.signal_shutdown();
         cm.wait_for_shutdown()
             .expect("Unable to shutdown connection manager");
-        auth_mgr.shutdown_and_await();
     }
 
+    /// Test that `list_connection_details` reports how long a connection has been established.
+    ///
+    /// 1. Request a connection and wait for it to be authorized
+    /// 2. Sleep briefly and list the connection details
+    /// 3. Verify the single outbound connection is returned with a connected duration of at least
+    ///    the time slept
     #[test]
-    fn test_remove_connection() {
-        let mut transport = Box::new(TcpTransport::default());
-        let mut listener = transport.listen("tcp://localhost:0").unwrap();
-        let endpoint = listener.endpoint();
-        let mesh = Mesh::new(512, 128);
+    fn test_list_connection_details() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
 
-        let (tx, rx) = mpsc::channel();
         thread::spawn(move || {
-            let mut mesh = Mesh::new(512, 128);
-            let conn = listener.accept().unwrap();
-            mesh.add(conn, "test_id".to_string()).unwrap();
-            negotiation_connection_auth(&mesh, "test_id", "some-peer");
-
-            // wait for completion
-            rx.recv().expect("Did not receive completion signal");
-
-            mesh.signal_shutdown();
-            mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
+            listener.accept().unwrap();
         });
 
-        let auth_mgr = AuthorizationManager::new(
-            "test_identity".into(),
-            #[cfg(feature = "challenge-authorization")]
-            vec![new_signer()],
-            #[cfg(feature = "challenge-authorization")]
-            Arc::new(Mutex::new(Box::new(NoopFactory))),
-        )
-        .expect("Unable to create authorization pool");
+        let mesh = Mesh::new(512, 128);
         let mut cm = ConnectionManager::builder()
-            .with_authorizer(Box::new(auth_mgr.authorization_connector()))
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_identity")))
             .with_matrix_life_cycle(mesh.get_life_cycle())
             .with_matrix_sender(mesh.get_sender())
             .with_transport(transport)
             .start()
             .expect("Unable to start Connection Manager");
-        let connector = cm.connector();
 
-        let (sub_tx, sub_rx): (
-            Sender<ConnectionManagerNotification>,
-            mpsc::Receiver<ConnectionManagerNotification>,
-        ) = channel();
-        connector.subscribe(sub_tx).expect("Unable to respond.");
+        let connector = cm.connector();
 
         connector
-            .request_connection(&endpoint, "test_id", None, None)
+            .request_connection_and_wait(
+                "inproc://test",
+                "test_id",
+                None,
+                None,
+                Duration::from_secs(10),
+            )
             .expect("A connection could not be created");
 
-        // Validate that the connection completed authorization
-        let notification = sub_rx.recv().expect("Cannot receive notification");
-        assert!(
-            notification
-                == ConnectionManagerNotification::Connected {
-                    endpoint: endpoint.clone(),
-                    connection_id: "test_id".to_string(),
-                    identity: ConnectionAuthorizationType::Trust {
-                        identity: "some-peer".into()
-                    },
-                    local_identity: ConnectionAuthorizationType::Trust {
-                        identity: "test_identity".into()
-                    }
-                }
-        );
+        thread::sleep(Duration::from_millis(100));
+
+        let details = connector
+            .list_connection_details()
+            .expect("Unable to list connection details");
 
+        assert_eq!(details.len(), 1);
+        assert_eq!(details[0].connection_id(), "test_id");
+        assert_eq!(details[0].endpoint(), "inproc://test");
         assert_eq!(
-            vec![endpoint.clone()],
-            connector
-                .list_connections()
-                .expect("Unable to list connections")
+            details[0].identity(),
+            &ConnectionAuthorizationType::Trust {
+                identity: "test_identity".into(),
+            }
         );
-
-        let endpoint_removed = connector
-            .remove_connection(&endpoint, "test_id")
-            .expect("Unable to remove connection");
-
-        assert_eq!(Some(endpoint.clone()), endpoint_removed);
-
-        assert!(connector
-            .list_connections()
-            .expect("Unable to list connections")
-            .is_empty());
-
-        tx.send(()).expect("Could not send completion signal");
+        assert!(details[0].is_outbound());
+        assert!(details[0].connected_duration() >= Duration::from_millis(100));
+        assert!(details[0].connected_duration() < Duration::from_secs(10));
 
         cm.signal_shutdown();
         cm.wait_for_shutdown()
             .expect("Unable to shutdown connection manager");
-        auth_mgr.shutdown_and_await();
     }
 
+    /// Test that `debug_dump` reports each connection with its direction and retry state.
+    ///
+    /// 1. Request an outbound connection and wait for it to be authorized
+    /// 2. Add an inbound connection and wait for it to be authorized
+    /// 3. Verify the dump includes the outbound connection, which is not reconnecting and has the
+    ///    initial retry frequency, and the inbound connection, which is not disconnected
+    /// 4. Verify the dump is serialized with the direction of each connection
     #[test]
-    fn test_remove_nonexistent_connection() {
-        let transport = Box::new(TcpTransport::default());
-        let mesh = Mesh::new(512, 128);
+    fn test_debug_dump() {
+        let mut transport = InprocTransport::default();
+        let mut outbound_listener = transport.listen("inproc://debug_outbound").unwrap();
+        let mut inbound_listener = transport.listen("inproc://debug_inbound").unwrap();
+
+        let (done_tx, done_rx) = mpsc::channel::<()>();
+        let mut remote_transport = transport.clone();
+        let jh = thread::spawn(move || {
+            let _accepted = outbound_listener.accept().unwrap();
+            let _connected = remote_transport.connect("inproc://debug_inbound").unwrap();
+
+            // block until done
+            let _ = done_rx.recv();
+        });
 
+        let mesh = Mesh::new(512, 128);
         let mut cm = ConnectionManager::builder()
             .with_authorizer(Box::new(NoopAuthorizer::new("test_identity")))
             .with_matrix_life_cycle(mesh.get_life_cycle())
             .with_matrix_sender(mesh.get_sender())
-            .with_transport(transport)
+            .with_transport(Box::new(transport))
             .start()
             .expect("Unable to start Connection Manager");
 
         let connector = cm.connector();
 
-        let endpoint_removed = connector
-            .remove_connection("tcp://localhost:5000", "test_id")
-            .expect("Unable to remove connection");
+        connector
+            .request_connection_and_wait(
+                "inproc://debug_outbound",
+                "outbound_id",
+                None,
+                None,
+                Duration::from_secs(10),
+            )
+            .expect("A connection could not be created");
 
-        assert_eq!(None, endpoint_removed);
-        cm.signal_shutdown();
-        cm.wait_for_shutdown()
-            .expect("Unable to shutdown connection manager");
-    }
+        let (subs_tx, subs_rx) = mpsc::channel();
+        connector.subscribe(subs_tx).expect("Cannot get subscriber");
 
-    /// test_reconnect_raw_tcp
-    ///
-    /// Test that if a connection disconnects, the connection manager will detect the connection
-    /// has disconnected by trying to send a heartbeat. Then connection manger will try to
-    /// reconnect to the endpoint.
-    #[test]
-    fn test_reconnect_raw_tcp() {
-        let mut transport = Box::new(TcpTransport::default());
-        let mut listener = transport
-            .listen("tcp://localhost:0")
-            .expect("Cannot listen for connections");
-        let endpoint = listener.endpoint();
-        let mesh1 = Mesh::new(512, 128);
+        let connection = inbound_listener.accept().unwrap();
+        connector
+            .add_inbound_connection(connection)
+            .expect("Unable to add inbound connection");
 
-        let (tx, rx) = mpsc::channel();
-        thread::spawn(move || {
-            // accept incoming connection and add it to mesh2
-            let mut mesh2 = Mesh::new(512, 128);
+        let inbound_id = match subs_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("Cannot get message from subscriber")
+        {
+            ConnectionManagerNotification::InboundConnection { connection_id, .. } => connection_id,
+            notification => panic!("Incorrect notification received: {:?}", notification),
+        };
+
+        let dump = connector.debug_dump().expect("Unable to dump state");
+
+        assert_eq!(dump.connections().len(), 2);
+        assert!(dump.pending_authorizations().is_empty());
+
+        let outbound = dump
+            .connections()
+            .iter()
+            .find(|connection| connection.connection_id() == "outbound_id")
+            .expect("Outbound connection not in dump");
+        assert_eq!(outbound.endpoint(), "inproc://debug_outbound");
+        assert_eq!(outbound.identity(), "trust:test_identity");
+        match outbound.direction() {
+            ConnectionDirectionDebugState::Outbound {
+                reconnecting,
+                retry_frequency,
+                reconnection_attempts,
+                ..
+            } => {
+                assert!(!reconnecting);
+                assert_eq!(*retry_frequency, INITIAL_RETRY_FREQUENCY);
+                assert_eq!(*reconnection_attempts, 0);
+            }
+            direction => panic!("Expected an outbound connection, got {:?}", direction),
+        }
+
+        let inbound = dump
+            .connections()
+            .iter()
+            .find(|connection| connection.connection_id() == inbound_id)
+            .expect("Inbound connection not in dump");
+        assert_eq!(inbound.endpoint(), "inproc://debug_inbound");
+        match inbound.direction() {
+            ConnectionDirectionDebugState::Inbound { disconnected, .. } => {
+                assert!(!disconnected)
+            }
+            direction => panic!("Expected an inbound connection, got {:?}", direction),
+        }
+
+        let json = serde_json::to_value(&dump).expect("Unable to serialize dump");
+        let mut directions = json["connections"]
+            .as_array()
+            .expect("Connections not serialized as an array")
+            .iter()
+            .map(|connection| {
+                connection["direction"]["direction"]
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        directions.sort();
+        assert_eq!(directions, vec!["inbound", "outbound"]);
+
+        done_tx.send(()).unwrap();
+        jh.join().unwrap();
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
+    /// Test that a subscriber added after a connection was established can find the connection
+    /// in a snapshot.
+    ///
+    /// 1. Request a connection and wait for it to be authorized
+    /// 2. Subscribe to notifications and take a snapshot of the connections
+    /// 3. Verify the snapshot includes the connection, whose notification was sent before the
+    ///    subscriber was added
+    #[test]
+    fn test_snapshot_after_subscribe() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+
+        thread::spawn(move || {
+            listener.accept().unwrap();
+        });
+
+        let mesh = Mesh::new(512, 128);
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_identity")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+
+        connector
+            .request_connection_and_wait(
+                "inproc://test",
+                "test_id",
+                None,
+                None,
+                Duration::from_secs(10),
+            )
+            .expect("A connection could not be created");
+
+        let (subs_tx, subs_rx) = mpsc::channel::<ConnectionManagerNotification>();
+        connector
+            .subscribe(subs_tx)
+            .expect("Unable to subscribe to notifications");
+
+        let snapshot = connector.snapshot().expect("Unable to take snapshot");
+
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].connection_id(), "test_id");
+        assert_eq!(
+            snapshot[0].identity(),
+            &ConnectionAuthorizationType::Trust {
+                identity: "test_identity".into(),
+            }
+        );
+        // The connection was established before subscribing, so it is only in the snapshot
+        assert!(subs_rx.try_recv().is_err());
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
+    /// Test that `request_connection_and_wait` returns an error if the connection fails
+    /// authorization.
+    #[test]
+    fn test_request_connection_and_wait_unauthorized() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+
+        thread::spawn(move || {
+            listener.accept().unwrap();
+        });
+
+        let mesh = Mesh::new(512, 128);
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(UnauthorizedAuthorizer(
+                UnauthorizedReason::UnknownIdentity,
+            )))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+
+        match connector.request_connection_and_wait(
+            "inproc://test",
+            "test_id",
+            None,
+            None,
+            Duration::from_secs(10),
+        ) {
+            Err(ConnectionManagerError::Unauthorized { connection_id, .. }) => {
+                assert_eq!(connection_id, "test_id")
+            }
+            res => panic!("Expected Err(Unauthorized), got {:?} instead", res),
+        }
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
+    /// Test that filtered subscribers are only sent the notifications that match their filter.
+    ///
+    /// This test does the following:
+    /// 1. Subscribe two filtered subscribers, each for a different connection ID
+    /// 2. Request a connection for each connection ID
+    /// 3. Verify that each subscriber receives the Connected notification for its connection
+    /// 4. Verify that neither subscriber receives any other notifications
+    #[test]
+    fn test_subscribe_filtered() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+
+        thread::spawn(move || {
+            listener.accept().unwrap();
+            listener.accept().unwrap();
+        });
+
+        let mesh = Mesh::new(512, 128);
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_identity")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+
+        let (subs_tx_1, subs_rx_1) = mpsc::channel();
+        connector
+            .subscribe_filtered(subs_tx_1, |notification: &ConnectionManagerNotification| {
+                notification.connection_id() == "test_id_1"
+            })
+            .expect("Cannot subscribe");
+        let (subs_tx_2, subs_rx_2) = mpsc::channel();
+        connector
+            .subscribe_filtered(subs_tx_2, |notification: &ConnectionManagerNotification| {
+                notification.connection_id() == "test_id_2"
+            })
+            .expect("Cannot subscribe");
+
+        connector
+            .request_connection("inproc://test", "test_id_1", None, None)
+            .expect("A connection could not be created");
+        connector
+            .request_connection("inproc://test", "test_id_2", None, None)
+            .expect("A connection could not be created");
+
+        for (subs_rx, expected_id) in &[(&subs_rx_1, "test_id_1"), (&subs_rx_2, "test_id_2")] {
+            let notification: ConnectionManagerNotification = subs_rx
+                .recv_timeout(Duration::from_secs(10))
+                .expect("Cannot get message from subscriber");
+            match notification {
+                ConnectionManagerNotification::Connected { connection_id, .. } => {
+                    assert_eq!(connection_id, *expected_id)
+                }
+                _ => panic!("Incorrect notification received: {:?}", notification),
+            }
+        }
+
+        // The connection manager handles requests in order, so any other notifications for the
+        // connections have been broadcast by the time this returns
+        assert_eq!(connector.list_connections().unwrap().len(), 2);
+
+        assert!(subs_rx_1.try_recv().is_err());
+        assert!(subs_rx_2.try_recv().is_err());
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
+    /// Test that a connection request with a timeout returns a `Timeout` error if the connection
+    /// manager does not respond in time, and that the request is still delivered to the
+    /// connection manager.
+    ///
+    /// The connection manager is stalled by handing the connector a channel whose messages are
+    /// not processed until after the timeout has elapsed.
+    #[test]
+    fn test_add_connection_request_timeout() {
+        let (sender, cm_receiver) = channel();
+        let connector = Connector { sender };
+
+        let result = connector.request_connection_timeout(
+            "inproc://test",
+            "test_id",
+            None,
+            None,
+            Duration::from_millis(100),
+        );
+
+        match result {
+            Err(ConnectionManagerError::Timeout(_)) => (),
+            res => panic!("Expected a timeout error, but got {:?}", res),
+        }
+
+        match cm_receiver.try_recv() {
+            Ok(CmMessage::Request(CmRequest::RequestOutboundConnection {
+                endpoint,
+                connection_id,
+                ..
+            })) => {
+                assert_eq!(endpoint, "inproc://test");
+                assert_eq!(connection_id, "test_id");
+            }
+            _ => panic!("The connection request was not sent to the connection manager"),
+        }
+    }
+
+    /// Test that adding the same connection twice is an idempotent operation
+    #[test]
+    fn test_mutiple_add_connection_requests() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+
+        thread::spawn(move || {
+            listener.accept().unwrap();
+        });
+
+        let mesh = Mesh::new(512, 128);
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_identity")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+
+        connector
+            .request_connection("inproc://test", "test_id", None, None)
+            .expect("A connection could not be created");
+
+        connector
+            .request_connection("inproc://test", "test_id", None, None)
+            .expect("A connection could not be re-requested");
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
+    /// Test that heartbeats are correctly sent to inproc connections
+    #[test]
+    fn test_heartbeat_inproc() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+        let mesh = Mesh::new(512, 128);
+        let mesh_clone = mesh.clone();
+
+        thread::spawn(move || {
+            let conn = listener.accept().unwrap();
+            mesh_clone.add(conn, "test_id".to_string()).unwrap();
+        });
+
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_identity")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+
+        connector
+            .request_connection("inproc://test", "test_id", None, None)
+            .expect("A connection could not be created");
+
+        // Verify mesh received heartbeat
+
+        let envelope = mesh.recv().unwrap();
+        let heartbeat: NetworkMessage = Message::parse_from_bytes(&envelope.payload()).unwrap();
+        assert_eq!(
+            heartbeat.get_message_type(),
+            NetworkMessageType::NETWORK_HEARTBEAT
+        );
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
+    /// Test that, with endpoint deduplication enabled, a second connection ID requested for the
+    /// same endpoint reuses the existing connection.
+    ///
+    /// This test does the following:
+    /// 1. Start a connection manager with endpoint deduplication enabled
+    /// 2. Request an inproc connection and wait for it to be connected
+    /// 3. Request a connection with a different ID to the same endpoint, and verify it is
+    ///    reported as connected
+    /// 4. Verify that only one connection exists
+    /// 5. Remove the second ID and verify that the first connection remains
+    #[test]
+    fn test_endpoint_deduplication() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://deduplication").unwrap();
+        let mesh = Mesh::new(512, 128);
+        let mesh_clone = mesh.clone();
+
+        thread::spawn(move || {
+            let conn = listener.accept().unwrap();
+            mesh_clone.add(conn, "test_id".to_string()).unwrap();
+        });
+
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_identity")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .with_endpoint_deduplication(true)
+            .start()
+            .expect("Unable to start Connection Manager");
+        let connector = cm.connector();
+
+        let (subs_tx, subs_rx) = mpsc::channel();
+        connector.subscribe(subs_tx).expect("Cannot subscribe");
+
+        connector
+            .request_connection("inproc://deduplication", "test_id_1", None, None)
+            .expect("A connection could not be created");
+        let notification: ConnectionManagerNotification = subs_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("Cannot get message from subscriber");
+        assert!(matches!(
+            notification,
+            ConnectionManagerNotification::Connected { connection_id, .. }
+                if connection_id == "test_id_1"
+        ));
+
+        connector
+            .request_connection("inproc://deduplication", "test_id_2", None, None)
+            .expect("A connection could not be created");
+        let notification: ConnectionManagerNotification = subs_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("Cannot get message from subscriber");
+        assert!(matches!(
+            notification,
+            ConnectionManagerNotification::Connected { connection_id, .. }
+                if connection_id == "test_id_2"
+        ));
+
+        assert_eq!(
+            connector
+                .list_connections()
+                .expect("Unable to list connections"),
+            vec!["inproc://deduplication".to_string()]
+        );
+
+        assert_eq!(
+            connector
+                .remove_connection("inproc://deduplication", "test_id_2")
+                .expect("Unable to remove connection"),
+            Some("inproc://deduplication".to_string())
+        );
+        assert_eq!(
+            connector
+                .list_connections()
+                .expect("Unable to list connections"),
+            vec!["inproc://deduplication".to_string()]
+        );
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
+    /// Test that, with endpoint deduplication enabled, a message sent to a connection ID that
+    /// reuses an existing connection is sent over that connection.
+    ///
+    /// This test does the following:
+    /// 1. Start a connection manager with endpoint deduplication enabled
+    /// 2. Request an inproc connection and wait for it to be connected
+    /// 3. Request a connection with a different ID to the same endpoint and wait for it to be
+    ///    connected
+    /// 4. Send a message using the second ID, and verify that it is received on the remote end of
+    ///    the existing connection
+    #[test]
+    fn test_endpoint_deduplication_send_on_alias() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://deduplication_send").unwrap();
+        let mesh = Mesh::new(512, 128);
+        let mesh_clone = mesh.clone();
+
+        thread::spawn(move || {
+            let conn = listener.accept().unwrap();
+            mesh_clone.add(conn, "test_id".to_string()).unwrap();
+        });
+
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_identity")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .with_endpoint_deduplication(true)
+            .start()
+            .expect("Unable to start Connection Manager");
+        let connector = cm.connector();
+
+        let (subs_tx, subs_rx) = mpsc::channel();
+        connector.subscribe(subs_tx).expect("Cannot subscribe");
+
+        for connection_id in &["test_id_1", "test_id_2"] {
+            connector
+                .request_connection("inproc://deduplication_send", connection_id, None, None)
+                .expect("A connection could not be created");
+            let notification: ConnectionManagerNotification = subs_rx
+                .recv_timeout(Duration::from_secs(10))
+                .expect("Cannot get message from subscriber");
+            assert!(matches!(
+                notification,
+                ConnectionManagerNotification::Connected { connection_id: id, .. }
+                    if id == *connection_id
+            ));
+        }
+
+        mesh.get_sender()
+            .send("test_id_2".to_string(), b"hello".to_vec())
+            .expect("Unable to send message using the second ID");
+
+        // Skip any heartbeats sent over the connection
+        loop {
+            let envelope = mesh
+                .recv_timeout(Duration::from_secs(10))
+                .expect("Cannot receive message");
+            if envelope.payload() == b"hello" {
+                assert_eq!("test_id", envelope.id());
+                break;
+            }
+        }
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
+    /// Test that, with endpoint deduplication enabled, subscribers are notified for a connection
+    /// ID that reuses an existing connection when that connection is disconnected.
+    ///
+    /// This test does the following:
+    /// 1. Start a connection manager with endpoint deduplication enabled
+    /// 2. Request a tcp connection and wait for it to be connected
+    /// 3. Request a connection with a different ID to the same endpoint and wait for it to be
+    ///    connected
+    /// 4. Disconnect the remote end of the connection after it receives a heartbeat
+    /// 5. Verify that subscribers receive a `Disconnected` notification for both IDs
+    #[test]
+    fn test_endpoint_deduplication_disconnect_alias() {
+        let mut transport = Box::new(TcpTransport::default());
+        let mut listener = transport
+            .listen("tcp://localhost:0")
+            .expect("Cannot listen for connections");
+        let endpoint = listener.endpoint();
+        let mesh1 = Mesh::new(512, 128);
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            // accept incoming connection and add it to mesh2
+            let mut mesh2 = Mesh::new(512, 128);
+            let conn = listener.accept().expect("Cannot accept connection");
+            mesh2
+                .add(conn, "test_id".to_string())
+                .expect("Cannot add connection to mesh");
+
+            negotiation_connection_auth(&mesh2, "test_id", "some-peer");
+
+            // Verify mesh received heartbeat
+            let envelope = mesh2.recv().expect("Cannot receive message");
+            let heartbeat: NetworkMessage = Message::parse_from_bytes(&envelope.payload())
+                .expect("Cannot parse NetworkMessage");
+            assert_eq!(
+                heartbeat.get_message_type(),
+                NetworkMessageType::NETWORK_HEARTBEAT
+            );
+
+            // remove connection to cause the next heartbeat to fail
+            let mut connection = mesh2
+                .remove(&"test_id".to_string())
+                .expect("Cannot remove connection from mesh");
+            connection
+                .disconnect()
+                .expect("Connection failed to disconnect");
+
+            // wait for completion
+            rx.recv().expect("Did not receive completion signal");
+
+            mesh2.signal_shutdown();
+            mesh2.wait_for_shutdown().expect("Unable to shutdown mesh");
+        });
+
+        let auth_mgr = AuthorizationManager::new(
+            "test_identity".into(),
+            #[cfg(feature = "challenge-authorization")]
+            vec![new_signer()],
+            #[cfg(feature = "challenge-authorization")]
+            Arc::new(Mutex::new(Box::new(NoopFactory))),
+        )
+        .expect("Unable to create authorization pool");
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(auth_mgr.authorization_connector()))
+            .with_matrix_life_cycle(mesh1.get_life_cycle())
+            .with_matrix_sender(mesh1.get_sender())
+            .with_transport(transport)
+            .with_endpoint_deduplication(true)
+            .start()
+            .expect("Unable to start Connection Manager");
+        let connector = cm.connector();
+
+        let (subs_tx, subs_rx) = mpsc::channel();
+        connector.subscribe(subs_tx).expect("Cannot subscribe");
+        let mut subscriber = subs_rx.iter();
+
+        for connection_id in &["test_id", "test_id_alias"] {
+            connector
+                .request_connection(&endpoint, connection_id, None, None)
+                .expect("A connection could not be created");
+            let notification: ConnectionManagerNotification = subscriber
+                .next()
+                .expect("Cannot get message from subscriber");
+            assert!(matches!(
+                notification,
+                ConnectionManagerNotification::Connected { connection_id: id, .. }
+                    if id == *connection_id
+            ));
+        }
+
+        let mut disconnected = vec![];
+        for _ in 0..2 {
+            match subscriber
+                .next()
+                .expect("Cannot get message from subscriber")
+            {
+                ConnectionManagerNotification::Disconnected { connection_id, .. } => {
+                    disconnected.push(connection_id)
+                }
+                notification => panic!("Unexpected notification {:?}", notification),
+            }
+        }
+        disconnected.sort();
+        assert_eq!(
+            vec!["test_id".to_string(), "test_id_alias".to_string()],
+            disconnected
+        );
+
+        tx.send(()).expect("Could not send completion signal");
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        auth_mgr.shutdown_and_await();
+    }
+
+    /// Test that, with the default endpoint change policy, requesting an existing connection ID
+    /// with a different endpoint connects to the new endpoint.
+    ///
+    /// This test does the following:
+    /// 1. Start a connection manager with the default endpoint change policy
+    /// 2. Request an inproc connection and wait for it to be connected
+    /// 3. Request the same connection ID with a different endpoint, and verify that the request
+    ///    succeeds
+    /// 4. Verify that the new endpoint receives a connection
+    #[test]
+    fn test_endpoint_change_policy_migrate() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut first_listener = transport.listen("inproc://migrate-first").unwrap();
+        let mut second_listener = transport.listen("inproc://migrate-second").unwrap();
+        let mesh = Mesh::new(512, 128);
+        let mesh_clone = mesh.clone();
+
+        thread::spawn(move || {
+            let conn = first_listener.accept().unwrap();
+            mesh_clone.add(conn, "test_id".to_string()).unwrap();
+        });
+
+        let (accepted_tx, accepted_rx) = mpsc::channel();
+        thread::spawn(move || {
+            let conn = second_listener.accept().unwrap();
+            accepted_tx.send(conn.remote_endpoint()).unwrap();
+        });
+
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_identity")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .start()
+            .expect("Unable to start Connection Manager");
+        let connector = cm.connector();
+
+        connector
+            .request_connection_and_wait(
+                "inproc://migrate-first",
+                "test_id",
+                None,
+                None,
+                Duration::from_secs(10),
+            )
+            .expect("A connection could not be created");
+
+        connector
+            .request_connection("inproc://migrate-second", "test_id", None, None)
+            .expect("The connection could not be migrated");
+
+        accepted_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("The new endpoint did not receive a connection");
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
+    /// Test that, with the reject endpoint change policy, requesting an existing connection ID
+    /// with a different endpoint returns an error and leaves the existing connection in place.
+    ///
+    /// This test does the following:
+    /// 1. Start a connection manager with the reject endpoint change policy
+    /// 2. Request an inproc connection and wait for it to be connected
+    /// 3. Request the same connection ID with a different endpoint, and verify that the request
+    ///    returns a `ConnectionCreationError`
+    /// 4. Verify that the connection still uses the original endpoint
+    #[test]
+    fn test_endpoint_change_policy_reject() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut first_listener = transport.listen("inproc://reject-first").unwrap();
+        let _second_listener = transport.listen("inproc://reject-second").unwrap();
+        let mesh = Mesh::new(512, 128);
+        let mesh_clone = mesh.clone();
+
+        thread::spawn(move || {
+            let conn = first_listener.accept().unwrap();
+            mesh_clone.add(conn, "test_id".to_string()).unwrap();
+        });
+
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_identity")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .with_endpoint_change_policy(EndpointChangePolicy::Reject)
+            .start()
+            .expect("Unable to start Connection Manager");
+        let connector = cm.connector();
+
+        connector
+            .request_connection_and_wait(
+                "inproc://reject-first",
+                "test_id",
+                None,
+                None,
+                Duration::from_secs(10),
+            )
+            .expect("A connection could not be created");
+
+        match connector.request_connection("inproc://reject-second", "test_id", None, None) {
+            Err(ConnectionManagerError::ConnectionCreationError { .. }) => (),
+            res => panic!("Expected Err(ConnectionCreationError), got {:?}", res),
+        }
+
+        assert_eq!(
+            connector
+                .list_connections()
+                .expect("Unable to list connections"),
+            vec!["inproc://reject-first".to_string()]
+        );
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
+    /// Test that heartbeats are correctly sent to tcp connections
+    #[test]
+    fn test_heartbeat_raw_tcp() {
+        let mut transport = Box::new(TcpTransport::default());
+        let mut listener = transport.listen("tcp://localhost:0").unwrap();
+        let endpoint = listener.endpoint();
+
+        let mesh = Mesh::new(512, 128);
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let mut mesh = Mesh::new(512, 128);
+            let conn = listener.accept().unwrap();
+            mesh.add(conn, "test_id".to_string()).unwrap();
+
+            negotiation_connection_auth(&mesh, "test_id", "some-peer");
+
+            // Verify mesh received heartbeat
+
+            let envelope = mesh.recv().unwrap();
+            let heartbeat: NetworkMessage = Message::parse_from_bytes(&envelope.payload()).unwrap();
+            assert_eq!(
+                heartbeat.get_message_type(),
+                NetworkMessageType::NETWORK_HEARTBEAT
+            );
+
+            tx.send(()).expect("Could not send completion signal");
+
+            mesh.signal_shutdown();
+            mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
+        });
+
+        let auth_mgr = AuthorizationManager::new(
+            "test_identity".into(),
+            #[cfg(feature = "challenge-authorization")]
+            vec![new_signer()],
+            #[cfg(feature = "challenge-authorization")]
+            Arc::new(Mutex::new(Box::new(NoopFactory))),
+        )
+        .expect("Unable to create authorization pool");
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(auth_mgr.authorization_connector()))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .start()
+            .expect("Unable to start Connection Manager");
+        let connector = cm.connector();
+
+        connector
+            .request_connection(&endpoint, "test_id", None, None)
+            .expect("A connection could not be created");
+
+        let (sub_tx, sub_rx): (
+            Sender<ConnectionManagerNotification>,
+            mpsc::Receiver<ConnectionManagerNotification>,
+        ) = channel();
+        connector.subscribe(sub_tx).expect("Unable to respond.");
+
+        // Validate that the connection completed authorization
+        let notification = sub_rx.recv().expect("Cannot receive notification");
+        assert!(
+            notification
+                == ConnectionManagerNotification::Connected {
+                    endpoint: endpoint.clone(),
+                    connection_id: "test_id".to_string(),
+                    identity: ConnectionAuthorizationType::Trust {
+                        identity: "some-peer".into()
+                    },
+                    local_identity: ConnectionAuthorizationType::Trust {
+                        identity: "test_identity".into()
+                    }
+                }
+        );
+
+        // wait for completion
+        rx.recv().expect("Did not receive completion signal");
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        auth_mgr.shutdown_and_await();
+    }
+
+    #[test]
+    fn test_remove_connection() {
+        let mut transport = Box::new(TcpTransport::default());
+        let mut listener = transport.listen("tcp://localhost:0").unwrap();
+        let endpoint = listener.endpoint();
+        let mesh = Mesh::new(512, 128);
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let mut mesh = Mesh::new(512, 128);
+            let conn = listener.accept().unwrap();
+            mesh.add(conn, "test_id".to_string()).unwrap();
+            negotiation_connection_auth(&mesh, "test_id", "some-peer");
+
+            // wait for completion
+            rx.recv().expect("Did not receive completion signal");
+
+            mesh.signal_shutdown();
+            mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
+        });
+
+        let auth_mgr = AuthorizationManager::new(
+            "test_identity".into(),
+            #[cfg(feature = "challenge-authorization")]
+            vec![new_signer()],
+            #[cfg(feature = "challenge-authorization")]
+            Arc::new(Mutex::new(Box::new(NoopFactory))),
+        )
+        .expect("Unable to create authorization pool");
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(auth_mgr.authorization_connector()))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .start()
+            .expect("Unable to start Connection Manager");
+        let connector = cm.connector();
+
+        let (sub_tx, sub_rx): (
+            Sender<ConnectionManagerNotification>,
+            mpsc::Receiver<ConnectionManagerNotification>,
+        ) = channel();
+        connector.subscribe(sub_tx).expect("Unable to respond.");
+
+        connector
+            .request_connection(&endpoint, "test_id", None, None)
+            .expect("A connection could not be created");
+
+        // Validate that the connection completed authorization
+        let notification = sub_rx.recv().expect("Cannot receive notification");
+        assert!(
+            notification
+                == ConnectionManagerNotification::Connected {
+                    endpoint: endpoint.clone(),
+                    connection_id: "test_id".to_string(),
+                    identity: ConnectionAuthorizationType::Trust {
+                        identity: "some-peer".into()
+                    },
+                    local_identity: ConnectionAuthorizationType::Trust {
+                        identity: "test_identity".into()
+                    }
+                }
+        );
+
+        assert_eq!(
+            vec![endpoint.clone()],
+            connector
+                .list_connections()
+                .expect("Unable to list connections")
+        );
+
+        let endpoint_removed = connector
+            .remove_connection(&endpoint, "test_id")
+            .expect("Unable to remove connection");
+
+        assert_eq!(Some(endpoint.clone()), endpoint_removed);
+
+        assert!(connector
+            .list_connections()
+            .expect("Unable to list connections")
+            .is_empty());
+
+        tx.send(()).expect("Could not send completion signal");
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        auth_mgr.shutdown_and_await();
+    }
+
+    #[test]
+    fn test_remove_nonexistent_connection() {
+        let transport = Box::new(TcpTransport::default());
+        let mesh = Mesh::new(512, 128);
+
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_identity")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+
+        let endpoint_removed = connector
+            .remove_connection("tcp://localhost:5000", "test_id")
+            .expect("Unable to remove connection");
+
+        assert_eq!(None, endpoint_removed);
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
+    /// test_reconnect_raw_tcp
+    ///
+    /// Test that if a connection disconnects, the connection manager will detect the connection
+    /// has disconnected by trying to send a heartbeat. Then connection manger will try to
+    /// reconnect to the endpoint.
+    #[test]
+    fn test_reconnect_raw_tcp() {
+        let mut transport = Box::new(TcpTransport::default());
+        let mut listener = transport
+            .listen("tcp://localhost:0")
+            .expect("Cannot listen for connections");
+        let endpoint = listener.endpoint();
+        let mesh1 = Mesh::new(512, 128);
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            // accept incoming connection and add it to mesh2
+            let mut mesh2 = Mesh::new(512, 128);
+            let conn = listener.accept().expect("Cannot accept connection");
+            mesh2
+                .add(conn, "test_id".to_string())
+                .expect("Cannot add connection to mesh");
+
+            negotiation_connection_auth(&mesh2, "test_id", "some-peer");
+
+            // Verify mesh received heartbeat
+            let envelope = mesh2.recv().expect("Cannot receive message");
+            let heartbeat: NetworkMessage = Message::parse_from_bytes(&envelope.payload())
+                .expect("Cannot parse NetworkMessage");
+            assert_eq!(
+                heartbeat.get_message_type(),
+                NetworkMessageType::NETWORK_HEARTBEAT
+            );
+
+            // remove connection to cause reconnection attempt
+            let mut connection = mesh2
+                .remove(&"test_id".to_string())
+                .expect("Cannot remove connection from mesh");
+            connection
+                .disconnect()
+                .expect("Connection failed to disconnect");
+
+            // wait for reconnection attempt
+            let conn = listener.accept().expect("Unable to accept connection");
+            mesh2
+                .add(conn, "test_id".to_string())
+                .expect("Cannot add connection to mesh");
+            negotiation_connection_auth(&mesh2, "test_id", "some-peer");
+
+            // wait for completion
+            rx.recv().expect("Did not receive completion signal");
+
+            mesh2.signal_shutdown();
+            mesh2.wait_for_shutdown().expect("Unable to shutdown mesh");
+        });
+
+        let auth_mgr = AuthorizationManager::new(
+            "test_identity".into(),
+            #[cfg(feature = "challenge-authorization")]
+            vec![new_signer()],
+            #[cfg(feature = "challenge-authorization")]
+            Arc::new(Mutex::new(Box::new(NoopFactory))),
+        )
+        .expect("Unable to create authorization pool");
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(auth_mgr.authorization_connector()))
+            .with_matrix_life_cycle(mesh1.get_life_cycle())
+            .with_matrix_sender(mesh1.get_sender())
+            .with_transport(transport)
+            .start()
+            .expect("Unable to start Connection Manager");
+        let connector = cm.connector();
+
+        let (sub_tx, sub_rx): (
+            Sender<ConnectionManagerNotification>,
+            mpsc::Receiver<ConnectionManagerNotification>,
+        ) = channel();
+        connector.subscribe(sub_tx).expect("Unable to respond.");
+
+        connector
+            .request_connection(&endpoint, "test_id", None, None)
+            .expect("A connection could not be created");
+
+        // Validate that the connection completed authorization
+        let notification = sub_rx.recv().expect("Cannot receive notification");
+        assert!(
+            notification
+                == ConnectionManagerNotification::Connected {
+                    endpoint: endpoint.clone(),
+                    connection_id: "test_id".to_string(),
+                    identity: ConnectionAuthorizationType::Trust {
+                        identity: "some-peer".into()
+                    },
+                    local_identity: ConnectionAuthorizationType::Trust {
+                        identity: "test_identity".into()
+                    }
+                }
+        );
+
+        let (subs_tx, subs_rx) = mpsc::channel();
+        connector.subscribe(subs_tx).expect("Cannot subscribe");
+        let mut subscriber = subs_rx.iter();
+
+        // receive reconnecting attempt
+        let reconnecting_notification: ConnectionManagerNotification = subscriber
+            .next()
+            .expect("Cannot get message from subscriber");
+
+        assert!(
+            reconnecting_notification
+                == ConnectionManagerNotification::Disconnected {
+                    endpoint: endpoint.clone(),
+                    identity: ConnectionAuthorizationType::Trust {
+                        identity: "some-peer".into()
+                    },
+                    connection_id: "test_id".into(),
+                }
+        );
+
+        // receive successful reconnect attempt
+        let reconnection_notification = subscriber
+            .next()
+            .expect("Cannot get message from subscriber");
+
+        assert_eq!(
+            reconnection_notification,
+            ConnectionManagerNotification::Connected {
+                endpoint: endpoint.clone(),
+                connection_id: "test_id".to_string(),
+                identity: ConnectionAuthorizationType::Trust {
+                    identity: "some-peer".into()
+                },
+                local_identity: ConnectionAuthorizationType::Trust {
+                    identity: "test_identity".into()
+                }
+            }
+        );
+
+        tx.send(()).expect("Could not send completion signal");
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        auth_mgr.shutdown_and_await();
+    }
+
+    /// Test that a forced reconnect is attempted immediately, rather than after the backoff
+    /// interval of a connection that is already reconnecting.
+    ///
+    /// This test does the following:
+    /// 1. Request an outbound tcp connection and wait for it to be connected
+    /// 2. Close the remote listener and connection, so that the heartbeat and the following
+    ///    reconnection attempt both fail, leaving the connection in the reconnecting state
+    /// 3. Listen on the endpoint again and force a reconnect
+    /// 4. Verify that the connection is reestablished before the retry frequency has elapsed
+    #[test]
+    fn test_force_reconnect() {
+        let mut transport = Box::new(TcpTransport::default());
+        let mut listener = transport
+            .listen("tcp://localhost:0")
+            .expect("Cannot listen for connections");
+        let endpoint = listener.endpoint();
+        let mesh1 = Mesh::new(512, 128);
+
+        thread::spawn(move || {
+            let mut mesh2 = Mesh::new(512, 128);
+            let conn = listener.accept().expect("Cannot accept connection");
+            mesh2
+                .add(conn, "test_id".to_string())
+                .expect("Cannot add connection to mesh");
+            negotiation_connection_auth(&mesh2, "test_id", "some-peer");
+
+            // wait for a heartbeat, then close the listener and the connection, so that the
+            // reconnection attempt fails
+            mesh2.recv().expect("Cannot receive message");
+            drop(listener);
+            let mut connection = mesh2
+                .remove(&"test_id".to_string())
+                .expect("Cannot remove connection from mesh");
+            connection
+                .disconnect()
+                .expect("Connection failed to disconnect");
+
+            mesh2.signal_shutdown();
+            mesh2.wait_for_shutdown().expect("Unable to shutdown mesh");
+        });
+
+        let auth_mgr = AuthorizationManager::new(
+            "test_identity".into(),
+            #[cfg(feature = "challenge-authorization")]
+            vec![new_signer()],
+            #[cfg(feature = "challenge-authorization")]
+            Arc::new(Mutex::new(Box::new(NoopFactory))),
+        )
+        .expect("Unable to create authorization pool");
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(auth_mgr.authorization_connector()))
+            .with_matrix_life_cycle(mesh1.get_life_cycle())
+            .with_matrix_sender(mesh1.get_sender())
+            .with_transport(transport)
+            .with_heartbeat_interval(1)
+            .start()
+            .expect("Unable to start Connection Manager");
+        let connector = cm.connector();
+
+        let (subs_tx, subs_rx) = mpsc::channel();
+        connector.subscribe(subs_tx).expect("Cannot subscribe");
+
+        connector
+            .request_connection(&endpoint, "test_id", None, None)
+            .expect("A connection could not be created");
+
+        let notification: ConnectionManagerNotification = subs_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("Cannot get message from subscriber");
+        assert!(matches!(
+            notification,
+            ConnectionManagerNotification::Connected { .. }
+        ));
+
+        // Wait for the reconnection attempt to fail
+        loop {
+            let notification = subs_rx
+                .recv_timeout(Duration::from_secs(10))
+                .expect("Cannot get message from subscriber");
+            if let ConnectionManagerNotification::NonFatalConnectionError { attempts, .. } =
+                notification
+            {
+                assert_eq!(attempts, 1);
+                break;
+            }
+        }
+
+        let mut listener = TcpTransport::default()
+            .listen(&endpoint)
+            .expect("Cannot listen for connections");
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let mut mesh2 = Mesh::new(512, 128);
+            let conn = listener.accept().expect("Cannot accept connection");
+            mesh2
+                .add(conn, "test_id".to_string())
+                .expect("Cannot add connection to mesh");
+            negotiation_connection_auth(&mesh2, "test_id", "some-peer");
+
+            // wait for completion
+            rx.recv().expect("Did not receive completion signal");
+
+            mesh2.signal_shutdown();
+            mesh2.wait_for_shutdown().expect("Unable to shutdown mesh");
+        });
+
+        connector
+            .force_reconnect("test_id")
+            .expect("Unable to force reconnect");
+
+        // The failed attempt doubled the retry frequency, so the connection would not otherwise
+        // be retried within the initial retry frequency
+        let notification: ConnectionManagerNotification = subs_rx
+            .recv_timeout(Duration::from_secs(INITIAL_RETRY_FREQUENCY))
+            .expect("Connection was not reestablished");
+        assert!(matches!(
+            notification,
+            ConnectionManagerNotification::Connected { .. }
+        ));
+
+        assert!(matches!(
+            connector.force_reconnect("unknown_id"),
+            Err(ConnectionManagerError::ConnectionReconnectError(_))
+        ));
+
+        tx.send(()).expect("Could not send completion signal");
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        auth_mgr.shutdown_and_await();
+    }
+
+    /// Test that the log records of a connection carry its ID and endpoint through a
+    /// reconnection.
+    ///
+    /// This test does the following:
+    /// 1. Install a logger that captures the connection manager's log records
+    /// 2. Request an outbound tcp connection and wait for it to be connected
+    /// 3. Disconnect the remote connection and wait for the connection to be reestablished
+    /// 4. Verify that the reconnection attempt and both authorizations were logged with the
+    ///    connection ID and endpoint
+    #[test]
+    fn test_connection_log_reconnect() {
+        install_capturing_logger();
+
+        let mut transport = Box::new(TcpTransport::default());
+        let mut listener = transport
+            .listen("tcp://localhost:0")
+            .expect("Cannot listen for connections");
+        let endpoint = listener.endpoint();
+        let mesh1 = Mesh::new(512, 128);
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let mut mesh2 = Mesh::new(512, 128);
+            let conn = listener.accept().expect("Cannot accept connection");
+            mesh2
+                .add(conn, "log_test_id".to_string())
+                .expect("Cannot add connection to mesh");
+            negotiation_connection_auth(&mesh2, "log_test_id", "some-peer");
+
+            // wait for a heartbeat, then remove the connection to cause a reconnection attempt
+            mesh2.recv().expect("Cannot receive message");
+            let mut connection = mesh2
+                .remove(&"log_test_id".to_string())
+                .expect("Cannot remove connection from mesh");
+            connection
+                .disconnect()
+                .expect("Connection failed to disconnect");
+
+            let conn = listener.accept().expect("Unable to accept connection");
+            mesh2
+                .add(conn, "log_test_id".to_string())
+                .expect("Cannot add connection to mesh");
+            negotiation_connection_auth(&mesh2, "log_test_id", "some-peer");
+
+            // wait for completion
+            rx.recv().expect("Did not receive completion signal");
+
+            mesh2.signal_shutdown();
+            mesh2.wait_for_shutdown().expect("Unable to shutdown mesh");
+        });
+
+        let auth_mgr = AuthorizationManager::new(
+            "test_identity".into(),
+            #[cfg(feature = "challenge-authorization")]
+            vec![new_signer()],
+            #[cfg(feature = "challenge-authorization")]
+            Arc::new(Mutex::new(Box::new(NoopFactory))),
+        )
+        .expect("Unable to create authorization pool");
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(auth_mgr.authorization_connector()))
+            .with_matrix_life_cycle(mesh1.get_life_cycle())
+            .with_matrix_sender(mesh1.get_sender())
+            .with_transport(transport)
+            .with_heartbeat_interval(1)
+            .start()
+            .expect("Unable to start Connection Manager");
+        let connector = cm.connector();
+
+        let (subs_tx, subs_rx) = mpsc::channel();
+        connector.subscribe(subs_tx).expect("Cannot subscribe");
+
+        connector
+            .request_connection(&endpoint, "log_test_id", None, None)
+            .expect("A connection could not be created");
+
+        // Wait for the initial connection and the reconnection
+        let mut connected = 0;
+        while connected < 2 {
+            let notification = subs_rx
+                .recv_timeout(Duration::from_secs(10))
+                .expect("Cannot get message from subscriber");
+            if let ConnectionManagerNotification::Connected { .. } = notification {
+                connected += 1;
+            }
+        }
+
+        let context = format!("connection_id=log_test_id endpoint={}: ", endpoint);
+        let records: Vec<String> = CAPTURED_LOGS
+            .lock()
+            .expect("Captured logs lock was poisoned")
+            .iter()
+            .filter_map(|record| record.strip_prefix(&context).map(String::from))
+            .collect();
+
+        assert_eq!(
+            records
+                .iter()
+                .filter(|record| record.as_str() == "Connection authorized")
+                .count(),
+            2
+        );
+        assert!(records
+            .iter()
+            .any(|record| record.as_str() == "Attempting to reconnect"));
+
+        tx.send(()).expect("Could not send completion signal");
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        auth_mgr.shutdown_and_await();
+    }
+
+    /// Test that a failed reconnection attempt reports the kind of error that caused it.
+    ///
+    /// This test does the following:
+    /// 1. Request an outbound tcp connection and wait for it to be connected
+    /// 2. Close the remote listener and connection, so that the following reconnection attempt
+    ///    is refused
+    /// 3. Verify that the NonFatalConnectionError notification reports `ConnectionRefused`
+    #[test]
+    fn test_reconnect_failure_error_kind() {
+        let mut transport = Box::new(TcpTransport::default());
+        let mut listener = transport
+            .listen("tcp://localhost:0")
+            .expect("Cannot listen for connections");
+        let endpoint = listener.endpoint();
+        let mesh1 = Mesh::new(512, 128);
+
+        thread::spawn(move || {
+            let mut mesh2 = Mesh::new(512, 128);
+            let conn = listener.accept().expect("Cannot accept connection");
+            mesh2
+                .add(conn, "test_id".to_string())
+                .expect("Cannot add connection to mesh");
+            negotiation_connection_auth(&mesh2, "test_id", "some-peer");
+
+            // wait for a heartbeat, then close the listener and the connection, so that the
+            // reconnection attempt is refused
+            mesh2.recv().expect("Cannot receive message");
+            drop(listener);
+            let mut connection = mesh2
+                .remove(&"test_id".to_string())
+                .expect("Cannot remove connection from mesh");
+            connection
+                .disconnect()
+                .expect("Connection failed to disconnect");
+
+            mesh2.signal_shutdown();
+            mesh2.wait_for_shutdown().expect("Unable to shutdown mesh");
+        });
+
+        let auth_mgr = AuthorizationManager::new(
+            "test_identity".into(),
+            #[cfg(feature = "challenge-authorization")]
+            vec![new_signer()],
+            #[cfg(feature = "challenge-authorization")]
+            Arc::new(Mutex::new(Box::new(NoopFactory))),
+        )
+        .expect("Unable to create authorization pool");
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(auth_mgr.authorization_connector()))
+            .with_matrix_life_cycle(mesh1.get_life_cycle())
+            .with_matrix_sender(mesh1.get_sender())
+            .with_transport(transport)
+            .with_heartbeat_interval(1)
+            .start()
+            .expect("Unable to start Connection Manager");
+        let connector = cm.connector();
+
+        let (subs_tx, subs_rx) = mpsc::channel();
+        connector.subscribe(subs_tx).expect("Cannot subscribe");
+
+        connector
+            .request_connection(&endpoint, "test_id", None, None)
+            .expect("A connection could not be created");
+
+        let notification: ConnectionManagerNotification = subs_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("Cannot get message from subscriber");
+        assert!(matches!(
+            notification,
+            ConnectionManagerNotification::Connected { .. }
+        ));
+
+        // Wait for the reconnection attempt to fail
+        loop {
+            let notification = subs_rx
+                .recv_timeout(Duration::from_secs(10))
+                .expect("Cannot get message from subscriber");
+            if let ConnectionManagerNotification::NonFatalConnectionError { error_kind, .. } =
+                notification
+            {
+                assert_eq!(error_kind, Some(io::ErrorKind::ConnectionRefused));
+                break;
+            }
+        }
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        auth_mgr.shutdown_and_await();
+    }
+
+    /// Test that the metrics recorder is notified as connections are established, dropped and
+    /// reconnected.
+    ///
+    /// This test does the following:
+    /// 1. Start a connection manager with a counting metrics recorder
+    /// 2. Request an outbound tcp connection and verify one connection is counted as established
+    /// 3. Disconnect the remote side of the connection, after it has received a heartbeat
+    /// 4. Verify that the heartbeat, the reconnection attempt and the reestablished connection
+    ///    are counted
+    #[test]
+    fn test_metrics() {
+        let mut transport = Box::new(TcpTransport::default());
+        let mut listener = transport
+            .listen("tcp://localhost:0")
+            .expect("Cannot listen for connections");
+        let endpoint = listener.endpoint();
+        let mesh1 = Mesh::new(512, 128);
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let mut mesh2 = Mesh::new(512, 128);
             let conn = listener.accept().expect("Cannot accept connection");
             mesh2
                 .add(conn, "test_id".to_string())
                 .expect("Cannot add connection to mesh");
-
             negotiation_connection_auth(&mesh2, "test_id", "some-peer");
 
-            // Verify mesh received heartbeat
-            let envelope = mesh2.recv().expect("Cannot receive message");
-            let heartbeat: NetworkMessage = Message::parse_from_bytes(&envelope.payload())
-                .expect("Cannot parse NetworkMessage");
-            assert_eq!(
-                heartbeat.get_message_type(),
-                NetworkMessageType::NETWORK_HEARTBEAT
-            );
-
-            // remove connection to cause reconnection attempt
+            // wait for a heartbeat, then remove the connection to cause a reconnection attempt
+            mesh2.recv().expect("Cannot receive message");
             let mut connection = mesh2
                 .remove(&"test_id".to_string())
                 .expect("Cannot remove connection from mesh");
@@ -1358,20 +3859,342 @@ mod tests {
                 .disconnect()
                 .expect("Connection failed to disconnect");
 
-            // wait for reconnection attempt
             let conn = listener.accept().expect("Unable to accept connection");
             mesh2
                 .add(conn, "test_id".to_string())
                 .expect("Cannot add connection to mesh");
             negotiation_connection_auth(&mesh2, "test_id", "some-peer");
 
-            // wait for completion
-            rx.recv().expect("Did not receive completion signal");
+            // wait for completion
+            rx.recv().expect("Did not receive completion signal");
+
+            mesh2.signal_shutdown();
+            mesh2.wait_for_shutdown().expect("Unable to shutdown mesh");
+        });
+
+        let auth_mgr = AuthorizationManager::new(
+            "test_identity".into(),
+            #[cfg(feature = "challenge-authorization")]
+            vec![new_signer()],
+            #[cfg(feature = "challenge-authorization")]
+            Arc::new(Mutex::new(Box::new(NoopFactory))),
+        )
+        .expect("Unable to create authorization pool");
+        let metrics = CountingMetrics::default();
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(auth_mgr.authorization_connector()))
+            .with_matrix_life_cycle(mesh1.get_life_cycle())
+            .with_matrix_sender(mesh1.get_sender())
+            .with_transport(transport)
+            .with_heartbeat_interval(1)
+            .with_metrics(Box::new(metrics.clone()))
+            .start()
+            .expect("Unable to start Connection Manager");
+        let connector = cm.connector();
+
+        let (subs_tx, subs_rx) = mpsc::channel();
+        connector.subscribe(subs_tx).expect("Cannot subscribe");
+
+        connector
+            .request_connection(&endpoint, "test_id", None, None)
+            .expect("A connection could not be created");
+
+        let notification: ConnectionManagerNotification = subs_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("Cannot get message from subscriber");
+        assert!(matches!(
+            notification,
+            ConnectionManagerNotification::Connected { .. }
+        ));
+        assert_eq!(metrics.connections_established(), 1);
+        assert_eq!(metrics.reconnection_attempts(), 0);
+
+        // Wait for the connection to be disconnected and reestablished
+        loop {
+            let notification = subs_rx
+                .recv_timeout(Duration::from_secs(10))
+                .expect("Cannot get message from subscriber");
+            if let ConnectionManagerNotification::Connected { .. } = notification {
+                break;
+            }
+        }
+
+        assert_eq!(metrics.connections_established(), 2);
+        assert!(metrics.reconnection_attempts() >= 1);
+        assert!(metrics.heartbeats_sent() >= 1);
+        assert_eq!(metrics.authorization_failures(), 0);
+
+        tx.send(()).expect("Could not send completion signal");
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        auth_mgr.shutdown_and_await();
+    }
+
+    /// Test that the metrics recorder is notified when a connection fails authorization.
+    #[test]
+    fn test_metrics_authorization_failure() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+
+        thread::spawn(move || {
+            listener.accept().unwrap();
+        });
+
+        let mesh = Mesh::new(512, 128);
+        let metrics = CountingMetrics::default();
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(UnauthorizedAuthorizer(
+                UnauthorizedReason::UnknownIdentity,
+            )))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .with_metrics(Box::new(metrics.clone()))
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+
+        assert!(connector
+            .request_connection_and_wait(
+                "inproc://test",
+                "test_id",
+                None,
+                None,
+                Duration::from_secs(10),
+            )
+            .is_err());
+
+        assert_eq!(metrics.authorization_failures(), 1);
+        assert_eq!(metrics.connections_established(), 0);
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
+    /// Test that the reason a connection failed authorization is reported in the
+    /// `FatalConnectionError` notification.
+    ///
+    /// This test does the following:
+    /// 1. Start a connection manager with an authorizer that rejects connections for having an
+    ///    invalid signature
+    /// 2. Request an outbound connection
+    /// 3. Verify that the `FatalConnectionError` notification carries an `Unauthorized` error
+    ///    with the invalid signature reason
+    #[test]
+    fn test_unauthorized_reason() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+
+        thread::spawn(move || {
+            listener.accept().unwrap();
+        });
+
+        let mesh = Mesh::new(512, 128);
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(UnauthorizedAuthorizer(
+                UnauthorizedReason::InvalidSignature,
+            )))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+
+        let (subs_tx, subs_rx) = mpsc::channel();
+        connector.subscribe(subs_tx).expect("Cannot subscribe");
+
+        connector
+            .request_connection("inproc://test", "test_id", None, None)
+            .expect("A connection could not be requested");
+
+        let notification = subs_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("Cannot get message from subscriber");
+        assert_eq!(
+            notification,
+            ConnectionManagerNotification::FatalConnectionError {
+                endpoint: "inproc://test".into(),
+                connection_id: "test_id".into(),
+                error: ConnectionManagerError::Unauthorized {
+                    connection_id: "test_id".into(),
+                    reason: UnauthorizedReason::InvalidSignature,
+                },
+            }
+        );
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
+    /// Test that an inbound connection may be added to the connection manager
+    /// This test does the following:
+    /// 1. Add an inbound connection to a connection manager
+    /// 2. Notify inbound listeners
+    /// 3. The connection can be removed by its reported remote endpoint
+    #[test]
+    fn test_inbound_connection() {
+        let mut transport = InprocTransport::default();
+        let mut listener = transport
+            .listen("inproc://test_inbound_connection")
+            .expect("Cannot listen for connections");
+
+        let mesh = Mesh::new(512, 128);
+
+        let (conn_tx, conn_rx) = mpsc::channel();
+
+        let mut remote_transport = transport.clone();
+        let jh = thread::spawn(move || {
+            let _connection = remote_transport
+                .connect("inproc://test_inbound_connection")
+                .unwrap();
+
+            // block until done
+            conn_rx.recv().unwrap();
+        });
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_identity")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(Box::new(transport))
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+
+        let (subs_tx, subs_rx) = mpsc::channel();
+        connector.subscribe(subs_tx).expect("Cannot get subscriber");
+
+        let connection = listener.accept().unwrap();
+        connector
+            .add_inbound_connection(connection)
+            .expect("Unable to add inbound connection");
+
+        let notification = subs_rx
+            .iter()
+            .next()
+            .expect("Cannot get message from subscriber");
+        let connection_id_for_removal;
+        if let ConnectionManagerNotification::InboundConnection {
+            endpoint,
+            connection_id,
+            ..
+        } = notification
+        {
+            connection_id_for_removal = connection_id.to_string();
+            assert_eq!("inproc://test_inbound_connection", &endpoint);
+        } else {
+            panic!("Incorrect notification received: {:?}", notification);
+        }
+
+        let connection_endpoints = connector.list_connections().unwrap();
+        assert_eq!(
+            vec!["inproc://test_inbound_connection".to_string()],
+            connection_endpoints
+        );
+
+        connector
+            .remove_connection(
+                "inproc://test_inbound_connection",
+                &connection_id_for_removal,
+            )
+            .unwrap();
+        let connection_endpoints = connector.list_connections().unwrap();
+        assert!(connection_endpoints.is_empty());
+
+        conn_tx.send(()).unwrap();
+        jh.join().unwrap();
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
+    /// Test that a custom connection ID generator is used for inbound connections
+    /// This test does the following:
+    /// 1. Start a connection manager with a generator that derives the ID from the remote endpoint
+    /// 2. Add an inbound connection to the connection manager
+    /// 3. Verify that the generated ID is reported in the `InboundConnection` notification
+    #[test]
+    fn test_inbound_connection_id_generator() {
+        let mut transport = InprocTransport::default();
+        let mut listener = transport
+            .listen("inproc://test_inbound_connection_id_generator")
+            .expect("Cannot listen for connections");
+
+        let mesh = Mesh::new(512, 128);
+
+        let (conn_tx, conn_rx) = mpsc::channel();
+
+        let mut remote_transport = transport.clone();
+        let jh = thread::spawn(move || {
+            let _connection = remote_transport
+                .connect("inproc://test_inbound_connection_id_generator")
+                .unwrap();
+
+            // block until done
+            conn_rx.recv().unwrap();
+        });
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_identity")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(Box::new(transport))
+            .with_connection_id_generator(Box::new(|connection| {
+                format!("{}-0001", connection.remote_endpoint())
+            }))
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+
+        let (subs_tx, subs_rx) = mpsc::channel();
+        connector.subscribe(subs_tx).expect("Cannot get subscriber");
+
+        let connection = listener.accept().unwrap();
+        connector
+            .add_inbound_connection(connection)
+            .expect("Unable to add inbound connection");
+
+        let notification = subs_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("Cannot get message from subscriber");
+        if let ConnectionManagerNotification::InboundConnection { connection_id, .. } = notification
+        {
+            assert_eq!(
+                "inproc://test_inbound_connection_id_generator-0001",
+                &connection_id
+            );
+        } else {
+            panic!("Incorrect notification received: {:?}", notification);
+        }
+
+        conn_tx.send(()).unwrap();
+        jh.join().unwrap();
 
-            mesh2.signal_shutdown();
-            mesh2.wait_for_shutdown().expect("Unable to shutdown mesh");
-        });
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
+    /// Test that an inbound tcp connection can be add and removed from the network.o
+    ///
+    /// This connection requires negotiating the connection authorization handshake.
+    #[test]
+    fn test_inbound_tcp_connection() {
+        let mut transport = Box::new(TcpTransport::default());
+        let mut listener = transport
+            .listen("tcp://localhost:0")
+            .expect("Cannot listen for tcp connections");
+        let endpoint = listener.endpoint();
 
+        let mesh = Mesh::new(512, 128);
         let auth_mgr = AuthorizationManager::new(
             "test_identity".into(),
             #[cfg(feature = "challenge-authorization")]
@@ -1380,81 +4203,81 @@ mod tests {
             Arc::new(Mutex::new(Box::new(NoopFactory))),
         )
         .expect("Unable to create authorization pool");
+
+        let (conn_tx, conn_rx) = mpsc::channel();
+        let server_endpoint = endpoint.clone();
+        let jh = thread::spawn(move || {
+            let mut mesh = Mesh::new(512, 128);
+            let mut transport = Box::new(TcpTransport::default());
+            let connection = transport.connect(&server_endpoint).unwrap();
+
+            mesh.add(connection, "test_id".into())
+                .expect("Unable to add to remote mesh");
+
+            negotiation_connection_auth(&mesh, "test_id", "inbound-identity");
+
+            // block until done
+            conn_rx.recv().unwrap();
+            mesh.signal_shutdown();
+            mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
+        });
+
         let mut cm = ConnectionManager::builder()
             .with_authorizer(Box::new(auth_mgr.authorization_connector()))
-            .with_matrix_life_cycle(mesh1.get_life_cycle())
-            .with_matrix_sender(mesh1.get_sender())
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
             .with_transport(transport)
             .start()
             .expect("Unable to start Connection Manager");
         let connector = cm.connector();
 
-        let (sub_tx, sub_rx): (
-            Sender<ConnectionManagerNotification>,
-            mpsc::Receiver<ConnectionManagerNotification>,
-        ) = channel();
-        connector.subscribe(sub_tx).expect("Unable to respond.");
+        let (subs_tx, subs_rx) = mpsc::channel();
+        connector.subscribe(subs_tx).expect("Cannot get subscriber");
 
+        let connection = listener.accept().unwrap();
+        let remote_endpoint = connection.remote_endpoint();
         connector
-            .request_connection(&endpoint, "test_id", None, None)
-            .expect("A connection could not be created");
-
-        // Validate that the connection completed authorization
-        let notification = sub_rx.recv().expect("Cannot receive notification");
-        assert!(
-            notification
-                == ConnectionManagerNotification::Connected {
-                    endpoint: endpoint.clone(),
-                    connection_id: "test_id".to_string(),
-                    identity: ConnectionAuthorizationType::Trust {
-                        identity: "some-peer".into()
-                    },
-                    local_identity: ConnectionAuthorizationType::Trust {
-                        identity: "test_identity".into()
-                    }
-                }
-        );
-
-        let (subs_tx, subs_rx) = mpsc::channel();
-        connector.subscribe(subs_tx).expect("Cannot subscribe");
-        let mut subscriber = subs_rx.iter();
+            .add_inbound_connection(connection)
+            .expect("Unable to add inbound connection");
 
-        // receive reconnecting attempt
-        let reconnecting_notification: ConnectionManagerNotification = subscriber
+        let notification = subs_rx
+            .iter()
             .next()
             .expect("Cannot get message from subscriber");
 
-        assert!(
-            reconnecting_notification
-                == ConnectionManagerNotification::Disconnected {
-                    endpoint: endpoint.clone(),
-                    identity: ConnectionAuthorizationType::Trust {
-                        identity: "some-peer".into()
-                    },
-                    connection_id: "test_id".into(),
-                }
-        );
-
-        // receive successful reconnect attempt
-        let reconnection_notification = subscriber
-            .next()
-            .expect("Cannot get message from subscriber");
+        let connection_id_to_remove;
 
-        assert_eq!(
-            reconnection_notification,
-            ConnectionManagerNotification::Connected {
-                endpoint: endpoint.clone(),
-                connection_id: "test_id".to_string(),
-                identity: ConnectionAuthorizationType::Trust {
-                    identity: "some-peer".into()
+        if let ConnectionManagerNotification::InboundConnection {
+            ref identity,
+            connection_id,
+            ..
+        } = &notification
+        {
+            assert_eq!(
+                identity,
+                &ConnectionAuthorizationType::Trust {
+                    identity: "inbound-identity".into()
                 },
-                local_identity: ConnectionAuthorizationType::Trust {
-                    identity: "test_identity".into()
-                }
-            }
-        );
+            );
+            connection_id_to_remove = connection_id.to_string();
+        } else {
+            panic!(
+                "Did not receive the correct notification: {:?}",
+                notification
+            );
+        }
 
-        tx.send(()).expect("Could not send completion signal");
+        let connection_endpoints = connector.list_connections().unwrap();
+        assert_eq!(vec![remote_endpoint.clone()], connection_endpoints);
+
+        connector
+            .remove_connection(&remote_endpoint, &connection_id_to_remove)
+            .unwrap();
+        let connection_endpoints = connector.list_connections().unwrap();
+        assert!(connection_endpoints.is_empty());
+
+        conn_tx.send(()).unwrap();
+        jh.join().unwrap();
 
         cm.signal_shutdown();
         cm.wait_for_shutdown()
@@ -1462,94 +4285,216 @@ mod tests {
         auth_mgr.shutdown_and_await();
     }
 
-    /// Test that an inbound connection may be added to the connection manager
+    /// Test that an outbound connection authorized with the local identity is refused.
+    ///
     /// This test does the following:
-    /// 1. Add an inbound connection to a connection manager
-    /// 2. Notify inbound listeners
-    /// 3. The connection can be removed by its reported remote endpoint
+    /// 1. Start a connection manager whose local identity matches the identity returned by its
+    ///    authorizer
+    /// 2. Request an outbound connection
+    /// 3. Verify that a FatalConnectionError is received for the connection
+    /// 4. Verify that the connection is not listed by the connection manager
     #[test]
-    fn test_inbound_connection() {
-        let mut transport = InprocTransport::default();
-        let mut listener = transport
-            .listen("inproc://test_inbound_connection")
-            .expect("Cannot listen for connections");
+    fn test_self_connection_refused() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+
+        thread::spawn(move || {
+            listener.accept().unwrap();
+        });
 
         let mesh = Mesh::new(512, 128);
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_identity")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .with_local_identity(ConnectionAuthorizationType::Trust {
+                identity: "test_identity".into(),
+            })
+            .start()
+            .expect("Unable to start Connection Manager");
 
-        let (conn_tx, conn_rx) = mpsc::channel();
+        let connector = cm.connector();
 
-        let mut remote_transport = transport.clone();
-        let jh = thread::spawn(move || {
-            let _connection = remote_transport
-                .connect("inproc://test_inbound_connection")
-                .unwrap();
+        let (subs_tx, subs_rx) = mpsc::channel();
+        connector.subscribe(subs_tx).expect("Cannot subscribe");
 
-            // block until done
-            conn_rx.recv().unwrap();
+        connector
+            .request_connection("inproc://test", "test_id", None, None)
+            .expect("A connection could not be created");
+
+        let notification = subs_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("Cannot get message from subscriber");
+        match notification {
+            ConnectionManagerNotification::FatalConnectionError {
+                endpoint,
+                connection_id,
+                ..
+            } => {
+                assert_eq!(endpoint, "inproc://test");
+                assert_eq!(connection_id, "test_id");
+            }
+            _ => panic!("Incorrect notification received: {:?}", notification),
+        }
+
+        assert!(connector.list_connections().unwrap().is_empty());
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
+    /// Test that a connection whose authorization never completes is dropped after the
+    /// authorization timeout.
+    ///
+    /// This test does the following:
+    /// 1. Start a connection manager with an authorizer that never reports a result
+    /// 2. Request an outbound connection
+    /// 3. Verify that a FatalConnectionError with a timeout error is received for the connection
+    /// 4. Verify that the connection is not listed by the connection manager
+    #[test]
+    fn test_authorization_timeout() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+
+        thread::spawn(move || {
+            listener.accept().unwrap();
         });
+
+        let mesh = Mesh::new(512, 128);
         let mut cm = ConnectionManager::builder()
-            .with_authorizer(Box::new(NoopAuthorizer::new("test_identity")))
+            .with_authorizer(Box::new(StalledAuthorizer))
             .with_matrix_life_cycle(mesh.get_life_cycle())
             .with_matrix_sender(mesh.get_sender())
-            .with_transport(Box::new(transport))
+            .with_transport(transport)
+            .with_heartbeat_interval(1)
+            .with_authorization_timeout(Duration::from_millis(100))
             .start()
             .expect("Unable to start Connection Manager");
 
         let connector = cm.connector();
 
         let (subs_tx, subs_rx) = mpsc::channel();
-        connector.subscribe(subs_tx).expect("Cannot get subscriber");
+        connector.subscribe(subs_tx).expect("Cannot subscribe");
 
-        let connection = listener.accept().unwrap();
         connector
-            .add_inbound_connection(connection)
-            .expect("Unable to add inbound connection");
+            .request_connection("inproc://test", "test_id", None, None)
+            .expect("A connection could not be created");
 
         let notification = subs_rx
-            .iter()
-            .next()
+            .recv_timeout(Duration::from_secs(10))
             .expect("Cannot get message from subscriber");
-        let connection_id_for_removal;
-        if let ConnectionManagerNotification::InboundConnection {
-            endpoint,
-            connection_id,
-            ..
-        } = notification
-        {
-            connection_id_for_removal = connection_id.to_string();
-            assert_eq!("inproc://test_inbound_connection", &endpoint);
-        } else {
-            panic!("Incorrect notification received: {:?}", notification);
+        match notification {
+            ConnectionManagerNotification::FatalConnectionError {
+                endpoint,
+                connection_id,
+                error: ConnectionManagerError::Timeout(_),
+            } => {
+                assert_eq!(endpoint, "inproc://test");
+                assert_eq!(connection_id, "test_id");
+            }
+            _ => panic!("Incorrect notification received: {:?}", notification),
+        }
+
+        assert!(connector.list_connections().unwrap().is_empty());
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+    }
+
+    /// Test that no more than the maximum number of concurrent authorizations are passed to the
+    /// authorizer at once, and that queued authorizations are started as earlier ones complete.
+    ///
+    /// This test does the following:
+    /// 1. Start a connection manager, limited to two concurrent authorizations, with an
+    ///    authorizer that holds each authorization until the test completes it
+    /// 2. Request four outbound connections
+    /// 3. Verify that only two authorizations are started
+    /// 4. Complete the authorizations one at a time, verifying that a queued authorization is
+    ///    started after each
+    /// 5. Verify that all four connections are connected and that no more than two
+    ///    authorizations were ever in flight at once
+    #[test]
+    fn test_max_concurrent_authorizations() {
+        let mut transport = Box::new(InprocTransport::default());
+        let mut listener = transport.listen("inproc://test").unwrap();
+
+        thread::spawn(move || {
+            for _ in 0..4 {
+                listener.accept().unwrap();
+            }
+        });
+
+        let authorizer = HoldingAuthorizer::default();
+
+        let mesh = Mesh::new(512, 128);
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(authorizer.clone()))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .with_max_concurrent_authorizations(2)
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+
+        let (subs_tx, subs_rx) = mpsc::channel();
+        connector.subscribe(subs_tx).expect("Cannot subscribe");
+
+        for i in 0..4 {
+            connector
+                .request_connection("inproc://test", &format!("test_id_{}", i), None, None)
+                .expect("A connection could not be created");
         }
 
-        let connection_endpoints = connector.list_connections().unwrap();
+        authorizer.wait_for_started(2);
+        // Give the connection manager a chance to exceed the limit
+        thread::sleep(Duration::from_millis(100));
+        assert_eq!(authorizer.started(), 2);
+
+        for completed in 1..=4 {
+            assert!(authorizer.complete_next());
+            authorizer.wait_for_started(min(completed + 2, 4));
+        }
+
+        let mut connected = vec![];
+        for _ in 0..4 {
+            match subs_rx
+                .recv_timeout(Duration::from_secs(10))
+                .expect("Cannot get message from subscriber")
+            {
+                ConnectionManagerNotification::Connected { connection_id, .. } => {
+                    connected.push(connection_id)
+                }
+                notification => panic!("Incorrect notification received: {:?}", notification),
+            }
+        }
+        connected.sort();
         assert_eq!(
-            vec!["inproc://test_inbound_connection".to_string()],
-            connection_endpoints
+            connected,
+            vec!["test_id_0", "test_id_1", "test_id_2", "test_id_3"]
         );
 
-        connector
-            .remove_connection(
-                "inproc://test_inbound_connection",
-                &connection_id_for_removal,
-            )
-            .unwrap();
-        let connection_endpoints = connector.list_connections().unwrap();
-        assert!(connection_endpoints.is_empty());
-
-        conn_tx.send(()).unwrap();
-        jh.join().unwrap();
+        assert_eq!(authorizer.max_in_flight(), 2);
 
         cm.signal_shutdown();
         cm.wait_for_shutdown()
             .expect("Unable to shutdown connection manager");
     }
 
-    /// Test that an inbound tcp connection can be add and removed from the network.o
+    /// Test that the disconnection of an inbound connection is reported to subscribers exactly
+    /// once, and that no reconnection is attempted.
     ///
-    /// This connection requires negotiating the connection authorization handshake.
+    /// This test does the following:
+    /// 1. Add an inbound tcp connection to a connection manager with a short heartbeat interval
+    /// 2. Disconnect the remote side of the connection
+    /// 3. Verify that a Disconnected notification is received for the connection
+    /// 4. Verify that no further notifications are received over several heartbeats
     #[test]
-    fn test_inbound_tcp_connection() {
+    fn test_inbound_tcp_disconnection() {
         let mut transport = Box::new(TcpTransport::default());
         let mut listener = transport
             .listen("tcp://localhost:0")
@@ -1566,7 +4511,7 @@ mod tests {
         )
         .expect("Unable to create authorization pool");
 
-        let (conn_tx, conn_rx) = mpsc::channel();
+        let (disconnect_tx, disconnect_rx) = mpsc::channel();
         let server_endpoint = endpoint.clone();
         let jh = thread::spawn(move || {
             let mut mesh = Mesh::new(512, 128);
@@ -1578,8 +4523,15 @@ mod tests {
 
             negotiation_connection_auth(&mesh, "test_id", "inbound-identity");
 
-            // block until done
-            conn_rx.recv().unwrap();
+            // wait until the inbound connection has been added, then hang up
+            disconnect_rx.recv().unwrap();
+            let mut connection = mesh
+                .remove(&"test_id".to_string())
+                .expect("Cannot remove connection from mesh");
+            connection
+                .disconnect()
+                .expect("Connection failed to disconnect");
+
             mesh.signal_shutdown();
             mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
         });
@@ -1589,6 +4541,7 @@ mod tests {
             .with_matrix_life_cycle(mesh.get_life_cycle())
             .with_matrix_sender(mesh.get_sender())
             .with_transport(transport)
+            .with_heartbeat_interval(1)
             .start()
             .expect("Unable to start Connection Manager");
         let connector = cm.connector();
@@ -1603,48 +4556,147 @@ mod tests {
             .expect("Unable to add inbound connection");
 
         let notification = subs_rx
-            .iter()
-            .next()
+            .recv_timeout(Duration::from_secs(10))
             .expect("Cannot get message from subscriber");
+        let inbound_connection_id = match notification {
+            ConnectionManagerNotification::InboundConnection { connection_id, .. } => connection_id,
+            _ => panic!("Incorrect notification received: {:?}", notification),
+        };
 
-        let connection_id_to_remove;
+        disconnect_tx.send(()).unwrap();
+        jh.join().unwrap();
 
-        if let ConnectionManagerNotification::InboundConnection {
-            ref identity,
-            connection_id,
-            ..
-        } = &notification
-        {
-            assert_eq!(
-                identity,
-                &ConnectionAuthorizationType::Trust {
+        let notification = subs_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("Cannot get message from subscriber");
+        assert_eq!(
+            notification,
+            ConnectionManagerNotification::Disconnected {
+                endpoint: remote_endpoint,
+                identity: ConnectionAuthorizationType::Trust {
                     identity: "inbound-identity".into()
                 },
-            );
-            connection_id_to_remove = connection_id.to_string();
-        } else {
-            panic!(
-                "Did not receive the correct notification: {:?}",
-                notification
-            );
-        }
+                connection_id: inbound_connection_id,
+            }
+        );
 
-        let connection_endpoints = connector.list_connections().unwrap();
-        assert_eq!(vec![remote_endpoint.clone()], connection_endpoints);
+        // Several heartbeats fail after the disconnection; none should be reported again
+        assert_eq!(
+            subs_rx.recv_timeout(Duration::from_secs(3)),
+            Err(mpsc::RecvTimeoutError::Timeout)
+        );
+
+        cm.signal_shutdown();
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        auth_mgr.shutdown_and_await();
+    }
+
+    /// Test that a disconnected inbound connection with a dial-back endpoint is reconnected to
+    /// that endpoint when inbound reconnection is enabled.
+    ///
+    /// This test does the following:
+    /// 1. Start a connection manager with inbound reconnection enabled and an authorizer that
+    ///    reports a dial-back endpoint
+    /// 2. Add an inbound tcp connection to the connection manager
+    /// 3. Disconnect the remote side of the connection
+    /// 4. Verify that the dial-back endpoint receives a connection
+    /// 5. Verify that a Connected notification is received for the inbound connection's ID at the
+    ///    dial-back endpoint
+    #[test]
+    fn test_inbound_reconnection() {
+        let mut transport = Box::new(TcpTransport::default());
+        let mut listener = transport
+            .listen("tcp://localhost:0")
+            .expect("Cannot listen for tcp connections");
+        let endpoint = listener.endpoint();
+
+        let mut dial_back_listener = transport
+            .listen("tcp://localhost:0")
+            .expect("Cannot listen for dial-back connections");
+        let dial_back_endpoint = dial_back_listener.endpoint();
+
+        let (dial_back_tx, dial_back_rx) = mpsc::channel();
+        thread::spawn(move || {
+            let connection = dial_back_listener
+                .accept()
+                .expect("Cannot accept dial-back connection");
+            dial_back_tx.send(()).unwrap();
+
+            // hold the connection open while the test checks the notifications
+            thread::sleep(Duration::from_secs(5));
+            drop(connection);
+        });
+
+        let (disconnect_tx, disconnect_rx) = mpsc::channel();
+        let server_endpoint = endpoint.clone();
+        let jh = thread::spawn(move || {
+            let mut transport = TcpTransport::default();
+            let mut connection = transport.connect(&server_endpoint).unwrap();
 
+            // wait until the inbound connection has been added, then hang up
+            disconnect_rx.recv().unwrap();
+            connection
+                .disconnect()
+                .expect("Connection failed to disconnect");
+        });
+
+        let mesh = Mesh::new(512, 128);
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(DialBackAuthorizer {
+                dial_back_endpoint: dial_back_endpoint.clone(),
+            }))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport)
+            .with_heartbeat_interval(1)
+            .with_inbound_reconnection(true)
+            .start()
+            .expect("Unable to start Connection Manager");
+        let connector = cm.connector();
+
+        let (subs_tx, subs_rx) = mpsc::channel();
+        connector.subscribe(subs_tx).expect("Cannot get subscriber");
+
+        let connection = listener.accept().unwrap();
         connector
-            .remove_connection(&remote_endpoint, &connection_id_to_remove)
-            .unwrap();
-        let connection_endpoints = connector.list_connections().unwrap();
-        assert!(connection_endpoints.is_empty());
+            .add_inbound_connection(connection)
+            .expect("Unable to add inbound connection");
 
-        conn_tx.send(()).unwrap();
+        let notification = subs_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("Cannot get message from subscriber");
+        let inbound_connection_id = match notification {
+            ConnectionManagerNotification::InboundConnection { connection_id, .. } => connection_id,
+            _ => panic!("Incorrect notification received: {:?}", notification),
+        };
+
+        disconnect_tx.send(()).unwrap();
         jh.join().unwrap();
 
+        dial_back_rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("No connection was made to the dial-back endpoint");
+
+        loop {
+            let notification = subs_rx
+                .recv_timeout(Duration::from_secs(10))
+                .expect("Cannot get message from subscriber");
+            if let ConnectionManagerNotification::Connected {
+                endpoint,
+                connection_id,
+                ..
+            } = notification
+            {
+                assert_eq!(dial_back_endpoint, endpoint);
+                assert_eq!(inbound_connection_id, connection_id);
+                break;
+            }
+        }
+
         cm.signal_shutdown();
         cm.wait_for_shutdown()
             .expect("Unable to shutdown connection manager");
-        auth_mgr.shutdown_and_await();
     }
 
     struct NoopAuthorizer {
@@ -1680,11 +4732,209 @@ mod tests {
                 local_authorization: ConnectionAuthorizationType::Trust {
                     identity: "test_identity".into(),
                 },
+                dial_back_endpoint: None,
+            })
+            .map_err(|err| AuthorizerError(format!("Unable to return result: {}", err)))
+        }
+    }
+
+    /// An authorizer that trusts every connection and reports a fixed dial-back endpoint.
+    struct DialBackAuthorizer {
+        dial_back_endpoint: String,
+    }
+
+    impl Authorizer for DialBackAuthorizer {
+        fn authorize_connection(
+            &self,
+            connection_id: String,
+            connection: Box<dyn Connection>,
+            callback: AuthorizerCallback,
+            _expected_authorization: Option<ConnectionAuthorizationType>,
+            _local_authorization: Option<ConnectionAuthorizationType>,
+        ) -> Result<(), AuthorizerError> {
+            (*callback)(AuthorizationResult::Authorized {
+                connection_id,
+                connection,
+                identity: ConnectionAuthorizationType::Trust {
+                    identity: "dial_back_identity".into(),
+                },
+                expected_authorization: ConnectionAuthorizationType::Trust {
+                    identity: "dial_back_identity".into(),
+                },
+                local_authorization: ConnectionAuthorizationType::Trust {
+                    identity: "test_identity".into(),
+                },
+                dial_back_endpoint: Some(self.dial_back_endpoint.clone()),
+            })
+            .map_err(|err| AuthorizerError(format!("Unable to return result: {}", err)))
+        }
+    }
+
+    /// A metrics recorder that counts each increment, shared between its clones.
+    #[derive(Clone, Default)]
+    struct CountingMetrics {
+        connections_established: Arc<AtomicUsize>,
+        reconnection_attempts: Arc<AtomicUsize>,
+        authorization_failures: Arc<AtomicUsize>,
+        heartbeats_sent: Arc<AtomicUsize>,
+    }
+
+    impl CountingMetrics {
+        fn connections_established(&self) -> usize {
+            self.connections_established.load(Ordering::SeqCst)
+        }
+
+        fn reconnection_attempts(&self) -> usize {
+            self.reconnection_attempts.load(Ordering::SeqCst)
+        }
+
+        fn authorization_failures(&self) -> usize {
+            self.authorization_failures.load(Ordering::SeqCst)
+        }
+
+        fn heartbeats_sent(&self) -> usize {
+            self.heartbeats_sent.load(Ordering::SeqCst)
+        }
+    }
+
+    impl ConnectionManagerMetrics for CountingMetrics {
+        fn increment_connections_established(&self) {
+            self.connections_established.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn increment_reconnection_attempts(&self) {
+            self.reconnection_attempts.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn increment_authorization_failures(&self) {
+            self.authorization_failures.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn increment_heartbeats_sent(&self) {
+            self.heartbeats_sent.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /// An authorizer that refuses every connection for the given reason.
+    struct UnauthorizedAuthorizer(UnauthorizedReason);
+
+    impl Authorizer for UnauthorizedAuthorizer {
+        fn authorize_connection(
+            &self,
+            connection_id: String,
+            connection: Box<dyn Connection>,
+            callback: AuthorizerCallback,
+            _expected_authorization: Option<ConnectionAuthorizationType>,
+            _local_authorization: Option<ConnectionAuthorizationType>,
+        ) -> Result<(), AuthorizerError> {
+            (*callback)(AuthorizationResult::Unauthorized {
+                connection_id,
+                connection,
+                reason: self.0.clone(),
             })
             .map_err(|err| AuthorizerError(format!("Unable to return result: {}", err)))
         }
     }
 
+    /// An authorizer that never reports the result of an authorization.
+    struct StalledAuthorizer;
+
+    impl Authorizer for StalledAuthorizer {
+        fn authorize_connection(
+            &self,
+            _connection_id: String,
+            _connection: Box<dyn Connection>,
+            _callback: AuthorizerCallback,
+            _expected_authorization: Option<ConnectionAuthorizationType>,
+            _local_authorization: Option<ConnectionAuthorizationType>,
+        ) -> Result<(), AuthorizerError> {
+            Ok(())
+        }
+    }
+
+    /// An authorization held by the `HoldingAuthorizer`.
+    type HeldAuthorization = (String, Box<dyn Connection>, AuthorizerCallback);
+
+    /// An authorizer that holds each authorization until the test completes it, recording the
+    /// largest number of authorizations that it held at once.
+    #[derive(Clone, Default)]
+    struct HoldingAuthorizer {
+        held: Arc<Mutex<Vec<HeldAuthorization>>>,
+        started: Arc<AtomicUsize>,
+        max_in_flight: Arc<AtomicUsize>,
+    }
+
+    impl HoldingAuthorizer {
+        fn started(&self) -> usize {
+            self.started.load(Ordering::SeqCst)
+        }
+
+        fn max_in_flight(&self) -> usize {
+            self.max_in_flight.load(Ordering::SeqCst)
+        }
+
+        /// Waits for the given number of authorizations to have been started.
+        fn wait_for_started(&self, count: usize) {
+            let deadline = Instant::now() + Duration::from_secs(10);
+            while self.started() < count {
+                assert!(
+                    Instant::now() < deadline,
+                    "Timed out waiting for {} authorizations",
+                    count
+                );
+                thread::sleep(Duration::from_millis(10));
+            }
+        }
+
+        /// Authorizes the oldest held authorization, returning false if none are held.
+        fn complete_next(&self) -> bool {
+            let held = {
+                let mut held = self.held.lock().expect("held authorizations lock poisoned");
+                if held.is_empty() {
+                    return false;
+                }
+                held.remove(0)
+            };
+
+            let (connection_id, connection, callback) = held;
+            (*callback)(AuthorizationResult::Authorized {
+                connection_id,
+                connection,
+                identity: ConnectionAuthorizationType::Trust {
+                    identity: "test_identity".into(),
+                },
+                expected_authorization: ConnectionAuthorizationType::Trust {
+                    identity: "test_identity".into(),
+                },
+                local_authorization: ConnectionAuthorizationType::Trust {
+                    identity: "local_identity".into(),
+                },
+                dial_back_endpoint: None,
+            })
+            .is_ok()
+        }
+    }
+
+    impl Authorizer for HoldingAuthorizer {
+        fn authorize_connection(
+            &self,
+            connection_id: String,
+            connection: Box<dyn Connection>,
+            callback: AuthorizerCallback,
+            _expected_authorization: Option<ConnectionAuthorizationType>,
+            _local_authorization: Option<ConnectionAuthorizationType>,
+        ) -> Result<(), AuthorizerError> {
+            let mut held = self
+                .held
+                .lock()
+                .map_err(|_| AuthorizerError("held authorizations lock poisoned".into()))?;
+            held.push((connection_id, connection, callback));
+            self.max_in_flight.fetch_max(held.len(), Ordering::SeqCst);
+            self.started.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
     struct NoopVerifier;
 
     impl Verifier for NoopVerifier {
@@ -1702,6 +4952,39 @@ mod tests {
         }
     }
 
+    /// The messages logged by the connection manager since the capturing logger was installed.
+    static CAPTURED_LOGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    /// A logger that captures the messages logged by the connection manager.
+    struct CapturingLogger;
+
+    impl log::Log for CapturingLogger {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.target() == module_path!().trim_end_matches("::tests")
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                CAPTURED_LOGS
+                    .lock()
+                    .expect("Captured logs lock was poisoned")
+                    .push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    /// Installs the capturing logger as the global logger, if it has not been already.
+    fn install_capturing_logger() {
+        static INSTALL: std::sync::Once = std::sync::Once::new();
+        INSTALL.call_once(|| {
+            log::set_boxed_logger(Box::new(CapturingLogger))
+                .expect("Unable to install capturing logger");
+            log::set_max_level(log::LevelFilter::Trace);
+        });
+    }
+
     struct NoopFactory;
 
     impl VerifierFactory for NoopFactory {
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io;
+
 use crate::network::auth::ConnectionAuthorizationType;
 
 use super::error::ConnectionManagerError;
@@ -46,5 +48,42 @@ pub enum ConnectionManagerNotification {
         attempts: u64,
         identity: ConnectionAuthorizationType,
         connection_id: String,
+        /// The kind of I/O error that caused the reconnection attempt to fail, if it was one
+        error_kind: Option<io::ErrorKind>,
+    },
+    Backpressure {
+        endpoint: String,
+        connection_id: String,
+        queue_depth: usize,
     },
 }
+
+impl ConnectionManagerNotification {
+    /// Returns the ID of the connection this notification is about.
+    pub fn connection_id(&self) -> &str {
+        match self {
+            ConnectionManagerNotification::Connected { connection_id, .. }
+            | ConnectionManagerNotification::FatalConnectionError { connection_id, .. }
+            | ConnectionManagerNotification::InboundConnection { connection_id, .. }
+            | ConnectionManagerNotification::Disconnected { connection_id, .. }
+            | ConnectionManagerNotification::NonFatalConnectionError { connection_id, .. }
+            | ConnectionManagerNotification::Backpressure { connection_id, .. } => connection_id,
+        }
+    }
+
+    /// Returns the notification with its connection ID replaced, to send it for an alias of the
+    /// connection.
+    pub(super) fn with_connection_id(mut self, id: String) -> Self {
+        match &mut self {
+            ConnectionManagerNotification::Connected { connection_id, .. }
+            | ConnectionManagerNotification::FatalConnectionError { connection_id, .. }
+            | ConnectionManagerNotification::InboundConnection { connection_id, .. }
+            | ConnectionManagerNotification::Disconnected { connection_id, .. }
+            | ConnectionManagerNotification::NonFatalConnectionError { connection_id, .. }
+            | ConnectionManagerNotification::Backpressure { connection_id, .. } => {
+                *connection_id = id
+            }
+        }
+        self
+    }
+}
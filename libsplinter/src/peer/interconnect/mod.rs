@@ -927,6 +927,7 @@ pub mod tests {
                 },
                 expected_authorization: expected_authorization.unwrap(),
                 local_authorization: local_authorization.unwrap(),
+                dial_back_endpoint: None,
             })
             .map_err(|err| AuthorizerError(format!("Unable to return result: {}", err)))
         }
@@ -1017,6 +1017,18 @@ fn handle_notifications(
             subscribers,
             max_retry_attempts,
         ),
+        ConnectionManagerNotification::Backpressure {
+            connection_id,
+            queue_depth,
+            ..
+        } => {
+            if let Some(peer_metadata) = peers.get_by_connection_id(&connection_id) {
+                warn!(
+                    "Peer {} is backpressured with {} queued messages",
+                    peer_metadata.id, queue_depth
+                );
+            }
+        }
     }
 }
 
@@ -2996,6 +3008,7 @@ pub mod tests {
                         identity: "my_id".into(),
                     },
                 ),
+                dial_back_endpoint: None,
             })
             .map_err(|err| AuthorizerError(format!("Unable to return result: {}", err)))
         }
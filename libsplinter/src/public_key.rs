@@ -15,11 +15,20 @@
 //! Splinter specific representation of a public key
 
 use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 
 use cylinder;
+use openssl::bn::BigNumContext;
+use openssl::ec::{EcGroup, EcKey, EcPoint, PointConversionForm};
+use openssl::error::ErrorStack;
+use openssl::memcmp;
+use openssl::nid::Nid;
+use openssl::pkey::PKey;
 
 /// Local representation of a public key
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone)]
 pub struct PublicKey {
     bytes: Vec<u8>,
 }
@@ -39,6 +48,70 @@ impl PublicKey {
     pub fn as_slice(&self) -> &[u8] {
         &self.bytes
     }
+
+    /// Create a `PublicKey` from a PEM-encoded SubjectPublicKeyInfo
+    ///
+    /// The key must be a secp256k1 key; it is stored as its compressed point.
+    pub fn from_pem(pem: &str) -> Result<Self, PublicKeyPemError> {
+        let ec_key = PKey::public_key_from_pem(pem.as_bytes())
+            .map_err(|err| PublicKeyPemError::InvalidPem(err.to_string()))?
+            .ec_key()
+            .map_err(|_| PublicKeyPemError::UnsupportedKey("public key is not an EC key".into()))?;
+
+        match ec_key.group().curve_name() {
+            Some(Nid::SECP256K1) => (),
+            Some(nid) => {
+                return Err(PublicKeyPemError::UnsupportedKey(format!(
+                    "unsupported curve: {}",
+                    nid.short_name().unwrap_or("unknown")
+                )))
+            }
+            None => {
+                return Err(PublicKeyPemError::UnsupportedKey(
+                    "public key does not use a named curve".into(),
+                ))
+            }
+        }
+
+        let mut ctx = BigNumContext::new()?;
+        let bytes = ec_key.public_key().to_bytes(
+            ec_key.group(),
+            PointConversionForm::COMPRESSED,
+            &mut ctx,
+        )?;
+
+        Ok(PublicKey::from_bytes(bytes))
+    }
+
+    /// Returns the public key as a PEM-encoded SubjectPublicKeyInfo
+    ///
+    /// The bytes of the public key must be a valid secp256k1 point.
+    pub fn to_pem(&self) -> Result<String, PublicKeyPemError> {
+        let group = EcGroup::from_curve_name(Nid::SECP256K1)?;
+        let mut ctx = BigNumContext::new()?;
+        let point = EcPoint::from_bytes(&group, &self.bytes, &mut ctx)
+            .map_err(|err| PublicKeyPemError::InvalidKey(err.to_string()))?;
+        let pem =
+            EcKey::from_public_key(&group, &point).and_then(|ec_key| ec_key.public_key_to_pem())?;
+
+        String::from_utf8(pem).map_err(|err| PublicKeyPemError::Internal(err.to_string()))
+    }
+}
+
+/// Public keys are compared in constant time, as they are often compared against client-provided
+/// keys when checking permissions. Only the contents of keys with the same length are compared.
+impl PartialEq for PublicKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes.len() == other.bytes.len() && memcmp::eq(&self.bytes, &other.bytes)
+    }
+}
+
+impl Eq for PublicKey {}
+
+impl Hash for PublicKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.bytes.hash(state);
+    }
 }
 
 impl Ord for PublicKey {
@@ -64,3 +137,108 @@ impl From<PublicKey> for cylinder::PublicKey {
         cylinder::PublicKey::new(public_key.into_bytes())
     }
 }
+
+/// Errors that may occur when converting a `PublicKey` to or from PEM
+#[derive(Debug)]
+pub enum PublicKeyPemError {
+    /// The PEM could not be parsed as a public key
+    InvalidPem(String),
+    /// The public key bytes are not a valid secp256k1 point
+    InvalidKey(String),
+    /// The PEM contains a key type or curve other than secp256k1
+    UnsupportedKey(String),
+    /// An unexpected error occurred in the underlying crypto library
+    Internal(String),
+}
+
+impl Error for PublicKeyPemError {}
+
+impl fmt::Display for PublicKeyPemError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PublicKeyPemError::InvalidPem(msg) => write!(f, "invalid PEM: {}", msg),
+            PublicKeyPemError::InvalidKey(msg) => write!(f, "invalid public key: {}", msg),
+            PublicKeyPemError::UnsupportedKey(msg) => write!(f, "unsupported public key: {}", msg),
+            PublicKeyPemError::Internal(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl From<ErrorStack> for PublicKeyPemError {
+    fn from(err: ErrorStack) -> Self {
+        PublicKeyPemError::Internal(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    use crate::hex::{parse_hex, to_hex};
+
+    const PEM: &str = "-----BEGIN PUBLIC KEY-----
+MFYwEAYHKoZIzj0CAQYFK4EEAAoDQgAEqmBmtteFEkqE5fXylP6uMZ4bHEHqoj0b
+i3RKow/gQC2BYY3ihEGGqhJ2EL6KCkBCbzNMshgtEBuVMBaLqGKLlw==
+-----END PUBLIC KEY-----
+";
+
+    const COMPRESSED_PEM: &str = "-----BEGIN PUBLIC KEY-----
+MDYwEAYHKoZIzj0CAQYFK4EEAAoDIgADqmBmtteFEkqE5fXylP6uMZ4bHEHqoj0b
+i3RKow/gQC0=
+-----END PUBLIC KEY-----
+";
+
+    const KEY_HEX: &str = "03aa6066b6d785124a84e5f5f294feae319e1b1c41eaa23d1b8b744aa30fe0402d";
+
+    /// Verifies that a PEM-encoded public key is parsed to its compressed bytes, whether the PEM
+    /// holds the compressed or uncompressed point, and that those bytes are written back to the
+    /// same PEM.
+    #[test]
+    fn test_pem_round_trip() {
+        let public_key = PublicKey::from_pem(PEM).expect("Unable to parse PEM");
+        assert_eq!(KEY_HEX, &to_hex(public_key.as_slice()));
+
+        let compressed = PublicKey::from_pem(COMPRESSED_PEM).expect("Unable to parse PEM");
+        assert_eq!(public_key, compressed);
+
+        assert_eq!(PEM, &public_key.to_pem().expect("Unable to write PEM"));
+
+        let from_hex = PublicKey::from_bytes(parse_hex(KEY_HEX).expect("Unable to parse hex"));
+        assert_eq!(PEM, &from_hex.to_pem().expect("Unable to write PEM"));
+    }
+
+    /// Verifies that keys are equal only when their bytes are equal, including keys of differing
+    /// lengths, and that equal keys hash the same.
+    #[test]
+    fn test_equality() {
+        let key = PublicKey::from_bytes(vec![1, 2, 3, 4]);
+
+        assert_eq!(key, PublicKey::from_bytes(vec![1, 2, 3, 4]));
+        assert_ne!(key, PublicKey::from_bytes(vec![1, 2, 3, 5]));
+        assert_ne!(key, PublicKey::from_bytes(vec![1, 2, 3]));
+        assert_ne!(key, PublicKey::from_bytes(vec![1, 2, 3, 4, 0]));
+        assert_ne!(key, PublicKey::from_bytes(vec![]));
+
+        let mut keys = HashSet::new();
+        keys.insert(key.clone());
+        assert!(keys.contains(&PublicKey::from_bytes(vec![1, 2, 3, 4])));
+        assert!(!keys.contains(&PublicKey::from_bytes(vec![1, 2, 3])));
+    }
+
+    /// Verifies that malformed PEM and invalid key bytes return an error.
+    #[test]
+    fn test_pem_errors() {
+        match PublicKey::from_pem("-----BEGIN PUBLIC KEY-----\nnot a key\n-----END PUBLIC KEY-----")
+        {
+            Err(PublicKeyPemError::InvalidPem(_)) => (),
+            res => panic!("Expected InvalidPem error, got {:?}", res),
+        }
+
+        match PublicKey::from_bytes(vec![1, 2, 3]).to_pem() {
+            Err(PublicKeyPemError::InvalidKey(_)) => (),
+            res => panic!("Expected InvalidKey error, got {:?}", res),
+        }
+    }
+}
@@ -30,6 +30,9 @@ use crate::error::InternalError;
 use crate::rest_api::RestApiServerError;
 use crate::threading::lifecycle::ShutdownHandle;
 
+use super::mount::configure_resources;
+#[cfg(feature = "authorization")]
+use super::permissions::RouteAuthorization;
 use super::ResourceProvider;
 
 /// Contains information about the ports to which the REST API is bound.
@@ -60,6 +63,7 @@ impl RestApi {
         bind_url: String,
         bind_acceptor_builder: Option<SslAcceptorBuilder>,
         resource_providers: Vec<Box<dyn ResourceProvider>>,
+        #[cfg(feature = "authorization")] authorization: RouteAuthorization,
     ) -> Result<Self, RestApiServerError> {
         let providers: Arc<Mutex<Vec<_>>> = Arc::new(Mutex::new(resource_providers));
         let (sender, receiver) = mpsc::channel();
@@ -69,16 +73,18 @@ impl RestApi {
             .spawn(move || {
                 let sys = ActixSystem::new("ActixSystem-Splinter-REST-API");
                 let mut http_server = HttpServer::new(move || {
-                    let app = App::new();
-
-                    let mut app = app.wrap(middleware::Logger::default());
-
-                    for provider in providers.lock().unwrap().iter() {
-                        for resource in provider.resources() {
-                            app = app.service(resource)
-                        }
-                    }
-                    app
+                    let providers = providers.lock().unwrap();
+
+                    App::new()
+                        .wrap(middleware::Logger::default())
+                        .configure(|config| {
+                            configure_resources(
+                                config,
+                                &providers,
+                                #[cfg(feature = "authorization")]
+                                &authorization,
+                            )
+                        })
                 });
 
                 http_server = match if let Some(acceptor_builder) = bind_acceptor_builder {
@@ -14,9 +14,13 @@
 
 //! Contains the implementation of `RestApiBuilder`.
 
+#[cfg(feature = "authorization")]
+use crate::rest_api::auth::{authorization::AuthorizationHandler, identity::IdentityProvider};
 use crate::rest_api::{BindConfig, RestApiServerError};
 
-use super::{ResourceProvider, RunnableRestApi};
+#[cfg(feature = "authorization")]
+use super::permissions::RouteAuthorization;
+use super::{ResourceProvider, RouteListingResourceProvider, RunnableRestApi};
 
 /// Builds a `RunnableRestApi`.
 ///
@@ -25,6 +29,11 @@ use super::{ResourceProvider, RunnableRestApi};
 pub struct RestApiBuilder {
     resource_providers: Vec<Box<dyn ResourceProvider>>,
     bind: Option<BindConfig>,
+    route_listing_path: Option<String>,
+    #[cfg(feature = "authorization")]
+    identity_providers: Vec<Box<dyn IdentityProvider>>,
+    #[cfg(feature = "authorization")]
+    authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
 }
 
 impl RestApiBuilder {
@@ -47,15 +56,59 @@ impl RestApiBuilder {
         self
     }
 
+    /// Serves a JSON listing of the routes of all of the resource providers at the given path,
+    /// such as `/openapi.json`.
+    ///
+    /// The listing is built from the providers' `route_descriptions` when the REST API is built,
+    /// so it includes the providers added both before and after this call.
+    pub fn with_route_listing(mut self, path: &str) -> Self {
+        self.route_listing_path = Some(path.to_string());
+        self
+    }
+
+    /// Sets the identity providers used to resolve the client's identity for routes that require a
+    /// permission.
+    #[cfg(feature = "authorization")]
+    pub fn with_identity_providers(
+        mut self,
+        identity_providers: Vec<Box<dyn IdentityProvider>>,
+    ) -> Self {
+        self.identity_providers = identity_providers;
+        self
+    }
+
+    /// Sets the authorization handlers used to check the permissions declared by the resource
+    /// providers.
+    #[cfg(feature = "authorization")]
+    pub fn with_authorization_handlers(
+        mut self,
+        authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    ) -> Self {
+        self.authorization_handlers = authorization_handlers;
+        self
+    }
+
     /// Validate the arguments and build the `RunnableRestApi` struct.
     pub fn build(self) -> Result<RunnableRestApi, RestApiServerError> {
         let bind = self
             .bind
             .ok_or_else(|| RestApiServerError::MissingField("bind".to_string()))?;
 
+        let mut resource_providers = self.resource_providers;
+        if let Some(path) = self.route_listing_path {
+            let route_listing =
+                RouteListingResourceProvider::from_providers(&path, &resource_providers);
+            resource_providers.push(Box::new(route_listing));
+        }
+
         Ok(RunnableRestApi {
             bind,
-            resource_providers: self.resource_providers,
+            resource_providers,
+            #[cfg(feature = "authorization")]
+            authorization: RouteAuthorization {
+                identity_providers: self.identity_providers,
+                authorization_handlers: self.authorization_handlers,
+            },
         })
     }
 }
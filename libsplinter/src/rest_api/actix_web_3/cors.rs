@@ -0,0 +1,363 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! CORS support for the resources of a `ResourceProvider`.
+
+use std::collections::HashMap;
+
+use actix_web_3::dev::{ServiceRequest, ServiceResponse};
+use actix_web_3::http::{
+    header::{self, HeaderMap, HeaderValue},
+    Method,
+};
+use actix_web_3::{HttpResponse, Resource};
+
+use super::{ApiVersion, ResourceMiddleware, ResourceProvider, RouteDescription};
+
+/// A `ResourceMiddleware` that adds CORS headers to the responses of a provider's resources.
+///
+/// Responses to requests from an allowed origin include the `Access-Control-Allow-*` headers,
+/// and preflight `OPTIONS` requests from an allowed origin are answered directly. Requests from
+/// other origins are handled as usual, without the CORS headers, so browsers will reject them.
+///
+/// The middleware may be returned from a provider's `middleware`, or applied to all of the
+/// resources of an existing provider using `CorsResourceProvider`.
+#[derive(Clone)]
+pub struct CorsMiddleware {
+    allowed_origins: Vec<String>,
+    allowed_methods: Vec<String>,
+    allowed_headers: Vec<String>,
+}
+
+impl CorsMiddleware {
+    /// Creates CORS middleware that allows the given origins, or any origin if the list contains
+    /// `"*"`. All methods and headers are allowed unless configured otherwise.
+    pub fn new(allowed_origins: Vec<String>) -> Self {
+        Self {
+            allowed_origins,
+            allowed_methods: vec!["*".into()],
+            allowed_headers: vec!["*".into()],
+        }
+    }
+
+    /// Sets the methods that may be used by a cross-origin request.
+    pub fn with_allowed_methods(mut self, allowed_methods: Vec<String>) -> Self {
+        self.allowed_methods = allowed_methods;
+        self
+    }
+
+    /// Sets the headers that may be sent with a cross-origin request.
+    pub fn with_allowed_headers(mut self, allowed_headers: Vec<String>) -> Self {
+        self.allowed_headers = allowed_headers;
+        self
+    }
+
+    /// Returns the request's origin, if it is one of the allowed origins.
+    fn allowed_origin(&self, headers: &HeaderMap) -> Option<HeaderValue> {
+        let origin = headers.get(header::ORIGIN)?;
+        let origin_str = origin.to_str().ok()?;
+        if self
+            .allowed_origins
+            .iter()
+            .any(|allowed| allowed == "*" || allowed == origin_str)
+        {
+            Some(origin.clone())
+        } else {
+            None
+        }
+    }
+
+    /// Adds the CORS headers for the given allowed origin.
+    ///
+    /// As the request's origin is echoed back, `Vary: Origin` is added so that caches do not
+    /// serve the response to other origins.
+    fn insert_headers(&self, headers: &mut HeaderMap, origin: HeaderValue) {
+        headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+        let varies_by_origin = headers.get_all(header::VARY).any(|value| {
+            value
+                .to_str()
+                .map(|value| {
+                    value
+                        .split(',')
+                        .any(|name| name.trim().eq_ignore_ascii_case("origin"))
+                })
+                .unwrap_or(false)
+        });
+        if !varies_by_origin {
+            headers.append(header::VARY, HeaderValue::from_static("Origin"));
+        }
+        if let Ok(methods) = HeaderValue::from_str(&self.allowed_methods.join(", ")) {
+            headers.insert(header::ACCESS_CONTROL_ALLOW_METHODS, methods);
+        }
+        if let Ok(allowed_headers) = HeaderValue::from_str(&self.allowed_headers.join(", ")) {
+            headers.insert(header::ACCESS_CONTROL_ALLOW_HEADERS, allowed_headers);
+        }
+    }
+}
+
+impl ResourceMiddleware for CorsMiddleware {
+    fn on_request(&self, request: &mut ServiceRequest) -> Result<(), HttpResponse> {
+        if request.method() != Method::OPTIONS {
+            return Ok(());
+        }
+
+        match self.allowed_origin(request.headers()) {
+            Some(origin) => {
+                let mut response = HttpResponse::Ok().finish();
+                self.insert_headers(response.headers_mut(), origin);
+                Err(response)
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn on_response(&self, response: &mut ServiceResponse) {
+        if let Some(origin) = self.allowed_origin(response.request().headers()) {
+            self.insert_headers(response.headers_mut(), origin);
+        }
+    }
+}
+
+/// Wraps a `ResourceProvider`, applying `CorsMiddleware` to all of its resources.
+///
+/// The CORS middleware runs before the provider's own middleware, but after the check of any
+/// permissions the provider declares. Preflight requests are exempt from that check, and responses
+/// rejected by it or by other middleware still include the CORS headers.
+pub struct CorsResourceProvider {
+    provider: Box<dyn ResourceProvider>,
+    cors: CorsMiddleware,
+}
+
+impl CorsResourceProvider {
+    pub fn new(provider: Box<dyn ResourceProvider>, cors: CorsMiddleware) -> Self {
+        Self { provider, cors }
+    }
+}
+
+impl ResourceProvider for CorsResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.provider.resources()
+    }
+
+    fn versioned_resources(&self) -> Vec<(ApiVersion, Vec<Resource>)> {
+        self.provider.versioned_resources()
+    }
+
+    fn middleware(&self) -> Vec<Box<dyn ResourceMiddleware>> {
+        let mut middleware: Vec<Box<dyn ResourceMiddleware>> = vec![Box::new(self.cors.clone())];
+        middleware.extend(self.provider.middleware());
+        middleware
+    }
+
+    fn resource_permissions(&self) -> HashMap<String, String> {
+        self.provider.resource_permissions()
+    }
+
+    fn route_descriptions(&self) -> Vec<RouteDescription> {
+        self.provider.route_descriptions()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use actix_web_3::{http::StatusCode, rt::System, test, web, App};
+
+    use super::super::mount::configure_resources;
+    #[cfg(feature = "authorization")]
+    use super::super::permissions::RouteAuthorization;
+
+    struct StatusProvider;
+
+    impl ResourceProvider for StatusProvider {
+        fn resources(&self) -> Vec<Resource> {
+            vec![web::resource("/status")
+                .route(web::get().to(|| async { HttpResponse::Ok().finish() }))]
+        }
+    }
+
+    #[cfg(feature = "authorization")]
+    struct PermissionedStatusProvider;
+
+    #[cfg(feature = "authorization")]
+    impl ResourceProvider for PermissionedStatusProvider {
+        fn resources(&self) -> Vec<Resource> {
+            StatusProvider.resources()
+        }
+
+        fn resource_permissions(&self) -> HashMap<String, String> {
+            let mut permissions = HashMap::new();
+            permissions.insert("/status".to_string(), "test.read".to_string());
+            permissions
+        }
+    }
+
+    /// Sends a GET request to `/status` from the given origin, returning the response's
+    /// `Access-Control-Allow-Origin` header.
+    async fn allow_origin_header(
+        providers: Vec<Box<dyn ResourceProvider>>,
+        origin: &str,
+    ) -> Option<HeaderValue> {
+        let mut app = test::init_service(App::new().configure(|config| {
+            configure_resources(
+                config,
+                &providers,
+                #[cfg(feature = "authorization")]
+                &RouteAuthorization::default(),
+            )
+        }))
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/status")
+            .header(header::ORIGIN, origin)
+            .to_request();
+        let resp = test::call_service(&mut app, req).await;
+        assert_eq!(StatusCode::OK, resp.status());
+        resp.headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .cloned()
+    }
+
+    /// Verifies that the `Access-Control-Allow-Origin` header is added to responses for an
+    /// allowed origin when CORS is configured, and is absent when it is not configured or the
+    /// origin is not allowed.
+    #[test]
+    fn cors_headers() {
+        System::new("cors_headers").block_on(async move {
+            let cors = CorsMiddleware::new(vec!["http://dashboard.example.com".into()]);
+
+            assert_eq!(
+                Some(HeaderValue::from_static("http://dashboard.example.com")),
+                allow_origin_header(
+                    vec![Box::new(CorsResourceProvider::new(
+                        Box::new(StatusProvider),
+                        cors.clone()
+                    ))],
+                    "http://dashboard.example.com",
+                )
+                .await
+            );
+
+            assert_eq!(
+                None,
+                allow_origin_header(
+                    vec![Box::new(CorsResourceProvider::new(
+                        Box::new(StatusProvider),
+                        cors
+                    ))],
+                    "http://other.example.com",
+                )
+                .await
+            );
+
+            assert_eq!(
+                None,
+                allow_origin_header(
+                    vec![Box::new(StatusProvider)],
+                    "http://dashboard.example.com"
+                )
+                .await
+            );
+        });
+    }
+
+    /// Verifies that a preflight request from an allowed origin is answered with the configured
+    /// methods and headers.
+    #[test]
+    fn cors_preflight() {
+        let providers: Vec<Box<dyn ResourceProvider>> = vec![Box::new(CorsResourceProvider::new(
+            Box::new(StatusProvider),
+            CorsMiddleware::new(vec!["*".into()])
+                .with_allowed_methods(vec!["GET".into(), "POST".into()])
+                .with_allowed_headers(vec!["Authorization".into()]),
+        ))];
+
+        System::new("cors_preflight").block_on(async move {
+            let mut app = test::init_service(App::new().configure(|config| {
+                configure_resources(
+                    config,
+                    &providers,
+                    #[cfg(feature = "authorization")]
+                    &RouteAuthorization::default(),
+                )
+            }))
+            .await;
+
+            let req = test::TestRequest::with_uri("/status")
+                .method(Method::OPTIONS)
+                .header(header::ORIGIN, "http://dashboard.example.com")
+                .to_request();
+            let resp = test::call_service(&mut app, req).await;
+            assert_eq!(StatusCode::OK, resp.status());
+            assert_eq!(
+                Some(&HeaderValue::from_static("http://dashboard.example.com")),
+                resp.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            );
+            assert_eq!(
+                Some(&HeaderValue::from_static("GET, POST")),
+                resp.headers().get(header::ACCESS_CONTROL_ALLOW_METHODS)
+            );
+            assert_eq!(
+                Some(&HeaderValue::from_static("Authorization")),
+                resp.headers().get(header::ACCESS_CONTROL_ALLOW_HEADERS)
+            );
+        });
+    }
+    /// Verifies that a preflight request to a route that requires a permission is answered
+    /// without credentials, and that a request rejected for lacking credentials still includes
+    /// the CORS headers.
+    #[cfg(feature = "authorization")]
+    #[test]
+    fn cors_with_permissions() {
+        let providers: Vec<Box<dyn ResourceProvider>> = vec![Box::new(CorsResourceProvider::new(
+            Box::new(PermissionedStatusProvider),
+            CorsMiddleware::new(vec!["http://dashboard.example.com".into()]),
+        ))];
+
+        System::new("cors_with_permissions").block_on(async move {
+            let mut app = test::init_service(App::new().configure(|config| {
+                configure_resources(config, &providers, &RouteAuthorization::default())
+            }))
+            .await;
+
+            let req = test::TestRequest::with_uri("/status")
+                .method(Method::OPTIONS)
+                .header(header::ORIGIN, "http://dashboard.example.com")
+                .to_request();
+            let resp = test::call_service(&mut app, req).await;
+            assert_eq!(StatusCode::OK, resp.status());
+            assert_eq!(
+                Some(&HeaderValue::from_static("http://dashboard.example.com")),
+                resp.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            );
+            assert_eq!(
+                Some(&HeaderValue::from_static("Origin")),
+                resp.headers().get(header::VARY)
+            );
+
+            let req = test::TestRequest::get()
+                .uri("/status")
+                .header(header::ORIGIN, "http://dashboard.example.com")
+                .to_request();
+            let resp = test::call_service(&mut app, req).await;
+            assert_eq!(StatusCode::UNAUTHORIZED, resp.status());
+            assert_eq!(
+                Some(&HeaderValue::from_static("http://dashboard.example.com")),
+                resp.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            );
+        });
+    }
+}
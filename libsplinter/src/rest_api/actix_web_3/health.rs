@@ -0,0 +1,126 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A `ResourceProvider` for the liveness and readiness endpoints of a REST API.
+
+use std::sync::Arc;
+
+use actix_web_3::{web, HttpResponse, Resource};
+
+use crate::rest_api::ErrorResponse;
+
+use super::{ResourceProvider, RouteDescription};
+
+/// Provides the `/health` and `/ready` endpoints.
+///
+/// `/health` always responds with `200 OK` while the process is able to serve requests.
+/// `/ready` calls the readiness check on each request, and responds with `200 OK` if it returns
+/// `true` and `503 Service Unavailable` otherwise. The readiness check could, for example, verify
+/// that a database connection can be made.
+#[derive(Clone)]
+pub struct HealthResourceProvider {
+    readiness_check: Arc<dyn Fn() -> bool + Send + Sync>,
+}
+
+impl HealthResourceProvider {
+    /// Creates a new `HealthResourceProvider` that uses the given readiness check.
+    pub fn new<F>(readiness_check: F) -> Self
+    where
+        F: Fn() -> bool + Send + Sync + 'static,
+    {
+        Self {
+            readiness_check: Arc::new(readiness_check),
+        }
+    }
+}
+
+impl Default for HealthResourceProvider {
+    /// Creates a `HealthResourceProvider` that is always ready.
+    fn default() -> Self {
+        Self::new(|| true)
+    }
+}
+
+impl ResourceProvider for HealthResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        let readiness_check = self.readiness_check.clone();
+        vec![
+            web::resource("/health").route(web::get().to(|| async { HttpResponse::Ok().finish() })),
+            web::resource("/ready").route(web::get().to(move || {
+                let ready = readiness_check();
+                async move {
+                    if ready {
+                        HttpResponse::Ok().finish()
+                    } else {
+                        HttpResponse::ServiceUnavailable()
+                            .json(ErrorResponse::service_unavailable("Service is not ready"))
+                    }
+                }
+            })),
+        ]
+    }
+
+    fn route_descriptions(&self) -> Vec<RouteDescription> {
+        vec![
+            RouteDescription::new("/health", "GET"),
+            RouteDescription::new("/ready", "GET"),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use actix_web_3::{http::StatusCode, rt::System, test, App};
+
+    /// Verifies that `/health` responds with `200 OK` and that `/ready` follows the result of the
+    /// readiness check, responding with `503 Service Unavailable` while it is not ready.
+    #[test]
+    fn health_and_readiness() {
+        let ready = Arc::new(AtomicBool::new(false));
+        let check_ready = ready.clone();
+        let provider = HealthResourceProvider::new(move || check_ready.load(Ordering::SeqCst));
+
+        System::new("health_and_readiness").block_on(async move {
+            let mut app = test::init_service(
+                provider
+                    .resources()
+                    .into_iter()
+                    .fold(App::new(), |app, resource| app.service(resource)),
+            )
+            .await;
+
+            let req = test::TestRequest::get().uri("/health").to_request();
+            let resp = test::call_service(&mut app, req).await;
+            assert_eq!(StatusCode::OK, resp.status());
+
+            let req = test::TestRequest::get().uri("/ready").to_request();
+            let resp = test::call_service(&mut app, req).await;
+            assert_eq!(StatusCode::SERVICE_UNAVAILABLE, resp.status());
+
+            ready.store(true, Ordering::SeqCst);
+
+            let req = test::TestRequest::get().uri("/health").to_request();
+            let resp = test::call_service(&mut app, req).await;
+            assert_eq!(StatusCode::OK, resp.status());
+
+            let req = test::TestRequest::get().uri("/ready").to_request();
+            let resp = test::call_service(&mut app, req).await;
+            assert_eq!(StatusCode::OK, resp.status());
+        });
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Middleware that may be applied to the resources of a single `ResourceProvider`.
+
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use actix_web_3::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web_3::{Error, HttpResponse};
+use futures_0_3::future::{ok, LocalBoxFuture, Ready};
+
+/// A hook that is run around each of the resources of a `ResourceProvider`.
+///
+/// Unlike middleware registered on the Actix app, these hooks only apply to the resources of the
+/// provider that returned them.
+pub trait ResourceMiddleware {
+    /// Inspects or modifies a request before it is handled by the provider's resource.
+    ///
+    /// Returning an `Err` response short-circuits the request; the resource and any remaining
+    /// middleware are not called.
+    fn on_request(&self, _request: &mut ServiceRequest) -> Result<(), HttpResponse> {
+        Ok(())
+    }
+
+    /// Inspects or modifies the response returned by the provider's resource.
+    ///
+    /// This is also called for a response returned by any middleware's `on_request`, so that
+    /// headers such as those added for CORS are present on error responses too.
+    fn on_response(&self, _response: &mut ServiceResponse) {}
+}
+
+/// An Actix `Transform` that runs a list of `ResourceMiddleware` in order.
+#[derive(Clone)]
+pub(super) struct MiddlewareChain {
+    middleware: Rc<Vec<Box<dyn ResourceMiddleware>>>,
+}
+
+impl MiddlewareChain {
+    pub fn new(middleware: Rc<Vec<Box<dyn ResourceMiddleware>>>) -> Self {
+        Self { middleware }
+    }
+}
+
+impl<S> Transform<S> for MiddlewareChain
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse, Error = Error> + 'static,
+    S::Future: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse;
+    type Error = Error;
+    type InitError = ();
+    type Transform = MiddlewareChainService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(MiddlewareChainService {
+            service,
+            middleware: self.middleware.clone(),
+        })
+    }
+}
+
+pub(super) struct MiddlewareChainService<S> {
+    service: S,
+    middleware: Rc<Vec<Box<dyn ResourceMiddleware>>>,
+}
+
+impl<S> Service for MiddlewareChainService<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse, Error = Error> + 'static,
+    S::Future: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: ServiceRequest) -> Self::Future {
+        for middleware in self.middleware.iter() {
+            if let Err(response) = middleware.on_request(&mut request) {
+                let mut response = request.into_response(response);
+                for middleware in self.middleware.iter() {
+                    middleware.on_response(&mut response);
+                }
+                return Box::pin(ok(response));
+            }
+        }
+
+        let middleware = self.middleware.clone();
+        let fut = self.service.call(request);
+        Box::pin(async move {
+            let mut response = fut.await?;
+            for middleware in middleware.iter() {
+                middleware.on_response(&mut response);
+            }
+            Ok(response)
+        })
+    }
+}
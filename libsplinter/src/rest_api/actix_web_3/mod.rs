@@ -19,12 +19,29 @@
 
 mod api;
 mod builder;
+#[cfg(feature = "rest-api-cors")]
+mod cors;
+mod health;
+mod middleware;
+mod mount;
+#[cfg(feature = "authorization")]
+mod permissions;
+mod rate_limit;
+mod routes;
 mod runnable;
 
+use std::collections::HashMap;
+
 use actix_web_3::Resource;
 
 pub use api::RestApi;
 pub use builder::RestApiBuilder;
+#[cfg(feature = "rest-api-cors")]
+pub use cors::{CorsMiddleware, CorsResourceProvider};
+pub use health::HealthResourceProvider;
+pub use middleware::ResourceMiddleware;
+pub use rate_limit::{RateLimitKey, RateLimitMiddleware, RateLimitResourceProvider};
+pub use routes::{list_routes, RouteDescription, RouteListingResourceProvider};
 pub use runnable::RunnableRestApi;
 
 /// A `ResourceProvider` provides a list of resources.
@@ -34,4 +51,59 @@ pub use runnable::RunnableRestApi;
 pub trait ResourceProvider: Send {
     /// Returns a list of Actix `Resource`s.
     fn resources(&self) -> Vec<Resource>;
+
+    /// Returns a list of Actix `Resource`s, grouped by the API version they belong to.
+    ///
+    /// Resources are mounted under the path prefix of their version, so a resource for
+    /// `/status` in the `ApiVersion::V1` group is served at `/v1/status`. By default, all of the
+    /// provider's `resources` are returned in the `ApiVersion::Unversioned` group.
+    fn versioned_resources(&self) -> Vec<(ApiVersion, Vec<Resource>)> {
+        vec![(ApiVersion::Unversioned, self.resources())]
+    }
+
+    /// Returns the middleware to run around each of this provider's resources.
+    ///
+    /// The middleware are applied in the order given, and only to the resources of this provider.
+    fn middleware(&self) -> Vec<Box<dyn ResourceMiddleware>> {
+        vec![]
+    }
+
+    /// Returns the permissions required by this provider's routes, keyed by route pattern.
+    ///
+    /// The pattern is the one the resource is mounted at, including any version prefix (for
+    /// example, `/v1/status`). When the `authorization` feature is enabled, a request to one of
+    /// these routes is only handled if the client's identity has the mapped permission. Routes that
+    /// are not listed are open.
+    fn resource_permissions(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+
+    /// Returns a description of each of this provider's routes, for the route listing.
+    ///
+    /// Actix `Resource`s do not expose their paths or methods, so a provider's routes are only
+    /// included in the listing served by `RouteListingResourceProvider` if they are described
+    /// here. As with `resource_permissions`, paths include any version prefix.
+    fn route_descriptions(&self) -> Vec<RouteDescription> {
+        vec![]
+    }
+}
+
+/// The version of the API that a group of resources belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ApiVersion {
+    /// Resources that are mounted without a version prefix
+    Unversioned,
+    V1,
+    V2,
+}
+
+impl ApiVersion {
+    /// Returns the path prefix for resources of this version, if any.
+    pub fn path_prefix(&self) -> Option<&'static str> {
+        match self {
+            ApiVersion::Unversioned => None,
+            ApiVersion::V1 => Some("/v1"),
+            ApiVersion::V2 => Some("/v2"),
+        }
+    }
 }
@@ -0,0 +1,345 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Contains the logic for mounting the resources of `ResourceProvider`s on an Actix app.
+
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use actix_web_3::dev::HttpServiceFactory;
+use actix_web_3::{web, Resource};
+
+use super::middleware::MiddlewareChain;
+#[cfg(feature = "authorization")]
+use super::permissions::{RouteAuthorization, RoutePermissions};
+use super::{ApiVersion, ResourceMiddleware, ResourceProvider};
+
+/// Registers the resources of all of the given providers with the Actix service config.
+///
+/// Resources of the same API version are grouped into a single scope, as Actix will not fall
+/// through to a second scope with the same prefix when the first has no matching resource. Each
+/// resource is wrapped in the middleware of the provider it came from and, when the
+/// `authorization` feature is enabled, requires the permissions the provider declares for it.
+pub(super) fn configure_resources(
+    config: &mut web::ServiceConfig,
+    providers: &[Box<dyn ResourceProvider>],
+    #[cfg(feature = "authorization")] authorization: &RouteAuthorization,
+) {
+    let mut versions: BTreeMap<ApiVersion, Vec<ProvidedResource>> = BTreeMap::new();
+    for provider in providers {
+        // Allowing unused_mut because middleware must be mutable if `authorization` is enabled
+        #[allow(unused_mut)]
+        let mut middleware = provider.middleware();
+        // The permission check runs before any of the provider's own middleware
+        #[cfg(feature = "authorization")]
+        {
+            let permissions = provider.resource_permissions();
+            if !permissions.is_empty() {
+                middleware.insert(
+                    0,
+                    Box::new(RoutePermissions::new(permissions, authorization.clone())),
+                );
+            }
+        }
+        let middleware = Rc::new(middleware);
+        for (version, resources) in provider.versioned_resources() {
+            versions
+                .entry(version)
+                .or_default()
+                .extend(resources.into_iter().map(|resource| ProvidedResource {
+                    resource,
+                    middleware: middleware.clone(),
+                }));
+        }
+    }
+
+    for (version, resources) in versions {
+        match version.path_prefix() {
+            Some(prefix) => {
+                config.service(
+                    resources
+                        .into_iter()
+                        .fold(web::scope(prefix), |scope, provided| {
+                            scope.service(provided.into_service())
+                        }),
+                );
+            }
+            None => {
+                for provided in resources {
+                    config.service(provided.into_service());
+                }
+            }
+        }
+    }
+}
+
+/// A resource along with the middleware of the provider that returned it.
+struct ProvidedResource {
+    resource: Resource,
+    middleware: Rc<Vec<Box<dyn ResourceMiddleware>>>,
+}
+
+impl ProvidedResource {
+    /// Returns the resource wrapped in its provider's middleware.
+    fn into_service(self) -> impl HttpServiceFactory + 'static {
+        self.resource.wrap(MiddlewareChain::new(self.middleware))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "authorization")]
+    use std::collections::HashMap;
+
+    use actix_web_3::dev::ServiceResponse;
+    use actix_web_3::http::header::{HeaderName, HeaderValue};
+    use actix_web_3::{http::StatusCode, rt::System, test, App, HttpResponse};
+
+    #[cfg(feature = "authorization")]
+    use crate::error::InternalError;
+    #[cfg(feature = "authorization")]
+    use crate::rest_api::auth::{
+        authorization::{AuthorizationHandler, AuthorizationHandlerResult},
+        identity::{Identity, IdentityProvider},
+        AuthorizationHeader,
+    };
+
+    struct VersionedProvider;
+
+    impl ResourceProvider for VersionedProvider {
+        fn resources(&self) -> Vec<Resource> {
+            vec![]
+        }
+
+        fn versioned_resources(&self) -> Vec<(ApiVersion, Vec<Resource>)> {
+            vec![
+                (
+                    ApiVersion::V1,
+                    vec![web::resource("/status")
+                        .route(web::get().to(|| async { HttpResponse::Ok().body("v1") }))],
+                ),
+                (
+                    ApiVersion::V2,
+                    vec![web::resource("/status")
+                        .route(web::get().to(|| async { HttpResponse::Ok().body("v2") }))],
+                ),
+            ]
+        }
+    }
+
+    struct UnversionedProvider;
+
+    impl ResourceProvider for UnversionedProvider {
+        fn resources(&self) -> Vec<Resource> {
+            vec![web::resource("/status")
+                .route(web::get().to(|| async { HttpResponse::Ok().body("unversioned") }))]
+        }
+    }
+
+    struct HeaderMiddleware;
+
+    impl ResourceMiddleware for HeaderMiddleware {
+        fn on_response(&self, response: &mut ServiceResponse) {
+            response.headers_mut().insert(
+                HeaderName::from_static("x-provider"),
+                HeaderValue::from_static("wrapped"),
+            );
+        }
+    }
+
+    struct WrappedProvider;
+
+    impl ResourceProvider for WrappedProvider {
+        fn resources(&self) -> Vec<Resource> {
+            vec![web::resource("/wrapped")
+                .route(web::get().to(|| async { HttpResponse::Ok().finish() }))]
+        }
+
+        fn middleware(&self) -> Vec<Box<dyn ResourceMiddleware>> {
+            vec![Box::new(HeaderMiddleware)]
+        }
+    }
+
+    #[cfg(feature = "authorization")]
+    struct PermissionedProvider;
+
+    #[cfg(feature = "authorization")]
+    impl ResourceProvider for PermissionedProvider {
+        fn resources(&self) -> Vec<Resource> {
+            vec![
+                web::resource("/protected")
+                    .route(web::get().to(|| async { HttpResponse::Ok().finish() })),
+                web::resource("/open")
+                    .route(web::get().to(|| async { HttpResponse::Ok().finish() })),
+            ]
+        }
+
+        fn resource_permissions(&self) -> HashMap<String, String> {
+            let mut permissions = HashMap::new();
+            permissions.insert("/protected".to_string(), "test.read".to_string());
+            permissions
+        }
+    }
+
+    /// Resolves any custom authorization header to an identity of the same value
+    #[cfg(feature = "authorization")]
+    #[derive(Clone)]
+    struct CustomIdentityProvider;
+
+    #[cfg(feature = "authorization")]
+    impl IdentityProvider for CustomIdentityProvider {
+        fn get_identity(
+            &self,
+            authorization: &AuthorizationHeader,
+        ) -> Result<Option<Identity>, InternalError> {
+            match authorization {
+                AuthorizationHeader::Custom(value) => Ok(Some(Identity::Custom(value.clone()))),
+                _ => Ok(None),
+            }
+        }
+
+        fn clone_box(&self) -> Box<dyn IdentityProvider> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// Only allows the `test.read` permission for the "allowed" identity
+    #[cfg(feature = "authorization")]
+    #[derive(Clone)]
+    struct TestAuthorizationHandler;
+
+    #[cfg(feature = "authorization")]
+    impl AuthorizationHandler for TestAuthorizationHandler {
+        fn has_permission(
+            &self,
+            identity: &Identity,
+            permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            match (identity, permission_id) {
+                (Identity::Custom(id), "test.read") if id == "allowed" => {
+                    Ok(AuthorizationHandlerResult::Allow)
+                }
+                _ => Ok(AuthorizationHandlerResult::Deny),
+            }
+        }
+
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// Verifies that versioned resources are mounted under their version's prefix, and that
+    /// providers only implementing `resources` are mounted without a prefix.
+    #[test]
+    fn versioned_resources_are_prefixed() {
+        let providers: Vec<Box<dyn ResourceProvider>> =
+            vec![Box::new(VersionedProvider), Box::new(UnversionedProvider)];
+
+        System::new("versioned_resources_are_prefixed").block_on(async move {
+            let mut app = test::init_service(App::new().configure(|config| {
+                configure_resources(
+                    config,
+                    &providers,
+                    #[cfg(feature = "authorization")]
+                    &RouteAuthorization::default(),
+                )
+            }))
+            .await;
+
+            for (path, expected) in &[
+                ("/v1/status", "v1"),
+                ("/v2/status", "v2"),
+                ("/status", "unversioned"),
+            ] {
+                let req = test::TestRequest::get().uri(path).to_request();
+                let body = test::read_response(&mut app, req).await;
+                assert_eq!(expected.as_bytes(), &body[..]);
+            }
+
+            let req = test::TestRequest::get().uri("/v3/status").to_request();
+            let resp = test::call_service(&mut app, req).await;
+            assert_eq!(StatusCode::NOT_FOUND, resp.status());
+        });
+    }
+
+    /// Verifies that a provider's middleware is applied to its own resources, but not to the
+    /// resources of other providers.
+    #[test]
+    fn middleware_applies_to_provider_resources() {
+        let providers: Vec<Box<dyn ResourceProvider>> =
+            vec![Box::new(WrappedProvider), Box::new(UnversionedProvider)];
+
+        System::new("middleware_applies_to_provider_resources").block_on(async move {
+            let mut app = test::init_service(App::new().configure(|config| {
+                configure_resources(
+                    config,
+                    &providers,
+                    #[cfg(feature = "authorization")]
+                    &RouteAuthorization::default(),
+                )
+            }))
+            .await;
+
+            let req = test::TestRequest::get().uri("/wrapped").to_request();
+            let resp = test::call_service(&mut app, req).await;
+            assert_eq!(StatusCode::OK, resp.status());
+            assert_eq!(
+                Some(&HeaderValue::from_static("wrapped")),
+                resp.headers().get("x-provider")
+            );
+
+            let req = test::TestRequest::get().uri("/status").to_request();
+            let resp = test::call_service(&mut app, req).await;
+            assert_eq!(StatusCode::OK, resp.status());
+            assert!(resp.headers().get("x-provider").is_none());
+        });
+    }
+
+    /// Verifies that a permission declared by a provider is required for its route, and that the
+    /// provider's other routes remain open.
+    #[cfg(feature = "authorization")]
+    #[test]
+    fn resource_permissions_are_enforced() {
+        let providers: Vec<Box<dyn ResourceProvider>> = vec![Box::new(PermissionedProvider)];
+        let authorization = RouteAuthorization {
+            identity_providers: vec![Box::new(CustomIdentityProvider)],
+            authorization_handlers: vec![Box::new(TestAuthorizationHandler)],
+        };
+
+        System::new("resource_permissions_are_enforced").block_on(async move {
+            let mut app = test::init_service(
+                App::new()
+                    .configure(|config| configure_resources(config, &providers, &authorization)),
+            )
+            .await;
+
+            for (path, auth, expected) in &[
+                ("/protected", Some("allowed"), StatusCode::OK),
+                ("/protected", Some("denied"), StatusCode::FORBIDDEN),
+                ("/protected", None, StatusCode::UNAUTHORIZED),
+                ("/open", Some("denied"), StatusCode::OK),
+                ("/open", None, StatusCode::OK),
+            ] {
+                let mut req = test::TestRequest::get().uri(path);
+                if let Some(auth) = auth {
+                    req = req.header("Authorization", *auth);
+                }
+                let resp = test::call_service(&mut app, req.to_request()).await;
+                assert_eq!(*expected, resp.status(), "{} with {:?}", path, auth);
+            }
+        });
+    }
+}
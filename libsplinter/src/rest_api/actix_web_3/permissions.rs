@@ -0,0 +1,122 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Enforcement of the per-route permissions declared by a `ResourceProvider`.
+
+use std::collections::HashMap;
+
+use actix_web_3::dev::ServiceRequest;
+use actix_web_3::http::{header::AUTHORIZATION, Method};
+use actix_web_3::{HttpMessage, HttpResponse};
+
+use crate::rest_api::auth::authorization::{AuthorizationHandler, AuthorizationHandlerResult};
+use crate::rest_api::auth::identity::{Identity, IdentityProvider};
+use crate::rest_api::auth::AuthorizationHeader;
+use crate::rest_api::ErrorResponse;
+
+use super::ResourceMiddleware;
+
+/// The identity providers and authorization handlers used to check route permissions.
+#[derive(Clone, Default)]
+pub(super) struct RouteAuthorization {
+    pub identity_providers: Vec<Box<dyn IdentityProvider>>,
+    pub authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+}
+
+/// A `ResourceMiddleware` that requires a permission for some of a provider's routes.
+///
+/// Routes that have no declared permission are left open, as are CORS preflight `OPTIONS`
+/// requests, which browsers send without credentials.
+pub(super) struct RoutePermissions {
+    permissions: HashMap<String, String>,
+    authorization: RouteAuthorization,
+}
+
+impl RoutePermissions {
+    pub fn new(permissions: HashMap<String, String>, authorization: RouteAuthorization) -> Self {
+        Self {
+            permissions,
+            authorization,
+        }
+    }
+}
+
+impl ResourceMiddleware for RoutePermissions {
+    fn on_request(&self, request: &mut ServiceRequest) -> Result<(), HttpResponse> {
+        if request.method() == Method::OPTIONS {
+            return Ok(());
+        }
+
+        let permission_id = match request
+            .match_pattern()
+            .and_then(|pattern| self.permissions.get(&pattern))
+        {
+            Some(permission_id) => permission_id,
+            None => return Ok(()),
+        };
+
+        let identity = request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|header| header.to_str().ok())
+            .and_then(|header| get_identity(header, &self.authorization.identity_providers))
+            .ok_or_else(|| HttpResponse::Unauthorized().json(ErrorResponse::unauthorized()))?;
+
+        if !identity_has_permission(
+            &identity,
+            permission_id,
+            &self.authorization.authorization_handlers,
+        ) {
+            return Err(HttpResponse::Forbidden().json(ErrorResponse::forbidden(
+                "Client does not have the required permission",
+            )));
+        }
+
+        request.extensions_mut().insert(identity);
+        Ok(())
+    }
+}
+
+/// Resolves the value of an authorization header to an identity, using the first identity
+/// provider that recognizes it.
+fn get_identity(
+    auth_header: &str,
+    identity_providers: &[Box<dyn IdentityProvider>],
+) -> Option<Identity> {
+    let authorization: AuthorizationHeader = auth_header.parse().ok()?;
+    identity_providers.iter().find_map(|provider| {
+        provider.get_identity(&authorization).unwrap_or_else(|err| {
+            error!("{}", err);
+            None
+        })
+    })
+}
+
+/// Checks the given identity against the authorization handlers in order. The first handler to
+/// allow or deny the permission decides the result; if none do, the permission is denied.
+fn identity_has_permission(
+    identity: &Identity,
+    permission_id: &str,
+    authorization_handlers: &[Box<dyn AuthorizationHandler>],
+) -> bool {
+    for handler in authorization_handlers {
+        match handler.has_permission(identity, permission_id) {
+            Ok(AuthorizationHandlerResult::Allow) => return true,
+            Ok(AuthorizationHandlerResult::Deny) => return false,
+            Ok(AuthorizationHandlerResult::Continue) => {}
+            Err(err) => error!("{}", err),
+        }
+    }
+    false
+}
@@ -0,0 +1,318 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rate limiting for the resources of a `ResourceProvider`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use actix_web_3::dev::ServiceRequest;
+use actix_web_3::http::header::{self, HeaderValue};
+#[cfg(feature = "authorization")]
+use actix_web_3::HttpMessage;
+use actix_web_3::{HttpResponse, Resource};
+
+#[cfg(feature = "authorization")]
+use crate::rest_api::auth::identity::Identity;
+use crate::rest_api::ErrorResponse;
+
+use super::{ApiVersion, ResourceMiddleware, ResourceProvider, RouteDescription};
+
+/// Determines which requests share a rate limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RateLimitKey {
+    /// Requests from the same client IP address share a limit
+    ClientIp,
+    /// Requests from the same identity share a limit
+    ///
+    /// A request only has an identity if its route requires a permission; requests to other
+    /// routes are limited by client IP address.
+    #[cfg(feature = "authorization")]
+    Identity,
+}
+
+/// A `ResourceMiddleware` that limits the rate of requests to a provider's resources, using a
+/// token bucket for each client.
+///
+/// Each client may make up to `capacity` requests in a burst, and regains one request every
+/// refill interval. Requests beyond the limit are answered with `429 Too Many Requests` and a
+/// `Retry-After` header.
+///
+/// Clones of the middleware share their buckets, so a limit applies across all of the REST API's
+/// workers. The middleware may be returned from a provider's `middleware`, or applied to all of
+/// the resources of an existing provider using `RateLimitResourceProvider`.
+#[derive(Clone)]
+pub struct RateLimitMiddleware {
+    capacity: u32,
+    refill_interval: Duration,
+    key: RateLimitKey,
+    buckets: Arc<Mutex<Buckets>>,
+}
+
+impl RateLimitMiddleware {
+    /// Creates middleware that allows each client IP address a burst of `capacity` requests,
+    /// regaining one request every `refill_interval`.
+    ///
+    /// A capacity of zero rejects every request.
+    pub fn new(capacity: u32, refill_interval: Duration) -> Self {
+        Self {
+            capacity,
+            refill_interval,
+            key: RateLimitKey::ClientIp,
+            buckets: Arc::new(Mutex::new(Buckets {
+                buckets: HashMap::new(),
+                last_pruned: Instant::now(),
+            })),
+        }
+    }
+
+    /// Sets which requests share a rate limit.
+    pub fn with_key(mut self, key: RateLimitKey) -> Self {
+        self.key = key;
+        self
+    }
+
+    /// Returns the key of the bucket the request is counted against.
+    fn client_key(&self, request: &ServiceRequest) -> String {
+        match self.key {
+            RateLimitKey::ClientIp => (),
+            #[cfg(feature = "authorization")]
+            RateLimitKey::Identity => {
+                if let Some(identity) = request.extensions().get::<Identity>() {
+                    return format!("{:?}", identity);
+                }
+            }
+        }
+
+        request
+            .peer_addr()
+            .map(|addr| addr.ip().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Takes a token from the client's bucket.
+    ///
+    /// # Returns
+    ///
+    /// `Err` with the time until a token is available, if the bucket is empty.
+    fn take_token(&self, buckets: &mut Buckets, client: String) -> Result<(), Duration> {
+        let now = Instant::now();
+        let capacity = f64::from(self.capacity);
+        let interval = self.refill_interval.as_secs_f64();
+
+        // Buckets that have been idle long enough to refill completely are no different from new
+        // ones, so they are dropped to bound the number of buckets kept
+        let full_after = self.refill_interval * self.capacity;
+        if now.duration_since(buckets.last_pruned) >= full_after {
+            buckets
+                .buckets
+                .retain(|_, bucket| now.duration_since(bucket.updated) < full_after);
+            buckets.last_pruned = now;
+        }
+
+        let bucket = buckets.buckets.entry(client).or_insert(TokenBucket {
+            tokens: capacity,
+            updated: now,
+        });
+        let refilled = now.duration_since(bucket.updated).as_secs_f64() / interval;
+        bucket.tokens = capacity.min(bucket.tokens + refilled);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) * interval))
+        }
+    }
+}
+
+impl ResourceMiddleware for RateLimitMiddleware {
+    fn on_request(&self, request: &mut ServiceRequest) -> Result<(), HttpResponse> {
+        let client = self.client_key(request);
+        let mut buckets = self.buckets.lock().map_err(|_| {
+            error!("Rate limit buckets lock was poisoned");
+            HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+        })?;
+
+        self.take_token(&mut buckets, client)
+            .map_err(|retry_after| {
+                // Round up, so that a client that waits as long as it is told will not be limited
+                let retry_secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+                HttpResponse::TooManyRequests()
+                    .header(header::RETRY_AFTER, HeaderValue::from(retry_secs.max(1)))
+                    .json(ErrorResponse::too_many_requests(
+                        "Too many requests; try again later",
+                    ))
+            })
+    }
+}
+
+/// The token buckets of a `RateLimitMiddleware`, keyed by client.
+struct Buckets {
+    buckets: HashMap<String, TokenBucket>,
+    last_pruned: Instant,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Wraps a `ResourceProvider`, applying `RateLimitMiddleware` to all of its resources.
+///
+/// The rate limit runs before the provider's own middleware, but after the check of any
+/// permissions the provider declares.
+pub struct RateLimitResourceProvider {
+    provider: Box<dyn ResourceProvider>,
+    rate_limit: RateLimitMiddleware,
+}
+
+impl RateLimitResourceProvider {
+    pub fn new(provider: Box<dyn ResourceProvider>, rate_limit: RateLimitMiddleware) -> Self {
+        Self {
+            provider,
+            rate_limit,
+        }
+    }
+}
+
+impl ResourceProvider for RateLimitResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.provider.resources()
+    }
+
+    fn versioned_resources(&self) -> Vec<(ApiVersion, Vec<Resource>)> {
+        self.provider.versioned_resources()
+    }
+
+    fn middleware(&self) -> Vec<Box<dyn ResourceMiddleware>> {
+        let mut middleware: Vec<Box<dyn ResourceMiddleware>> =
+            vec![Box::new(self.rate_limit.clone())];
+        middleware.extend(self.provider.middleware());
+        middleware
+    }
+
+    fn resource_permissions(&self) -> HashMap<String, String> {
+        self.provider.resource_permissions()
+    }
+
+    fn route_descriptions(&self) -> Vec<RouteDescription> {
+        self.provider.route_descriptions()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::SocketAddr;
+
+    use actix_web_3::{http::StatusCode, rt::System, test, web, App};
+
+    use super::super::cors::{CorsMiddleware, CorsResourceProvider};
+    use super::super::mount::configure_resources;
+    #[cfg(feature = "authorization")]
+    use super::super::permissions::RouteAuthorization;
+
+    struct StatusProvider;
+
+    impl ResourceProvider for StatusProvider {
+        fn resources(&self) -> Vec<Resource> {
+            vec![web::resource("/status")
+                .route(web::get().to(|| async { HttpResponse::Ok().finish() }))]
+        }
+    }
+
+    /// Verifies that requests from a client are rejected with `429 Too Many Requests` once its
+    /// bucket is empty, while requests from another client are still handled.
+    #[test]
+    fn rate_limit() {
+        let providers: Vec<Box<dyn ResourceProvider>> =
+            vec![Box::new(RateLimitResourceProvider::new(
+                Box::new(StatusProvider),
+                RateLimitMiddleware::new(2, Duration::from_secs(60)),
+            ))];
+
+        System::new("rate_limit").block_on(async move {
+            let mut app = test::init_service(App::new().configure(|config| {
+                configure_resources(
+                    config,
+                    &providers,
+                    #[cfg(feature = "authorization")]
+                    &RouteAuthorization::default(),
+                )
+            }))
+            .await;
+
+            let request = |peer_addr: &str| {
+                test::TestRequest::get()
+                    .uri("/status")
+                    .peer_addr(peer_addr.parse::<SocketAddr>().expect("Invalid address"))
+                    .to_request()
+            };
+
+            for _ in 0..2 {
+                let resp = test::call_service(&mut app, request("10.0.0.1:5000")).await;
+                assert_eq!(StatusCode::OK, resp.status());
+            }
+
+            let resp = test::call_service(&mut app, request("10.0.0.1:5001")).await;
+            assert_eq!(StatusCode::TOO_MANY_REQUESTS, resp.status());
+            assert_eq!(
+                Some(&HeaderValue::from_static("60")),
+                resp.headers().get(header::RETRY_AFTER)
+            );
+
+            let resp = test::call_service(&mut app, request("10.0.0.2:5000")).await;
+            assert_eq!(StatusCode::OK, resp.status());
+        });
+    }
+    /// Verifies that a request rejected by the rate limit of a provider wrapped in CORS support
+    /// still includes the CORS headers, so that a browser can read the `429` response.
+    #[test]
+    fn rate_limit_with_cors() {
+        let providers: Vec<Box<dyn ResourceProvider>> = vec![Box::new(CorsResourceProvider::new(
+            Box::new(RateLimitResourceProvider::new(
+                Box::new(StatusProvider),
+                RateLimitMiddleware::new(0, Duration::from_secs(60)),
+            )),
+            CorsMiddleware::new(vec!["http://dashboard.example.com".into()]),
+        ))];
+
+        System::new("rate_limit_with_cors").block_on(async move {
+            let mut app = test::init_service(App::new().configure(|config| {
+                configure_resources(
+                    config,
+                    &providers,
+                    #[cfg(feature = "authorization")]
+                    &RouteAuthorization::default(),
+                )
+            }))
+            .await;
+
+            let req = test::TestRequest::get()
+                .uri("/status")
+                .header(header::ORIGIN, "http://dashboard.example.com")
+                .to_request();
+            let resp = test::call_service(&mut app, req).await;
+            assert_eq!(StatusCode::TOO_MANY_REQUESTS, resp.status());
+            assert_eq!(
+                Some(&HeaderValue::from_static("http://dashboard.example.com")),
+                resp.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            );
+        });
+    }
+}
@@ -0,0 +1,170 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A `ResourceProvider` that serves the routes of the REST API as a JSON document.
+
+use actix_web_3::{web, HttpResponse, Resource};
+
+use super::ResourceProvider;
+
+/// A route served by a `ResourceProvider`, as included in the route listing.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct RouteDescription {
+    /// The pattern the route is mounted at, including any version prefix
+    pub path: String,
+    /// The HTTP method of the route, such as `GET`
+    pub method: String,
+}
+
+impl RouteDescription {
+    pub fn new<P: Into<String>, M: Into<String>>(path: P, method: M) -> Self {
+        Self {
+            path: path.into(),
+            method: method.into(),
+        }
+    }
+}
+
+/// Collects the routes described by each of the given providers, sorted by path and method.
+pub fn list_routes(providers: &[Box<dyn ResourceProvider>]) -> Vec<RouteDescription> {
+    let mut routes: Vec<RouteDescription> = providers
+        .iter()
+        .flat_map(|provider| provider.route_descriptions())
+        .collect();
+    routes.sort();
+    routes.dedup();
+    routes
+}
+
+/// Provides an endpoint that lists the routes of the REST API as a JSON array of path and method
+/// pairs.
+///
+/// This is not a full OpenAPI document; it is only an inventory of the available routes. The
+/// listing includes its own route.
+#[derive(Clone)]
+pub struct RouteListingResourceProvider {
+    path: String,
+    routes: Vec<RouteDescription>,
+}
+
+impl RouteListingResourceProvider {
+    /// Creates a provider that serves the given routes at `path`.
+    pub fn new(path: &str, mut routes: Vec<RouteDescription>) -> Self {
+        routes.push(RouteDescription::new(path, "GET"));
+        routes.sort();
+        routes.dedup();
+        Self {
+            path: path.to_string(),
+            routes,
+        }
+    }
+
+    /// Creates a provider that serves the routes of the given providers at `path`.
+    pub fn from_providers(path: &str, providers: &[Box<dyn ResourceProvider>]) -> Self {
+        Self::new(path, list_routes(providers))
+    }
+}
+
+impl ResourceProvider for RouteListingResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        let routes = self.routes.clone();
+        vec![
+            web::resource(self.path.as_str()).route(web::get().to(move || {
+                let response = HttpResponse::Ok().json(&routes);
+                async move { response }
+            })),
+        ]
+    }
+
+    fn route_descriptions(&self) -> Vec<RouteDescription> {
+        vec![RouteDescription::new(self.path.as_str(), "GET")]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use actix_web_3::{rt::System, test, App};
+
+    use super::super::ApiVersion;
+
+    struct StatusProvider;
+
+    impl ResourceProvider for StatusProvider {
+        fn resources(&self) -> Vec<Resource> {
+            vec![web::resource("/status")
+                .route(web::get().to(|| async { HttpResponse::Ok().finish() }))]
+        }
+
+        fn route_descriptions(&self) -> Vec<RouteDescription> {
+            vec![RouteDescription::new("/status", "GET")]
+        }
+    }
+
+    struct ItemsProvider;
+
+    impl ResourceProvider for ItemsProvider {
+        fn resources(&self) -> Vec<Resource> {
+            vec![]
+        }
+
+        fn versioned_resources(&self) -> Vec<(ApiVersion, Vec<Resource>)> {
+            vec![(
+                ApiVersion::V1,
+                vec![web::resource("/items")
+                    .route(web::get().to(|| async { HttpResponse::Ok().finish() }))
+                    .route(web::post().to(|| async { HttpResponse::Ok().finish() }))],
+            )]
+        }
+
+        fn route_descriptions(&self) -> Vec<RouteDescription> {
+            vec![
+                RouteDescription::new("/v1/items", "POST"),
+                RouteDescription::new("/v1/items", "GET"),
+            ]
+        }
+    }
+
+    /// Verifies that the route listing serves the routes of all of the given providers, sorted by
+    /// path and method, along with its own route.
+    #[test]
+    fn route_listing() {
+        let providers: Vec<Box<dyn ResourceProvider>> =
+            vec![Box::new(StatusProvider), Box::new(ItemsProvider)];
+        let listing = RouteListingResourceProvider::from_providers("/openapi.json", &providers);
+
+        System::new("route_listing").block_on(async move {
+            let mut app = test::init_service(
+                listing
+                    .resources()
+                    .into_iter()
+                    .fold(App::new(), |app, resource| app.service(resource)),
+            )
+            .await;
+
+            let req = test::TestRequest::get().uri("/openapi.json").to_request();
+            let routes: Vec<RouteDescription> = test::read_response_json(&mut app, req).await;
+            assert_eq!(
+                routes,
+                vec![
+                    RouteDescription::new("/openapi.json", "GET"),
+                    RouteDescription::new("/status", "GET"),
+                    RouteDescription::new("/v1/items", "GET"),
+                    RouteDescription::new("/v1/items", "POST"),
+                ]
+            );
+        });
+    }
+}
@@ -16,12 +16,16 @@
 
 use crate::rest_api::{BindConfig, RestApiServerError};
 
+#[cfg(feature = "authorization")]
+use super::permissions::RouteAuthorization;
 use super::{ResourceProvider, RestApi};
 
 /// A configured REST API which may best started with `run` function.
 pub struct RunnableRestApi {
     pub(super) resource_providers: Vec<Box<dyn ResourceProvider>>,
     pub(super) bind: BindConfig,
+    #[cfg(feature = "authorization")]
+    pub(super) authorization: RouteAuthorization,
 }
 
 impl RunnableRestApi {
@@ -31,6 +35,8 @@ impl RunnableRestApi {
         let RunnableRestApi {
             resource_providers,
             bind,
+            #[cfg(feature = "authorization")]
+            authorization,
         } = self;
 
         let (bind_url, acceptor_opt) = match bind {
@@ -50,6 +56,12 @@ impl RunnableRestApi {
             BindConfig::Http(url) => (url, None),
         };
 
-        RestApi::new(bind_url, acceptor_opt, resource_providers)
+        RestApi::new(
+            bind_url,
+            acceptor_opt,
+            resource_providers,
+            #[cfg(feature = "authorization")]
+            authorization,
+        )
     }
 }
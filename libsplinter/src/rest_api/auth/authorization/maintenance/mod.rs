@@ -120,9 +120,13 @@ impl AuthorizationHandler for MaintenanceModeAuthorizationHandler {
 mod tests {
     use super::*;
 
+    use std::collections::HashSet;
+    use std::sync::mpsc::Receiver;
+    use std::time::SystemTime;
+
     use crate::rest_api::auth::authorization::rbac::store::{
-        Assignment, AssignmentBuilder, Role, RoleBasedAuthorizationStore,
-        RoleBasedAuthorizationStoreError,
+        Assignment, AssignmentBuilder, ImportMode, RbacChangeEvent, RbacSnapshot, Role,
+        RoleBasedAuthorizationStore, RoleBasedAuthorizationStoreError,
     };
 
     const ADMIN_USER_IDENTITY: &str = "admin_user";
@@ -244,6 +248,15 @@ mod tests {
             unimplemented!()
         }
 
+        fn search_roles(
+            &self,
+            _query: &str,
+            _limit: u64,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>
+        {
+            unimplemented!()
+        }
+
         fn add_role(&self, _role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
             unimplemented!()
         }
@@ -252,6 +265,10 @@ mod tests {
             unimplemented!()
         }
 
+        fn upsert_role(&self, _role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
         fn remove_role(&self, _role_id: &str) -> Result<(), RoleBasedAuthorizationStoreError> {
             unimplemented!()
         }
@@ -291,6 +308,13 @@ mod tests {
             unimplemented!()
         }
 
+        fn get_effective_permissions(
+            &self,
+            _identity: &RBACIdentity,
+        ) -> Result<HashSet<String>, RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
         fn list_assignments(
             &self,
         ) -> Result<Box<dyn ExactSizeIterator<Item = Assignment>>, RoleBasedAuthorizationStoreError>
@@ -298,6 +322,13 @@ mod tests {
             unimplemented!()
         }
 
+        fn list_empty_assignments(
+            &self,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = RBACIdentity>>, RoleBasedAuthorizationStoreError>
+        {
+            unimplemented!()
+        }
+
         fn add_assignment(
             &self,
             _assignment: Assignment,
@@ -312,6 +343,13 @@ mod tests {
             unimplemented!()
         }
 
+        fn upsert_assignment(
+            &self,
+            _assignment: Assignment,
+        ) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
         fn remove_assignment(
             &self,
             _identity: &RBACIdentity,
@@ -319,6 +357,33 @@ mod tests {
             unimplemented!()
         }
 
+        fn remove_empty_assignments(&self) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn remove_expired_assignments(
+            &self,
+            _now: SystemTime,
+        ) -> Result<u64, RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn export_state(&self) -> Result<RbacSnapshot, RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn import_state(
+            &self,
+            _snapshot: RbacSnapshot,
+            _mode: ImportMode,
+        ) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn subscribe(&self) -> Receiver<RbacChangeEvent> {
+            unimplemented!()
+        }
+
         fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore> {
             Box::new(self.clone())
         }
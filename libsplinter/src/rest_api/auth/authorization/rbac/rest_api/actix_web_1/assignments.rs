@@ -368,16 +368,18 @@ fn delete_assignment(
 mod tests {
     use super::*;
 
-    use std::collections::BTreeMap;
+    use std::collections::{BTreeMap, HashSet};
+    use std::sync::mpsc::Receiver;
     use std::sync::{Arc, Mutex};
+    use std::time::SystemTime;
 
     use reqwest::{blocking::Client, StatusCode, Url};
     use serde_json::{to_value, Value as JsonValue};
 
     use crate::error::{ConstraintViolationError, ConstraintViolationType};
     use crate::rest_api::auth::authorization::rbac::store::{
-        Assignment, AssignmentBuilder, Identity, Role, RoleBasedAuthorizationStoreError,
-        RoleBuilder,
+        Assignment, AssignmentBuilder, Identity, ImportMode, RbacChangeEvent, RbacSnapshot, Role,
+        RoleBasedAuthorizationStoreError, RoleBuilder,
     };
     use crate::rest_api::{
         actix_web_1::{RestApiBuilder, RestApiShutdownHandle},
@@ -1323,6 +1325,15 @@ mod tests {
             unimplemented!()
         }
 
+        fn search_roles(
+            &self,
+            _query: &str,
+            _limit: u64,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>
+        {
+            unimplemented!()
+        }
+
         fn add_role(&self, _role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
             Ok(())
         }
@@ -1331,6 +1342,10 @@ mod tests {
             unimplemented!()
         }
 
+        fn upsert_role(&self, _role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
         fn remove_role(&self, _role_id: &str) -> Result<(), RoleBasedAuthorizationStoreError> {
             unimplemented!()
         }
@@ -1355,6 +1370,13 @@ mod tests {
             unimplemented!()
         }
 
+        fn get_effective_permissions(
+            &self,
+            _identity: &Identity,
+        ) -> Result<HashSet<String>, RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
         fn list_assignments(
             &self,
         ) -> Result<Box<dyn ExactSizeIterator<Item = Assignment>>, RoleBasedAuthorizationStoreError>
@@ -1370,6 +1392,13 @@ mod tests {
             ))
         }
 
+        fn list_empty_assignments(
+            &self,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = Identity>>, RoleBasedAuthorizationStoreError>
+        {
+            unimplemented!()
+        }
+
         fn add_assignment(
             &self,
             assignment: Assignment,
@@ -1414,6 +1443,18 @@ mod tests {
             }
         }
 
+        fn upsert_assignment(
+            &self,
+            assignment: Assignment,
+        ) -> Result<(), RoleBasedAuthorizationStoreError> {
+            self.assignments
+                .lock()
+                .expect("mem role based authorization store lock was poisoned")
+                .insert(id_to_string(assignment.identity()), assignment);
+
+            Ok(())
+        }
+
         fn remove_assignment(
             &self,
             identity: &Identity,
@@ -1430,6 +1471,33 @@ mod tests {
             Ok(())
         }
 
+        fn remove_empty_assignments(&self) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn remove_expired_assignments(
+            &self,
+            _now: SystemTime,
+        ) -> Result<u64, RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn export_state(&self) -> Result<RbacSnapshot, RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn import_state(
+            &self,
+            _snapshot: RbacSnapshot,
+            _mode: ImportMode,
+        ) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn subscribe(&self) -> Receiver<RbacChangeEvent> {
+            unimplemented!()
+        }
+
         fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore> {
             Box::new(self.clone())
         }
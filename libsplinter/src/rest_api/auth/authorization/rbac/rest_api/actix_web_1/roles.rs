@@ -348,15 +348,18 @@ fn delete_role(
 mod tests {
     use super::*;
 
-    use std::collections::BTreeMap;
+    use std::collections::{BTreeMap, HashSet};
+    use std::sync::mpsc::Receiver;
     use std::sync::{Arc, Mutex};
+    use std::time::SystemTime;
 
     use reqwest::{blocking::Client, StatusCode, Url};
     use serde_json::{to_value, Value as JsonValue};
 
     use crate::error::{ConstraintViolationError, ConstraintViolationType};
     use crate::rest_api::auth::authorization::rbac::store::{
-        Assignment, Identity, Role, RoleBasedAuthorizationStoreError, RoleBuilder,
+        Assignment, Identity, ImportMode, RbacChangeEvent, RbacSnapshot, Role,
+        RoleBasedAuthorizationStoreError, RoleBuilder,
     };
     use crate::rest_api::{
         actix_web_1::{RestApiBuilder, RestApiShutdownHandle},
@@ -1119,6 +1122,15 @@ mod tests {
             ))
         }
 
+        fn search_roles(
+            &self,
+            _query: &str,
+            _limit: u64,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>
+        {
+            unimplemented!()
+        }
+
         fn add_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
             let mut roles = self
                 .roles
@@ -1153,6 +1165,15 @@ mod tests {
             }
         }
 
+        fn upsert_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
+            self.roles
+                .lock()
+                .expect("mem role based authorization store lock was poisoned")
+                .insert(role.id().to_string(), role);
+
+            Ok(())
+        }
+
         fn remove_role(&self, role_id: &str) -> Result<(), RoleBasedAuthorizationStoreError> {
             self.roles
                 .lock()
@@ -1177,6 +1198,13 @@ mod tests {
             unimplemented!()
         }
 
+        fn get_effective_permissions(
+            &self,
+            _identity: &Identity,
+        ) -> Result<HashSet<String>, RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
         fn list_assignments(
             &self,
         ) -> Result<Box<dyn ExactSizeIterator<Item = Assignment>>, RoleBasedAuthorizationStoreError>
@@ -1184,6 +1212,13 @@ mod tests {
             unimplemented!()
         }
 
+        fn list_empty_assignments(
+            &self,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = Identity>>, RoleBasedAuthorizationStoreError>
+        {
+            unimplemented!()
+        }
+
         fn add_assignment(
             &self,
             _assignment: Assignment,
@@ -1198,6 +1233,13 @@ mod tests {
             unimplemented!()
         }
 
+        fn upsert_assignment(
+            &self,
+            _assignment: Assignment,
+        ) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
         fn remove_assignment(
             &self,
             _identity: &Identity,
@@ -1205,6 +1247,33 @@ mod tests {
             unimplemented!()
         }
 
+        fn remove_empty_assignments(&self) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn remove_expired_assignments(
+            &self,
+            _now: SystemTime,
+        ) -> Result<u64, RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn export_state(&self) -> Result<RbacSnapshot, RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn import_state(
+            &self,
+            _snapshot: RbacSnapshot,
+            _mode: ImportMode,
+        ) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn subscribe(&self) -> Receiver<RbacChangeEvent> {
+            unimplemented!()
+        }
+
         fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore> {
             Box::new(self.clone())
         }
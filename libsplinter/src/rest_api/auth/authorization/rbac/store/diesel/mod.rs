@@ -16,8 +16,11 @@ mod models;
 mod operations;
 mod schema;
 
+use std::collections::HashSet;
 use std::convert::TryFrom;
-use std::sync::{Arc, RwLock};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
 
 use crate::error::{
     ConstraintViolationError, ConstraintViolationType, InternalError, InvalidStateError,
@@ -27,32 +30,49 @@ use crate::store::pool::ConnectionPool;
 use diesel::r2d2::{ConnectionManager, Pool};
 
 use super::{
-    Assignment, Identity, Role, RoleBasedAuthorizationStore, RoleBasedAuthorizationStoreError,
-    RoleBuilder, ADMIN_ROLE_ID,
+    Assignment, Identity, ImportMode, RbacChangeEvent, RbacSnapshot, Role,
+    RoleBasedAuthorizationStore, RoleBasedAuthorizationStoreError, RoleBuilder, ADMIN_ROLE_ID,
 };
 
 use operations::add_assignment::RoleBasedAuthorizationStoreAddAssignment as _;
 use operations::add_role::RoleBasedAuthorizationStoreAddRole as _;
+use operations::export_state::RoleBasedAuthorizationStoreExportState as _;
 use operations::get_assigned_roles::RoleBasedAuthorizationStoreGetAssignedRoles as _;
 use operations::get_assignment::RoleBasedAuthorizationStoreGetAssignment as _;
+use operations::get_effective_permissions::RoleBasedAuthorizationStoreGetEffectivePermissions as _;
 use operations::get_role::RoleBasedAuthorizationStoreGetRole as _;
+use operations::import_state::RoleBasedAuthorizationStoreImportState as _;
 use operations::list_assignments::RoleBasedAuthorizationStoreListAssignments as _;
+use operations::list_empty_assignments::RoleBasedAuthorizationStoreListEmptyAssignments as _;
 use operations::list_roles::RoleBasedAuthorizationStoreListRoles as _;
 use operations::remove_assignment::RoleBasedAuthorizationStoreRemoveAssignment as _;
+use operations::remove_empty_assignments::RoleBasedAuthorizationStoreRemoveEmptyAssignments as _;
+use operations::remove_expired_assignments::RoleBasedAuthorizationStoreRemoveExpiredAssignments as _;
 use operations::remove_role::RoleBasedAuthorizationStoreRemoveRole as _;
+use operations::search_roles::RoleBasedAuthorizationStoreSearchRoles as _;
 use operations::update_assignment::RoleBasedAuthorizationStoreUpdateAssignment as _;
 use operations::update_role::RoleBasedAuthorizationStoreUpdateRole as _;
+use operations::upsert_assignment::RoleBasedAuthorizationStoreUpsertAssignment as _;
+use operations::upsert_role::RoleBasedAuthorizationStoreUpsertRole as _;
 use operations::RoleBasedAuthorizationStoreOperations;
 
+/// The permission that grants every permission, which is always accepted by the permission
+/// registry.
+const WILDCARD_PERMISSION: &str = "*";
+
 /// A database-backed [RoleBasedAuthorizationStore], powered by [diesel].
 pub struct DieselRoleBasedAuthorizationStore<C: diesel::Connection + 'static> {
     connection_pool: ConnectionPool<C>,
+    permission_registry: Option<Arc<HashSet<String>>>,
+    subscribers: Arc<Mutex<Vec<Sender<RbacChangeEvent>>>>,
 }
 
 impl<C: diesel::Connection + 'static> DieselRoleBasedAuthorizationStore<C> {
     pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
         Self {
             connection_pool: connection_pool.into(),
+            permission_registry: None,
+            subscribers: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -69,8 +89,60 @@ impl<C: diesel::Connection + 'static> DieselRoleBasedAuthorizationStore<C> {
     ) -> Self {
         Self {
             connection_pool: connection_pool.into(),
+            permission_registry: None,
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Sets the registry of known permissions.
+    ///
+    /// Once a registry is set, roles may only be added or updated with permissions that are in
+    /// the registry, or with the `*` wildcard permission. By default, no registry is set and any
+    /// permission is accepted.
+    pub fn with_permission_registry(mut self, permissions: Vec<String>) -> Self {
+        self.permission_registry = Some(Arc::new(permissions.into_iter().collect()));
+        self
+    }
+
+    /// Checks the role's permissions against the permission registry, if one has been set.
+    fn validate_permissions(&self, role: &Role) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let registry = match &self.permission_registry {
+            Some(registry) => registry,
+            None => return Ok(()),
+        };
+
+        match role
+            .permissions()
+            .iter()
+            .find(|perm| perm.as_str() != WILDCARD_PERMISSION && !registry.contains(*perm))
+        {
+            Some(perm) => Err(RoleBasedAuthorizationStoreError::InvalidState(
+                InvalidStateError::with_message(format!(
+                    "Role '{}' has unknown permission '{}'",
+                    role.id(),
+                    perm
+                )),
+            )),
+            None => Ok(()),
         }
     }
+
+    /// Sends the event to each live subscriber, dropping any whose receiver has been dropped.
+    fn notify_subscribers(&self, event: RbacChangeEvent) {
+        self.subscribers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+    }
+
+    fn add_subscriber(&self) -> Receiver<RbacChangeEvent> {
+        let (sender, receiver) = channel();
+        self.subscribers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(sender);
+        receiver
+    }
 }
 
 #[cfg(feature = "sqlite")]
@@ -93,15 +165,32 @@ impl RoleBasedAuthorizationStore
         })
     }
 
+    fn search_roles(
+        &self,
+        query: &str,
+        limit: u64,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection).search_roles(query, limit)
+        })
+    }
+
     /// Adds a role.
     ///
     /// # Errors
     ///
     /// Returns a `ConstraintViolation` error if a duplicate role ID is added.
+    ///
+    /// Returns an `InvalidState` error if a permission registry is set and the role has an
+    /// unknown permission.
     fn add_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
+        self.validate_permissions(&role)?;
+        let event = RbacChangeEvent::RoleAdded(role.clone());
         self.connection_pool.execute_write(|connection| {
             RoleBasedAuthorizationStoreOperations::new(connection).add_role(role)
-        })
+        })?;
+        self.notify_subscribers(event);
+        Ok(())
     }
 
     /// Updates a role.
@@ -109,6 +198,9 @@ impl RoleBasedAuthorizationStore
     /// # Errors
     ///
     /// Returns a `InvalidState` error if the role does not exist.
+    ///
+    /// Returns an `InvalidState` error if a permission registry is set and the role has an
+    /// unknown permission.
     fn update_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
         if role.id() == ADMIN_ROLE_ID {
             return Err(RoleBasedAuthorizationStoreError::ConstraintViolation(
@@ -117,9 +209,44 @@ impl RoleBasedAuthorizationStore
                 )),
             ));
         }
+        self.validate_permissions(&role)?;
+        let event = RbacChangeEvent::RoleUpdated(role.clone());
         self.connection_pool.execute_write(|connection| {
             RoleBasedAuthorizationStoreOperations::new(connection).update_role(role)
-        })
+        })?;
+        self.notify_subscribers(event);
+        Ok(())
+    }
+
+    /// Adds or replaces a role.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConstraintViolation` error if the role is the `admin` role.
+    ///
+    /// Returns an `InvalidState` error if a permission registry is set and the role has an
+    /// unknown permission.
+    fn upsert_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
+        if role.id() == ADMIN_ROLE_ID {
+            return Err(RoleBasedAuthorizationStoreError::ConstraintViolation(
+                ConstraintViolationError::with_violation_type(ConstraintViolationType::Other(
+                    format!("'{}' role cannot be altered", ADMIN_ROLE_ID),
+                )),
+            ));
+        }
+        self.validate_permissions(&role)?;
+        let existed = self.connection_pool.execute_write(|connection| {
+            let operations = RoleBasedAuthorizationStoreOperations::new(connection);
+            let existed = operations.get_role(role.id())?.is_some();
+            operations.upsert_role(role.clone())?;
+            Ok(existed)
+        })?;
+        self.notify_subscribers(if existed {
+            RbacChangeEvent::RoleUpdated(role)
+        } else {
+            RbacChangeEvent::RoleAdded(role)
+        });
+        Ok(())
     }
 
     /// Removes a role.
@@ -135,9 +262,12 @@ impl RoleBasedAuthorizationStore
                 )),
             ));
         }
+        let event = RbacChangeEvent::RoleRemoved(role_id.to_string());
         self.connection_pool.execute_write(|connection| {
             RoleBasedAuthorizationStoreOperations::new(connection).remove_role(role_id)
-        })
+        })?;
+        self.notify_subscribers(event);
+        Ok(())
     }
 
     /// Returns the role for the given Identity, if one exists.
@@ -160,6 +290,18 @@ impl RoleBasedAuthorizationStore
         })
     }
 
+    /// Returns the deduplicated set of permissions granted by all of the roles assigned to the
+    /// given Identity.
+    fn get_effective_permissions(
+        &self,
+        identity: &Identity,
+    ) -> Result<HashSet<String>, RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection)
+                .get_effective_permissions(identity)
+        })
+    }
+
     /// Lists all assignments.
     fn list_assignments(
         &self,
@@ -170,6 +312,15 @@ impl RoleBasedAuthorizationStore
         })
     }
 
+    /// Lists the identities whose assignment has no roles.
+    fn list_empty_assignments(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Identity>>, RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection).list_empty_assignments()
+        })
+    }
+
     /// Adds an assignment.
     ///
     /// # Errors
@@ -180,9 +331,12 @@ impl RoleBasedAuthorizationStore
         &self,
         assignment: Assignment,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let event = RbacChangeEvent::AssignmentAdded(assignment.clone());
         self.connection_pool.execute_write(|connection| {
             RoleBasedAuthorizationStoreOperations::new(connection).add_assignment(assignment)
-        })
+        })?;
+        self.notify_subscribers(event);
+        Ok(())
     }
 
     /// Updates an assignment.
@@ -194,9 +348,31 @@ impl RoleBasedAuthorizationStore
         &self,
         assignment: Assignment,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let event = RbacChangeEvent::AssignmentUpdated(assignment.clone());
         self.connection_pool.execute_write(|connection| {
             RoleBasedAuthorizationStoreOperations::new(connection).update_assignment(assignment)
-        })
+        })?;
+        self.notify_subscribers(event);
+        Ok(())
+    }
+
+    /// Adds or replaces an assignment.
+    fn upsert_assignment(
+        &self,
+        assignment: Assignment,
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let existed = self.connection_pool.execute_write(|connection| {
+            let operations = RoleBasedAuthorizationStoreOperations::new(connection);
+            let existed = operations.get_assignment(assignment.identity())?.is_some();
+            operations.upsert_assignment(assignment.clone())?;
+            Ok(existed)
+        })?;
+        self.notify_subscribers(if existed {
+            RbacChangeEvent::AssignmentUpdated(assignment)
+        } else {
+            RbacChangeEvent::AssignmentAdded(assignment)
+        });
+        Ok(())
     }
 
     /// Removes an assignment.
@@ -208,15 +384,88 @@ impl RoleBasedAuthorizationStore
         &self,
         identity: &Identity,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let event = RbacChangeEvent::AssignmentRemoved(identity.clone());
         self.connection_pool.execute_write(|connection| {
             RoleBasedAuthorizationStoreOperations::new(connection).remove_assignment(identity)
+        })?;
+        self.notify_subscribers(event);
+        Ok(())
+    }
+
+    /// Removes the assignments that have no roles.
+    fn remove_empty_assignments(&self) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let removed = self.connection_pool.execute_write(|connection| {
+            let operations = RoleBasedAuthorizationStoreOperations::new(connection);
+            let removed = operations.list_empty_assignments()?.collect::<Vec<_>>();
+            operations.remove_empty_assignments()?;
+            Ok(removed)
+        })?;
+        for identity in removed {
+            self.notify_subscribers(RbacChangeEvent::AssignmentRemoved(identity));
+        }
+        Ok(())
+    }
+
+    /// Removes all of the assignments that have expired as of the given time.
+    fn remove_expired_assignments(
+        &self,
+        now: SystemTime,
+    ) -> Result<u64, RoleBasedAuthorizationStoreError> {
+        let (count, removed) = self.connection_pool.execute_write(|connection| {
+            let operations = RoleBasedAuthorizationStoreOperations::new(connection);
+            let removed = operations
+                .list_assignments()?
+                .filter(|assignment| assignment.is_expired(now))
+                .map(|assignment| assignment.identity().clone())
+                .collect::<Vec<_>>();
+            let count = operations.remove_expired_assignments(now)?;
+            Ok((count, removed))
+        })?;
+        for identity in removed {
+            self.notify_subscribers(RbacChangeEvent::AssignmentRemoved(identity));
+        }
+        Ok(count)
+    }
+
+    /// Exports all of the roles and assignments.
+    fn export_state(&self) -> Result<RbacSnapshot, RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection).export_state()
         })
     }
 
+    /// Imports the roles and assignments of a snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidState` error if a permission registry is set and a role in the snapshot
+    /// has an unknown permission.
+    fn import_state(
+        &self,
+        snapshot: RbacSnapshot,
+        mode: ImportMode,
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        for role in snapshot.roles() {
+            self.validate_permissions(role)?;
+        }
+        self.connection_pool.execute_write(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection).import_state(snapshot, mode)
+        })?;
+        self.notify_subscribers(RbacChangeEvent::StateImported(mode));
+        Ok(())
+    }
+
+    /// Subscribes to the changes made to the roles and assignments in the store.
+    fn subscribe(&self) -> Receiver<RbacChangeEvent> {
+        self.add_subscriber()
+    }
+
     /// Clone into a boxed, dynamically dispatched store
     fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore> {
         Box::new(DieselRoleBasedAuthorizationStore {
             connection_pool: self.connection_pool.clone(),
+            permission_registry: self.permission_registry.clone(),
+            subscribers: self.subscribers.clone(),
         })
     }
 }
@@ -239,15 +488,32 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
         })
     }
 
+    fn search_roles(
+        &self,
+        query: &str,
+        limit: u64,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection).search_roles(query, limit)
+        })
+    }
+
     /// Adds a role.
     ///
     /// # Errors
     ///
     /// Returns a `ConstraintViolation` error if a duplicate role ID is added.
+    ///
+    /// Returns an `InvalidState` error if a permission registry is set and the role has an
+    /// unknown permission.
     fn add_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
+        self.validate_permissions(&role)?;
+        let event = RbacChangeEvent::RoleAdded(role.clone());
         self.connection_pool.execute_write(|connection| {
             RoleBasedAuthorizationStoreOperations::new(connection).add_role(role)
-        })
+        })?;
+        self.notify_subscribers(event);
+        Ok(())
     }
 
     /// Updates a role.
@@ -255,6 +521,9 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
     /// # Errors
     ///
     /// Returns a `InvalidState` error if the role does not exist.
+    ///
+    /// Returns an `InvalidState` error if a permission registry is set and the role has an
+    /// unknown permission.
     fn update_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
         if role.id() == ADMIN_ROLE_ID {
             return Err(RoleBasedAuthorizationStoreError::ConstraintViolation(
@@ -263,9 +532,44 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
                 )),
             ));
         }
+        self.validate_permissions(&role)?;
+        let event = RbacChangeEvent::RoleUpdated(role.clone());
         self.connection_pool.execute_write(|connection| {
             RoleBasedAuthorizationStoreOperations::new(connection).update_role(role)
-        })
+        })?;
+        self.notify_subscribers(event);
+        Ok(())
+    }
+
+    /// Adds or replaces a role.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConstraintViolation` error if the role is the `admin` role.
+    ///
+    /// Returns an `InvalidState` error if a permission registry is set and the role has an
+    /// unknown permission.
+    fn upsert_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
+        if role.id() == ADMIN_ROLE_ID {
+            return Err(RoleBasedAuthorizationStoreError::ConstraintViolation(
+                ConstraintViolationError::with_violation_type(ConstraintViolationType::Other(
+                    format!("'{}' role cannot be altered", ADMIN_ROLE_ID),
+                )),
+            ));
+        }
+        self.validate_permissions(&role)?;
+        let existed = self.connection_pool.execute_write(|connection| {
+            let operations = RoleBasedAuthorizationStoreOperations::new(connection);
+            let existed = operations.get_role(role.id())?.is_some();
+            operations.upsert_role(role.clone())?;
+            Ok(existed)
+        })?;
+        self.notify_subscribers(if existed {
+            RbacChangeEvent::RoleUpdated(role)
+        } else {
+            RbacChangeEvent::RoleAdded(role)
+        });
+        Ok(())
     }
 
     /// Removes a role.
@@ -281,9 +585,12 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
                 )),
             ));
         }
+        let event = RbacChangeEvent::RoleRemoved(role_id.to_string());
         self.connection_pool.execute_write(|connection| {
             RoleBasedAuthorizationStoreOperations::new(connection).remove_role(role_id)
-        })
+        })?;
+        self.notify_subscribers(event);
+        Ok(())
     }
 
     /// Returns the role for the given Identity, if one exists.
@@ -306,6 +613,18 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
         })
     }
 
+    /// Returns the deduplicated set of permissions granted by all of the roles assigned to the
+    /// given Identity.
+    fn get_effective_permissions(
+        &self,
+        identity: &Identity,
+    ) -> Result<HashSet<String>, RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection)
+                .get_effective_permissions(identity)
+        })
+    }
+
     /// Lists all assignments.
     fn list_assignments(
         &self,
@@ -316,6 +635,15 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
         })
     }
 
+    /// Lists the identities whose assignment has no roles.
+    fn list_empty_assignments(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Identity>>, RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection).list_empty_assignments()
+        })
+    }
+
     /// Adds an assignment.
     ///
     /// # Errors
@@ -326,9 +654,12 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
         &self,
         assignment: Assignment,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let event = RbacChangeEvent::AssignmentAdded(assignment.clone());
         self.connection_pool.execute_write(|connection| {
             RoleBasedAuthorizationStoreOperations::new(connection).add_assignment(assignment)
-        })
+        })?;
+        self.notify_subscribers(event);
+        Ok(())
     }
 
     /// Updates an assignment.
@@ -340,9 +671,31 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
         &self,
         assignment: Assignment,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let event = RbacChangeEvent::AssignmentUpdated(assignment.clone());
         self.connection_pool.execute_write(|connection| {
             RoleBasedAuthorizationStoreOperations::new(connection).update_assignment(assignment)
-        })
+        })?;
+        self.notify_subscribers(event);
+        Ok(())
+    }
+
+    /// Adds or replaces an assignment.
+    fn upsert_assignment(
+        &self,
+        assignment: Assignment,
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let existed = self.connection_pool.execute_write(|connection| {
+            let operations = RoleBasedAuthorizationStoreOperations::new(connection);
+            let existed = operations.get_assignment(assignment.identity())?.is_some();
+            operations.upsert_assignment(assignment.clone())?;
+            Ok(existed)
+        })?;
+        self.notify_subscribers(if existed {
+            RbacChangeEvent::AssignmentUpdated(assignment)
+        } else {
+            RbacChangeEvent::AssignmentAdded(assignment)
+        });
+        Ok(())
     }
 
     /// Removes an assignment.
@@ -354,15 +707,88 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
         &self,
         identity: &Identity,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let event = RbacChangeEvent::AssignmentRemoved(identity.clone());
         self.connection_pool.execute_write(|connection| {
             RoleBasedAuthorizationStoreOperations::new(connection).remove_assignment(identity)
+        })?;
+        self.notify_subscribers(event);
+        Ok(())
+    }
+
+    /// Removes the assignments that have no roles.
+    fn remove_empty_assignments(&self) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let removed = self.connection_pool.execute_write(|connection| {
+            let operations = RoleBasedAuthorizationStoreOperations::new(connection);
+            let removed = operations.list_empty_assignments()?.collect::<Vec<_>>();
+            operations.remove_empty_assignments()?;
+            Ok(removed)
+        })?;
+        for identity in removed {
+            self.notify_subscribers(RbacChangeEvent::AssignmentRemoved(identity));
+        }
+        Ok(())
+    }
+
+    /// Removes all of the assignments that have expired as of the given time.
+    fn remove_expired_assignments(
+        &self,
+        now: SystemTime,
+    ) -> Result<u64, RoleBasedAuthorizationStoreError> {
+        let (count, removed) = self.connection_pool.execute_write(|connection| {
+            let operations = RoleBasedAuthorizationStoreOperations::new(connection);
+            let removed = operations
+                .list_assignments()?
+                .filter(|assignment| assignment.is_expired(now))
+                .map(|assignment| assignment.identity().clone())
+                .collect::<Vec<_>>();
+            let count = operations.remove_expired_assignments(now)?;
+            Ok((count, removed))
+        })?;
+        for identity in removed {
+            self.notify_subscribers(RbacChangeEvent::AssignmentRemoved(identity));
+        }
+        Ok(count)
+    }
+
+    /// Exports all of the roles and assignments.
+    fn export_state(&self) -> Result<RbacSnapshot, RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection).export_state()
         })
     }
 
+    /// Imports the roles and assignments of a snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidState` error if a permission registry is set and a role in the snapshot
+    /// has an unknown permission.
+    fn import_state(
+        &self,
+        snapshot: RbacSnapshot,
+        mode: ImportMode,
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        for role in snapshot.roles() {
+            self.validate_permissions(role)?;
+        }
+        self.connection_pool.execute_write(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection).import_state(snapshot, mode)
+        })?;
+        self.notify_subscribers(RbacChangeEvent::StateImported(mode));
+        Ok(())
+    }
+
+    /// Subscribes to the changes made to the roles and assignments in the store.
+    fn subscribe(&self) -> Receiver<RbacChangeEvent> {
+        self.add_subscriber()
+    }
+
     /// Clone into a boxed, dynamically dispatched store
     fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore> {
         Box::new(DieselRoleBasedAuthorizationStore {
             connection_pool: self.connection_pool.clone(),
+            permission_registry: self.permission_registry.clone(),
+            subscribers: self.subscribers.clone(),
         })
     }
 }
@@ -403,16 +829,23 @@ impl TryFrom<(models::RoleModel, Vec<models::RolePermissionModel>)> for Role {
 
 impl From<Assignment> for (models::IdentityModel, Vec<models::AssignmentModel>) {
     fn from(assignment: Assignment) -> Self {
-        let (identity, roles) = assignment.into_parts();
+        let Assignment {
+            identity,
+            roles,
+            expires_at,
+        } = assignment;
+        let expires_at = expires_at.map(models::secs_from_system_time);
 
         let identity_model = match identity {
             Identity::Key(identity) => models::IdentityModel {
                 identity,
                 identity_type: models::IdentityModelType::Key,
+                expires_at,
             },
             Identity::User(identity) => models::IdentityModel {
                 identity,
                 identity_type: models::IdentityModelType::User,
+                expires_at,
             },
         };
 
@@ -437,6 +870,7 @@ impl TryFrom<(models::IdentityModel, Vec<models::AssignmentModel>)> for Assignme
         let models::IdentityModel {
             identity,
             identity_type,
+            expires_at,
         } = identity_model;
         let identity = match identity_type {
             models::IdentityModelType::Key => Identity::Key(identity),
@@ -452,6 +886,7 @@ impl TryFrom<(models::IdentityModel, Vec<models::AssignmentModel>)> for Assignme
                 .into_iter()
                 .map(|models::AssignmentModel { role_id, .. }| role_id)
                 .collect(),
+            expires_at: expires_at.map(models::system_time_from_secs).transpose()?,
         })
     }
 }
@@ -497,6 +932,8 @@ impl From<diesel::r2d2::PoolError> for RoleBasedAuthorizationStoreError {
 mod tests {
     use super::*;
 
+    use std::time::{Duration, UNIX_EPOCH};
+
     use crate::rest_api::auth::authorization::rbac::store::{AssignmentBuilder, RoleBuilder};
 
     use crate::store::sqlite::create_sqlite_connection_pool;
@@ -612,6 +1049,57 @@ mod tests {
         );
     }
 
+    /// This tests verifies the following:
+    /// 1. Adds several roles via the store API
+    /// 2. Verifies a query matches role IDs and display names, ignoring case, ordered by ID
+    /// 3. Verifies the number of roles returned is limited
+    /// 4. Verifies `_` and `%` in a query are matched literally rather than as wildcards
+    #[test]
+    fn sqlite_search_roles() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
+
+        for (id, display_name) in &[
+            ("circuit-writer", "Circuit Writer"),
+            ("circuit-reader", "Circuit Reader"),
+            ("registry_reader", "Registry Reader"),
+            ("registryxreader", "Registry X Reader"),
+            ("full-access", "100% Access"),
+        ] {
+            let role = RoleBuilder::new()
+                .with_id(id.to_string())
+                .with_display_name(display_name.to_string())
+                .with_permissions(vec!["a".to_string()])
+                .build()
+                .expect("Unable to build role");
+
+            role_based_auth_store
+                .add_role(role)
+                .expect("Unable to add role");
+        }
+
+        let search = |query: &str, limit: u64| -> Vec<String> {
+            role_based_auth_store
+                .search_roles(query, limit)
+                .expect("Unable to search roles")
+                .map(|role| role.id().to_string())
+                .collect()
+        };
+
+        assert_eq!(
+            vec!["circuit-reader".to_string(), "circuit-writer".to_string()],
+            search("CIRC", 10)
+        );
+        assert_eq!(vec!["circuit-reader".to_string()], search("circ", 1));
+        // Only the display name contains a space
+        assert_eq!(vec!["circuit-writer".to_string()], search("circuit w", 10));
+        assert_eq!(vec!["registry_reader".to_string()], search("registry_", 10));
+        assert_eq!(vec!["full-access".to_string()], search("0%", 10));
+        assert_eq!(vec!["full-access".to_string()], search("%", 10));
+        assert!(search("no-such-role", 10).is_empty());
+    }
+
     /// This tests verifies the following:
     /// 1. Adds a role and verifies that it has been inserted
     /// 2. Update the role and verifies that it has been changed, via the store API
@@ -861,6 +1349,69 @@ mod tests {
         );
     }
 
+    /// This test verifies the following:
+    /// 1. Adds two roles with overlapping permissions
+    /// 2. Adds an assignment for those roles
+    /// 3. Verifies the deduplicated union of the roles' permissions is returned via the
+    ///    get_effective_permissions API
+    /// 4. Verifies an identity without an assignment has no effective permissions
+    #[test]
+    fn sqlite_get_effective_permissions() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
+
+        let role = RoleBuilder::new()
+            .with_id("test-role-1".into())
+            .with_display_name("Test Role 1".into())
+            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let role = RoleBuilder::new()
+            .with_id("test-role-2".into())
+            .with_display_name("Test Role 2".into())
+            .with_permissions(vec!["b".to_string(), "c".to_string(), "d".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::User("some-user-id".into()))
+            .with_roles(vec!["test-role-1".to_string(), "test-role-2".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+
+        role_based_auth_store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let permissions = role_based_auth_store
+            .get_effective_permissions(&Identity::User("some-user-id".into()))
+            .expect("Unable to get effective permissions");
+
+        assert_eq!(
+            vec!["a", "b", "c", "d"]
+                .into_iter()
+                .map(String::from)
+                .collect::<HashSet<_>>(),
+            permissions
+        );
+
+        let permissions = role_based_auth_store
+            .get_effective_permissions(&Identity::User("other-user-id".into()))
+            .expect("Unable to get effective permissions");
+
+        assert!(permissions.is_empty());
+    }
+
     /// This test verifies the following:
     /// 1. Adds a role.
     /// 2. Add two assignments for that role
@@ -1204,21 +1755,710 @@ mod tests {
         assert!(stored_assignment.roles().is_empty());
     }
 
-    /// This tests verifies that the `admin` role is present by default and cannot be removed or
-    /// modified
+    /// This test verifies the following:
+    /// 1. Add a role and two assignments, one with the role and one with another role
+    /// 2. Remove the role, leaving one assignment without roles
+    /// 3. Verify that only the identity of the empty assignment is listed
+    /// 4. Remove the empty assignments
+    /// 5. Verify that the empty assignment was removed and the other assignment remains
     #[test]
-    fn sqlite_admin_role() {
+    fn sqlite_list_and_remove_empty_assignments() {
         let pool = create_connection_pool_and_migrate();
 
         let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
 
-        let role = role_based_auth_store
-            .get_role(ADMIN_ROLE_ID)
-            .expect("Unable to lookup role by id")
-            .expect("Role not found");
-        assert_eq!(ADMIN_ROLE_ID, role.id());
-        assert!(role_based_auth_store.update_role(role).is_err());
-        assert!(role_based_auth_store.remove_role(ADMIN_ROLE_ID).is_err());
+        for role_id in &["test-role", "other-role"] {
+            let role = RoleBuilder::new()
+                .with_id(role_id.to_string())
+                .with_display_name("Test Role".into())
+                .with_permissions(vec!["a".to_string()])
+                .build()
+                .expect("Unable to build role");
+
+            role_based_auth_store
+                .add_role(role)
+                .expect("Unable to add role");
+        }
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::User("some-user-id".into()))
+            .with_roles(vec!["test-role".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+        role_based_auth_store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let other_assignment = AssignmentBuilder::new()
+            .with_identity(Identity::Key("some-key".into()))
+            .with_roles(vec!["other-role".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+        role_based_auth_store
+            .add_assignment(other_assignment)
+            .expect("Unable to add assignment");
+
+        assert_eq!(
+            0,
+            role_based_auth_store
+                .list_empty_assignments()
+                .expect("Unable to list empty assignments")
+                .len()
+        );
+
+        role_based_auth_store
+            .remove_role("test-role")
+            .expect("Unable to remove role");
+
+        let empty_assignments = role_based_auth_store
+            .list_empty_assignments()
+            .expect("Unable to list empty assignments")
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![Identity::User("some-user-id".into())],
+            empty_assignments
+        );
+
+        role_based_auth_store
+            .remove_empty_assignments()
+            .expect("Unable to remove empty assignments");
+
+        assert_eq!(
+            0,
+            role_based_auth_store
+                .list_empty_assignments()
+                .expect("Unable to list empty assignments")
+                .len()
+        );
+        assert!(role_based_auth_store
+            .get_assignment(&Identity::User("some-user-id".into()))
+            .expect("Unable to get assignment")
+            .is_none());
+        let stored_assignment = role_based_auth_store
+            .get_assignment(&Identity::Key("some-key".into()))
+            .expect("Unable to get assignment")
+            .expect("Assignment was not found");
+        assert_eq!(&vec!["other-role".to_string()], stored_assignment.roles());
+    }
+
+    /// This tests verifies the following:
+    /// 1. Upserts a role that does not exist and verifies that it has been added
+    /// 2. Upserts the same role with a new display name and permissions
+    /// 3. Verifies that the stored role matches the last upsert
+    #[test]
+    fn sqlite_upsert_role() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
+
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .upsert_role(role)
+            .expect("Unable to upsert role");
+
+        let stored_role = role_based_auth_store
+            .get_role("test-role")
+            .expect("Unable to lookup role by id")
+            .expect("Did not find the upserted role");
+
+        assert_eq!("Test Role", stored_role.display_name());
+        assert_eq!(
+            &["a".to_string(), "b".to_string(), "c".to_string()],
+            stored_role.permissions()
+        );
+
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Updated Test Role".into())
+            .with_permissions(vec!["x".to_string(), "y".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .upsert_role(role)
+            .expect("Unable to upsert role");
+
+        let stored_role = role_based_auth_store
+            .get_role("test-role")
+            .expect("Unable to lookup role by id")
+            .expect("Did not find the upserted role");
+
+        assert_eq!("test-role", stored_role.id());
+        assert_eq!("Updated Test Role", stored_role.display_name());
+        assert_eq!(
+            &["x".to_string(), "y".to_string()],
+            stored_role.permissions()
+        );
+
+        assert_eq!(
+            2,
+            role_based_auth_store
+                .list_roles()
+                .expect("Unable to list roles")
+                .len()
+        );
+    }
+
+    /// This tests verifies the following:
+    /// 1. Adds two roles
+    /// 2. Upserts an assignment that does not exist and verifies that it has been added
+    /// 3. Upserts the same assignment with a different role
+    /// 4. Verifies that the stored assignment matches the last upsert
+    #[test]
+    fn sqlite_upsert_assignment() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
+
+        for role_id in &["test-role-1", "test-role-2"] {
+            let role = RoleBuilder::new()
+                .with_id(role_id.to_string())
+                .with_display_name("Test Role".into())
+                .with_permissions(vec!["a".to_string()])
+                .build()
+                .expect("Unable to build role");
+
+            role_based_auth_store
+                .add_role(role)
+                .expect("Unable to add role");
+        }
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::User("some-user-id".into()))
+            .with_roles(vec!["test-role-1".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+
+        role_based_auth_store
+            .upsert_assignment(assignment)
+            .expect("Unable to upsert assignment");
+
+        let stored_assignment = role_based_auth_store
+            .get_assignment(&Identity::User("some-user-id".into()))
+            .expect("Unable to get assignment")
+            .expect("Assignment was not found");
+        assert_eq!(&vec!["test-role-1".to_string()], stored_assignment.roles());
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::User("some-user-id".into()))
+            .with_roles(vec!["test-role-2".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+
+        role_based_auth_store
+            .upsert_assignment(assignment)
+            .expect("Unable to upsert assignment");
+
+        let stored_assignment = role_based_auth_store
+            .get_assignment(&Identity::User("some-user-id".into()))
+            .expect("Unable to get assignment")
+            .expect("Assignment was not found");
+        assert_eq!(
+            &Identity::User("some-user-id".into()),
+            stored_assignment.identity()
+        );
+        assert_eq!(&vec!["test-role-2".to_string()], stored_assignment.roles());
+
+        assert_eq!(
+            1,
+            role_based_auth_store
+                .list_assignments()
+                .expect("Unable to list assignments")
+                .len()
+        );
+    }
+
+    /// This tests verifies that, with a permission registry set, a role with only known
+    /// permissions and the `*` wildcard can be added and updated.
+    #[test]
+    fn sqlite_add_role_with_known_permissions() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool)
+            .with_permission_registry(vec!["a".to_string(), "b".to_string()]);
+
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string(), "b".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string(), "*".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .update_role(role)
+            .expect("Unable to update role");
+
+        let stored_role = role_based_auth_store
+            .get_role("test-role")
+            .expect("Unable to lookup role by id")
+            .expect("Did not find the added role");
+        assert_eq!(
+            &["a".to_string(), "*".to_string()],
+            stored_role.permissions()
+        );
+    }
+
+    /// This tests verifies that, with a permission registry set, a role with an unknown
+    /// permission is rejected with an `InvalidState` error and is not stored.
+    #[test]
+    fn sqlite_add_role_with_unknown_permission() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool)
+            .with_permission_registry(vec!["a".to_string(), "b".to_string()]);
+
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string(), "c".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        assert!(matches!(
+            role_based_auth_store.add_role(role.clone()),
+            Err(RoleBasedAuthorizationStoreError::InvalidState(_))
+        ));
+        assert!(matches!(
+            role_based_auth_store.update_role(role),
+            Err(RoleBasedAuthorizationStoreError::InvalidState(_))
+        ));
+
+        assert!(role_based_auth_store
+            .get_role("test-role")
+            .expect("Unable to lookup role by id")
+            .is_none());
+    }
+
+    /// This tests verifies that the `admin` role is present by default and cannot be removed or
+    /// modified
+    #[test]
+    fn sqlite_admin_role() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
+
+        let role = role_based_auth_store
+            .get_role(ADMIN_ROLE_ID)
+            .expect("Unable to lookup role by id")
+            .expect("Role not found");
+        assert_eq!(ADMIN_ROLE_ID, role.id());
+        assert!(role_based_auth_store.update_role(role.clone()).is_err());
+        assert!(role_based_auth_store.upsert_role(role).is_err());
+        assert!(role_based_auth_store.remove_role(ADMIN_ROLE_ID).is_err());
+    }
+
+    /// This test verifies the following:
+    /// 1. Add two roles and two assignments to a store
+    /// 2. Export the state of the store
+    /// 3. Import the snapshot into a fresh store, replacing its state
+    /// 4. Verify that the state exported from the fresh store equals the snapshot
+    /// 5. Verify that the snapshot survives a round-trip through JSON
+    #[test]
+    fn sqlite_export_and_import_state() {
+        let source_store =
+            DieselRoleBasedAuthorizationStore::new(create_connection_pool_and_migrate());
+
+        source_store
+            .add_role(
+                RoleBuilder::new()
+                    .with_id("test-role-1".into())
+                    .with_display_name("Test Role 1".into())
+                    .with_permissions(vec!["a".to_string(), "b".to_string()])
+                    .build()
+                    .expect("Unable to build role"),
+            )
+            .expect("Unable to add role");
+        source_store
+            .add_role(
+                RoleBuilder::new()
+                    .with_id("test-role-2".into())
+                    .with_display_name("Test Role 2".into())
+                    .with_permissions(vec!["c".to_string()])
+                    .build()
+                    .expect("Unable to build role"),
+            )
+            .expect("Unable to add role");
+        source_store
+            .add_assignment(
+                AssignmentBuilder::new()
+                    .with_identity(Identity::Key("some-key".into()))
+                    .with_roles(vec![ADMIN_ROLE_ID.to_string(), "test-role-1".to_string()])
+                    .build()
+                    .expect("Unable to build assignment"),
+            )
+            .expect("Unable to add assignment");
+        source_store
+            .add_assignment(
+                AssignmentBuilder::new()
+                    .with_identity(Identity::User("some-user".into()))
+                    .with_roles(vec!["test-role-2".to_string()])
+                    .build()
+                    .expect("Unable to build assignment"),
+            )
+            .expect("Unable to add assignment");
+
+        let snapshot = source_store.export_state().expect("Unable to export state");
+        assert_eq!(3, snapshot.roles().len());
+        assert_eq!(2, snapshot.assignments().len());
+
+        let target_store =
+            DieselRoleBasedAuthorizationStore::new(create_connection_pool_and_migrate());
+        target_store
+            .import_state(snapshot.clone(), ImportMode::Replace)
+            .expect("Unable to import state");
+
+        assert_eq!(
+            snapshot,
+            target_store.export_state().expect("Unable to export state")
+        );
+
+        let json = serde_json::to_string(&snapshot).expect("Unable to serialize snapshot");
+        assert_eq!(
+            snapshot,
+            serde_json::from_str::<RbacSnapshot>(&json).expect("Unable to deserialize snapshot")
+        );
+    }
+
+    /// This test verifies the following:
+    /// 1. Add a role to a store
+    /// 2. Merge a snapshot with an altered admin role and another role into the store
+    /// 3. Verify that the admin role is unchanged, and that both other roles exist
+    /// 4. Replace the state of the store with the same snapshot
+    /// 5. Verify that the admin role is unchanged, and only the role of the snapshot remains
+    #[test]
+    fn sqlite_import_state_protects_admin_role() {
+        let store = DieselRoleBasedAuthorizationStore::new(create_connection_pool_and_migrate());
+
+        let admin_role = store
+            .get_role(ADMIN_ROLE_ID)
+            .expect("Unable to lookup role by id")
+            .expect("Role not found");
+
+        store
+            .add_role(
+                RoleBuilder::new()
+                    .with_id("existing-role".into())
+                    .with_display_name("Existing Role".into())
+                    .with_permissions(vec!["a".to_string()])
+                    .build()
+                    .expect("Unable to build role"),
+            )
+            .expect("Unable to add role");
+
+        let snapshot = RbacSnapshot::new(
+            vec![
+                RoleBuilder::new()
+                    .with_id(ADMIN_ROLE_ID.into())
+                    .with_display_name("Altered Admin".into())
+                    .with_permissions(vec!["b".to_string()])
+                    .build()
+                    .expect("Unable to build role"),
+                RoleBuilder::new()
+                    .with_id("imported-role".into())
+                    .with_display_name("Imported Role".into())
+                    .with_permissions(vec!["c".to_string()])
+                    .build()
+                    .expect("Unable to build role"),
+            ],
+            vec![],
+        );
+
+        store
+            .import_state(snapshot.clone(), ImportMode::Merge)
+            .expect("Unable to import state");
+
+        assert_eq!(
+            Some(admin_role.clone()),
+            store
+                .get_role(ADMIN_ROLE_ID)
+                .expect("Unable to lookup role by id")
+        );
+        assert!(store
+            .get_role("existing-role")
+            .expect("Unable to lookup role by id")
+            .is_some());
+        assert!(store
+            .get_role("imported-role")
+            .expect("Unable to lookup role by id")
+            .is_some());
+
+        store
+            .import_state(snapshot, ImportMode::Replace)
+            .expect("Unable to import state");
+
+        assert_eq!(
+            Some(admin_role),
+            store
+                .get_role(ADMIN_ROLE_ID)
+                .expect("Unable to lookup role by id")
+        );
+        assert!(store
+            .get_role("existing-role")
+            .expect("Unable to lookup role by id")
+            .is_none());
+        assert!(store
+            .get_role("imported-role")
+            .expect("Unable to lookup role by id")
+            .is_some());
+    }
+
+    /// This test verifies the following:
+    /// 1. Adds a role.
+    /// 2. Adds an assignment expiring in the future and one that has already expired.
+    /// 3. Verifies the unexpired assignment, its roles and its permissions are returned, along
+    ///    with its expiry.
+    /// 4. Verifies the expired assignment, its roles and its permissions are not returned.
+    /// 5. Verifies both assignments are still listed until they are removed.
+    #[test]
+    fn sqlite_assignment_expiry() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
+
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string(), "b".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .add_role(role.clone())
+            .expect("Unable to add role");
+
+        // Expiry times are stored with a precision of one second
+        let now = UNIX_EPOCH
+            + Duration::from_secs(
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("Unable to get time")
+                    .as_secs(),
+            );
+
+        let unexpired = AssignmentBuilder::new()
+            .with_identity(Identity::User("contractor".into()))
+            .with_roles(vec!["test-role".to_string()])
+            .with_expires_at(now + Duration::from_secs(3600))
+            .build()
+            .expect("Unable to build assignment");
+
+        role_based_auth_store
+            .add_assignment(unexpired.clone())
+            .expect("Unable to add assignment");
+
+        let expired = AssignmentBuilder::new()
+            .with_identity(Identity::User("former-contractor".into()))
+            .with_roles(vec!["test-role".to_string()])
+            .with_expires_at(now - Duration::from_secs(3600))
+            .build()
+            .expect("Unable to build assignment");
+
+        role_based_auth_store
+            .add_assignment(expired)
+            .expect("Unable to add assignment");
+
+        let identity = Identity::User("contractor".into());
+        assert_eq!(
+            Some(unexpired),
+            role_based_auth_store
+                .get_assignment(&identity)
+                .expect("Unable to get assignment")
+        );
+        assert_eq!(
+            vec![role],
+            role_based_auth_store
+                .get_assigned_roles(&identity)
+                .expect("Unable to get assigned roles")
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["a", "b"]
+                .into_iter()
+                .map(String::from)
+                .collect::<HashSet<_>>(),
+            role_based_auth_store
+                .get_effective_permissions(&identity)
+                .expect("Unable to get effective permissions")
+        );
+
+        let identity = Identity::User("former-contractor".into());
+        assert!(role_based_auth_store
+            .get_assignment(&identity)
+            .expect("Unable to get assignment")
+            .is_none());
+        assert_eq!(
+            0,
+            role_based_auth_store
+                .get_assigned_roles(&identity)
+                .expect("Unable to get assigned roles")
+                .len()
+        );
+        assert!(role_based_auth_store
+            .get_effective_permissions(&identity)
+            .expect("Unable to get effective permissions")
+            .is_empty());
+
+        assert_eq!(
+            2,
+            role_based_auth_store
+                .list_assignments()
+                .expect("Unable to list assignments")
+                .len()
+        );
+    }
+
+    /// This test verifies the following:
+    /// 1. Adds a role.
+    /// 2. Adds an assignment without an expiry, one expiring in the future and one that has
+    ///    already expired.
+    /// 3. Removes the expired assignments and verifies only one was removed.
+    /// 4. Verifies the remaining assignments are the ones that have not expired.
+    #[test]
+    fn sqlite_remove_expired_assignments() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
+
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let now = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+
+        let permanent = AssignmentBuilder::new()
+            .with_identity(Identity::User("employee".into()))
+            .with_roles(vec!["test-role".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+        let unexpired = AssignmentBuilder::new()
+            .with_identity(Identity::User("contractor".into()))
+            .with_roles(vec!["test-role".to_string()])
+            .with_expires_at(now + Duration::from_secs(60))
+            .build()
+            .expect("Unable to build assignment");
+        let expired = AssignmentBuilder::new()
+            .with_identity(Identity::Key("former-contractor-key".into()))
+            .with_roles(vec!["test-role".to_string()])
+            .with_expires_at(now - Duration::from_secs(60))
+            .build()
+            .expect("Unable to build assignment");
+
+        for assignment in vec![permanent.clone(), unexpired.clone(), expired] {
+            role_based_auth_store
+                .add_assignment(assignment)
+                .expect("Unable to add assignment");
+        }
+
+        assert_eq!(
+            1,
+            role_based_auth_store
+                .remove_expired_assignments(now)
+                .expect("Unable to remove expired assignments")
+        );
+
+        let mut assignments = role_based_auth_store
+            .list_assignments()
+            .expect("Unable to list assignments")
+            .collect::<Vec<_>>();
+        assignments.sort_by_key(|assignment| format!("{:?}", assignment.identity()));
+
+        assert_eq!(vec![unexpired, permanent], assignments);
+
+        assert_eq!(
+            0,
+            role_based_auth_store
+                .remove_expired_assignments(now)
+                .expect("Unable to remove expired assignments")
+        );
+    }
+
+    /// This test verifies the following:
+    /// 1. Subscribe to the changes of a store
+    /// 2. Through a clone of the store, add a role and an assignment, update the role, and remove
+    ///    the assignment
+    /// 3. Attempt to update a role that does not exist
+    /// 4. Verify that an event was received for each successful change, in order, and none for
+    ///    the failed update
+    #[test]
+    fn sqlite_subscribe() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
+        let changes = role_based_auth_store.subscribe();
+        let store = role_based_auth_store.clone_box();
+
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string()])
+            .build()
+            .expect("Unable to build role");
+        store.add_role(role.clone()).expect("Unable to add role");
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::User("some-user-id".into()))
+            .with_roles(vec!["test-role".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+        store
+            .add_assignment(assignment.clone())
+            .expect("Unable to add assignment");
+
+        let updated_role = role
+            .clone()
+            .into_update_builder()
+            .with_permissions(vec!["a".to_string(), "b".to_string()])
+            .build()
+            .expect("Unable to build role");
+        store
+            .update_role(updated_role.clone())
+            .expect("Unable to update role");
+
+        store
+            .remove_assignment(&Identity::User("some-user-id".into()))
+            .expect("Unable to remove assignment");
+
+        let unknown_role = RoleBuilder::new()
+            .with_id("unknown-role".into())
+            .with_display_name("Unknown Role".into())
+            .with_permissions(vec!["a".to_string()])
+            .build()
+            .expect("Unable to build role");
+        assert!(store.update_role(unknown_role).is_err());
+
+        assert_eq!(
+            vec![
+                RbacChangeEvent::RoleAdded(role),
+                RbacChangeEvent::AssignmentAdded(assignment),
+                RbacChangeEvent::RoleUpdated(updated_role),
+                RbacChangeEvent::AssignmentRemoved(Identity::User("some-user-id".into())),
+            ],
+            changes.try_iter().collect::<Vec<_>>()
+        );
     }
 
     /// Creates a connection pool for an in-memory SQLite database with only a single connection
@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::convert::TryFrom;
 use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use diesel::{
     backend::Backend,
@@ -35,6 +37,8 @@ use diesel::pg::Pg;
 #[cfg(feature = "sqlite")]
 use diesel::sqlite::Sqlite;
 
+use crate::error::InvalidStateError;
+
 use super::schema::{rbac_assignments, rbac_identities, rbac_role_permissions, rbac_roles};
 
 #[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable)]
@@ -212,6 +216,8 @@ impl HasSqlType<IdentityModelTypeMapping> for Sqlite {
 pub(super) struct IdentityModel {
     pub identity: String,
     pub identity_type: IdentityModelType,
+    /// The expiry of the identity's assignment, in seconds since the Unix epoch
+    pub expires_at: Option<i64>,
 }
 
 #[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable)]
@@ -222,3 +228,25 @@ pub(super) struct AssignmentModel {
     pub identity: String,
     pub role_id: String,
 }
+
+/// Converts a `SystemTime` into seconds since the Unix epoch, for the `expires_at` column. Times
+/// before the Unix epoch are stored as the epoch.
+pub(super) fn secs_from_system_time(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Converts seconds since the Unix epoch, as stored in the `expires_at` column, into a
+/// `SystemTime`.
+pub(super) fn system_time_from_secs(secs: i64) -> Result<SystemTime, InvalidStateError> {
+    u64::try_from(secs)
+        .ok()
+        .and_then(|secs| UNIX_EPOCH.checked_add(Duration::from_secs(secs)))
+        .ok_or_else(|| {
+            InvalidStateError::with_message(format!(
+                "Timestamp {} could not be represented as a `SystemTime`",
+                secs
+            ))
+        })
+}
@@ -0,0 +1,41 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::rest_api::auth::authorization::rbac::store::{
+    RbacSnapshot, RoleBasedAuthorizationStoreError,
+};
+
+use super::{
+    list_assignments::RoleBasedAuthorizationStoreListAssignments,
+    list_roles::RoleBasedAuthorizationStoreListRoles, RoleBasedAuthorizationStoreOperations,
+};
+
+pub trait RoleBasedAuthorizationStoreExportState {
+    fn export_state(&self) -> Result<RbacSnapshot, RoleBasedAuthorizationStoreError>;
+}
+
+impl<'a, C> RoleBasedAuthorizationStoreExportState for RoleBasedAuthorizationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    Self: RoleBasedAuthorizationStoreListRoles + RoleBasedAuthorizationStoreListAssignments,
+{
+    fn export_state(&self) -> Result<RbacSnapshot, RoleBasedAuthorizationStoreError> {
+        self.conn.transaction::<_, _, _>(|| {
+            Ok(RbacSnapshot::new(
+                self.list_roles()?.collect(),
+                self.list_assignments()?.collect(),
+            ))
+        })
+    }
+}
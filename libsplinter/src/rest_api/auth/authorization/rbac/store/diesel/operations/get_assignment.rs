@@ -13,12 +13,16 @@
 // limitations under the License.
 
 use std::convert::TryInto;
+use std::time::SystemTime;
 
 use diesel::prelude::*;
 
 use crate::rest_api::auth::authorization::rbac::store::{
     diesel::{
-        models::{AssignmentModel, IdentityModel, IdentityModelType, IdentityModelTypeMapping},
+        models::{
+            secs_from_system_time, AssignmentModel, IdentityModel, IdentityModelType,
+            IdentityModelTypeMapping,
+        },
         schema::rbac_identities,
     },
     Assignment, Identity, RoleBasedAuthorizationStoreError,
@@ -39,6 +43,8 @@ where
     C: diesel::Connection,
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
     i16: diesel::deserialize::FromSql<diesel::sql_types::SmallInt, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    i64: diesel::serialize::ToSql<diesel::sql_types::BigInt, C::Backend>,
     <C as diesel::Connection>::Backend: diesel::types::HasSqlType<IdentityModelTypeMapping>,
     IdentityModelType: diesel::deserialize::FromSql<IdentityModelTypeMapping, C::Backend>,
 {
@@ -50,9 +56,15 @@ where
             Identity::Key(ref key) => key,
             Identity::User(ref user_id) => user_id,
         };
+        let now = secs_from_system_time(SystemTime::now());
         self.conn.transaction(|| {
             let identities = rbac_identities::table
                 .filter(rbac_identities::identity.eq(search_identity))
+                .filter(
+                    rbac_identities::expires_at
+                        .is_null()
+                        .or(rbac_identities::expires_at.gt(now)),
+                )
                 .load::<IdentityModel>(self.conn)?;
 
             let assignments = AssignmentModel::belonging_to(&identities)
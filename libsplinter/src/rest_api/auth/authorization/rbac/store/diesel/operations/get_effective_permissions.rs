@@ -0,0 +1,80 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::time::SystemTime;
+
+use diesel::prelude::*;
+
+use crate::rest_api::auth::authorization::rbac::store::{
+    diesel::{
+        models::{secs_from_system_time, IdentityModelTypeMapping},
+        schema::{rbac_assignments, rbac_identities, rbac_role_permissions},
+    },
+    Identity, RoleBasedAuthorizationStoreError,
+};
+
+use super::RoleBasedAuthorizationStoreOperations;
+
+pub trait RoleBasedAuthorizationStoreGetEffectivePermissions {
+    fn get_effective_permissions(
+        &self,
+        identity: &Identity,
+    ) -> Result<HashSet<String>, RoleBasedAuthorizationStoreError>;
+}
+
+impl<'a, C> RoleBasedAuthorizationStoreGetEffectivePermissions
+    for RoleBasedAuthorizationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::serialize::ToSql<diesel::sql_types::BigInt, C::Backend>,
+    <C as diesel::Connection>::Backend: diesel::types::HasSqlType<IdentityModelTypeMapping>,
+{
+    fn get_effective_permissions(
+        &self,
+        identity: &Identity,
+    ) -> Result<HashSet<String>, RoleBasedAuthorizationStoreError> {
+        let search_identity = match identity {
+            Identity::Key(ref key) => key,
+            Identity::User(ref user_id) => user_id,
+        };
+        let now = secs_from_system_time(SystemTime::now());
+        self.conn.transaction::<HashSet<String>, _, _>(|| {
+            // An expired assignment grants no permissions
+            let identities = rbac_identities::table
+                .filter(rbac_identities::identity.eq(search_identity))
+                .filter(
+                    rbac_identities::expires_at
+                        .is_null()
+                        .or(rbac_identities::expires_at.gt(now)),
+                )
+                .select(rbac_identities::identity)
+                .load::<String>(self.conn)?;
+
+            let role_ids = rbac_assignments::table
+                .filter(rbac_assignments::identity.eq_any(identities))
+                .select(rbac_assignments::role_id)
+                .load::<String>(self.conn)?;
+
+            Ok(rbac_role_permissions::table
+                .filter(rbac_role_permissions::role_id.eq_any(role_ids))
+                .select(rbac_role_permissions::permission)
+                .distinct()
+                .load::<String>(self.conn)?
+                .into_iter()
+                .collect())
+        })
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::rest_api::auth::authorization::rbac::store::{
+    diesel::{
+        models::IdentityModelTypeMapping,
+        schema::{rbac_assignments, rbac_identities, rbac_role_permissions, rbac_roles},
+    },
+    ImportMode, RbacSnapshot, RoleBasedAuthorizationStoreError, ADMIN_ROLE_ID,
+};
+
+use super::{
+    upsert_assignment::RoleBasedAuthorizationStoreUpsertAssignment,
+    upsert_role::RoleBasedAuthorizationStoreUpsertRole, RoleBasedAuthorizationStoreOperations,
+};
+
+pub trait RoleBasedAuthorizationStoreImportState {
+    fn import_state(
+        &self,
+        snapshot: RbacSnapshot,
+        mode: ImportMode,
+    ) -> Result<(), RoleBasedAuthorizationStoreError>;
+}
+
+impl<'a, C> RoleBasedAuthorizationStoreImportState for RoleBasedAuthorizationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    <C as diesel::Connection>::Backend: diesel::types::HasSqlType<IdentityModelTypeMapping>,
+    Self: RoleBasedAuthorizationStoreUpsertRole + RoleBasedAuthorizationStoreUpsertAssignment,
+{
+    fn import_state(
+        &self,
+        snapshot: RbacSnapshot,
+        mode: ImportMode,
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let (roles, assignments) = snapshot.into_parts();
+
+        self.conn.transaction::<_, _, _>(|| {
+            if mode == ImportMode::Replace {
+                delete(rbac_assignments::table).execute(self.conn)?;
+                delete(rbac_identities::table).execute(self.conn)?;
+                delete(
+                    rbac_role_permissions::table
+                        .filter(rbac_role_permissions::role_id.ne(ADMIN_ROLE_ID)),
+                )
+                .execute(self.conn)?;
+                delete(rbac_roles::table.filter(rbac_roles::id.ne(ADMIN_ROLE_ID)))
+                    .execute(self.conn)?;
+            }
+
+            let has_admin_role = rbac_roles::table
+                .find(ADMIN_ROLE_ID)
+                .select(rbac_roles::id)
+                .first::<String>(self.conn)
+                .optional()?
+                .is_some();
+
+            for role in roles {
+                // The existing admin role is never altered
+                if role.id() == ADMIN_ROLE_ID && has_admin_role {
+                    continue;
+                }
+                self.upsert_role(role)?;
+            }
+
+            for assignment in assignments {
+                self.upsert_assignment(assignment)?;
+            }
+
+            Ok(())
+        })
+    }
+}
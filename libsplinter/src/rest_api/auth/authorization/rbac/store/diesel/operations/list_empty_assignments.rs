@@ -0,0 +1,68 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{
+    dsl::{exists, not},
+    prelude::*,
+};
+
+use crate::rest_api::auth::authorization::rbac::store::{
+    diesel::{
+        models::{IdentityModel, IdentityModelType, IdentityModelTypeMapping},
+        schema::{rbac_assignments, rbac_identities},
+    },
+    Identity, RoleBasedAuthorizationStoreError,
+};
+
+use super::RoleBasedAuthorizationStoreOperations;
+
+pub trait RoleBasedAuthorizationStoreListEmptyAssignments {
+    fn list_empty_assignments(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Identity>>, RoleBasedAuthorizationStoreError>;
+}
+
+impl<'a, C> RoleBasedAuthorizationStoreListEmptyAssignments
+    for RoleBasedAuthorizationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    <C as diesel::Connection>::Backend: diesel::types::HasSqlType<IdentityModelTypeMapping>,
+    IdentityModelType: diesel::deserialize::FromSql<IdentityModelTypeMapping, C::Backend>,
+{
+    fn list_empty_assignments(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Identity>>, RoleBasedAuthorizationStoreError> {
+        let identities = rbac_identities::table
+            .filter(not(exists(rbac_assignments::table.filter(
+                rbac_assignments::identity.eq(rbac_identities::identity),
+            ))))
+            .load::<IdentityModel>(self.conn)?
+            .into_iter()
+            .map(
+                |IdentityModel {
+                     identity,
+                     identity_type,
+                     ..
+                 }| match identity_type {
+                    IdentityModelType::Key => Identity::Key(identity),
+                    IdentityModelType::User => Identity::User(identity),
+                },
+            )
+            .collect::<Vec<_>>();
+
+        Ok(Box::new(identities.into_iter()))
+    }
+}
@@ -14,15 +14,24 @@
 
 pub(super) mod add_assignment;
 pub(super) mod add_role;
+pub(super) mod export_state;
 pub(super) mod get_assigned_roles;
 pub(super) mod get_assignment;
+pub(super) mod get_effective_permissions;
 pub(super) mod get_role;
+pub(super) mod import_state;
 pub(super) mod list_assignments;
+pub(super) mod list_empty_assignments;
 pub(super) mod list_roles;
 pub(super) mod remove_assignment;
+pub(super) mod remove_empty_assignments;
+pub(super) mod remove_expired_assignments;
 pub(super) mod remove_role;
+pub(super) mod search_roles;
 pub(super) mod update_assignment;
 pub(super) mod update_role;
+pub(super) mod upsert_assignment;
+pub(super) mod upsert_role;
 
 pub(super) struct RoleBasedAuthorizationStoreOperations<'a, C> {
     conn: &'a C,
@@ -0,0 +1,52 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{
+    dsl::{delete, exists, not},
+    prelude::*,
+};
+
+use crate::rest_api::auth::authorization::rbac::store::{
+    diesel::{
+        models::IdentityModelTypeMapping,
+        schema::{rbac_assignments, rbac_identities},
+    },
+    RoleBasedAuthorizationStoreError,
+};
+
+use super::RoleBasedAuthorizationStoreOperations;
+
+pub trait RoleBasedAuthorizationStoreRemoveEmptyAssignments {
+    fn remove_empty_assignments(&self) -> Result<(), RoleBasedAuthorizationStoreError>;
+}
+
+impl<'a, C> RoleBasedAuthorizationStoreRemoveEmptyAssignments
+    for RoleBasedAuthorizationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    <C as diesel::Connection>::Backend: diesel::types::HasSqlType<IdentityModelTypeMapping>,
+{
+    fn remove_empty_assignments(&self) -> Result<(), RoleBasedAuthorizationStoreError> {
+        self.conn.transaction::<_, _, _>(|| {
+            delete(rbac_identities::table.filter(
+                not(exists(rbac_assignments::table.filter(
+                    rbac_assignments::identity.eq(rbac_identities::identity),
+                ))),
+            ))
+            .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::SystemTime;
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::rest_api::auth::authorization::rbac::store::{
+    diesel::{
+        models::{secs_from_system_time, IdentityModelTypeMapping},
+        schema::{rbac_assignments, rbac_identities},
+    },
+    RoleBasedAuthorizationStoreError,
+};
+
+use super::RoleBasedAuthorizationStoreOperations;
+
+pub trait RoleBasedAuthorizationStoreRemoveExpiredAssignments {
+    fn remove_expired_assignments(
+        &self,
+        now: SystemTime,
+    ) -> Result<u64, RoleBasedAuthorizationStoreError>;
+}
+
+impl<'a, C> RoleBasedAuthorizationStoreRemoveExpiredAssignments
+    for RoleBasedAuthorizationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::serialize::ToSql<diesel::sql_types::BigInt, C::Backend>,
+    <C as diesel::Connection>::Backend: diesel::types::HasSqlType<IdentityModelTypeMapping>,
+{
+    fn remove_expired_assignments(
+        &self,
+        now: SystemTime,
+    ) -> Result<u64, RoleBasedAuthorizationStoreError> {
+        let now = secs_from_system_time(now);
+        self.conn.transaction::<_, _, _>(|| {
+            let expired = rbac_identities::table
+                .filter(rbac_identities::expires_at.le(now))
+                .select(rbac_identities::identity)
+                .load::<String>(self.conn)?;
+
+            if expired.is_empty() {
+                return Ok(0);
+            }
+
+            delete(rbac_assignments::table.filter(rbac_assignments::identity.eq_any(&expired)))
+                .execute(self.conn)?;
+            let removed =
+                delete(rbac_identities::table.filter(rbac_identities::identity.eq_any(&expired)))
+                    .execute(self.conn)?;
+
+            Ok(removed as u64)
+        })
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::{TryFrom, TryInto};
+
+use diesel::prelude::*;
+
+use crate::rest_api::auth::authorization::rbac::store::{
+    diesel::{
+        models::{RoleModel, RolePermissionModel},
+        schema::rbac_roles,
+    },
+    Role, RoleBasedAuthorizationStoreError,
+};
+
+use super::RoleBasedAuthorizationStoreOperations;
+
+sql_function! {
+    /// Converts a string to lower case.
+    fn lower(x: diesel::sql_types::Text) -> diesel::sql_types::Text;
+}
+
+/// The escape character used in the `LIKE` pattern created by `contains_pattern`.
+const LIKE_ESCAPE: char = '\\';
+
+pub trait RoleBasedAuthorizationStoreSearchRoles {
+    fn search_roles(
+        &self,
+        query: &str,
+        limit: u64,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>;
+}
+
+impl<'a, C> RoleBasedAuthorizationStoreSearchRoles for RoleBasedAuthorizationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::serialize::ToSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn search_roles(
+        &self,
+        query: &str,
+        limit: u64,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
+        // Lower case both sides, as `LIKE` is case-sensitive in PostgreSQL
+        let pattern = contains_pattern(&query.to_lowercase());
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+
+        self.conn
+            .transaction::<Box<dyn ExactSizeIterator<Item = Role>>, _, _>(|| {
+                let roles = rbac_roles::table
+                    .filter(
+                        lower(rbac_roles::id)
+                            .like(pattern.clone())
+                            .escape(LIKE_ESCAPE)
+                            .or(lower(rbac_roles::display_name)
+                                .like(pattern)
+                                .escape(LIKE_ESCAPE)),
+                    )
+                    .order(rbac_roles::id.asc())
+                    .limit(limit)
+                    .load::<RoleModel>(self.conn)?;
+
+                let perms = RolePermissionModel::belonging_to(&roles)
+                    .load::<RolePermissionModel>(self.conn)?
+                    .grouped_by(&roles);
+
+                Ok(Box::new(
+                    roles
+                        .into_iter()
+                        .zip(perms)
+                        .map(|models| models.try_into())
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(RoleBasedAuthorizationStoreError::from)?
+                        .into_iter(),
+                ))
+            })
+    }
+}
+
+/// Creates a `LIKE` pattern matching any value that contains the given string. The `%` and `_`
+/// wildcards in the string are escaped, using `LIKE_ESCAPE`, so they only match themselves.
+fn contains_pattern(value: &str) -> String {
+    let mut pattern = String::with_capacity(value.len() + 2);
+    pattern.push('%');
+    for c in value.chars() {
+        if c == LIKE_ESCAPE || c == '%' || c == '_' {
+            pattern.push(LIKE_ESCAPE);
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
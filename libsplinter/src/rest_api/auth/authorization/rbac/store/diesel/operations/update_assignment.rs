@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use diesel::{
-    dsl::{delete, insert_into},
+    dsl::{delete, insert_into, update},
     prelude::*,
 };
 
@@ -62,6 +62,10 @@ impl<'a> RoleBasedAuthorizationStoreUpdateAssignment
                 ));
             }
 
+            update(rbac_identities::table.find(&identity.identity))
+                .set(rbac_identities::expires_at.eq(identity.expires_at))
+                .execute(self.conn)?;
+
             delete(
                 rbac_assignments::table.filter(rbac_assignments::identity.eq(&identity.identity)),
             )
@@ -103,6 +107,10 @@ impl<'a> RoleBasedAuthorizationStoreUpdateAssignment
                 ));
             }
 
+            update(rbac_identities::table.find(&identity.identity))
+                .set(rbac_identities::expires_at.eq(identity.expires_at))
+                .execute(self.conn)?;
+
             delete(
                 rbac_assignments::table.filter(rbac_assignments::identity.eq(&identity.identity)),
             )
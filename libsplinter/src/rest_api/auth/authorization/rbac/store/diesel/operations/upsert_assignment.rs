@@ -0,0 +1,111 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{
+    dsl::{delete, insert_into, update},
+    prelude::*,
+};
+
+use crate::rest_api::auth::authorization::rbac::store::{
+    diesel::{
+        models::{AssignmentModel, IdentityModel},
+        schema::{rbac_assignments, rbac_identities},
+    },
+    Assignment, RoleBasedAuthorizationStoreError,
+};
+
+use super::RoleBasedAuthorizationStoreOperations;
+
+pub trait RoleBasedAuthorizationStoreUpsertAssignment {
+    fn upsert_assignment(
+        &self,
+        assignment: Assignment,
+    ) -> Result<(), RoleBasedAuthorizationStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> RoleBasedAuthorizationStoreUpsertAssignment
+    for RoleBasedAuthorizationStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn upsert_assignment(
+        &self,
+        assignment: Assignment,
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let (identity, roles): (IdentityModel, Vec<AssignmentModel>) = assignment.into();
+        self.conn.transaction::<_, _, _>(|| {
+            let updated = update(rbac_identities::table.find(&identity.identity))
+                .set((
+                    rbac_identities::identity_type.eq(identity.identity_type),
+                    rbac_identities::expires_at.eq(identity.expires_at),
+                ))
+                .execute(self.conn)?;
+
+            if updated == 0 {
+                insert_into(rbac_identities::table)
+                    .values(&identity)
+                    .execute(self.conn)?;
+            } else {
+                delete(
+                    rbac_assignments::table
+                        .filter(rbac_assignments::identity.eq(&identity.identity)),
+                )
+                .execute(self.conn)?;
+            }
+
+            insert_into(rbac_assignments::table)
+                .values(roles)
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> RoleBasedAuthorizationStoreUpsertAssignment
+    for RoleBasedAuthorizationStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn upsert_assignment(
+        &self,
+        assignment: Assignment,
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        let (identity, roles): (IdentityModel, Vec<AssignmentModel>) = assignment.into();
+        self.conn.transaction::<_, _, _>(|| {
+            let updated = update(rbac_identities::table.find(&identity.identity))
+                .set((
+                    rbac_identities::identity_type.eq(identity.identity_type),
+                    rbac_identities::expires_at.eq(identity.expires_at),
+                ))
+                .execute(self.conn)?;
+
+            if updated == 0 {
+                insert_into(rbac_identities::table)
+                    .values(&identity)
+                    .execute(self.conn)?;
+            } else {
+                delete(
+                    rbac_assignments::table
+                        .filter(rbac_assignments::identity.eq(&identity.identity)),
+                )
+                .execute(self.conn)?;
+            }
+
+            insert_into(rbac_assignments::table)
+                .values(roles)
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
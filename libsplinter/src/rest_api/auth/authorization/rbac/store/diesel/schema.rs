@@ -35,6 +35,7 @@ table! {
         identity_type ->
             // the macro output can't find this type if it isn't fully qualified.
             crate::rest_api::auth::authorization::rbac::store::diesel::models::IdentityModelTypeMapping,
+        expires_at -> Nullable<BigInt>,
     }
 }
 
@@ -44,3 +45,5 @@ table! {
         role_id -> Text,
     }
 }
+
+allow_tables_to_appear_in_same_query!(rbac_identities, rbac_assignments);
@@ -18,6 +18,12 @@
 mod diesel;
 mod error;
 
+use std::collections::HashSet;
+use std::sync::mpsc::Receiver;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
 use crate::error::InvalidStateError;
 
 #[cfg(feature = "diesel")]
@@ -28,7 +34,7 @@ pub use error::RoleBasedAuthorizationStoreError;
 pub const ADMIN_ROLE_ID: &str = "admin";
 
 /// A Role is a named set of permissions.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Role {
     id: String,
     display_name: String,
@@ -195,7 +201,7 @@ impl RoleUpdateBuilder {
 }
 
 /// An identity that may be assigned roles.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Identity {
     /// A public key-based identity.
     Key(String),
@@ -219,10 +225,12 @@ impl From<&crate::rest_api::auth::identity::Identity> for Option<Identity> {
 }
 
 /// An assignment of roles to a particular identity.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Assignment {
     identity: Identity,
     roles: Vec<String>,
+    #[serde(default)]
+    expires_at: Option<SystemTime>,
 }
 
 impl Assignment {
@@ -236,10 +244,30 @@ impl Assignment {
         &self.roles
     }
 
+    /// Returns the time at which the assignment expires, if it is time-bounded.
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.expires_at
+    }
+
+    /// Returns true if the assignment has expired as of the given time.
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        self.expires_at
+            .map(|expires_at| expires_at <= now)
+            .unwrap_or(false)
+    }
+
     /// Convert this assignment back into a builder, in order to update its values.
     pub fn into_update_builder(self) -> AssignmentUpdateBuilder {
-        let Assignment { identity, roles } = self;
-        AssignmentUpdateBuilder { identity, roles }
+        let Assignment {
+            identity,
+            roles,
+            expires_at,
+        } = self;
+        AssignmentUpdateBuilder {
+            identity,
+            roles,
+            expires_at,
+        }
     }
 
     /// Converts this assignment into it's constituent parts.  These parts are in the tuple:
@@ -254,6 +282,7 @@ impl Assignment {
 pub struct AssignmentBuilder {
     identity: Option<Identity>,
     roles: Vec<String>,
+    expires_at: Option<SystemTime>,
 }
 
 impl AssignmentBuilder {
@@ -274,6 +303,15 @@ impl AssignmentBuilder {
         self
     }
 
+    /// Sets the time at which the assignment expires.
+    ///
+    /// Once expired, the assignment is treated as absent when looking up an identity's
+    /// assignment, roles or permissions. Assignments without an expiry never expire.
+    pub fn with_expires_at(mut self, expires_at: SystemTime) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
     /// Builds a new assignment.
     ///
     /// # Errors
@@ -293,6 +331,7 @@ impl AssignmentBuilder {
                 InvalidStateError::with_message("An assignment requires an identity field".into())
             })?,
             roles: self.roles,
+            expires_at: self.expires_at,
         })
     }
 }
@@ -303,6 +342,7 @@ impl AssignmentBuilder {
 pub struct AssignmentUpdateBuilder {
     identity: Identity,
     roles: Vec<String>,
+    expires_at: Option<SystemTime>,
 }
 
 impl AssignmentUpdateBuilder {
@@ -312,6 +352,12 @@ impl AssignmentUpdateBuilder {
         self
     }
 
+    /// Updates the time at which the assignment expires, or removes the expiry if `None`.
+    pub fn with_expires_at(mut self, expires_at: Option<SystemTime>) -> Self {
+        self.expires_at = expires_at;
+        self
+    }
+
     /// Builds the updated assignment.
     ///
     /// # Errors
@@ -328,10 +374,67 @@ impl AssignmentUpdateBuilder {
         Ok(Assignment {
             identity: self.identity,
             roles: self.roles,
+            expires_at: self.expires_at,
         })
     }
 }
 
+/// A snapshot of all of the roles and assignments in a store.
+///
+/// A snapshot may be serialized, in order to restore the roles and assignments in another store.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RbacSnapshot {
+    roles: Vec<Role>,
+    assignments: Vec<Assignment>,
+}
+
+impl RbacSnapshot {
+    /// Constructs a new snapshot of the given roles and assignments.
+    pub fn new(roles: Vec<Role>, assignments: Vec<Assignment>) -> Self {
+        Self { roles, assignments }
+    }
+
+    /// Returns the roles in the snapshot.
+    pub fn roles(&self) -> &[Role] {
+        &self.roles
+    }
+
+    /// Returns the assignments in the snapshot.
+    pub fn assignments(&self) -> &[Assignment] {
+        &self.assignments
+    }
+
+    /// Converts this snapshot into it's constituent parts.  These parts are in the tuple:
+    /// `(roles, assignments)`.
+    pub fn into_parts(self) -> (Vec<Role>, Vec<Assignment>) {
+        (self.roles, self.assignments)
+    }
+}
+
+/// How the roles and assignments of a snapshot are imported into a store.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImportMode {
+    /// Replaces all of the roles and assignments in the store with those of the snapshot.
+    Replace,
+    /// Adds the roles and assignments of the snapshot, replacing any roles with the same ID and
+    /// any assignments to the same identity.
+    Merge,
+}
+
+/// A change to the roles or assignments of a store, sent to subscribers after it is committed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RbacChangeEvent {
+    RoleAdded(Role),
+    RoleUpdated(Role),
+    /// A role was removed, which also removes it from every assignment that included it.
+    RoleRemoved(String),
+    AssignmentAdded(Assignment),
+    AssignmentUpdated(Assignment),
+    AssignmentRemoved(Identity),
+    /// A snapshot was imported, which may have changed any of the roles and assignments.
+    StateImported(ImportMode),
+}
+
 /// Defines methods for CRUD operations on Role and assignment data.
 pub trait RoleBasedAuthorizationStore: Send + Sync {
     /// Returns the role for the given ID, if one exists.
@@ -342,6 +445,16 @@ pub trait RoleBasedAuthorizationStore: Send + Sync {
         &self,
     ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>;
 
+    /// Searches the roles whose ID or display name contains the given query, ignoring case.
+    ///
+    /// The `%` and `_` characters in the query only match themselves. At most `limit` roles are
+    /// returned, ordered by ID.
+    fn search_roles(
+        &self,
+        query: &str,
+        limit: u64,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>;
+
     /// Adds a role.
     ///
     /// # Errors
@@ -356,6 +469,14 @@ pub trait RoleBasedAuthorizationStore: Send + Sync {
     /// Returns a `ConstraintViolation` error if the role does not exist.
     fn update_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError>;
 
+    /// Adds a role, or replaces the display name and permissions of the role if one with the same
+    /// ID already exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConstraintViolation` error if the role is the `admin` role.
+    fn upsert_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError>;
+
     /// Removes a role.
     ///
     /// # Errors
@@ -364,22 +485,42 @@ pub trait RoleBasedAuthorizationStore: Send + Sync {
     fn remove_role(&self, role_id: &str) -> Result<(), RoleBasedAuthorizationStoreError>;
 
     /// Returns the role for the given Identity, if one exists.
+    ///
+    /// An expired assignment is treated as absent.
     fn get_assignment(
         &self,
         identity: &Identity,
     ) -> Result<Option<Assignment>, RoleBasedAuthorizationStoreError>;
 
     /// Returns the assigned roles for the given Identity.
+    ///
+    /// No roles are returned if the identity's assignment has expired.
     fn get_assigned_roles(
         &self,
         identity: &Identity,
     ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>;
 
-    /// Lists all assignments.
+    /// Returns the permissions granted to the given Identity by all of its assigned roles.
+    ///
+    /// A permission granted by more than one role is only included once. No permissions are
+    /// returned if the identity's assignment has expired.
+    fn get_effective_permissions(
+        &self,
+        identity: &Identity,
+    ) -> Result<HashSet<String>, RoleBasedAuthorizationStoreError>;
+
+    /// Lists all assignments, including those that have expired but have not yet been removed.
     fn list_assignments(
         &self,
     ) -> Result<Box<dyn ExactSizeIterator<Item = Assignment>>, RoleBasedAuthorizationStoreError>;
 
+    /// Lists the identities whose assignment has no roles.
+    ///
+    /// An assignment is left without roles when all of its assigned roles have been removed.
+    fn list_empty_assignments(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Identity>>, RoleBasedAuthorizationStoreError>;
+
     /// Adds an assignment.
     ///
     /// # Errors
@@ -401,6 +542,13 @@ pub trait RoleBasedAuthorizationStore: Send + Sync {
         assignment: Assignment,
     ) -> Result<(), RoleBasedAuthorizationStoreError>;
 
+    /// Adds an assignment, or replaces the assigned roles if the identity already has an
+    /// assignment.
+    fn upsert_assignment(
+        &self,
+        assignment: Assignment,
+    ) -> Result<(), RoleBasedAuthorizationStoreError>;
+
     /// Removes an assignment.
     ///
     /// # Errors
@@ -411,6 +559,40 @@ pub trait RoleBasedAuthorizationStore: Send + Sync {
         identity: &Identity,
     ) -> Result<(), RoleBasedAuthorizationStoreError>;
 
+    /// Removes all of the assignments that have no roles.
+    fn remove_empty_assignments(&self) -> Result<(), RoleBasedAuthorizationStoreError>;
+
+    /// Removes all of the assignments that have expired as of the given time, returning the
+    /// number of assignments removed.
+    fn remove_expired_assignments(
+        &self,
+        now: SystemTime,
+    ) -> Result<u64, RoleBasedAuthorizationStoreError>;
+
+    /// Exports all of the roles and assignments in the store.
+    fn export_state(&self) -> Result<RbacSnapshot, RoleBasedAuthorizationStoreError>;
+
+    /// Imports the roles and assignments of a snapshot, in a single transaction.
+    ///
+    /// The `admin` role of the snapshot is only added if the store does not have an `admin` role;
+    /// an existing `admin` role is neither altered nor removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidState` error if a role in the snapshot is not valid for the store.
+    fn import_state(
+        &self,
+        snapshot: RbacSnapshot,
+        mode: ImportMode,
+    ) -> Result<(), RoleBasedAuthorizationStoreError>;
+
+    /// Subscribes to the changes made to the roles and assignments in the store.
+    ///
+    /// An event is sent on the returned channel for every change made through this store, or any
+    /// of its clones, after the change has been committed. The subscription ends when the
+    /// receiver is dropped.
+    fn subscribe(&self) -> Receiver<RbacChangeEvent>;
+
     /// Clone into a boxed, dynamically dispatched store
     fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore>;
 }
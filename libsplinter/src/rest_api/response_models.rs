@@ -68,4 +68,18 @@ impl ErrorResponse {
             message: message.to_string(),
         }
     }
+
+    pub fn too_many_requests(message: &str) -> ErrorResponse {
+        ErrorResponse {
+            code: "429".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    pub fn service_unavailable(message: &str) -> ErrorResponse {
+        ErrorResponse {
+            code: "503".to_string(),
+            message: message.to_string(),
+        }
+    }
 }
@@ -755,6 +755,7 @@ pub mod tests {
                 local_authorization: ConnectionAuthorizationType::Trust {
                     identity: "local_id".into(),
                 },
+                dial_back_endpoint: None,
             })
             .map_err(|err| AuthorizerError(format!("Unable to return result: {}", err)))
         }
@@ -585,6 +585,18 @@ impl ServiceConnectionAgent {
                     );
                 }
             }
+            ConnectionManagerNotification::Backpressure {
+                endpoint,
+                queue_depth,
+                ..
+            } => {
+                if let Some(info) = self.services.get_connection_info_by_endpoint_mut(&endpoint) {
+                    warn!(
+                        "Service processor {} is backpressured with {} queued messages",
+                        info.identity, queue_depth
+                    );
+                }
+            }
         }
     }
 }
@@ -793,6 +805,7 @@ mod tests {
                 local_authorization: ConnectionAuthorizationType::Trust {
                     identity: "local_id".into(),
                 },
+                dial_back_endpoint: None,
             })
             .map_err(|err| AuthorizerError(format!("Unable to return result: {}", err)))
         }
@@ -18,7 +18,7 @@ pub mod command;
 #[cfg(all(feature = "store-factory", feature = "memory"))]
 pub mod memory;
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
-pub(crate) mod pool;
+pub mod pool;
 #[cfg(all(feature = "store-factory", feature = "postgres"))]
 pub mod postgres;
 #[cfg(all(feature = "store-factory", feature = "sqlite"))]
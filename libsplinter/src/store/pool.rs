@@ -12,39 +12,156 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! A wrapper around a diesel connection pool that may enforce exclusive write access.
+
+use std::error::Error;
 use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::result::DatabaseErrorKind;
 
 use crate::error::InternalError;
 
-pub enum ConnectionPool<C: diesel::Connection + 'static> {
+/// A snapshot of the connections held by a `ConnectionPool`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolState {
+    connections: u32,
+    idle_connections: u32,
+    max_size: u32,
+}
+
+impl PoolState {
+    /// Returns the number of connections that are currently checked out of the pool
+    pub fn active_connections(&self) -> u32 {
+        self.connections - self.idle_connections
+    }
+
+    /// Returns the number of open connections that are waiting to be checked out
+    pub fn idle_connections(&self) -> u32 {
+        self.idle_connections
+    }
+
+    /// Returns the maximum number of connections the pool will open
+    pub fn max_size(&self) -> u32 {
+        self.max_size
+    }
+}
+
+/// A pool of database connections, shared by the diesel-backed stores
+///
+/// A pool created from an `Arc<RwLock<Pool>>` only allows one write at a time, and no reads while
+/// a write is in progress, which is required for databases such as SQLite.
+pub struct ConnectionPool<C: diesel::Connection + 'static> {
+    pool: InnerPool<C>,
+    checkout_timeout: Option<Duration>,
+    busy_retry: Option<BusyRetry>,
+}
+
+/// How writes that fail because the database is busy are retried
+#[derive(Clone, Copy)]
+struct BusyRetry {
+    max_retries: u32,
+    initial_backoff: Duration,
+}
+
+enum InnerPool<C: diesel::Connection + 'static> {
     Normal(Pool<ConnectionManager<C>>),
     WriteExclusive(Arc<RwLock<Pool<ConnectionManager<C>>>>),
 }
 
+// r2d2 returns an error from `get_timeout` once the timeout elapses without a connection becoming
+// available, including the last error encountered opening a connection, if any. The elapsed time
+// is checked to identify the timeout, with the connection error kept in the message.
 macro_rules! conn {
-    ($pool:ident) => {
-        $pool
-            .get()
-            .map_err(|e| InternalError::from_source(Box::new(e)))
-    };
+    ($pool:ident, $timeout:expr) => {{
+        let timeout = $timeout.unwrap_or_else(|| $pool.connection_timeout());
+        let start = Instant::now();
+        $pool.get_timeout(timeout).map_err(|e| {
+            if start.elapsed() >= timeout {
+                InternalError::from_source_with_prefix(
+                    Box::new(e),
+                    format!(
+                        "Connection pool exhausted: no connection available after {:?}",
+                        timeout
+                    ),
+                )
+            } else {
+                InternalError::from_source(Box::new(e))
+            }
+        })
+    }};
 }
 
 impl<C: diesel::Connection> ConnectionPool<C> {
+    /// Sets how long `execute_write` and `execute_read` wait for a connection to become available
+    /// before returning an error. By default, the connection timeout of the underlying pool is
+    /// used.
+    pub fn with_checkout_timeout(mut self, timeout: Duration) -> Self {
+        self.checkout_timeout = Some(timeout);
+        self
+    }
+
+    /// Enables retrying writes made with `execute_write_with_retry` that fail because an SQLite
+    /// database is busy or locked by another connection. The write is retried up to
+    /// `max_retries` times, waiting `initial_backoff` before the first retry and doubling the wait
+    /// before each following retry. By default, such writes are not retried.
+    pub fn with_busy_retry(mut self, max_retries: u32, initial_backoff: Duration) -> Self {
+        self.busy_retry = Some(BusyRetry {
+            max_retries,
+            initial_backoff,
+        });
+        self
+    }
+
     pub fn execute_write<F, T, E>(&self, f: F) -> Result<T, E>
     where
         F: FnOnce(&C) -> Result<T, E>,
         E: From<InternalError>,
     {
-        match self {
-            Self::Normal(pool) => f(&*conn!(pool)?),
-            Self::WriteExclusive(locked_pool) => locked_pool
+        match &self.pool {
+            InnerPool::Normal(pool) => f(&*conn!(pool, self.checkout_timeout)?),
+            InnerPool::WriteExclusive(locked_pool) => locked_pool
                 .write()
                 .map_err(|_| {
                     InternalError::with_message("Connection pool rwlock is poisoned".into()).into()
                 })
-                .and_then(|pool| f(&*conn!(pool)?)),
+                .and_then(|pool| f(&*conn!(pool, self.checkout_timeout)?)),
+        }
+    }
+
+    /// Executes a write as `execute_write` does, retrying it if it fails because the database is
+    /// busy, as configured by `with_busy_retry`. If the write still fails after the last retry,
+    /// that error is returned. As `f` may be called more than once, it should borrow, rather than
+    /// clone, the values it writes.
+    pub fn execute_write_with_retry<F, T, E>(&self, mut f: F) -> Result<T, E>
+    where
+        F: FnMut(&C) -> Result<T, E>,
+        E: From<InternalError> + Error + 'static,
+    {
+        let busy_retry = match self.busy_retry {
+            Some(busy_retry) => busy_retry,
+            None => return self.execute_write(f),
+        };
+
+        let mut retries = 0;
+        let mut backoff = busy_retry.initial_backoff;
+        loop {
+            match self.execute_write(&mut f) {
+                Err(err) if retries < busy_retry.max_retries && is_database_busy(&err) => {
+                    debug!(
+                        "Database is busy, retrying write in {:?} ({} of {})",
+                        backoff,
+                        retries + 1,
+                        busy_retry.max_retries
+                    );
+                    thread::sleep(backoff);
+                    retries += 1;
+                    backoff *= 2;
+                }
+                res => return res,
+            }
         }
     }
 
@@ -53,37 +170,105 @@ impl<C: diesel::Connection> ConnectionPool<C> {
         F: FnOnce(&C) -> Result<T, E>,
         E: From<InternalError>,
     {
-        match self {
-            Self::Normal(pool) => f(&*conn!(pool)?),
-            Self::WriteExclusive(locked_pool) => locked_pool
+        match &self.pool {
+            InnerPool::Normal(pool) => f(&*conn!(pool, self.checkout_timeout)?),
+            InnerPool::WriteExclusive(locked_pool) => locked_pool
                 .read()
                 .map_err(|_| {
                     InternalError::with_message("Connection pool rwlock is poisoned".into()).into()
                 })
-                .and_then(|pool| f(&*conn!(pool)?)),
+                .and_then(|pool| f(&*conn!(pool, self.checkout_timeout)?)),
+        }
+    }
+
+    /// Returns the current state of the underlying pool's connections
+    pub fn state(&self) -> Result<PoolState, InternalError> {
+        let pool_state = |pool: &Pool<ConnectionManager<C>>| {
+            let state = pool.state();
+            PoolState {
+                connections: state.connections,
+                idle_connections: state.idle_connections,
+                max_size: pool.max_size(),
+            }
+        };
+
+        match &self.pool {
+            InnerPool::Normal(pool) => Ok(pool_state(pool)),
+            InnerPool::WriteExclusive(locked_pool) => locked_pool
+                .read()
+                .map(|pool| pool_state(&pool))
+                .map_err(|_| {
+                    InternalError::with_message("Connection pool rwlock is poisoned".into())
+                }),
         }
     }
 }
 
 impl<C: diesel::Connection> Clone for ConnectionPool<C> {
     fn clone(&self) -> Self {
-        match self {
-            Self::Normal(pool) => Self::Normal(pool.clone()),
-            Self::WriteExclusive(locked_pool) => Self::WriteExclusive(locked_pool.clone()),
+        let pool = match &self.pool {
+            InnerPool::Normal(pool) => InnerPool::Normal(pool.clone()),
+            InnerPool::WriteExclusive(locked_pool) => {
+                InnerPool::WriteExclusive(locked_pool.clone())
+            }
+        };
+
+        Self {
+            pool,
+            checkout_timeout: self.checkout_timeout,
+            busy_retry: self.busy_retry,
         }
     }
 }
 
 impl<C: diesel::Connection> From<Pool<ConnectionManager<C>>> for ConnectionPool<C> {
     fn from(pool: Pool<ConnectionManager<C>>) -> Self {
-        Self::Normal(pool)
+        Self {
+            pool: InnerPool::Normal(pool),
+            checkout_timeout: None,
+            busy_retry: None,
+        }
     }
 }
 
 impl<C: diesel::Connection> From<Arc<RwLock<Pool<ConnectionManager<C>>>>> for ConnectionPool<C> {
     fn from(pool: Arc<RwLock<Pool<ConnectionManager<C>>>>) -> Self {
-        Self::WriteExclusive(pool)
+        Self {
+            pool: InnerPool::WriteExclusive(pool),
+            checkout_timeout: None,
+            busy_retry: None,
+        }
+    }
+}
+
+/// The messages SQLite reports for the `SQLITE_BUSY` and `SQLITE_LOCKED` result codes. Diesel
+/// reports every result code it does not map to another kind as `DatabaseErrorKind::__Unknown`,
+/// and does not expose the result code itself, so the message of an error of that kind is
+/// compared to identify them.
+const SQLITE_BUSY_MESSAGE: &str = "database is locked";
+const SQLITE_LOCKED_MESSAGE: &str = "database table is locked";
+
+/// Returns true if the error, or any of its sources, is an SQLite busy or locked error
+fn is_database_busy(err: &(dyn Error + 'static)) -> bool {
+    let mut source = Some(err);
+    while let Some(err) = source {
+        if let Some(diesel::result::Error::DatabaseError(kind, info)) =
+            err.downcast_ref::<diesel::result::Error>()
+        {
+            return match kind {
+                DatabaseErrorKind::__Unknown => {
+                    let message = info.message();
+                    message == SQLITE_BUSY_MESSAGE
+                        || message == SQLITE_LOCKED_MESSAGE
+                        || message.starts_with(&format!("{}: ", SQLITE_LOCKED_MESSAGE))
+                }
+                _ => false,
+            };
+        }
+        source = err.source();
     }
+
+    false
 }
 
 #[cfg(all(test, feature = "sqlite"))]
@@ -164,6 +349,152 @@ mod tests {
         Ok(())
     }
 
+    /// Verify that the state of a pool reports a connection as active while it is checked out, for
+    /// both the plain and the Arc-RwLock wrapped pools.
+    #[test]
+    fn test_pool_state() -> Result<(), Box<dyn std::error::Error>> {
+        let pool = create_connection_pool("test_pool_state")?;
+        let max_size = pool.max_size();
+
+        for conn_pool in vec![
+            ConnectionPool::from(pool.clone()),
+            ConnectionPool::from(Arc::new(RwLock::new(pool))),
+        ] {
+            let state = conn_pool.state()?;
+            assert_eq!(0, state.active_connections());
+            assert_eq!(max_size, state.max_size());
+
+            let active = conn_pool.execute_read::<_, _, InternalError>(|_| {
+                Ok(conn_pool.state()?.active_connections())
+            })?;
+            assert_eq!(1, active);
+
+            assert_eq!(0, conn_pool.state()?.active_connections());
+        }
+
+        Ok(())
+    }
+
+    /// Verify that when the only connection of a pool is checked out, a read times out after the
+    /// configured checkout timeout with a pool exhausted error.
+    #[test]
+    fn test_checkout_timeout() -> Result<(), Box<dyn std::error::Error>> {
+        let connection_manager = ConnectionManager::<diesel::SqliteConnection>::new(
+            "file:test_checkout_timeout?mode=memory&cache=shared",
+        );
+        let pool = Pool::builder().max_size(1).build(connection_manager)?;
+        let conn_pool =
+            ConnectionPool::from(pool).with_checkout_timeout(Duration::from_millis(100));
+
+        let err = conn_pool
+            .execute_read::<_, (), InternalError>(|_| {
+                conn_pool.execute_read::<_, (), InternalError>(|_| Ok(()))
+            })
+            .expect_err("Second checkout should have timed out");
+
+        assert!(err.to_string().starts_with("Connection pool exhausted"));
+
+        Ok(())
+    }
+
+    /// Verify that when a connection cannot be opened before the checkout timeout elapses, the
+    /// error returned by a read includes the connection error.
+    #[test]
+    fn test_checkout_connection_error() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::tempdir()?;
+        let db_path = temp_dir
+            .path()
+            .join("missing")
+            .join("test_checkout_connection_error.db")
+            .to_string_lossy()
+            .to_string();
+
+        let pool = Pool::builder()
+            .max_size(1)
+            .build_unchecked(ConnectionManager::<diesel::SqliteConnection>::new(&db_path));
+        let conn_pool =
+            ConnectionPool::from(pool).with_checkout_timeout(Duration::from_millis(100));
+
+        let err = conn_pool
+            .execute_read::<_, (), InternalError>(|_| Ok(()))
+            .expect_err("Checkout should have failed");
+
+        assert!(err.to_string().starts_with("Connection pool exhausted"));
+        assert!(err.to_string().contains("unable to open database file"));
+
+        Ok(())
+    }
+
+    /// Verify that a write that fails because the database is locked by another connection is
+    /// retried when busy retries are enabled.
+    ///
+    /// 1. Create a single-connection pool on an SQLite database file with a simple table
+    /// 2. Lock the database from a separate connection with an exclusive transaction
+    /// 3. Validate that a write without busy retries fails immediately
+    /// 4. Release the lock from another thread after a short delay
+    /// 5. Validate that a write with busy retries succeeds once the lock is released
+    #[test]
+    fn test_write_retry_when_busy() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = tempfile::tempdir()?;
+        let db_path = temp_dir
+            .path()
+            .join("test_write_retry_when_busy.db")
+            .to_string_lossy()
+            .to_string();
+
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(ConnectionManager::<diesel::SqliteConnection>::new(&db_path))?;
+        pool.get()?.batch_execute(
+            r#"
+            CREATE TABLE test_table (
+                id TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );
+            "#,
+        )?;
+
+        let insert = |id: &str| {
+            let id = id.to_string();
+            move |conn: &diesel::SqliteConnection| {
+                diesel::sql_query("INSERT INTO test_table (id, value) VALUES (?, ?)")
+                    .bind::<diesel::sql_types::Text, _>(&id)
+                    .bind::<diesel::sql_types::Text, _>("test")
+                    .execute(conn)
+                    .map(|_| ())
+                    .map_err(|e| InternalError::from_source(Box::new(e)))
+            }
+        };
+
+        let blocker = diesel::SqliteConnection::establish(&db_path)?;
+        blocker.batch_execute("BEGIN EXCLUSIVE")?;
+
+        let conn_pool = ConnectionPool::from(pool);
+        conn_pool
+            .execute_write_with_retry(insert("without-retry"))
+            .expect_err("Write should have failed while the database is locked");
+
+        let unlock = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(200));
+            blocker.batch_execute("COMMIT")
+        });
+
+        let conn_pool = conn_pool.with_busy_retry(10, Duration::from_millis(20));
+        conn_pool.execute_write_with_retry(insert("with-retry"))?;
+
+        unlock.join().expect("Unlock thread panicked")?;
+
+        let values = conn_pool.execute_read::<_, _, InternalError>(|conn| {
+            diesel::sql_query("SELECT * FROM test_table")
+                .get_results::<LookupData>(conn)
+                .map_err(|e| InternalError::from_source(Box::new(e)))
+        })?;
+        assert_eq!(1, values.len());
+        assert_eq!("with-retry", &values[0]._id);
+
+        Ok(())
+    }
+
     #[derive(QueryableByName)]
     struct LookupData {
         #[column_name = "id"]
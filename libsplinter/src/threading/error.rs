@@ -24,3 +24,14 @@ impl fmt::Display for PacemakerStartError {
         write!(f, "Pacemaker was unable to start: {}", self.0)
     }
 }
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PacemakerTickError(pub String);
+
+impl error::Error for PacemakerTickError {}
+
+impl fmt::Display for PacemakerTickError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Pacemaker was unable to tick: {}", self.0)
+    }
+}
@@ -41,3 +41,213 @@ pub trait ShutdownHandle {
     /// `wait_for_shutdown`.
     fn wait_for_shutdown(self) -> Result<(), InternalError>;
 }
+
+/// A `ShutdownHandle` that shuts down a group of other `ShutdownHandle`s together.
+///
+/// Signaling the group signals every registered handle, in the order they were added. Waiting on
+/// the group waits for every registered handle, in the same order, even if some of them fail; any
+/// errors are combined into a single `InternalError`.
+#[derive(Default)]
+pub struct ShutdownGroup {
+    handles: Vec<Box<dyn GroupedShutdownHandle>>,
+}
+
+impl ShutdownGroup {
+    /// Creates a new, empty `ShutdownGroup`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a handle to the group
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - the `ShutdownHandle` to shut down with the rest of the group
+    pub fn add<H>(&mut self, handle: H)
+    where
+        H: ShutdownHandle + Send + 'static,
+    {
+        self.handles.push(Box::new(handle));
+    }
+
+    /// Adds a handle to the group, returning the group
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - the `ShutdownHandle` to shut down with the rest of the group
+    pub fn with_handle<H>(mut self, handle: H) -> Self
+    where
+        H: ShutdownHandle + Send + 'static,
+    {
+        self.add(handle);
+        self
+    }
+
+    /// Returns the number of handles in the group
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Returns true if no handles have been added to the group
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+}
+
+impl ShutdownHandle for ShutdownGroup {
+    fn signal_shutdown(&mut self) {
+        for handle in self.handles.iter_mut() {
+            handle.signal();
+        }
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        let mut errors = self
+            .handles
+            .into_iter()
+            .filter_map(|handle| handle.wait().err())
+            .collect::<Vec<_>>();
+
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            _ => Err(InternalError::with_message(format!(
+                "Multiple errors occurred during shutdown: {}",
+                errors
+                    .into_iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+}
+
+/// An object-safe form of `ShutdownHandle`, as `ShutdownHandle::wait_for_shutdown` cannot be
+/// called on a boxed trait object.
+trait GroupedShutdownHandle: Send {
+    fn signal(&mut self);
+
+    fn wait(self: Box<Self>) -> Result<(), InternalError>;
+}
+
+impl<H> GroupedShutdownHandle for H
+where
+    H: ShutdownHandle + Send,
+{
+    fn signal(&mut self) {
+        self.signal_shutdown()
+    }
+
+    fn wait(self: Box<Self>) -> Result<(), InternalError> {
+        (*self).wait_for_shutdown()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    /// Verify that a `ShutdownGroup` signals and waits for all of its handles.
+    ///
+    /// 1. Add two handles to a group
+    /// 2. Signal the group and validate that both handles were signaled, but not waited for
+    /// 3. Wait for the group and validate that it succeeds and both handles were waited for
+    #[test]
+    fn test_shutdown_group() {
+        let first = TestHandle::new(None);
+        let second = TestHandle::new(None);
+
+        let mut group = ShutdownGroup::new()
+            .with_handle(first.clone())
+            .with_handle(second.clone());
+        assert_eq!(group.len(), 2);
+
+        group.signal_shutdown();
+        assert!(first.signaled() && second.signaled());
+        assert!(!first.waited() && !second.waited());
+
+        group
+            .wait_for_shutdown()
+            .expect("Unable to wait for shutdown");
+        assert!(first.waited() && second.waited());
+    }
+
+    /// Verify that a `ShutdownGroup` waits for all of its handles when some fail, and returns
+    /// their errors.
+    ///
+    /// 1. Create a group with a failing handle followed by a successful one
+    /// 2. Shut down the group and validate that the error is returned, and that the second handle
+    ///    was still waited for
+    /// 3. Create a group with two failing handles
+    /// 4. Shut down the group and validate that a single error containing both is returned
+    #[test]
+    fn test_shutdown_group_errors() {
+        let failing = TestHandle::new(Some("first failed"));
+        let succeeding = TestHandle::new(None);
+
+        let mut group = ShutdownGroup::new()
+            .with_handle(failing.clone())
+            .with_handle(succeeding.clone());
+        group.signal_shutdown();
+        let err = group
+            .wait_for_shutdown()
+            .expect_err("Shutdown should have failed");
+        assert_eq!(err.to_string(), "first failed");
+        assert!(failing.waited() && succeeding.waited());
+
+        let mut group = ShutdownGroup::new()
+            .with_handle(TestHandle::new(Some("first failed")))
+            .with_handle(TestHandle::new(Some("second failed")));
+        group.signal_shutdown();
+        let err = group
+            .wait_for_shutdown()
+            .expect_err("Shutdown should have failed");
+        assert_eq!(
+            err.to_string(),
+            "Multiple errors occurred during shutdown: first failed, second failed"
+        );
+    }
+
+    #[derive(Clone)]
+    struct TestHandle {
+        signaled: Arc<AtomicBool>,
+        waited: Arc<AtomicBool>,
+        error: Option<&'static str>,
+    }
+
+    impl TestHandle {
+        fn new(error: Option<&'static str>) -> Self {
+            Self {
+                signaled: Arc::new(AtomicBool::new(false)),
+                waited: Arc::new(AtomicBool::new(false)),
+                error,
+            }
+        }
+
+        fn signaled(&self) -> bool {
+            self.signaled.load(Ordering::SeqCst)
+        }
+
+        fn waited(&self) -> bool {
+            self.waited.load(Ordering::SeqCst)
+        }
+    }
+
+    impl ShutdownHandle for TestHandle {
+        fn signal_shutdown(&mut self) {
+            self.signaled.store(true, Ordering::SeqCst);
+        }
+
+        fn wait_for_shutdown(self) -> Result<(), InternalError> {
+            self.waited.store(true, Ordering::SeqCst);
+            match self.error {
+                Some(msg) => Err(InternalError::with_message(msg.to_string())),
+                None => Ok(()),
+            }
+        }
+    }
+}
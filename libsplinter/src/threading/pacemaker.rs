@@ -13,14 +13,14 @@
 // limitations under the License.
 
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicU64, Ordering},
     mpsc::Sender,
-    Arc,
+    Arc, Mutex,
 };
 use std::thread;
 use std::time::{Duration, Instant};
 
-use super::error::PacemakerStartError;
+use super::error::{PacemakerStartError, PacemakerTickError};
 
 /// Build new Pacemakers
 ///
@@ -77,6 +77,7 @@ where
     /// pacemaker thread fails to start.
     pub fn start(mut self) -> Result<Pacemaker, PacemakerStartError> {
         let running = Arc::new(AtomicBool::new(true));
+        let tick_count = Arc::new(AtomicU64::new(0));
 
         let running_clone = running.clone();
         let interval = self
@@ -92,6 +93,7 @@ where
             .take()
             .ok_or_else(|| PacemakerStartError("No message factory function provided".into()))?;
 
+        let thread_tick_count = tick_count.clone();
         let join_handle = thread::Builder::new()
             .name("Pacemaker".into())
             .spawn(move || {
@@ -110,24 +112,66 @@ where
                             );
                             break;
                         }
+                        thread_tick_count.fetch_add(1, Ordering::SeqCst);
                     }
                     thread::sleep(loop_duration);
                 }
             })
             .map_err(|err| PacemakerStartError(err.to_string()))?;
         Ok(Pacemaker {
-            join_handle,
+            join_handle: Some(join_handle),
             shutdown_signaler: ShutdownSignaler { running },
+            tick_count,
+            manual_tick: None,
+        })
+    }
+
+    /// Construct a Pacemaker in manual mode.
+    ///
+    /// A manual pacemaker does not fire on an interval, and no interval is required; instead, a
+    /// message is sent each time `Pacemaker::tick` is called. This allows components that are
+    /// driven by a pacemaker to be tested deterministically.
+    ///
+    /// # Errors
+    ///
+    /// A `PacemakerStartError` will be returned if the sender or message factory are not
+    /// configured.
+    pub fn start_manual(mut self) -> Result<Pacemaker, PacemakerStartError> {
+        let sender = self
+            .sender
+            .take()
+            .ok_or_else(|| PacemakerStartError("No sender provided".into()))?;
+        let new_message = self
+            .message_factory
+            .take()
+            .ok_or_else(|| PacemakerStartError("No message factory function provided".into()))?;
+
+        Ok(Pacemaker {
+            join_handle: None,
+            shutdown_signaler: ShutdownSignaler {
+                running: Arc::new(AtomicBool::new(true)),
+            },
+            tick_count: Arc::new(AtomicU64::new(0)),
+            manual_tick: Some(Mutex::new(Box::new(move || {
+                sender
+                    .send(new_message())
+                    .map_err(|_| PacemakerTickError("Sender has disconnected".into()))
+            }))),
         })
     }
 }
 
+type ManualTick = Box<dyn Fn() -> Result<(), PacemakerTickError> + Send>;
+
 /// Pacemaker is responsible for periodically sending a message to
 /// another component over a channel. The message is meant to be used as
 /// a notfication that some action should take place.
 pub struct Pacemaker {
-    join_handle: thread::JoinHandle<()>,
+    join_handle: Option<thread::JoinHandle<()>>,
     shutdown_signaler: ShutdownSignaler,
+    tick_count: Arc<AtomicU64>,
+    /// Sends a message, if the pacemaker was started in manual mode.
+    manual_tick: Option<Mutex<ManualTick>>,
 }
 
 impl Pacemaker {
@@ -144,9 +188,34 @@ impl Pacemaker {
         self.shutdown_signaler.clone()
     }
 
+    /// Returns the number of messages the pacemaker has sent.
+    pub fn tick_count(&self) -> u64 {
+        self.tick_count.load(Ordering::SeqCst)
+    }
+
+    /// Sends a message immediately.
+    ///
+    /// # Errors
+    ///
+    /// A `PacemakerTickError` will be returned if the pacemaker was not started in manual mode,
+    /// or if the receiver has disconnected.
+    pub fn tick(&self) -> Result<(), PacemakerTickError> {
+        let manual_tick = self.manual_tick.as_ref().ok_or_else(|| {
+            PacemakerTickError("Only a pacemaker in manual mode can be ticked".into())
+        })?;
+        let manual_tick = manual_tick
+            .lock()
+            .map_err(|_| PacemakerTickError("Manual tick lock was poisoned".into()))?;
+        (*manual_tick)()?;
+        self.tick_count.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
     pub fn await_shutdown(self) {
-        if let Err(err) = self.join_handle.join() {
-            error!("Failed to shutdown heartbeat monitor gracefully: {:?}", err);
+        if let Some(join_handle) = self.join_handle {
+            if let Err(err) = join_handle.join() {
+                error!("Failed to shutdown heartbeat monitor gracefully: {:?}", err);
+            }
         }
     }
 }
@@ -161,3 +230,46 @@ impl ShutdownSignaler {
         self.running.store(false, Ordering::SeqCst)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::mpsc::channel;
+
+    /// Test that a manual pacemaker only sends a message when it is ticked.
+    ///
+    /// 1. Start a pacemaker in manual mode, with a message factory that counts its calls
+    /// 2. Tick the pacemaker three times
+    /// 3. Verify that the message factory was called and a message was received three times, and
+    ///    that the tick count is three
+    /// 4. Verify that ticking fails once the receiver has been dropped
+    #[test]
+    fn test_manual_tick() {
+        let (sender, receiver) = channel();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let factory_calls = calls.clone();
+
+        let pacemaker = Pacemaker::builder()
+            .with_sender(sender)
+            .with_message_factory(move || factory_calls.fetch_add(1, Ordering::SeqCst))
+            .start_manual()
+            .expect("Unable to start manual pacemaker");
+
+        for _ in 0..3 {
+            pacemaker.tick().expect("Unable to tick pacemaker");
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(pacemaker.tick_count(), 3);
+
+        drop(receiver);
+        assert!(pacemaker.tick().is_err());
+        assert_eq!(pacemaker.tick_count(), 3);
+
+        pacemaker.shutdown_signaler().shutdown();
+        pacemaker.await_shutdown();
+    }
+}
@@ -302,6 +302,64 @@ impl std::fmt::Display for ConnectionMatrixSendError {
     }
 }
 
+/// Returned by `ConnectionMatrixSender::send_confirmed` when a message could not be enqueued on
+/// its connection.
+#[derive(Debug)]
+pub enum ConnectionMatrixDeliveryError {
+    /// No connection with the given identifier is present in the matrix
+    UnknownConnection,
+    /// The connection is present, but its outgoing queue is full
+    QueueFull,
+    /// The connection is present, but has disconnected or is being removed
+    Disconnected,
+    InternalError {
+        context: String,
+        source: Option<Box<dyn Error + Send>>,
+    },
+}
+
+impl ConnectionMatrixDeliveryError {
+    pub fn new_internal_error(context: String, source: Option<Box<dyn Error + Send>>) -> Self {
+        ConnectionMatrixDeliveryError::InternalError { context, source }
+    }
+}
+
+impl Error for ConnectionMatrixDeliveryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConnectionMatrixDeliveryError::UnknownConnection => None,
+            ConnectionMatrixDeliveryError::QueueFull => None,
+            ConnectionMatrixDeliveryError::Disconnected => None,
+            ConnectionMatrixDeliveryError::InternalError { source, .. } => {
+                source.as_ref().map(|err| &**err as &(dyn Error + 'static))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ConnectionMatrixDeliveryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ConnectionMatrixDeliveryError::UnknownConnection => {
+                f.write_str("Unable to send: connection is not in the matrix")
+            }
+            ConnectionMatrixDeliveryError::QueueFull => {
+                f.write_str("Unable to send: connection send queue is full")
+            }
+            ConnectionMatrixDeliveryError::Disconnected => {
+                f.write_str("Unable to send: connection has disconnected")
+            }
+            ConnectionMatrixDeliveryError::InternalError { context, source } => {
+                if let Some(ref err) = source {
+                    write!(f, "{}: {}", context, err)
+                } else {
+                    f.write_str(context)
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum ConnectionMatrixRecvError {
     Disconnected,
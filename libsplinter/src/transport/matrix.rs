@@ -35,8 +35,8 @@ use std::time::Duration;
 use super::Connection;
 
 pub use super::error::{
-    ConnectionMatrixAddError, ConnectionMatrixRecvError, ConnectionMatrixRecvTimeoutError,
-    ConnectionMatrixRemoveError, ConnectionMatrixSendError,
+    ConnectionMatrixAddError, ConnectionMatrixDeliveryError, ConnectionMatrixRecvError,
+    ConnectionMatrixRecvTimeoutError, ConnectionMatrixRemoveError, ConnectionMatrixSendError,
 };
 
 /// Contains a payload and the identifier for the connection on which the payload was received
@@ -117,6 +117,40 @@ pub trait ConnectionMatrixLifeCycle: Clone + Send {
     ///
     /// If the remove failed, a `ConnectionMatrixRemoveError` will be returned.
     fn remove(&self, id: &str) -> Result<Box<dyn Connection>, ConnectionMatrixRemoveError>;
+
+    /// Adds an alias for a connection in the connection matrix, so that messages sent to the
+    /// alias are sent over the connection
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` - Identifier to add for the connection; must be unique within the connection
+    ///   matrix
+    /// * `id` - the connection identifier for the connection being aliased
+    ///
+    /// The alias is removed when the connection is removed. If the add failed, a
+    /// `ConnectionMatrixAddError` will be returned. The default implementation does not support
+    /// aliases and always returns an error.
+    fn add_alias(&self, _alias: String, _id: &str) -> Result<(), ConnectionMatrixAddError> {
+        Err(ConnectionMatrixAddError::new(
+            "Connection aliases are not supported by this connection matrix".to_string(),
+            None,
+        ))
+    }
+
+    /// Removes an alias from the connection matrix, leaving the connection in place
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` - the alias being removed
+    ///
+    /// If the remove failed, a `ConnectionMatrixRemoveError` will be returned. The default
+    /// implementation does not support aliases and always returns an error.
+    fn remove_alias(&self, _alias: &str) -> Result<(), ConnectionMatrixRemoveError> {
+        Err(ConnectionMatrixRemoveError::new(
+            "Connection aliases are not supported by this connection matrix".to_string(),
+            None,
+        ))
+    }
 }
 
 /// Defines a function to send a message using a connection identifier
@@ -130,6 +164,47 @@ pub trait ConnectionMatrixSender: Clone + Send {
     ///
     /// If the send failed, a `ConnectionMatrixSendError` will be returned.
     fn send(&self, id: String, message: Vec<u8>) -> Result<(), ConnectionMatrixSendError>;
+
+    /// Sends a message over the specified connection, reporting whether it was enqueued.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier of the connection on which the message should be sent
+    /// * `message` - the bytes of the message
+    ///
+    /// Returns `Ok(())` once the message has been enqueued on the connection. If it could not be,
+    /// the returned `ConnectionMatrixDeliveryError` distinguishes an unknown connection from a
+    /// full queue. The default implementation can not tell these apart and reports any send
+    /// failure as an internal error.
+    fn send_confirmed(
+        &self,
+        id: String,
+        message: Vec<u8>,
+    ) -> Result<(), ConnectionMatrixDeliveryError> {
+        self.send(id, message).map_err(|err| {
+            ConnectionMatrixDeliveryError::new_internal_error(err.context, err.source)
+        })
+    }
+
+    /// Returns the number of messages queued for the specified connection, if the implementation
+    /// tracks it.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier of the connection
+    fn queue_depth(&self, _id: &str) -> Option<usize> {
+        None
+    }
+
+    /// Returns whether the specified connection's queue has exceeded the implementation's
+    /// high-water mark, indicating that senders should throttle.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - the identifier of the connection
+    fn is_backpressured(&self, _id: &str) -> bool {
+        false
+    }
 }
 
 /// Defines functions to receive messages from connections within the connection matrix
@@ -39,10 +39,14 @@ mod error;
 pub mod inproc;
 pub(crate) mod matrix;
 pub mod multi;
+#[cfg(feature = "mux-transport")]
+pub mod mux;
 #[deprecated(since = "0.3.14", note = "please use splinter::transport::socket")]
 pub mod raw;
 pub mod socket;
 pub mod tls;
+#[cfg(all(feature = "unix-transport", unix))]
+pub mod unix;
 #[cfg(feature = "ws-transport")]
 pub mod ws;
 
@@ -78,6 +82,40 @@ pub trait Connection: Send {
 
     /// Returns a `mio::event::Evented` for this connection which can be used for polling.
     fn evented(&self) -> &dyn Evented;
+
+    /// Return the details of the certificate presented by the remote peer, if any.
+    ///
+    /// Only connections secured with TLS have a peer certificate; by default, this returns
+    /// `None`.
+    fn peer_certificate(&self) -> Option<PeerCertInfo> {
+        None
+    }
+}
+
+/// Details of the certificate presented by the remote peer of a connection
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeerCertInfo {
+    fingerprint: Vec<u8>,
+    subject: String,
+}
+
+impl PeerCertInfo {
+    pub fn new(fingerprint: Vec<u8>, subject: String) -> Self {
+        PeerCertInfo {
+            fingerprint,
+            subject,
+        }
+    }
+
+    /// Returns the SHA-256 digest of the DER encoding of the certificate
+    pub fn fingerprint(&self) -> &[u8] {
+        &self.fingerprint
+    }
+
+    /// Returns the subject of the certificate, such as `CN=localhost`
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
 }
 
 pub trait Listener: Send {
@@ -0,0 +1,761 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A transport that multiplexes many logical connections over a single connection of an inner
+//! transport.
+//!
+//! Each message sent over the underlying connection is wrapped in a frame with a small header:
+//!
+//! ```text
+//! +------+---------------+--------+---------+
+//! | kind | connection id | length | payload |
+//! |  u8  |    u32 (BE)   |  u32   |         |
+//! +------+---------------+--------+---------+
+//! ```
+//!
+//! The `MuxTransport` opens a single underlying connection per endpoint, and each call to
+//! `connect` creates a new logical connection over it. A listener returned by
+//! `MuxTransport::listen` accepts underlying connections from the inner transport's listener and
+//! returns a logical connection for each one opened by the remote side.
+//!
+//! Logical connections implement [`Connection`], so they can be used by the `Mesh` and the
+//! `ConnectionManager` like the connections of any other transport.
+//!
+//! [`Connection`]: ../trait.Connection.html
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use mio::{Evented, Events, Poll, PollOpt, Ready, Token};
+use mio_extras::channel as mio_channel;
+
+use super::{
+    AcceptError, ConnectError, Connection, DisconnectError, ListenError, Listener, RecvError,
+    SendError, Transport,
+};
+
+// The length of the frame header: the frame kind, connection ID and payload length
+const HEADER_LEN: usize = 9;
+
+// How long the session thread waits for the underlying connection to become readable before
+// checking whether the session has been closed
+const POLL_TIMEOUT: Duration = Duration::from_millis(100);
+
+// How long the listener thread waits after the inner listener fails to accept a connection; the
+// wait is doubled after each consecutive failure, up to `MAX_ACCEPT_RETRY_DELAY`
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(10);
+const MAX_ACCEPT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+// The number of consecutive accept failures after which the listener thread stops
+const MAX_ACCEPT_FAILURES: u32 = 10;
+
+/// The kind of a frame sent over the underlying connection.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FrameKind {
+    /// Opens a new logical connection
+    Open,
+    /// Carries a message for a logical connection
+    Data,
+    /// Closes a logical connection
+    Close,
+}
+
+impl From<FrameKind> for u8 {
+    fn from(kind: FrameKind) -> Self {
+        match kind {
+            FrameKind::Open => 0,
+            FrameKind::Data => 1,
+            FrameKind::Close => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for FrameKind {
+    type Error = RecvError;
+
+    fn try_from(byte: u8) -> Result<Self, Self::Error> {
+        match byte {
+            0 => Ok(FrameKind::Open),
+            1 => Ok(FrameKind::Data),
+            2 => Ok(FrameKind::Close),
+            _ => Err(RecvError::ProtocolError(format!(
+                "Unknown mux frame kind {}",
+                byte
+            ))),
+        }
+    }
+}
+
+struct Frame {
+    kind: FrameKind,
+    connection_id: u32,
+    payload: Vec<u8>,
+}
+
+impl Frame {
+    fn encode(kind: FrameKind, connection_id: u32, payload: &[u8]) -> Result<Vec<u8>, SendError> {
+        let length = u32::try_from(payload.len()).map_err(|_| {
+            SendError::ProtocolError(format!(
+                "Message of {} bytes is too large for a mux frame",
+                payload.len()
+            ))
+        })?;
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+        bytes.push(kind.into());
+        bytes.extend_from_slice(&connection_id.to_be_bytes());
+        bytes.extend_from_slice(&length.to_be_bytes());
+        bytes.extend_from_slice(payload);
+        Ok(bytes)
+    }
+
+    fn decode(mut bytes: Vec<u8>) -> Result<Self, RecvError> {
+        if bytes.len() < HEADER_LEN {
+            return Err(RecvError::ProtocolError(format!(
+                "Mux frame of {} bytes is shorter than the frame header",
+                bytes.len()
+            )));
+        }
+
+        let kind = FrameKind::try_from(bytes[0])?;
+
+        let mut connection_id = [0; 4];
+        connection_id.copy_from_slice(&bytes[1..5]);
+        let connection_id = u32::from_be_bytes(connection_id);
+
+        let mut length = [0; 4];
+        length.copy_from_slice(&bytes[5..HEADER_LEN]);
+        let length = u32::from_be_bytes(length) as usize;
+
+        let payload = bytes.split_off(HEADER_LEN);
+        if payload.len() != length {
+            return Err(RecvError::ProtocolError(format!(
+                "Mux frame length {} does not match its payload of {} bytes",
+                length,
+                payload.len()
+            )));
+        }
+
+        Ok(Frame {
+            kind,
+            connection_id,
+            payload,
+        })
+    }
+}
+
+/// A `Transport` that multiplexes logical connections over the connections of an inner transport.
+pub struct MuxTransport {
+    inner: Box<dyn Transport + Send>,
+    sessions: HashMap<String, Arc<Session>>,
+}
+
+impl MuxTransport {
+    /// Constructs a new `MuxTransport` that multiplexes connections of the given transport.
+    pub fn new(inner: Box<dyn Transport + Send>) -> Self {
+        Self {
+            inner,
+            sessions: HashMap::new(),
+        }
+    }
+}
+
+impl Transport for MuxTransport {
+    fn accepts(&self, address: &str) -> bool {
+        self.inner.accepts(address)
+    }
+
+    fn connect(&mut self, endpoint: &str) -> Result<Box<dyn Connection>, ConnectError> {
+        // Sessions close once their last logical connection is disconnected
+        self.sessions.retain(|_, session| !session.is_closed());
+
+        let connection = match self.sessions.get(endpoint).and_then(Session::open_next) {
+            Some(connection) => connection,
+            None => {
+                let session = Session::start(self.inner.connect(endpoint)?, None)?;
+                self.sessions.insert(endpoint.to_string(), session.clone());
+                Session::open_next(&session).ok_or_else(|| {
+                    ConnectError::ProtocolError(format!(
+                        "Mux session with {} closed before a connection was opened",
+                        endpoint
+                    ))
+                })?
+            }
+        };
+
+        connection
+            .session
+            .send_frame(FrameKind::Open, connection.connection_id, &[])
+            .map_err(|err| {
+                ConnectError::ProtocolError(format!(
+                    "Unable to open mux connection to {}: {}",
+                    endpoint, err
+                ))
+            })?;
+
+        Ok(Box::new(connection))
+    }
+
+    fn listen(&mut self, bind: &str) -> Result<Box<dyn Listener>, ListenError> {
+        let mut inner_listener = self.inner.listen(bind)?;
+        let endpoint = inner_listener.endpoint();
+        let (accepted_tx, accepted_rx) = channel();
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let thread_stopped = stopped.clone();
+        thread::Builder::new()
+            .name(format!("MuxListener-{}", endpoint))
+            .spawn(move || accept_loop(inner_listener, accepted_tx, thread_stopped))
+            .map_err(|err| {
+                ListenError::IoError(format!("Unable to start mux listener for {}", bind), err)
+            })?;
+
+        Ok(Box::new(MuxListener {
+            endpoint,
+            accepted: accepted_rx,
+            stopped,
+        }))
+    }
+}
+
+/// Accepts connections from the inner listener and starts a session for each one, until the
+/// `MuxListener` is dropped or the inner listener fails repeatedly.
+fn accept_loop(
+    mut inner_listener: Box<dyn Listener>,
+    accepted: Sender<Box<dyn Connection>>,
+    stopped: Arc<AtomicBool>,
+) {
+    let mut failures = 0;
+    let mut retry_delay = ACCEPT_RETRY_DELAY;
+
+    while !stopped.load(Ordering::SeqCst) {
+        match inner_listener.accept() {
+            Ok(connection) => {
+                failures = 0;
+                retry_delay = ACCEPT_RETRY_DELAY;
+
+                // The listener may have been dropped while waiting for the connection
+                if stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Err(err) = Session::start(connection, Some(accepted.clone())) {
+                    error!("Unable to start mux session: {}", err);
+                }
+            }
+            Err(err) => {
+                failures += 1;
+                if failures >= MAX_ACCEPT_FAILURES {
+                    error!(
+                        "Stopping mux listener for {} after {} failures to accept a connection: {}",
+                        inner_listener.endpoint(),
+                        failures,
+                        err
+                    );
+                    return;
+                }
+
+                warn!(
+                    "Failed to accept connection on {}: {}",
+                    inner_listener.endpoint(),
+                    err
+                );
+                thread::sleep(retry_delay);
+                retry_delay = (retry_delay * 2).min(MAX_ACCEPT_RETRY_DELAY);
+            }
+        }
+    }
+
+    debug!(
+        "Stopping mux listener for {}; listener has been dropped",
+        inner_listener.endpoint()
+    );
+}
+
+/// A `Listener` that returns the logical connections opened by remote `MuxTransport`s.
+pub struct MuxListener {
+    endpoint: String,
+    accepted: Receiver<Box<dyn Connection>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl Listener for MuxListener {
+    fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
+        self.accepted.recv().map_err(|_| {
+            AcceptError::ProtocolError(format!("Mux listener for {} has stopped", self.endpoint))
+        })
+    }
+
+    fn endpoint(&self) -> String {
+        self.endpoint.clone()
+    }
+}
+
+impl Drop for MuxListener {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+    }
+}
+
+/// A logical connection carried over a connection of the inner transport.
+pub struct MuxConnection {
+    connection_id: u32,
+    session: Arc<Session>,
+    incoming: mio_channel::Receiver<Vec<u8>>,
+    disconnected: bool,
+}
+
+impl Connection for MuxConnection {
+    fn send(&mut self, message: &[u8]) -> Result<(), SendError> {
+        if self.disconnected {
+            return Err(SendError::Disconnected);
+        }
+
+        self.session
+            .send_frame(FrameKind::Data, self.connection_id, message)
+    }
+
+    fn recv(&mut self) -> Result<Vec<u8>, RecvError> {
+        match self.incoming.try_recv() {
+            Ok(message) => Ok(message),
+            Err(TryRecvError::Empty) => Err(RecvError::WouldBlock),
+            Err(TryRecvError::Disconnected) => Err(RecvError::Disconnected),
+        }
+    }
+
+    fn remote_endpoint(&self) -> String {
+        self.session.remote_endpoint.clone()
+    }
+
+    fn local_endpoint(&self) -> String {
+        self.session.local_endpoint.clone()
+    }
+
+    fn disconnect(&mut self) -> Result<(), DisconnectError> {
+        if self.disconnected {
+            return Ok(());
+        }
+        self.disconnected = true;
+
+        // The close frame is sent before the route is removed, as removing the last route may
+        // close the session
+        let result = match self
+            .session
+            .send_frame(FrameKind::Close, self.connection_id, &[])
+        {
+            Ok(()) | Err(SendError::Disconnected) => Ok(()),
+            Err(SendError::IoError(err)) => Err(DisconnectError::IoError(err)),
+            Err(err) => Err(DisconnectError::ProtocolError(err.to_string())),
+        };
+        self.session.close(self.connection_id);
+
+        result
+    }
+
+    fn evented(&self) -> &dyn Evented {
+        &self.incoming
+    }
+}
+
+impl Drop for MuxConnection {
+    fn drop(&mut self) {
+        if let Err(err) = self.disconnect() {
+            debug!(
+                "Unable to close mux connection {}: {}",
+                self.connection_id, err
+            );
+        }
+    }
+}
+
+/// The state shared by the logical connections carried over one connection of the inner
+/// transport.
+struct Session {
+    connection: Mutex<Box<dyn Connection>>,
+    routes: Mutex<HashMap<u32, mio_channel::Sender<Vec<u8>>>>,
+    next_connection_id: AtomicU32,
+    closed: AtomicBool,
+    /// Whether the session is closed when its last logical connection is closed; true for
+    /// sessions opened by `connect`
+    close_when_idle: bool,
+    local_endpoint: String,
+    remote_endpoint: String,
+}
+
+impl Session {
+    /// Starts a session over the given connection, spawning a thread that reads frames from the
+    /// connection and routes them to the logical connections.
+    ///
+    /// If `accepted` is provided, logical connections opened by the remote side are sent to it;
+    /// otherwise requests to open logical connections are ignored, and the session is closed
+    /// once all of the logical connections opened over it have been closed.
+    fn start(
+        connection: Box<dyn Connection>,
+        accepted: Option<Sender<Box<dyn Connection>>>,
+    ) -> Result<Arc<Self>, io::Error> {
+        let session = Arc::new(Session {
+            local_endpoint: connection.local_endpoint(),
+            remote_endpoint: connection.remote_endpoint(),
+            connection: Mutex::new(connection),
+            routes: Mutex::new(HashMap::new()),
+            next_connection_id: AtomicU32::new(0),
+            closed: AtomicBool::new(false),
+            close_when_idle: accepted.is_none(),
+        });
+
+        let thread_session = session.clone();
+        thread::Builder::new()
+            .name(format!("MuxSession-{}", session.remote_endpoint))
+            .spawn(move || {
+                if let Err(err) = Session::run(&thread_session, accepted) {
+                    error!(
+                        "Mux session with {} failed: {}",
+                        thread_session.remote_endpoint, err
+                    );
+                }
+                thread_session.shutdown();
+            })?;
+
+        Ok(session)
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    /// Registers a logical connection with the given ID and returns it, or `None` if the session
+    /// has been closed.
+    fn open(session: &Arc<Session>, connection_id: u32) -> Option<MuxConnection> {
+        let (tx, rx) = mio_channel::channel();
+        // The closed flag is checked while holding the routes lock, so a session that is closing
+        // because its last route was removed does not gain a new one
+        let mut routes = session
+            .routes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if session.is_closed() {
+            return None;
+        }
+        routes.insert(connection_id, tx);
+
+        Some(MuxConnection {
+            connection_id,
+            session: session.clone(),
+            incoming: rx,
+            disconnected: false,
+        })
+    }
+
+    /// Registers a logical connection with the next unused ID and returns it, or `None` if the
+    /// session has been closed.
+    fn open_next(session: &Arc<Session>) -> Option<MuxConnection> {
+        let connection_id = session.next_connection_id.fetch_add(1, Ordering::SeqCst);
+        Session::open(session, connection_id)
+    }
+
+    /// Stops routing messages to the logical connection with the given ID, closing the session
+    /// if it has no logical connections left and `close_when_idle` is set.
+    ///
+    /// The session thread disconnects the underlying connection once it sees the session has
+    /// been closed.
+    fn close(&self, connection_id: u32) {
+        let mut routes = self
+            .routes
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        routes.remove(&connection_id);
+        if self.close_when_idle && routes.is_empty() {
+            self.closed.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Marks the session as closed, drops all routes, which disconnects the logical
+    /// connections, and disconnects the underlying connection.
+    fn shutdown(&self) {
+        {
+            let mut routes = self
+                .routes
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            self.closed.store(true, Ordering::SeqCst);
+            routes.clear();
+        }
+
+        if let Err(err) = self
+            .connection
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .disconnect()
+        {
+            debug!(
+                "Unable to disconnect mux session with {}: {}",
+                self.remote_endpoint, err
+            );
+        }
+    }
+
+    fn send_frame(
+        &self,
+        kind: FrameKind,
+        connection_id: u32,
+        payload: &[u8],
+    ) -> Result<(), SendError> {
+        if self.is_closed() {
+            return Err(SendError::Disconnected);
+        }
+
+        let frame = Frame::encode(kind, connection_id, payload)?;
+        self.connection
+            .lock()
+            .map_err(|_| SendError::ProtocolError("Mux connection lock was poisoned".into()))?
+            .send(&frame)
+    }
+
+    /// Reads frames from the underlying connection until it is disconnected.
+    fn run(
+        session: &Arc<Session>,
+        accepted: Option<Sender<Box<dyn Connection>>>,
+    ) -> Result<(), RecvError> {
+        let poll = Poll::new()?;
+        poll.register(
+            session
+                .connection
+                .lock()
+                .map_err(|_| RecvError::ProtocolError("Mux connection lock was poisoned".into()))?
+                .evented(),
+            Token(0),
+            Ready::readable(),
+            PollOpt::edge(),
+        )?;
+        let mut events = Events::with_capacity(16);
+
+        loop {
+            if session.is_closed() {
+                debug!("Mux session with {} closed", session.remote_endpoint);
+                return Ok(());
+            }
+
+            poll.poll(&mut events, Some(POLL_TIMEOUT))?;
+
+            // Read every available frame, since the poll times out or is woken once for any
+            // number of frames.
+            loop {
+                let result = session
+                    .connection
+                    .lock()
+                    .map_err(|_| {
+                        RecvError::ProtocolError("Mux connection lock was poisoned".into())
+                    })?
+                    .recv();
+
+                match result {
+                    Ok(bytes) => Session::route(session, Frame::decode(bytes)?, accepted.as_ref()),
+                    Err(RecvError::WouldBlock) => break,
+                    Err(RecvError::Disconnected) => {
+                        debug!("Mux session with {} disconnected", session.remote_endpoint);
+                        return Ok(());
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+    }
+
+    /// Handles a frame received from the remote side.
+    fn route(session: &Arc<Session>, frame: Frame, accepted: Option<&Sender<Box<dyn Connection>>>) {
+        match frame.kind {
+            FrameKind::Open => match accepted {
+                Some(accepted) => {
+                    if let Some(connection) = Session::open(session, frame.connection_id) {
+                        if accepted.send(Box::new(connection)).is_err() {
+                            debug!(
+                                "Dropping mux connection {} from {}; listener has been dropped",
+                                frame.connection_id, session.remote_endpoint
+                            );
+                        }
+                    }
+                }
+                None => warn!(
+                    "Ignoring request from {} to open mux connection {}",
+                    session.remote_endpoint, frame.connection_id
+                ),
+            },
+            FrameKind::Data => {
+                let mut routes = session
+                    .routes
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                match routes.get(&frame.connection_id) {
+                    Some(sender) => {
+                        if sender.send(frame.payload).is_err() {
+                            routes.remove(&frame.connection_id);
+                        }
+                    }
+                    None => debug!(
+                        "Dropping message from {} for unknown mux connection {}",
+                        session.remote_endpoint, frame.connection_id
+                    ),
+                }
+            }
+            FrameKind::Close => session.close(frame.connection_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Instant;
+
+    use crate::transport::inproc::InprocTransport;
+    use crate::transport::tests;
+
+    // Receives a message from the connection, waiting up to 10 seconds for one to arrive
+    fn recv_blocking(connection: &mut dyn Connection) -> Vec<u8> {
+        let start = Instant::now();
+        loop {
+            match connection.recv() {
+                Ok(message) => return message,
+                Err(RecvError::WouldBlock) => {
+                    assert!(
+                        start.elapsed() < Duration::from_secs(10),
+                        "blocked for too long"
+                    );
+                    thread::sleep(Duration::from_millis(10));
+                }
+                Err(err) => panic!("Unable to receive message: {}", err),
+            }
+        }
+    }
+
+    #[test]
+    fn test_transport() {
+        let transport = MuxTransport::new(Box::new(InprocTransport::default()));
+        tests::test_transport(transport, "inproc://mux_test");
+    }
+
+    /// Verify that logical connections opened over a single underlying connection receive only
+    /// their own messages.
+    ///
+    /// 1. Open three logical connections to an inproc listener and accept them
+    /// 2. Validate that only one underlying connection was opened
+    /// 3. Send a distinct message on each client connection and validate that each message is
+    ///    received by the matching server connection
+    /// 4. Reply on each server connection and validate that each reply is received by the
+    ///    matching client connection
+    #[test]
+    fn test_demux_logical_connections() {
+        let mut transport = MuxTransport::new(Box::new(InprocTransport::default()));
+        let mut listener = transport
+            .listen("inproc://mux_demux")
+            .expect("Unable to listen");
+
+        let mut clients = (0..3)
+            .map(|_| {
+                transport
+                    .connect("inproc://mux_demux")
+                    .expect("Unable to connect")
+            })
+            .collect::<Vec<_>>();
+        let mut servers = (0..3)
+            .map(|_| listener.accept().expect("Unable to accept"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(1, transport.sessions.len());
+
+        for (i, client) in clients.iter_mut().enumerate() {
+            client
+                .send(format!("request {}", i).as_bytes())
+                .expect("Unable to send request");
+        }
+
+        for (i, server) in servers.iter_mut().enumerate() {
+            assert_eq!(
+                format!("request {}", i).into_bytes(),
+                recv_blocking(server.as_mut())
+            );
+            server
+                .send(format!("reply {}", i).as_bytes())
+                .expect("Unable to send reply");
+        }
+
+        for (i, client) in clients.iter_mut().enumerate() {
+            assert_eq!(
+                format!("reply {}", i).into_bytes(),
+                recv_blocking(client.as_mut())
+            );
+        }
+    }
+
+    /// Verify that a session opened by `connect` is closed once its last logical connection is
+    /// disconnected, and that a later connection to the same endpoint opens a new session.
+    ///
+    /// 1. Open a logical connection to an inproc listener and accept it
+    /// 2. Disconnect the client connection and validate that the session is closed
+    /// 3. Validate that the server connection is disconnected
+    /// 4. Open another logical connection and validate that the closed session was replaced
+    /// 5. Send a message on the new connection and validate that it is received
+    #[test]
+    fn test_idle_session_closed() {
+        let mut transport = MuxTransport::new(Box::new(InprocTransport::default()));
+        let mut listener = transport
+            .listen("inproc://mux_idle")
+            .expect("Unable to listen");
+
+        let mut client = transport
+            .connect("inproc://mux_idle")
+            .expect("Unable to connect");
+        let mut server = listener.accept().expect("Unable to accept");
+
+        client.disconnect().expect("Unable to disconnect");
+        assert!(transport
+            .sessions
+            .values()
+            .all(|session| session.is_closed()));
+
+        let start = Instant::now();
+        loop {
+            match server.recv() {
+                Err(RecvError::Disconnected) => break,
+                Err(RecvError::WouldBlock) => {
+                    assert!(
+                        start.elapsed() < Duration::from_secs(10),
+                        "blocked for too long"
+                    );
+                    thread::sleep(Duration::from_millis(10));
+                }
+                res => panic!("Unexpected result: {:?}", res),
+            }
+        }
+
+        let mut client = transport
+            .connect("inproc://mux_idle")
+            .expect("Unable to reconnect");
+        let mut server = listener.accept().expect("Unable to accept");
+        assert_eq!(1, transport.sessions.len());
+        assert!(transport
+            .sessions
+            .values()
+            .all(|session| !session.is_closed()));
+
+        client.send(b"hello").expect("Unable to send");
+        assert_eq!(b"hello".to_vec(), recv_blocking(server.as_mut()));
+    }
+}
@@ -14,7 +14,7 @@
 
 use std::io::{self, Cursor, Read, Write};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
 
@@ -102,9 +102,25 @@ impl Frame {
     /// - the data length doesn't match the header length
     /// - an IO error occurs
     pub fn read<R: Read>(reader: &mut R) -> Result<Self, FrameError> {
+        Self::read_with_timeout(reader, None)
+    }
+
+    /// Read a frame from the given reader, giving up if the complete frame has not been read
+    /// before the timeout elapses.
+    ///
+    /// # Errors
+    ///
+    /// Returns a FrameError in the same cases as `read`, and an IO error of kind `TimedOut` if the
+    /// timeout elapses.
+    pub fn read_with_timeout<R: Read>(
+        reader: &mut R,
+        timeout: Option<Duration>,
+    ) -> Result<Self, FrameError> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
         let frame_header = loop {
             match FrameHeader::read(reader) {
                 Err(FrameError::IoError(ref e)) if e.kind() == io::ErrorKind::WouldBlock => {
+                    check_deadline(deadline, "Timed out reading frame header")?;
                     thread::sleep(Duration::from_millis(100));
                     continue;
                 }
@@ -127,6 +143,7 @@ impl Frame {
                         }
                         Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
                         Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                            check_deadline(deadline, "Timed out reading frame data")?;
                             thread::sleep(Duration::from_millis(100));
                         }
                         Err(e) => return Err(FrameError::IoError(e)),
@@ -166,12 +183,29 @@ impl<'a> FrameRef<'a> {
     ///
     /// Returns a FrameError if an IO error occurs.
     pub fn write<W: Write>(self, writer: &mut W) -> Result<(), FrameError> {
+        self.write_with_timeout(writer, None)
+    }
+
+    /// Write the frame to the given writer, giving up if the complete frame has not been written
+    /// before the timeout elapses.
+    ///
+    /// # Errors
+    ///
+    /// Returns a FrameError if an IO error occurs, including an IO error of kind `TimedOut` if the
+    /// timeout elapses.
+    pub fn write_with_timeout<W: Write>(
+        self,
+        writer: &mut W,
+        timeout: Option<Duration>,
+    ) -> Result<(), FrameError> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
         let frame_header = match self.version {
             FrameVersion::V1 => FrameHeader::v1(self.data.len() as u32),
         };
         loop {
             match frame_header.write(writer) {
                 Err(FrameError::IoError(ref e)) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    check_deadline(deadline, "Timed out writing frame header")?;
                     thread::sleep(Duration::from_millis(100));
                     continue;
                 }
@@ -192,6 +226,7 @@ impl<'a> FrameRef<'a> {
                 Ok(n) => buffer = &buffer[n..],
                 Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
                 Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    check_deadline(deadline, "Timed out writing frame data")?;
                     thread::sleep(Duration::from_millis(100));
                 }
                 Err(e) => return Err(FrameError::IoError(e)),
@@ -202,6 +237,17 @@ impl<'a> FrameRef<'a> {
     }
 }
 
+/// Returns a `TimedOut` IO error if the deadline, when there is one, has passed.
+fn check_deadline(deadline: Option<Instant>, msg: &str) -> Result<(), FrameError> {
+    match deadline {
+        Some(deadline) if Instant::now() >= deadline => Err(FrameError::IoError(io::Error::new(
+            io::ErrorKind::TimedOut,
+            msg,
+        ))),
+        _ => Ok(()),
+    }
+}
+
 /// A FrameHeader.
 ///
 /// Each variant corresponds to the implementation for a given version.
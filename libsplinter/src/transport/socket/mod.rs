@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-mod frame;
+pub(in crate::transport) mod frame;
 mod tcp;
 mod tls;
 
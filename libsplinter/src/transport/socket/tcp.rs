@@ -14,7 +14,9 @@
 
 use mio::{net::TcpStream as MioTcpStream, Evented};
 
+use std::io;
 use std::net::{Shutdown, TcpListener as StdTcpListener, TcpStream};
+use std::time::Duration;
 
 use crate::transport::{
     AcceptError, ConnectError, Connection, DisconnectError, ListenError, Listener, RecvError,
@@ -26,7 +28,46 @@ use super::frame::{Frame, FrameError, FrameNegotiation, FrameRef, FrameVersion};
 const PROTOCOL_PREFIX: &str = "tcp://";
 
 #[derive(Default)]
-pub struct TcpTransport {}
+pub struct TcpTransport {
+    timeouts: SocketTimeouts,
+}
+
+impl TcpTransport {
+    /// Sets how long receiving a message on a connection, or the protocol handshake when a
+    /// connection is established, may wait for data before failing with a `TimedOut` error.
+    ///
+    /// By default, reads wait indefinitely, which leaves a connection whose peer has vanished
+    /// without closing it to be detected by heartbeats alone.
+    pub fn with_read_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.read = Some(timeout);
+        self
+    }
+
+    /// Sets how long sending a message on a connection, or the protocol handshake when a
+    /// connection is established, may wait for the socket to accept data before failing with a
+    /// `TimedOut` error.
+    ///
+    /// By default, writes wait indefinitely.
+    pub fn with_write_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.write = Some(timeout);
+        self
+    }
+}
+
+/// The read and write timeouts applied to the connections of a `TcpTransport`
+#[derive(Clone, Copy, Default)]
+struct SocketTimeouts {
+    read: Option<Duration>,
+    write: Option<Duration>,
+}
+
+impl SocketTimeouts {
+    /// Applies the timeouts to the blocking stream used for the protocol handshake
+    fn apply(&self, stream: &TcpStream) -> Result<(), io::Error> {
+        stream.set_read_timeout(self.read)?;
+        stream.set_write_timeout(self.write)
+    }
+}
 
 impl Transport for TcpTransport {
     fn accepts(&self, address: &str) -> bool {
@@ -48,6 +89,7 @@ impl Transport for TcpTransport {
         };
         // Connect a std::net::TcpStream to make sure connect() block
         let mut stream = TcpStream::connect(address)?;
+        self.timeouts.apply(&stream)?;
 
         let frame_version = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V1)
             .negotiate(&mut stream)
@@ -63,6 +105,7 @@ impl Transport for TcpTransport {
         Ok(Box::new(TcpConnection {
             frame_version,
             stream: mio_stream,
+            timeouts: self.timeouts,
         }))
     }
 
@@ -84,17 +127,20 @@ impl Transport for TcpTransport {
             listener: StdTcpListener::bind(address).map_err(|err| {
                 ListenError::IoError(format!("Failed to bind to {}", address), err)
             })?,
+            timeouts: self.timeouts,
         }))
     }
 }
 
 struct TcpListener {
     listener: StdTcpListener,
+    timeouts: SocketTimeouts,
 }
 
 impl Listener for TcpListener {
     fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
         let (mut stream, _) = self.listener.accept()?;
+        self.timeouts.apply(&stream)?;
 
         let frame_version = FrameNegotiation::inbound(FrameVersion::V1)
             .negotiate(&mut stream)
@@ -111,6 +157,7 @@ impl Listener for TcpListener {
         let connection = TcpConnection {
             frame_version,
             stream: MioTcpStream::from_stream(stream)?,
+            timeouts: self.timeouts,
         };
         Ok(Box::new(connection))
     }
@@ -123,11 +170,14 @@ impl Listener for TcpListener {
 struct TcpConnection {
     frame_version: FrameVersion,
     stream: MioTcpStream,
+    timeouts: SocketTimeouts,
 }
 
 impl Connection for TcpConnection {
     fn send(&mut self, message: &[u8]) -> Result<(), SendError> {
-        match FrameRef::new(self.frame_version, message).write(&mut self.stream) {
+        match FrameRef::new(self.frame_version, message)
+            .write_with_timeout(&mut self.stream, self.timeouts.write)
+        {
             Err(FrameError::IoError(e)) => Err(SendError::from(e)),
             Err(err) => Err(SendError::ProtocolError(err.to_string())),
             Ok(_) => Ok(()),
@@ -135,7 +185,7 @@ impl Connection for TcpConnection {
     }
 
     fn recv(&mut self) -> Result<Vec<u8>, RecvError> {
-        match Frame::read(&mut self.stream) {
+        match Frame::read_with_timeout(&mut self.stream, self.timeouts.read) {
             Err(FrameError::IoError(e)) => Err(RecvError::from(e)),
             Err(err) => Err(RecvError::ProtocolError(err.to_string())),
             Ok(frame) => Ok(frame.into_inner()),
@@ -164,6 +214,9 @@ impl Connection for TcpConnection {
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    use std::time::Instant;
+
     use crate::transport::tests;
 
     #[test]
@@ -196,4 +249,61 @@ mod tests {
         let transport = TcpTransport::default();
         tests::test_poll(transport, "127.0.0.1:0");
     }
+
+    /// Test that connecting to a peer that accepts the connection but never completes the
+    /// protocol handshake fails once the read timeout elapses, rather than blocking forever.
+    #[test]
+    fn test_connect_read_timeout() {
+        let listener = StdTcpListener::bind("127.0.0.1:0").expect("Unable to bind listener");
+        let address = listener.local_addr().expect("Unable to get local address");
+
+        // Accept the connection and hold it open without responding
+        let (tx, rx) = std::sync::mpsc::channel::<()>();
+        let handle = std::thread::spawn(move || {
+            let (_stream, _) = listener.accept().expect("Unable to accept connection");
+            let _ = rx.recv();
+        });
+
+        let mut transport = TcpTransport::default().with_read_timeout(Duration::from_millis(200));
+
+        let start = Instant::now();
+        assert!(transport.connect(&format!("tcp://{}", address)).is_err());
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(200));
+        assert!(elapsed < Duration::from_secs(5));
+
+        drop(tx);
+        handle.join().expect("Listener thread panicked");
+    }
+
+    /// Test that receiving on a connection whose peer never sends anything fails with a
+    /// `TimedOut` error once the read timeout elapses.
+    #[test]
+    fn test_recv_timeout() {
+        let mut transport = TcpTransport::default().with_read_timeout(Duration::from_millis(200));
+        let mut listener = transport
+            .listen("tcp://127.0.0.1:0")
+            .expect("Unable to listen");
+        let endpoint = listener.endpoint();
+
+        let (tx, rx) = std::sync::mpsc::channel::<()>();
+        let handle = std::thread::spawn(move || {
+            let _connection = listener.accept().expect("Unable to accept connection");
+            let _ = rx.recv();
+        });
+
+        let mut connection = transport.connect(&endpoint).expect("Unable to connect");
+
+        let start = Instant::now();
+        match connection.recv() {
+            Err(RecvError::IoError(err)) => assert_eq!(err.kind(), io::ErrorKind::TimedOut),
+            res => panic!("Expected a timed out error, got {:?}", res),
+        }
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(200));
+        assert!(elapsed < Duration::from_secs(5));
+
+        drop(tx);
+        handle.join().expect("Listener thread panicked");
+    }
 }
@@ -14,6 +14,7 @@
 
 use mio::{unix::EventedFd, Evented, Poll, PollOpt, Ready, Token};
 use openssl::error::ErrorStack;
+use openssl::hash::MessageDigest;
 use openssl::ssl::{
     Error as OpensslError, HandshakeError, SslAcceptor, SslConnector, SslFiletype, SslMethod,
     SslStream, SslVerifyMode,
@@ -26,10 +27,11 @@ use std::io;
 use std::net::{Ipv4Addr, Ipv6Addr, TcpListener, TcpStream};
 use std::os::unix::io::{AsRawFd, RawFd};
 use std::path::Path;
+use std::sync::{Arc, RwLock};
 
 use crate::transport::{
-    AcceptError, ConnectError, Connection, DisconnectError, ListenError, Listener, RecvError,
-    SendError, Transport,
+    AcceptError, ConnectError, Connection, DisconnectError, ListenError, Listener, PeerCertInfo,
+    RecvError, SendError, Transport,
 };
 
 use super::frame::{Frame, FrameError, FrameNegotiation, FrameRef, FrameVersion};
@@ -40,7 +42,10 @@ const PROTOCOL_PREFIX: &str = "tcps://";
 
 pub struct TlsTransport {
     connector: SslConnector,
-    acceptor: SslAcceptor,
+    // Shared with every listener created by this transport, so that reloaded certificates are
+    // used for new handshakes on existing listeners
+    acceptor: Arc<RwLock<SslAcceptor>>,
+    ca_cert: Option<String>,
 }
 
 impl TlsTransport {
@@ -53,8 +58,6 @@ impl TlsTransport {
     ) -> Result<Self, TlsInitError> {
         let client_cert_path = Path::new(&client_cert);
         let client_key_path = Path::new(&client_key);
-        let server_cert_path = Path::new(&server_cert);
-        let server_key_path = Path::new(&server_key);
 
         // Build TLS Connector
         let mut connector = SslConnector::builder(SslMethod::tls())?;
@@ -62,33 +65,71 @@ impl TlsTransport {
         connector.set_certificate_chain_file(client_cert_path)?;
         connector.check_private_key()?;
 
-        // Build TLS Acceptor
-        let mut acceptor = SslAcceptor::mozilla_modern(SslMethod::tls())?;
-        acceptor.set_private_key_file(server_key_path, SslFiletype::PEM)?;
-        acceptor.set_certificate_chain_file(&server_cert_path)?;
-        acceptor.check_private_key()?;
-
         // if ca_cert is provided set as accept cert, otherwise set verify to none
-        let (acceptor, connector) = {
-            if let Some(ca_cert) = ca_cert {
-                let ca_cert_path = Path::new(&ca_cert);
-                acceptor.set_ca_file(ca_cert_path)?;
-                connector.set_ca_file(ca_cert_path)?;
-                connector.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
-                acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
-            } else {
-                connector.set_verify(SslVerifyMode::NONE);
-                acceptor.set_verify(SslVerifyMode::NONE);
-            }
+        if let Some(ca_cert) = &ca_cert {
+            connector.set_ca_file(Path::new(ca_cert))?;
+            connector.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+        } else {
+            connector.set_verify(SslVerifyMode::NONE);
+        }
 
-            (acceptor.build(), connector.build())
-        };
+        let acceptor = build_acceptor(ca_cert.as_deref(), &server_key, &server_cert)?;
 
         Ok(TlsTransport {
-            connector,
-            acceptor,
+            connector: connector.build(),
+            acceptor: Arc::new(RwLock::new(acceptor)),
+            ca_cert,
         })
     }
+
+    /// Replaces the server certificate and private key used by this transport's listeners.
+    ///
+    /// New connections accepted by any listener created by this transport, including listeners
+    /// created before the reload, are handshaked with the new certificate. Connections that have
+    /// already been accepted are not affected.
+    ///
+    /// # Arguments
+    ///
+    /// * `server_cert` - The path to the new server certificate chain file
+    /// * `server_key` - The path to the new server private key file
+    ///
+    /// Returns an error if the certificate or key cannot be loaded, or if they do not match. In
+    /// that case the current certificate remains in use.
+    pub fn reload_certificates(
+        &self,
+        server_cert: String,
+        server_key: String,
+    ) -> Result<(), TlsInitError> {
+        let acceptor = build_acceptor(self.ca_cert.as_deref(), &server_key, &server_cert)?;
+
+        *self.acceptor.write().map_err(|_| {
+            TlsInitError::ProtocolError("TLS acceptor lock was poisoned".to_string())
+        })? = acceptor;
+
+        Ok(())
+    }
+}
+
+/// Builds the acceptor for incoming connections from the given server key and certificate. If a
+/// CA certificate is provided, clients must present a certificate signed by it.
+fn build_acceptor(
+    ca_cert: Option<&str>,
+    server_key: &str,
+    server_cert: &str,
+) -> Result<SslAcceptor, ErrorStack> {
+    let mut acceptor = SslAcceptor::mozilla_modern(SslMethod::tls())?;
+    acceptor.set_private_key_file(Path::new(server_key), SslFiletype::PEM)?;
+    acceptor.set_certificate_chain_file(Path::new(server_cert))?;
+    acceptor.check_private_key()?;
+
+    if let Some(ca_cert) = ca_cert {
+        acceptor.set_ca_file(Path::new(ca_cert))?;
+        acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+    } else {
+        acceptor.set_verify(SslVerifyMode::NONE);
+    }
+
+    Ok(acceptor.build())
 }
 
 fn endpoint_to_dns_name(endpoint: &str) -> Result<String, ParseError> {
@@ -177,13 +218,19 @@ impl Transport for TlsTransport {
 
 pub struct TlsListener {
     listener: TcpListener,
-    acceptor: SslAcceptor,
+    acceptor: Arc<RwLock<SslAcceptor>>,
 }
 
 impl Listener for TlsListener {
     fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
         let (stream, _) = self.listener.accept()?;
-        let mut tls_stream = self.acceptor.accept(stream)?;
+        // Clone the acceptor, rather than holding the lock for the duration of the handshake
+        let acceptor = self
+            .acceptor
+            .read()
+            .map_err(|_| AcceptError::ProtocolError("TLS acceptor lock was poisoned".into()))?
+            .clone();
+        let mut tls_stream = acceptor.accept(stream)?;
 
         let frame_version = FrameNegotiation::inbound(FrameVersion::V1)
             .negotiate(&mut tls_stream)
@@ -249,6 +296,35 @@ impl Connection for TlsConnection {
     fn evented(&self) -> &dyn Evented {
         self
     }
+
+    fn peer_certificate(&self) -> Option<PeerCertInfo> {
+        let cert = self.stream.ssl().peer_certificate()?;
+        let fingerprint = match cert.digest(MessageDigest::sha256()) {
+            Ok(digest) => digest.to_vec(),
+            Err(err) => {
+                error!("Unable to compute fingerprint of peer certificate: {}", err);
+                return None;
+            }
+        };
+
+        // Formats the subject as comma-separated `key=value` entries, e.g. `CN=localhost`
+        let subject = cert
+            .subject_name()
+            .entries()
+            .map(|entry| {
+                let key = entry.object().nid().short_name().unwrap_or("UNKNOWN");
+                let value = entry
+                    .data()
+                    .as_utf8()
+                    .map(|value| value.to_string())
+                    .unwrap_or_default();
+                format!("{}={}", key, value)
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Some(PeerCertInfo::new(fingerprint, subject))
+    }
 }
 
 impl TlsConnection {
@@ -351,7 +427,11 @@ pub(crate) mod tests {
 
     use std::fs::File;
     use std::io::Write;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
+    use std::thread;
+
+    use openssl::pkey::{PKey, Private};
+    use openssl::x509::X509;
     use tempfile::Builder;
 
     fn write_file(mut temp_dir: PathBuf, file_name: &str, bytes: &[u8]) -> String {
@@ -363,6 +443,18 @@ pub(crate) mod tests {
         path
     }
 
+    fn write_cert(temp_dir: &Path, file_name: &str, cert: &X509) -> String {
+        write_file(temp_dir.to_path_buf(), file_name, &cert.to_pem().unwrap())
+    }
+
+    fn write_key(temp_dir: &Path, file_name: &str, key: &PKey<Private>) -> String {
+        write_file(
+            temp_dir.to_path_buf(),
+            file_name,
+            &key.private_key_to_pem_pkcs8().unwrap(),
+        )
+    }
+
     pub fn create_test_tls_transport(insecure: bool) -> TlsTransport {
         // Genearte Certificat Authority keys and certificate
         let (ca_key, ca_cert) = make_ca_cert();
@@ -466,4 +558,147 @@ pub(crate) mod tests {
             Ready::readable() | Ready::writable(),
         );
     }
+
+    /// Test that the server certificate of a TLS transport can be replaced without recreating its
+    /// listener.
+    ///
+    /// 1. Create a transport with a server certificate and start a listener
+    /// 2. Connect to the listener and verify that the server presents the first certificate
+    /// 3. Reload the transport with a second server certificate
+    /// 4. Connect to the listener again and verify that the server presents the second
+    ///    certificate
+    /// 5. Verify that messages can still be sent in both directions on the first connection
+    #[test]
+    fn test_reload_certificates() {
+        let (ca_key, ca_cert) = make_ca_cert();
+        let temp_dir = Builder::new()
+            .prefix("tls-transport-test")
+            .tempdir()
+            .unwrap();
+        let temp_dir_path = temp_dir.path();
+
+        let (client_key, client_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+        let (first_key, first_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+        let (second_key, second_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+
+        let mut transport = TlsTransport::new(
+            None,
+            write_key(temp_dir_path, "client.key", &client_key),
+            write_cert(temp_dir_path, "client.cert", &client_cert),
+            write_key(temp_dir_path, "first.key", &first_key),
+            write_cert(temp_dir_path, "first.cert", &first_cert),
+        )
+        .unwrap();
+
+        let mut listener = transport.listen("tcps://127.0.0.1:0").unwrap();
+        let endpoint = listener.endpoint();
+
+        let client_endpoint = endpoint.clone();
+        let handle = thread::spawn(move || connect_and_get_peer_cert(&client_endpoint));
+        let mut first_server = listener.accept().unwrap();
+        let (mut first_client, peer_cert) = handle.join().unwrap();
+        assert_eq!(peer_cert.to_der().unwrap(), first_cert.to_der().unwrap());
+
+        transport
+            .reload_certificates(
+                write_cert(temp_dir_path, "second.cert", &second_cert),
+                write_key(temp_dir_path, "second.key", &second_key),
+            )
+            .unwrap();
+
+        let client_endpoint = endpoint.clone();
+        let handle = thread::spawn(move || connect_and_get_peer_cert(&client_endpoint));
+        let _second_server = listener.accept().unwrap();
+        let (_second_client, peer_cert) = handle.join().unwrap();
+        assert_eq!(peer_cert.to_der().unwrap(), second_cert.to_der().unwrap());
+
+        // The first connection is still up
+        FrameRef::new(FrameVersion::V1, &[0, 1, 2])
+            .write(&mut first_client)
+            .unwrap();
+        let message = loop {
+            match first_server.recv() {
+                Err(RecvError::WouldBlock) => thread::yield_now(),
+                res => break res.unwrap(),
+            }
+        };
+        assert_eq!(vec![0, 1, 2], message);
+
+        first_server.send(&[3, 4, 5]).unwrap();
+        assert_eq!(
+            vec![3, 4, 5],
+            Frame::read(&mut first_client).unwrap().into_inner()
+        );
+    }
+
+    /// Test that both ends of a TLS connection report the certificate presented by their peer.
+    ///
+    /// 1. Create a transport that requires clients to present a certificate signed by the CA
+    /// 2. Connect to a listener created by the transport
+    /// 3. Verify that the client connection reports the fingerprint and subject of the server
+    ///    certificate
+    /// 4. Verify that the server connection reports the fingerprint of the client certificate
+    #[test]
+    fn test_peer_certificate() {
+        let (ca_key, ca_cert) = make_ca_cert();
+        let temp_dir = Builder::new()
+            .prefix("tls-transport-test")
+            .tempdir()
+            .unwrap();
+        let temp_dir_path = temp_dir.path();
+
+        let (client_key, client_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+        let (server_key, server_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+
+        let mut transport = TlsTransport::new(
+            Some(write_cert(temp_dir_path, "ca.cert", &ca_cert)),
+            write_key(temp_dir_path, "client.key", &client_key),
+            write_cert(temp_dir_path, "client.cert", &client_cert),
+            write_key(temp_dir_path, "server.key", &server_key),
+            write_cert(temp_dir_path, "server.cert", &server_cert),
+        )
+        .unwrap();
+
+        let mut listener = transport.listen("tcps://127.0.0.1:0").unwrap();
+        let endpoint = listener.endpoint();
+
+        let handle = thread::spawn(move || transport.connect(&endpoint).unwrap());
+        let server = listener.accept().unwrap();
+        let client = handle.join().unwrap();
+
+        let server_cert_info = client
+            .peer_certificate()
+            .expect("Client did not report the server certificate");
+        assert_eq!(
+            server_cert_info.fingerprint(),
+            &server_cert.digest(MessageDigest::sha256()).unwrap()[..]
+        );
+        assert_eq!(server_cert_info.subject(), "CN=localhost");
+
+        let client_cert_info = server
+            .peer_certificate()
+            .expect("Server did not report the client certificate");
+        assert_eq!(
+            client_cert_info.fingerprint(),
+            &client_cert.digest(MessageDigest::sha256()).unwrap()[..]
+        );
+    }
+
+    /// Connects to the given endpoint without verifying the server, and returns the stream along
+    /// with the certificate that the server presented.
+    fn connect_and_get_peer_cert(endpoint: &str) -> (SslStream<TcpStream>, X509) {
+        let address = endpoint.strip_prefix(PROTOCOL_PREFIX).unwrap();
+
+        let mut connector = SslConnector::builder(SslMethod::tls()).unwrap();
+        connector.set_verify(SslVerifyMode::NONE);
+        let stream = TcpStream::connect(address).unwrap();
+        let mut tls_stream = connector.build().connect("localhost", stream).unwrap();
+
+        FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V1)
+            .negotiate(&mut tls_stream)
+            .unwrap();
+
+        let peer_cert = tls_stream.ssl().peer_certificate().unwrap();
+        (tls_stream, peer_cert)
+    }
 }
@@ -178,7 +178,7 @@ pub(super) fn build_acceptor(config: &TlsConfig) -> Result<SslAcceptor, ErrorSta
 }
 
 #[cfg(test)]
-pub(super) mod tests {
+pub(crate) mod tests {
     use openssl::asn1::Asn1Time;
     use openssl::bn::{BigNum, MsbOption};
     use openssl::hash::MessageDigest;
@@ -0,0 +1,124 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io;
+use std::net::Shutdown;
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::{SocketAddr, UnixStream};
+
+use mio::{unix::EventedFd, Evented, Poll, PollOpt, Ready, Token};
+
+use crate::transport::socket::frame::{Frame, FrameError, FrameRef, FrameVersion};
+use crate::transport::{Connection, DisconnectError, RecvError, SendError};
+
+use super::transport::UNIX_PROTOCOL_PREFIX;
+
+pub(super) struct UnixConnection {
+    stream: UnixStream,
+    frame_version: FrameVersion,
+    remote_endpoint: String,
+    local_endpoint: String,
+}
+
+impl UnixConnection {
+    /// Creates a connection from a stream that has completed frame version negotiation.
+    ///
+    /// The stream is switched to non-blocking mode, so that it may be polled.
+    pub fn new(
+        stream: UnixStream,
+        frame_version: FrameVersion,
+        remote_endpoint: String,
+        local_endpoint: String,
+    ) -> Result<Self, io::Error> {
+        stream.set_nonblocking(true)?;
+
+        Ok(UnixConnection {
+            stream,
+            frame_version,
+            remote_endpoint,
+            local_endpoint,
+        })
+    }
+
+    /// Returns the endpoint for a socket address. The client side of a connection is usually not
+    /// bound to a path, in which case the endpoint has an empty path.
+    pub fn endpoint(addr: SocketAddr) -> String {
+        match addr.as_pathname() {
+            Some(path) => format!("{}{}", UNIX_PROTOCOL_PREFIX, path.display()),
+            None => UNIX_PROTOCOL_PREFIX.to_string(),
+        }
+    }
+}
+
+impl Connection for UnixConnection {
+    fn send(&mut self, message: &[u8]) -> Result<(), SendError> {
+        match FrameRef::new(self.frame_version, message).write(&mut self.stream) {
+            Err(FrameError::IoError(e)) => Err(SendError::from(e)),
+            Err(err) => Err(SendError::ProtocolError(err.to_string())),
+            Ok(_) => Ok(()),
+        }
+    }
+
+    fn recv(&mut self) -> Result<Vec<u8>, RecvError> {
+        match Frame::read(&mut self.stream) {
+            Err(FrameError::IoError(e)) => Err(RecvError::from(e)),
+            Err(err) => Err(RecvError::ProtocolError(err.to_string())),
+            Ok(frame) => Ok(frame.into_inner()),
+        }
+    }
+
+    fn remote_endpoint(&self) -> String {
+        self.remote_endpoint.clone()
+    }
+
+    fn local_endpoint(&self) -> String {
+        self.local_endpoint.clone()
+    }
+
+    fn disconnect(&mut self) -> Result<(), DisconnectError> {
+        self.stream
+            .shutdown(Shutdown::Both)
+            .map_err(DisconnectError::from)
+    }
+
+    fn evented(&self) -> &dyn Evented {
+        self
+    }
+}
+
+impl Evented for UnixConnection {
+    fn register(
+        &self,
+        poll: &Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.stream.as_raw_fd()).register(poll, token, interest, opts)
+    }
+
+    fn reregister(
+        &self,
+        poll: &Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.stream.as_raw_fd()).reregister(poll, token, interest, opts)
+    }
+
+    fn deregister(&self, poll: &Poll) -> io::Result<()> {
+        EventedFd(&self.stream.as_raw_fd()).deregister(poll)
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fs::remove_file;
+use std::os::unix::net::UnixListener as StdUnixListener;
+use std::path::PathBuf;
+
+use crate::transport::socket::frame::{FrameError, FrameNegotiation, FrameVersion};
+use crate::transport::{AcceptError, Connection, Listener};
+
+use super::connection::UnixConnection;
+use super::transport::UNIX_PROTOCOL_PREFIX;
+
+pub(super) struct UnixListener {
+    listener: StdUnixListener,
+    path: PathBuf,
+    local_endpoint: String,
+}
+
+impl UnixListener {
+    pub fn new(listener: StdUnixListener, path: PathBuf, local_endpoint: String) -> Self {
+        UnixListener {
+            listener,
+            path,
+            local_endpoint,
+        }
+    }
+}
+
+impl Listener for UnixListener {
+    fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
+        let (mut stream, _) = self.listener.accept()?;
+
+        let frame_version = FrameNegotiation::inbound(FrameVersion::V1)
+            .negotiate(&mut stream)
+            .map_err(|err| match err {
+                FrameError::UnsupportedVersion => AcceptError::ProtocolError(format!(
+                    "Local {} protocol version {} not supported by remote",
+                    UNIX_PROTOCOL_PREFIX,
+                    FrameVersion::V1
+                )),
+                FrameError::IoError(err) => AcceptError::from(err),
+                err => AcceptError::ProtocolError(format!("Unexpected protocol error: {}", err)),
+            })?;
+
+        let remote_endpoint = UnixConnection::endpoint(stream.peer_addr()?);
+        Ok(Box::new(UnixConnection::new(
+            stream,
+            frame_version,
+            remote_endpoint,
+            self.local_endpoint.clone(),
+        )?))
+    }
+
+    fn endpoint(&self) -> String {
+        self.local_endpoint.clone()
+    }
+}
+
+impl Drop for UnixListener {
+    fn drop(&mut self) {
+        // The socket file is left behind when the listener is closed, and would cause the next
+        // bind to the same path to fail.
+        if let Err(err) = remove_file(&self.path) {
+            debug!(
+                "Unable to remove socket file {}: {}",
+                self.path.display(),
+                err
+            );
+        }
+    }
+}
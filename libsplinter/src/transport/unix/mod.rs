@@ -0,0 +1,80 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A Unix domain socket-based transport implementation.
+//!
+//! The `splinter::transport::unix` module provides a `Transport` implementation for processes
+//! on the same host, on top of an underlying Unix domain socket.
+
+mod connection;
+mod listener;
+mod transport;
+
+pub use transport::UnixTransport;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::transport::tests;
+    use crate::transport::Transport;
+
+    use tempfile::{Builder, TempDir};
+
+    fn socket_endpoint(temp_dir: &TempDir) -> String {
+        format!(
+            "unix://{}",
+            temp_dir
+                .path()
+                .join("splinter.sock")
+                .to_str()
+                .expect("Failed to get path")
+        )
+    }
+
+    #[test]
+    fn test_accepts() {
+        let transport = UnixTransport::default();
+        assert!(transport.accepts("unix:///var/run/splinter.sock"));
+        assert!(transport.accepts("unix://splinter.sock"));
+
+        assert!(!transport.accepts("/var/run/splinter.sock"));
+        assert!(!transport.accepts("tcp://127.0.0.1:8044"));
+        assert!(!transport.accepts("127.0.0.1:8044"));
+    }
+
+    #[test]
+    fn test_transport() {
+        let temp_dir = Builder::new()
+            .prefix("test_unix_transport")
+            .tempdir()
+            .expect("Failed to create temp dir");
+
+        let transport = UnixTransport::default();
+
+        tests::test_transport(transport, &socket_endpoint(&temp_dir));
+    }
+
+    #[test]
+    fn test_poll() {
+        let temp_dir = Builder::new()
+            .prefix("test_unix_poll")
+            .tempdir()
+            .expect("Failed to create temp dir");
+
+        let transport = UnixTransport::default();
+
+        tests::test_poll(transport, &socket_endpoint(&temp_dir));
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::os::unix::net::{UnixListener as StdUnixListener, UnixStream};
+
+use crate::transport::socket::frame::{FrameError, FrameNegotiation, FrameVersion};
+use crate::transport::{ConnectError, Connection, ListenError, Listener, Transport};
+
+use super::connection::UnixConnection;
+use super::listener::UnixListener;
+
+pub(super) const UNIX_PROTOCOL_PREFIX: &str = "unix://";
+
+/// A Unix domain socket-based `Transport`.
+///
+/// Supports endpoints of the format `unix:///path/to/socket`. Listening on an endpoint creates
+/// the socket file, which must not already exist; the file is removed when the listener is
+/// dropped.
+#[derive(Default)]
+pub struct UnixTransport {}
+
+impl Transport for UnixTransport {
+    fn accepts(&self, address: &str) -> bool {
+        address.starts_with(UNIX_PROTOCOL_PREFIX)
+    }
+
+    fn connect(&mut self, endpoint: &str) -> Result<Box<dyn Connection>, ConnectError> {
+        let path = endpoint.strip_prefix(UNIX_PROTOCOL_PREFIX).ok_or_else(|| {
+            ConnectError::ProtocolError(format!("Invalid protocol \"{}\"", endpoint))
+        })?;
+
+        // Connect in blocking mode so that the frame version negotiation completes before the
+        // connection is returned
+        let mut stream = UnixStream::connect(path)?;
+
+        let frame_version = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V1)
+            .negotiate(&mut stream)
+            .map_err(|err| match err {
+                FrameError::UnsupportedVersion => ConnectError::ProtocolError(
+                    "Unable to connect; remote version is not with in range".into(),
+                ),
+                FrameError::IoError(err) => ConnectError::from(err),
+                e => ConnectError::ProtocolError(format!("Unexpected protocol error: {}", e)),
+            })?;
+
+        let local_endpoint = UnixConnection::endpoint(stream.local_addr()?);
+        Ok(Box::new(UnixConnection::new(
+            stream,
+            frame_version,
+            endpoint.to_string(),
+            local_endpoint,
+        )?))
+    }
+
+    fn listen(&mut self, bind: &str) -> Result<Box<dyn Listener>, ListenError> {
+        let path = bind
+            .strip_prefix(UNIX_PROTOCOL_PREFIX)
+            .ok_or_else(|| ListenError::ProtocolError(format!("Invalid protocol \"{}\"", bind)))?;
+
+        let listener = StdUnixListener::bind(path)
+            .map_err(|err| ListenError::IoError(format!("Failed to bind to {}", path), err))?;
+
+        Ok(Box::new(UnixListener::new(
+            listener,
+            path.into(),
+            bind.to_string(),
+        )))
+    }
+}
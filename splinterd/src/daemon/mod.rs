@@ -850,12 +850,22 @@ impl SplinterDaemon {
             error!("Unable to cleanly shut down ConnectionManager: {}", err);
         }
 
+        self.mesh.close_connections();
         self.mesh.signal_shutdown();
         if let Err(err) = interconnect.wait_for_shutdown() {
             error!("Unable to cleanly shut down peer interconnect: {}", err);
         }
-        if let Err(err) = self.mesh.clone().wait_for_shutdown() {
-            error!("Unable to cleanly shut down Mesh: {}", err);
+        match self.mesh.clone().wait_for_shutdown_with_summary() {
+            Ok(summary) => {
+                debug!(
+                    "Mesh closed {} connections during shutdown",
+                    summary.connections_closed()
+                );
+                for err in summary.errors() {
+                    warn!("{}", err);
+                }
+            }
+            Err(err) => error!("Unable to cleanly shut down Mesh: {}", err),
         }
         Ok(())
     }
@@ -73,6 +73,7 @@ impl ShutdownHandle for NetworkSubsystem {
         self.authorization_manager.shutdown_signaler().shutdown();
         self.circuit_dispatch_loop.signal_shutdown();
         self.network_dispatch_loop.signal_shutdown();
+        self.mesh.close_connections();
         self.mesh.signal_shutdown();
     }
 
@@ -100,8 +101,17 @@ impl ShutdownHandle for NetworkSubsystem {
             errors.push(err)
         }
 
-        if let Err(err) = self.mesh.wait_for_shutdown() {
-            errors.push(err)
+        match self.mesh.wait_for_shutdown_with_summary() {
+            Ok(summary) => {
+                debug!(
+                    "Mesh closed {} connections during shutdown",
+                    summary.connections_closed()
+                );
+                for err in summary.errors() {
+                    warn!("{}", err);
+                }
+            }
+            Err(err) => errors.push(err),
         }
 
         match errors.len() {
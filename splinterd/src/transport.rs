@@ -19,6 +19,8 @@ use splinter::transport::multi::MultiTransport;
 use splinter::transport::socket::TcpTransport;
 use splinter::transport::socket::TlsTransport;
 use splinter::transport::tls::{TlsConfig, TlsConfigBuilder};
+#[cfg(all(feature = "unix-transport", unix))]
+use splinter::transport::unix::UnixTransport;
 #[cfg(feature = "ws-transport")]
 use splinter::transport::ws::WsTransport;
 use splinter::transport::Transport;
@@ -35,6 +37,10 @@ pub fn build_transport(config: &Config) -> Result<MultiTransport, GetTransportEr
         Box::new(TcpTransport::default()),
     ];
 
+    // add unix domain socket transport, for nodes and services on the same host
+    #[cfg(all(feature = "unix-transport", unix))]
+    transports.push(Box::new(UnixTransport::default()));
+
     // add web socket transport
 
     // add tls transport